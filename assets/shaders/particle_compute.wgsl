@@ -1,15 +1,18 @@
 // Paramètres de simulation
 @group(0) @binding(0) var<uniform> num_particles: u32;
+// Timestep de la passe, 0 pour une passe inactive qui recopie son entrée
 @group(0) @binding(1) var<uniform> dt: f32;
-@group(0) @binding(2) var<uniform> world_size: f32;
+// Dimensions de la grille (largeur, hauteur, profondeur), w inutilisé
+@group(0) @binding(2) var<uniform> world_extents: vec4<f32>;
 @group(0) @binding(3) var<uniform> num_types: u32;
-@group(0) @binding(4) var<uniform> max_force_range: f32;
-@group(0) @binding(5) var<uniform> boundary_mode: u32; // 0=bounce, 1=teleport
+// Portée des forces, une par simulation
+@group(0) @binding(4) var<storage, read> force_ranges: array<f32>;
+@group(0) @binding(5) var<uniform> boundary_mode: u32; // bits des axes en téléportation (1=X, 2=Y, 4=Z)
 
 // Positions des particules (x, y, z, particle_type)
 @group(0) @binding(6) var<storage, read> positions: array<vec4<f32>>;
 
-// Vélocités des particules (x, y, z, unused)
+// Vélocités des particules (x, y, z, sim_id)
 @group(0) @binding(7) var<storage, read> velocities: array<vec4<f32>>;
 
 // Nouvelles positions (output)
@@ -18,214 +21,351 @@
 // Nouvelles vélocités (output)
 @group(0) @binding(9) var<storage, read_write> new_velocities: array<vec4<f32>>;
 
-// Matrice des forces d'interaction entre types (format linéaire)
+// Matrices des forces d'interaction, une par simulation (format linéaire)
+// index = sim_id * num_types * num_types + type_a * num_types + type_b
 @group(0) @binding(10) var<storage, read> force_matrix: array<f32>;
 
 // Positions de nourriture (x, y, z, is_active)
-@group(0) @binding(11) var<storage, read> food_positions: array<vec4<f32>>;
+@group(0) @binding(11) var<storage, read> food_positions: array<vec4<f32>>; // w = valeur, 0 si cachée
 @group(0) @binding(12) var<uniform> food_count: u32;
 
-// Forces de nourriture par type
+// Forces de nourriture par type, une série par simulation (sim_id * num_types + type)
 @group(0) @binding(13) var<storage, read> food_forces: array<f32>;
 
+// Nombre max d'interactions par particule (0 = illimité)
+@group(0) @binding(14) var<uniform> max_interactions: u32;
+
+// Facteur multiplicatif des forces du génome
+@group(0) @binding(15) var<uniform> force_scale_factor: f32;
+
+// Vitesse max et fraction de vitesse conservée au rebond (0 = collé, 1 = parfait)
+@group(0) @binding(16) var<uniform> max_velocity: f32;
+@group(0) @binding(17) var<uniform> collision_damping: f32;
+
+// Nourriture mangée pendant le dispatch (0 = libre, sinon index de la simulation + 1)
+@group(0) @binding(18) var<storage, read_write> food_eaten: array<atomic<u32>>;
+// Score gagné par simulation, en virgule fixe (voir SCORE_FIXED_POINT)
+@group(0) @binding(19) var<storage, read_write> score_accumulators: array<atomic<i32>>;
+
+// Grille spatiale construite côté CPU (voir SpatialGrid::to_compact) :
+// début de chaque cellule (dims.w + 1 entrées) puis index des particules triés par cellule
+struct SpatialGridParams {
+    dims: vec4<u32>, // cellules par axe, w = nombre total de cellules
+    cell_extent: vec4<f32>,
+    half_extents: vec4<f32>,
+}
+@group(0) @binding(20) var<storage, read> spatial_grid: array<u32>;
+@group(0) @binding(21) var<uniform> grid_params: SpatialGridParams;
+// Index de `ForceCurve::gpu_index` (0 triangle, 1 linéaire, 2 Lennard-Jones, 3 lisse)
+@group(0) @binding(22) var<uniform> force_curve: u32;
+// Distance de répulsion (`SimulationParameters::min_distance`)
+@group(0) @binding(23) var<uniform> min_distance: f32;
+// Demi-vie de la vitesse en secondes (`SimulationParameters::velocity_half_life`)
+@group(0) @binding(24) var<uniform> velocity_half_life: f32;
+// Rayon et masse de chaque type (`ParticleTypesConfig`), quatre types par vec4 ; la taille
+// des tableaux doit correspondre à MAX_PARTICLE_TYPES / 4 arrondi au-dessus
+struct ParticleTypeProperties {
+    radii: array<vec4<f32>, 3>,
+    masses: array<vec4<f32>, 3>,
+}
+@group(0) @binding(25) var<uniform> type_properties: ParticleTypeProperties;
+
 // Constantes physiques
-const PARTICLE_RADIUS: f32 = 2.5;
-const FOOD_RADIUS: f32 = 1.0;
+const PARTICLE_RADIUS: f32 = 4.0; // Doit correspondre à PARTICLE_RADIUS
+const FOOD_RADIUS: f32 = 2.0;
+const SCORE_FIXED_POINT: f32 = 1000.0; // Doit correspondre à GPU_SCORE_FIXED_POINT
 const MIN_DISTANCE: f32 = 0.001;
-const FORCE_SCALE_FACTOR: f32 = 80.0;
-const MAX_VELOCITY: f32 = 200.0;
-const VELOCITY_HALF_LIFE: f32 = 0.043;
-const MAX_INTERACTIONS_PER_PARTICLE: u32 = 100;
-
-// Fonction pour obtenir la force entre deux types de particules
-fn get_force_between_types(type_a: u32, type_b: u32) -> f32 {
-    let index = type_a * num_types + type_b;
-    return force_matrix[index];
+const PI: f32 = 3.14159265;
+const MAX_INTERACTIONS: u32 = 128u; // Doit correspondre à GPU_MAX_INTERACTIONS
+
+// Voisines retenues quand les interactions sont plafonnées : xyz = force, w = distance²
+var<private> nearest: array<vec4<f32>, MAX_INTERACTIONS>;
+var<private> nearest_count: u32;
+var<private> farthest_slot: u32;
+
+// Rayon d'un type de particule
+fn particle_radius(particle_type: u32) -> f32 {
+    return type_properties.radii[particle_type / 4u][particle_type % 4u];
 }
 
-// Calcule la distance minimale dans un espace torus 3D
-fn torus_distance(pos1: vec3<f32>, pos2: vec3<f32>, grid_size: f32) -> f32 {
-    let delta = pos2 - pos1;
-    let half_size = grid_size * 0.5;
+// Masse d'un type de particule
+fn particle_mass(particle_type: u32) -> f32 {
+    return type_properties.masses[particle_type / 4u][particle_type % 4u];
+}
 
-    let dx = abs(delta.x);
-    let min_dx = min(dx, grid_size - dx);
+// Fonction pour obtenir la force entre deux types de particules d'une simulation
+fn get_force_between_types(sim_id: u32, type_a: u32, type_b: u32) -> f32 {
+    let index = sim_id * num_types * num_types + type_a * num_types + type_b;
+    return force_matrix[index];
+}
 
-    let dy = abs(delta.y);
-    let min_dy = min(dy, grid_size - dy);
+// Force de nourriture d'un type de particule d'une simulation
+fn get_food_force(sim_id: u32, particle_type: u32) -> f32 {
+    return food_forces[sim_id * num_types + particle_type];
+}
 
-    let dz = abs(delta.z);
-    let min_dz = min(dz, grid_size - dz);
+// Axes en téléportation, décodés depuis boundary_mode (`BoundaryMode::gpu_mask`)
+fn wrap_axes() -> vec3<bool> {
+    return (vec3<u32>(boundary_mode) & vec3<u32>(1u, 2u, 4u)) != vec3<u32>(0u);
+}
 
-    return sqrt(min_dx * min_dx + min_dy * min_dy + min_dz * min_dz);
+// Calcule la distance minimale dans un espace torus 3D (repliement sur les axes en
+// téléportation seulement)
+fn torus_distance(pos1: vec3<f32>, pos2: vec3<f32>, extents: vec3<f32>) -> f32 {
+    let delta = abs(pos2 - pos1);
+    return length(select(delta, min(delta, extents - delta), wrap_axes()));
 }
 
-// Calcule le vecteur de direction minimal dans un espace torus 3D
-fn torus_direction_vector(from: vec3<f32>, to: vec3<f32>, grid_size: f32) -> vec3<f32> {
-    var direction = vec3<f32>(0.0);
-    let half_size = grid_size * 0.5;
+// Calcule le vecteur de direction minimal dans un espace torus 3D (image la plus proche
+// sur chaque axe en téléportation, comme torus_direction_vector côté CPU)
+fn torus_direction_vector(origin: vec3<f32>, destination: vec3<f32>, extents: vec3<f32>) -> vec3<f32> {
+    let delta = destination - origin;
+    let wrapped = delta - sign(delta) * extents;
+    return select(delta, wrapped, wrap_axes() & (abs(delta) > extents * 0.5));
+}
 
-    // Axe X
-    let dx = to.x - from.x;
-    if (abs(dx) <= half_size) {
-        direction.x = dx;
-    } else {
-        direction.x = select(dx + grid_size, dx - grid_size, dx > 0.0);
-    }
+// Cellule contenant une position, bornée à la grille
+fn grid_cell_of(pos: vec3<f32>) -> vec3<i32> {
+    let local = (pos + grid_params.half_extents.xyz) / grid_params.cell_extent.xyz;
+    let max_cell = vec3<i32>(grid_params.dims.xyz) - vec3<i32>(1);
+    return clamp(vec3<i32>(floor(local)), vec3<i32>(0), max_cell);
+}
 
-    // Axe Y
-    let dy = to.y - from.y;
-    if (abs(dy) <= half_size) {
-        direction.y = dy;
-    } else {
-        direction.y = select(dy + grid_size, dy - grid_size, dy > 0.0);
+// step-ième cellule voisine sur un axe (-1 si hors grille, sauf si l'axe se replie) ; un
+// axe de moins de 3 cellules est parcouru une seule fois, comme SpatialGrid::neighbor_cells
+fn grid_neighbor(cell: i32, step: u32, dim: u32, wrap: bool) -> i32 {
+    let n = i32(dim);
+    if (n < 3) {
+        return i32(step);
     }
-
-    // Axe Z
-    let dz = to.z - from.z;
-    if (abs(dz) <= half_size) {
-        direction.z = dz;
-    } else {
-        direction.z = select(dz + grid_size, dz - grid_size, dz > 0.0);
+    let neighbor = cell + i32(step) - 1;
+    if (wrap) {
+        return (neighbor + n) % n;
     }
+    return select(neighbor, -1, neighbor < 0 || neighbor >= n);
+}
 
-    return direction;
+// Force signée (positive : attraction), copie de `ForceCurve::force` côté CPU
+fn curve_force(r: f32, rmin: f32, a: f32) -> f32 {
+    if (r >= 1.0) {
+        return 0.0;
+    }
+    if (force_curve == 2u) {
+        // Lennard-Jones, répulsion bornée sous rmin
+        let s2 = (rmin / r) * (rmin / r);
+        let s6 = s2 * s2 * s2;
+        if (r < rmin) {
+            return max(1.0 - s6, -1.0);
+        }
+        return a * 4.0 * (s6 - s6 * s6);
+    }
+    if (r < rmin) {
+        // Force de répulsion (toujours négative)
+        return r / rmin - 1.0;
+    }
+    let span = 1.0 - rmin;
+    switch (force_curve) {
+        case 1u: {
+            return a * (1.0 - r) / span;
+        }
+        case 3u: {
+            return a * sin(PI * (r - rmin) / span);
+        }
+        default: {
+            return a * (1.0 - abs(1.0 + rmin - 2.0 * r) / span);
+        }
+    }
 }
 
-// Calcule l'accélération entre deux particules
+// Calcule l'accélération entre deux particules (distances normalisées, comme côté CPU)
 fn acceleration(rmin: f32, dpos: vec3<f32>, a: f32, max_range: f32) -> vec3<f32> {
     let dist = length(dpos);
     if (dist < MIN_DISTANCE || dist > max_range) {
         return vec3<f32>(0.0);
     }
 
-    var force: f32;
-    if (dist < rmin) {
-        // Force de répulsion (toujours négative)
-        force = (dist / rmin - 1.0);
-    } else {
-        // Force d'attraction/répulsion basée sur le génome
-        force = a * (1.0 - abs(1.0 + rmin - 2.0 * dist) / (1.0 - rmin));
-    }
+    let normalized_pos = dpos / max_range;
+    let normalized_dist = dist / max_range;
+    let force = curve_force(normalized_dist, rmin / max_range, a);
 
-    return dpos * force / dist;
+    return normalized_pos * force / normalized_dist;
 }
 
-// Applique les limites avec rebond
-fn apply_bounce_bounds(position: vec3<f32>, velocity: vec3<f32>) -> vec4<f32> {
-    var result_pos = position;
-    var result_vel = velocity;
-    let half_size = world_size * 0.5;
-
-    // Rebonds sur les murs
-    if (abs(result_pos.x) > half_size - PARTICLE_RADIUS) {
-        result_pos.x = sign(result_pos.x) * (half_size - PARTICLE_RADIUS);
-        result_vel.x *= -0.5;
-    }
+// Position et vélocité après rebond
+struct BounceResult {
+    position: vec3<f32>,
+    velocity: vec3<f32>,
+}
 
-    if (abs(result_pos.y) > half_size - PARTICLE_RADIUS) {
-        result_pos.y = sign(result_pos.y) * (half_size - PARTICLE_RADIUS);
-        result_vel.y *= -0.5;
-    }
+// Applique les limites avec rebond
+fn apply_bounce_bounds(position: vec3<f32>, velocity: vec3<f32>) -> BounceResult {
+    let limit = world_extents.xyz * 0.5 - vec3<f32>(PARTICLE_RADIUS);
 
-    if (abs(result_pos.z) > half_size - PARTICLE_RADIUS) {
-        result_pos.z = sign(result_pos.z) * (half_size - PARTICLE_RADIUS);
-        result_vel.z *= -0.5;
-    }
+    // Rebonds sur les murs, axe par axe
+    let hit_wall = abs(position) > limit;
+    let result_pos = select(position, sign(position) * limit, hit_wall);
+    let result_vel = select(velocity, velocity * -collision_damping, hit_wall);
 
-    return vec4<f32>(result_pos, length(result_vel));
+    return BounceResult(result_pos, result_vel);
 }
 
 // Applique les limites avec téléportation
 fn apply_teleport_bounds(position: vec3<f32>) -> vec3<f32> {
-    var result = position;
-    let half_size = world_size * 0.5;
-
-    // Téléportation
-    if (result.x > half_size) {
-        result.x = -half_size + (result.x - half_size);
-    } else if (result.x < -half_size) {
-        result.x = half_size + (result.x + half_size);
+    let half_extents = world_extents.xyz * 0.5;
+
+    // Téléportation vers le bord opposé, axe par axe
+    let wrapped = position - sign(position) * world_extents.xyz;
+    return select(position, wrapped, abs(position) > half_extents);
+}
+
+// Garde les `cap` voisines les plus proches, comme le tri partiel du CPU : une fois le
+// plafond atteint, une voisine plus proche remplace la plus lointaine des retenues
+fn keep_nearest(contribution: vec4<f32>, cap: u32) {
+    if (nearest_count < cap) {
+        nearest[nearest_count] = contribution;
+        if (contribution.w > nearest[farthest_slot].w) {
+            farthest_slot = nearest_count;
+        }
+        nearest_count++;
+        return;
     }
+    if (contribution.w >= nearest[farthest_slot].w) {
+        return;
+    }
+    nearest[farthest_slot] = contribution;
+    for (var s = 0u; s < cap; s++) {
+        if (nearest[s].w > nearest[farthest_slot].w) {
+            farthest_slot = s;
+        }
+    }
+}
 
-    if (result.y > half_size) {
-        result.y = -half_size + (result.y - half_size);
-    } else if (result.y < -half_size) {
-        result.y = half_size + (result.y + half_size);
+// Force exercée par la particule i ; w = distance², négatif si elle ne compte pas
+fn neighbor_force(index: u32, i: u32, current_pos: vec3<f32>, current_type: u32, current_sim: u32, max_force_range: f32, min_distance: f32) -> vec4<f32> {
+    // Les populations de simulations différentes n'interagissent pas
+    if (i == index || u32(velocities[i].w) != current_sim) {
+        return vec4<f32>(-1.0);
     }
 
-    if (result.z > half_size) {
-        result.z = -half_size + (result.z - half_size);
-    } else if (result.z < -half_size) {
-        result.z = half_size + (result.z + half_size);
+    let other_pos = positions[i].xyz;
+    let other_type = u32(positions[i].w);
+
+    // Calcul de distance selon le mode de bord de chaque axe
+    let distance_vec = torus_direction_vector(current_pos, other_pos, world_extents.xyz);
+
+    let distance_squared = dot(distance_vec, distance_vec);
+
+    if (distance_squared < MIN_DISTANCE || distance_squared > max_force_range * max_force_range) {
+        return vec4<f32>(-1.0);
     }
 
-    return result;
+    let attraction = get_force_between_types(current_sim, current_type, other_type) * force_scale_factor;
+    // Répulsion proportionnelle au rayon moyen du couple, comme côté CPU
+    let pair_radius = (particle_radius(current_type) + particle_radius(other_type)) * 0.5;
+    let pair_min_distance = min_distance * pair_radius / PARTICLE_RADIUS;
+    let accel = acceleration(pair_min_distance, distance_vec, attraction, max_force_range);
+    return vec4<f32>(accel * max_force_range, distance_squared);
+}
+
+// Additionne la contribution d'une voisine, ou la met en concurrence si le nombre
+// d'interactions est plafonné
+fn accumulate_neighbor(contribution: vec4<f32>, cap: u32, total_force: ptr<function, vec3<f32>>) {
+    if (contribution.w < 0.0) {
+        return;
+    }
+    if (cap == 0u) {
+        *total_force += contribution.xyz;
+    } else {
+        keep_nearest(contribution, cap);
+    }
 }
 
-@compute @workgroup_size(64, 1, 1)
+// WORKGROUP_SIZE est injecté par le pipeline (shader_defs), validé contre les limites du GPU
+@compute @workgroup_size(#{WORKGROUP_SIZE}, 1, 1)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let index = global_id.x;
     if (index >= num_particles) {
         return;
     }
+    // Passe au-delà des pas de la frame : la suivante doit retrouver les mêmes données
+    if (dt == 0.0) {
+        new_positions[index] = positions[index];
+        new_velocities[index] = velocities[index];
+        return;
+    }
 
     // Lire les données de la particule courante
     let current_pos = positions[index].xyz;
     let current_type = u32(positions[index].w);
     let current_vel = velocities[index].xyz;
+    let current_sim = u32(velocities[index].w);
+    let max_force_range = force_ranges[current_sim];
 
     var total_force = vec3<f32>(0.0, 0.0, 0.0);
 
-    // Forces avec les autres particules
-    var interactions_count = 0u;
-    let min_distance = f32(num_types) * PARTICLE_RADIUS;
-
-    for (var i = 0u; i < num_particles && interactions_count < MAX_INTERACTIONS_PER_PARTICLE; i++) {
-        if (i == index) {
+    // Forces avec les autres particules ; 0 = illimité, sinon les N plus proches
+    let cap = min(max_interactions, MAX_INTERACTIONS);
+    nearest_count = 0u;
+    farthest_slot = 0u;
+
+#ifdef SPATIAL_GRID
+    let cell = grid_cell_of(current_pos);
+    let dims = grid_params.dims.xyz;
+    let span = min(dims, vec3<u32>(3u));
+    let wrap = wrap_axes();
+    for (var sx = 0u; sx < span.x; sx++) {
+        let cx = grid_neighbor(cell.x, sx, dims.x, wrap.x);
+        if (cx < 0) {
             continue;
         }
-
-        let other_pos = positions[i].xyz;
-        let other_type = u32(positions[i].w);
-
-        // Calcul de distance selon le mode de bord
-        let distance_vec = select(
-            other_pos - current_pos,
-            torus_direction_vector(current_pos, other_pos, world_size),
-            boundary_mode == 1u
-        );
-
-        let distance_squared = dot(distance_vec, distance_vec);
-
-        if (distance_squared == 0.0 || distance_squared > max_force_range * max_force_range) {
-            continue;
+        for (var sy = 0u; sy < span.y; sy++) {
+            let cy = grid_neighbor(cell.y, sy, dims.y, wrap.y);
+            if (cy < 0) {
+                continue;
+            }
+            for (var sz = 0u; sz < span.z; sz++) {
+                let cz = grid_neighbor(cell.z, sz, dims.z, wrap.z);
+                if (cz < 0) {
+                    continue;
+                }
+                let cell_index = (u32(cx) * dims.y + u32(cy)) * dims.z + u32(cz);
+                let first = spatial_grid[cell_index];
+                let last = spatial_grid[cell_index + 1u];
+                for (var k = first; k < last; k++) {
+                    let i = spatial_grid[grid_params.dims.w + 1u + k];
+                    let contribution = neighbor_force(index, i, current_pos, current_type, current_sim, max_force_range, min_distance);
+                    accumulate_neighbor(contribution, cap, &total_force);
+                }
+            }
         }
-
-        interactions_count++;
-
-        let attraction = get_force_between_types(current_type, other_type) * FORCE_SCALE_FACTOR;
-        let accel = acceleration(min_distance, distance_vec, attraction, max_force_range);
-        total_force += accel;
+    }
+#else
+    // Parcours exhaustif ; les affectations fantômes gardent la grille dans le layout
+    _ = spatial_grid[0];
+    _ = grid_params.dims;
+    for (var i = 0u; i < num_particles; i++) {
+        let contribution = neighbor_force(index, i, current_pos, current_type, current_sim, max_force_range, min_distance);
+        accumulate_neighbor(contribution, cap, &total_force);
+    }
+#endif
+    for (var s = 0u; s < nearest_count; s++) {
+        total_force += nearest[s].xyz;
     }
 
     // Forces avec la nourriture
-    let particle_food_force = food_forces[current_type] * FORCE_SCALE_FACTOR;
+    let particle_food_force = get_food_force(current_sim, current_type) * force_scale_factor;
 
     if (abs(particle_food_force) > 0.001) {
         for (var i = 0u; i < food_count; i++) {
             let food_pos = food_positions[i].xyz;
-            let is_active = food_positions[i].w > 0.5;
+            // Une nourriture mangée à une passe précédente n'attire plus
+            let is_active = food_positions[i].w != 0.0 && atomicLoad(&food_eaten[i]) == 0u;
 
             if (!is_active) {
                 continue;
             }
 
-            let distance_vec_food = select(
-                food_pos - current_pos,
-                torus_direction_vector(current_pos, food_pos, world_size),
-                boundary_mode == 1u
-            );
+            let distance_vec_food = torus_direction_vector(current_pos, food_pos, world_extents.xyz);
 
             let distance = length(distance_vec_food);
 
@@ -239,30 +379,44 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     // Appliquer les forces
-    var new_vel = current_vel + total_force * dt;
+    var new_vel = current_vel + total_force / particle_mass(current_type) * dt;
 
     // Amortissement
-    new_vel *= pow(0.5, dt / VELOCITY_HALF_LIFE);
+    new_vel *= pow(0.5, dt / velocity_half_life);
 
     // Limiter la vitesse
     let speed = length(new_vel);
-    if (speed > MAX_VELOCITY) {
-        new_vel = normalize(new_vel) * MAX_VELOCITY;
+    if (speed > max_velocity) {
+        new_vel = normalize(new_vel) * max_velocity;
     }
 
     // Appliquer la vélocité
     var new_pos = current_pos + new_vel * dt;
 
-    // Appliquer les limites
-    if (boundary_mode == 0u) {
-        let bounce_result = apply_bounce_bounds(new_pos, new_vel);
-        new_pos = bounce_result.xyz;
-        new_vel = current_vel * (bounce_result.w / speed);
-    } else {
-        new_pos = apply_teleport_bounds(new_pos);
+    // Appliquer les limites, rebond ou téléportation selon l'axe
+    let bounce_result = apply_bounce_bounds(new_pos, new_vel);
+    new_pos = select(bounce_result.position, apply_teleport_bounds(new_pos), wrap_axes());
+    new_vel = select(bounce_result.velocity, new_vel, wrap_axes());
+
+    // Collision avec la nourriture : la première particule qui la réclame la mange
+    let eat_distance = particle_radius(current_type) + FOOD_RADIUS;
+    for (var i = 0u; i < food_count; i++) {
+        let food_value = food_positions[i].w;
+        if (food_value == 0.0 || length(food_positions[i].xyz - new_pos) >= eat_distance) {
+            continue;
+        }
+        // La version faible peut échouer sans concurrent : on réessaie tant que la
+        // nourriture n'a pas été réclamée
+        var claim = atomicCompareExchangeWeak(&food_eaten[i], 0u, current_sim + 1u);
+        while (!claim.exchanged && claim.old_value == 0u) {
+            claim = atomicCompareExchangeWeak(&food_eaten[i], 0u, current_sim + 1u);
+        }
+        if (claim.exchanged) {
+            atomicAdd(&score_accumulators[current_sim], i32(round(food_value * SCORE_FIXED_POINT)));
+        }
     }
 
     // Écrire les résultats
     new_positions[index] = vec4<f32>(new_pos, f32(current_type));
-    new_velocities[index] = vec4<f32>(new_vel, 0.0);
+    new_velocities[index] = vec4<f32>(new_vel, f32(current_sim));
 }
\ No newline at end of file
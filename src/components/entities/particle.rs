@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use std::collections::VecDeque;
 
 /// Type de particule (0, 1, 2, etc.)
 #[derive(Component, Clone, Copy, Debug, Default)]
@@ -8,7 +9,15 @@ pub struct ParticleType(pub usize);
 #[derive(Component, Default, Clone, Copy, Debug)]
 pub struct Velocity(pub Vec3);
 
+/// Accélération du pas précédent, utilisée par l'intégration de Verlet (None au premier pas)
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct PreviousAcceleration(pub Option<Vec3>);
+
+/// Dernières positions de la particule, pour l'affichage des traînées
+#[derive(Component, Default, Clone, Debug)]
+pub struct ParticleTrail(pub VecDeque<Vec3>);
+
 /// Marqueur pour identifier une particule
 #[derive(Component)]
-#[require(ParticleType, Velocity, Transform, Mesh3d, MeshMaterial3d<StandardMaterial>)]
+#[require(ParticleType, Velocity, PreviousAcceleration, ParticleTrail, Transform, Mesh3d, MeshMaterial3d<StandardMaterial>)]
 pub struct Particle;
\ No newline at end of file
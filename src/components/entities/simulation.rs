@@ -1,12 +1,24 @@
 use bevy::prelude::*;
+use crate::components::genetics::exploration::*;
 use crate::components::genetics::genotype::*;
 use crate::components::genetics::score::*;
+use crate::globals::DEFAULT_MAX_FORCE_RANGE;
 
 /// ID de la simulation
 #[derive(Component, Default)]
 pub struct SimulationId(pub usize);
 
+/// Portée des forces propre à la simulation (initialisée depuis la portée globale)
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ForceRange(pub f32);
+
+impl Default for ForceRange {
+    fn default() -> Self {
+        Self(DEFAULT_MAX_FORCE_RANGE)
+    }
+}
+
 /// Marqueur pour une simulation
 #[derive(Component)]
-#[require(SimulationId, Genotype, Score, Transform, Visibility, InheritedVisibility, ViewVisibility)]
+#[require(SimulationId, Genotype, Score, Exploration, ForceRange, Transform, Visibility, InheritedVisibility, ViewVisibility)]
 pub struct Simulation;
\ No newline at end of file
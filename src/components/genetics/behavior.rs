@@ -0,0 +1,214 @@
+use crate::components::genetics::genotype::Genotype;
+
+pub const BEHAVIOR_SWARM: &str = "Comportement d'essaim";
+pub const BEHAVIOR_TERRITORIAL: &str = "Territorialité";
+pub const BEHAVIOR_CHASE: &str = "Poursuite entre types";
+pub const BEHAVIOR_CLUSTERING: &str = "Agrégation par type";
+pub const BEHAVIOR_DISPERSION: &str = "Dispersion";
+pub const BEHAVIOR_FORAGING: &str = "Recherche de nourriture";
+pub const BEHAVIOR_FOOD_AVOIDANCE: &str = "Évitement de la nourriture";
+pub const BEHAVIOR_CHASE_CHAIN: &str = "Chaîne de poursuite";
+pub const BEHAVIOR_ORBIT: &str = "Orbites stables";
+pub const BEHAVIOR_SEGREGATION: &str = "Ségrégation des types";
+
+/// Types minimum d'une chaîne de poursuite (A poursuit B qui poursuit C qui poursuit D)
+const CHASE_CHAIN_MIN_TYPES: usize = 4;
+
+/// Lecture statique d'un génome : comportements émergents attendus d'après ses forces, avec
+/// leur confiance (0 à 1)
+#[derive(Clone, Debug, Default)]
+pub struct PopulationAnalysis {
+    pub predicted_behaviors: Vec<(String, f32)>,
+    /// Voir `coherence_score`
+    pub coherence: f32,
+}
+
+impl PopulationAnalysis {
+    pub fn labels(&self) -> impl Iterator<Item = &String> {
+        self.predicted_behaviors.iter().map(|(label, _)| label)
+    }
+
+    pub fn contains(&self, label: &str) -> bool {
+        self.labels().any(|known| known == label)
+    }
+}
+
+pub fn analyze_behavior(genotype: &Genotype) -> PopulationAnalysis {
+    PopulationAnalysis {
+        predicted_behaviors: predict_emergent_behaviors(genotype)
+            .into_iter()
+            .map(|(label, confidence)| (label.to_string(), confidence))
+            .collect(),
+        coherence: coherence_score(genotype),
+    }
+}
+
+/// Réciprocité des forces entre types, de 0 (A attire B au maximum pendant que B le repousse
+/// au maximum, pour chaque paire) à 1 (matrice symétrique). Des forces réciproques se
+/// compensent : les structures tiennent au lieu de dériver en poursuites sans fin
+pub fn coherence_score(genotype: &Genotype) -> f32 {
+    let type_count = genotype.type_count;
+    if type_count < 2 || genotype.force_matrix.len() < type_count * type_count {
+        return 1.0;
+    }
+
+    let asymmetries: Vec<f32> = (0..type_count)
+        .flat_map(|a| (a + 1..type_count).map(move |b| (a, b)))
+        .map(|(a, b)| (genotype.get_force(a, b) - genotype.get_force(b, a)).abs() / 2.0)
+        .collect();
+    1.0 - asymmetries.iter().sum::<f32>() / asymmetries.len() as f32
+}
+
+/// Confiance d'un critère franchi : 0.5 au seuil, 1 à `full` et au-delà
+fn strength(value: f32, threshold: f32, full: f32) -> f32 {
+    let span = (full - threshold).abs().max(f32::EPSILON);
+    0.5 + 0.5 * ((value - threshold).abs() / span).clamp(0.0, 1.0)
+}
+
+/// A attire vers B pendant que B fuit A, intensité de la relation la plus faible des deux
+fn chase_strength(genotype: &Genotype, a: usize, b: usize) -> Option<f32> {
+    let pursuit = genotype.get_force(a, b);
+    let flight = -genotype.get_force(b, a);
+    (a != b && pursuit > 0.5 && flight > 0.5).then(|| pursuit.min(flight))
+}
+
+/// Plus longue chaîne de poursuite sans type répété (programmation dynamique sur les
+/// sous-ensembles de types : au plus 2^12 × 12 états)
+fn longest_chase_chain(genotype: &Genotype) -> usize {
+    let type_count = genotype.type_count;
+    let chases: Vec<Vec<bool>> = (0..type_count)
+        .map(|a| (0..type_count).map(|b| chase_strength(genotype, a, b).is_some()).collect())
+        .collect();
+
+    // reachable[mask][end] : chaîne passant exactement par `mask` et finissant par `end`
+    let mut reachable = vec![vec![false; type_count]; 1 << type_count];
+    let mut longest = 0;
+    for end in 0..type_count {
+        reachable[1 << end][end] = true;
+    }
+    for mask in 1..1usize << type_count {
+        for end in 0..type_count {
+            if !reachable[mask][end] {
+                continue;
+            }
+            longest = longest.max(mask.count_ones() as usize);
+            for next in 0..type_count {
+                if mask & (1 << next) == 0 && chases[end][next] {
+                    reachable[mask | (1 << next)][next] = true;
+                }
+            }
+        }
+    }
+    // Une chaîne d'un seul type n'est pas une poursuite
+    if longest < 2 { 0 } else { longest }
+}
+
+/// Heuristiques sur la matrice (forces dans [-1, 1]) ; la confiance mesure de combien le
+/// génome dépasse le seuil de chaque critère
+pub fn predict_emergent_behaviors(genotype: &Genotype) -> Vec<(&'static str, f32)> {
+    let type_count = genotype.type_count;
+    let mut behaviors = Vec::new();
+    if type_count == 0 || genotype.force_matrix.len() < type_count * type_count {
+        return behaviors;
+    }
+
+    let self_forces: Vec<f32> = (0..type_count).map(|t| genotype.get_force(t, t)).collect();
+    let cross_forces: Vec<f32> = (0..type_count)
+        .flat_map(|a| (0..type_count).filter(move |b| *b != a).map(move |b| (a, b)))
+        .map(|(a, b)| genotype.get_force(a, b))
+        .collect();
+
+    let mean = |values: &[f32]| {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f32>() / values.len() as f32
+        }
+    };
+    let self_mean = mean(&self_forces);
+    let cross_mean = mean(&cross_forces);
+    let overall_mean = mean(&genotype.force_matrix);
+    let food_mean = mean(&genotype.food_forces);
+
+    if cross_mean > 0.3 {
+        behaviors.push((BEHAVIOR_SWARM, strength(cross_mean, 0.3, 1.0)));
+    }
+
+    if self_mean < -0.5 && cross_mean < 0.0 {
+        let confidence = strength(self_mean, -0.5, -1.0).min(strength(cross_mean, 0.0, -1.0));
+        behaviors.push((BEHAVIOR_TERRITORIAL, confidence));
+    }
+
+    let strongest_chase = (0..type_count)
+        .flat_map(|a| (0..type_count).map(move |b| (a, b)))
+        .filter_map(|(a, b)| chase_strength(genotype, a, b))
+        .reduce(f32::max);
+    if let Some(chase) = strongest_chase {
+        behaviors.push((BEHAVIOR_CHASE, strength(chase, 0.5, 1.0)));
+    }
+
+    if type_count >= CHASE_CHAIN_MIN_TYPES {
+        let chain = longest_chase_chain(genotype);
+        if chain >= CHASE_CHAIN_MIN_TYPES {
+            let confidence = strength(
+                chain as f32,
+                (CHASE_CHAIN_MIN_TYPES - 1) as f32,
+                type_count as f32,
+            );
+            behaviors.push((BEHAVIOR_CHASE_CHAIN, confidence));
+        }
+    }
+
+    // Poursuite équilibrée : A attire vers B autant que B fuit A, la distance se maintient et
+    // le couple tourne au lieu de se rattraper
+    let orbit_pairs: Vec<f32> = (0..type_count)
+        .flat_map(|a| (0..type_count).filter(move |b| *b != a).map(move |b| (a, b)))
+        .filter_map(|(a, b)| {
+            let pursuit = genotype.get_force(a, b);
+            let flight = -genotype.get_force(b, a);
+            if pursuit < 0.3 || flight < 0.3 {
+                return None;
+            }
+            let balance = 1.0 - (pursuit - flight).abs() / pursuit.max(flight);
+            (balance >= 0.75).then_some(balance * pursuit.min(flight).min(1.0))
+        })
+        .collect();
+    if !orbit_pairs.is_empty() {
+        behaviors.push((BEHAVIOR_ORBIT, strength(mean(&orbit_pairs), 0.0, 0.8)));
+    }
+
+    if self_forces.iter().filter(|force| **force > 0.3).count() * 2 >= type_count {
+        let clustered: Vec<f32> = self_forces.iter().copied().filter(|force| *force > 0.3).collect();
+        behaviors.push((BEHAVIOR_CLUSTERING, strength(mean(&clustered), 0.3, 1.0)));
+    }
+
+    // Types qui repoussent tous les autres et en sont repoussés
+    if type_count >= 2 {
+        let segregated: Vec<f32> = (0..type_count)
+            .filter_map(|t| {
+                (0..type_count)
+                    .filter(|other| *other != t)
+                    .map(|other| -genotype.get_force(t, other).max(genotype.get_force(other, t)))
+                    .reduce(f32::min)
+                    .filter(|weakest| *weakest > 0.2)
+            })
+            .collect();
+        if !segregated.is_empty() {
+            let coverage = segregated.len() as f32 / type_count as f32;
+            let confidence = strength(mean(&segregated), 0.2, 1.0) * (0.5 + 0.5 * coverage);
+            behaviors.push((BEHAVIOR_SEGREGATION, confidence));
+        }
+    }
+
+    if overall_mean < -0.3 {
+        behaviors.push((BEHAVIOR_DISPERSION, strength(overall_mean, -0.3, -1.0)));
+    }
+
+    if food_mean > 0.5 {
+        behaviors.push((BEHAVIOR_FORAGING, strength(food_mean, 0.5, 1.0)));
+    } else if food_mean < -0.5 {
+        behaviors.push((BEHAVIOR_FOOD_AVOIDANCE, strength(food_mean, -0.5, -1.0)));
+    }
+
+    behaviors
+}
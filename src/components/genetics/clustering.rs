@@ -0,0 +1,116 @@
+use crate::components::genetics::genotype::Genotype;
+use std::collections::BTreeMap;
+
+/// Groupe de génomes stratégiquement proches
+#[derive(Clone, Debug)]
+pub struct GenomeCluster {
+    /// Génomes de types différents ne sont jamais comparés : chaque groupe a un seul nombre de types
+    pub type_count: usize,
+    /// Index dans la liste passée à `cluster_genomes`
+    pub members: Vec<usize>,
+    /// Membre le plus proche de tous les autres (médoïde)
+    pub representative: usize,
+    /// Distance génétique moyenne entre les paires du groupe, 0 pour un singleton
+    pub mean_distance: f32,
+}
+
+/// Classification ascendante hiérarchique (lien moyen) : fusionne les deux groupes les plus
+/// proches jusqu'à en avoir `cluster_count` par nombre de types. Les génomes de nombres de
+/// types différents sont regroupés séparément, leurs matrices n'étant pas comparables
+pub fn cluster_genomes(genotypes: &[Genotype], cluster_count: usize) -> Vec<GenomeCluster> {
+    let mut by_type_count: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, genotype) in genotypes.iter().enumerate() {
+        by_type_count.entry(genotype.type_count).or_default().push(index);
+    }
+
+    let mut clusters = Vec::new();
+    for (type_count, indices) in by_type_count {
+        let distances: Vec<Vec<f32>> = indices
+            .iter()
+            .map(|&a| {
+                indices
+                    .iter()
+                    .map(|&b| genotypes[a].genetic_distance(&genotypes[b]))
+                    .collect()
+            })
+            .collect();
+
+        // Groupes en positions locales (index dans `indices`)
+        let mut groups: Vec<Vec<usize>> = (0..indices.len()).map(|i| vec![i]).collect();
+        let target = cluster_count.max(1);
+        while groups.len() > target {
+            let mut closest = (0, 1, f32::INFINITY);
+            for a in 0..groups.len() {
+                for b in a + 1..groups.len() {
+                    let linkage = average_distance(&distances, &groups[a], &groups[b]);
+                    if linkage < closest.2 {
+                        closest = (a, b, linkage);
+                    }
+                }
+            }
+            let merged = groups.remove(closest.1);
+            groups[closest.0].extend(merged);
+        }
+
+        for group in groups {
+            let representative = *group
+                .iter()
+                .min_by(|&&a, &&b| {
+                    let total = |i: usize| group.iter().map(|&j| distances[i][j]).sum::<f32>();
+                    total(a).total_cmp(&total(b))
+                })
+                .unwrap_or(&group[0]);
+            let pair_count = group.len() * (group.len() - 1) / 2;
+            let mean_distance = if pair_count == 0 {
+                0.0
+            } else {
+                let sum: f32 = group
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(k, &a)| group[k + 1..].iter().map(move |&b| (a, b)))
+                    .map(|(a, b)| distances[a][b])
+                    .sum();
+                sum / pair_count as f32
+            };
+
+            clusters.push(GenomeCluster {
+                type_count,
+                members: group.iter().map(|&local| indices[local]).collect(),
+                representative: indices[representative],
+                mean_distance,
+            });
+        }
+    }
+
+    clusters
+}
+
+/// Distance génétique moyenne de chaque génome aux autres génomes de même nombre de types,
+/// 0 pour un génome sans pair : plus elle est grande, plus il apporte de diversité
+pub fn diversity_contributions(genotypes: &[Genotype]) -> Vec<f32> {
+    genotypes
+        .iter()
+        .enumerate()
+        .map(|(index, genotype)| {
+            let distances: Vec<f32> = genotypes
+                .iter()
+                .enumerate()
+                .filter(|(other, peer)| *other != index && peer.type_count == genotype.type_count)
+                .map(|(_, peer)| genotype.genetic_distance(peer))
+                .collect();
+            if distances.is_empty() {
+                0.0
+            } else {
+                distances.iter().sum::<f32>() / distances.len() as f32
+            }
+        })
+        .collect()
+}
+
+fn average_distance(distances: &[Vec<f32>], a: &[usize], b: &[usize]) -> f32 {
+    let sum: f32 = a
+        .iter()
+        .flat_map(|&i| b.iter().map(move |&j| distances[i][j]))
+        .sum();
+    sum / (a.len() * b.len()) as f32
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+/// Cellules de la grille spatiale visitées par au moins une particule de la simulation
+#[derive(Component, Default, Debug, Clone)]
+pub struct Exploration {
+    pub visited: Vec<bool>,
+    pub visited_count: usize,
+}
+
+impl Exploration {
+    pub fn visit(&mut self, cell: usize, cell_count: usize) {
+        if self.visited.len() != cell_count {
+            self.visited = vec![false; cell_count];
+            self.visited_count = 0;
+        }
+        if let Some(visited) = self.visited.get_mut(cell)
+            && !*visited
+        {
+            *visited = true;
+            self.visited_count += 1;
+        }
+    }
+
+    /// Fraction des cellules visitées, entre 0 et 1
+    pub fn fraction(&self) -> f32 {
+        if self.visited.is_empty() {
+            0.0
+        } else {
+            self.visited_count as f32 / self.visited.len() as f32
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.visited.fill(false);
+        self.visited_count = 0;
+    }
+}
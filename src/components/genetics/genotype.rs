@@ -1,3 +1,7 @@
+use crate::globals::{
+    DEFAULT_MUTATION_RATE, FITNESS_HISTORY_LENGTH, GAUSSIAN_MUTATION_SIGMA, MAX_PARTICLE_TYPES,
+};
+use crate::resources::config::simulation::{CrossoverStrategy, MutationKind};
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -7,6 +11,9 @@ pub struct Genotype {
     pub force_matrix: Vec<f32>,  // Matrice des forces particule-particule
     pub food_forces: Vec<f32>,   // Forces de nourriture par type
     pub type_count: usize,
+    pub fitness_history: Vec<f32>, // Derniers scores obtenus par ce génome
+    /// Cellules de la matrice figées (ni mutation ni crossover), vide = aucune
+    pub locked_mask: Vec<bool>,
 }
 
 impl Genotype {
@@ -16,12 +23,21 @@ impl Genotype {
             force_matrix: vec![0.0; matrix_size],
             food_forces: vec![0.0; type_count],
             type_count,
+            fitness_history: Vec::new(),
+            locked_mask: Vec::new(),
         }
     }
 
-    /// Génère un génome aléatoire
-    pub fn random(type_count: usize) -> Self {
-        let mut rng = rand::rng();
+    /// Génome de départ connu (voir `set_interesting_forces`)
+    pub fn interesting(type_count: usize, allow_self_attraction: bool, rng: &mut impl Rng) -> Self {
+        let mut genotype = Self::new(type_count);
+        genotype.set_interesting_forces(allow_self_attraction, rng);
+        genotype
+    }
+
+    /// Génère un génome aléatoire. Sans `allow_self_attraction`, chaque type se repousse
+    /// lui-même pour éviter l'agglomération
+    pub fn random(type_count: usize, allow_self_attraction: bool, rng: &mut impl Rng) -> Self {
         let matrix_size = type_count * type_count;
 
         let force_matrix = (0..matrix_size)
@@ -29,7 +45,7 @@ impl Genotype {
                 let type_a = i / type_count;
                 let type_b = i % type_count;
 
-                if type_a == type_b {
+                if type_a == type_b && !allow_self_attraction {
                     // Auto-répulsion pour éviter l'agglomération
                     rng.random_range(-1.0..=-0.1)
                 } else {
@@ -47,7 +63,76 @@ impl Genotype {
             force_matrix,
             food_forces,
             type_count,
+            fitness_history: Vec::new(),
+            locked_mask: Vec::new(),
+        }
+    }
+
+    /// Vérifie la cohérence des dimensions et des valeurs du génome
+    pub fn validate(&self) -> Result<(), String> {
+        if self.type_count == 0 {
+            return Err("Le génome n'a aucun type de particule".to_string());
+        }
+        // Au-delà, le génome ne tient pas dans les buffers GPU
+        if self.type_count > MAX_PARTICLE_TYPES {
+            return Err(format!(
+                "{} types de particules, {} au maximum",
+                self.type_count, MAX_PARTICLE_TYPES
+            ));
+        }
+        if self.force_matrix.len() != self.type_count * self.type_count {
+            return Err(format!(
+                "Matrice de {} valeurs, {} attendues pour {} types",
+                self.force_matrix.len(),
+                self.type_count * self.type_count,
+                self.type_count
+            ));
+        }
+        if self.food_forces.len() != self.type_count {
+            return Err(format!(
+                "{} forces de nourriture, {} attendues",
+                self.food_forces.len(),
+                self.type_count
+            ));
+        }
+        if !self.locked_mask.is_empty() && self.locked_mask.len() != self.force_matrix.len() {
+            return Err(format!(
+                "Masque de verrouillage de {} valeurs, {} attendues",
+                self.locked_mask.len(),
+                self.force_matrix.len()
+            ));
+        }
+        if let Some(value) = self
+            .force_matrix
+            .iter()
+            .chain(&self.food_forces)
+            .find(|value| !value.is_finite())
+        {
+            return Err(format!("Valeur invalide dans le génome: {}", value));
         }
+        Ok(())
+    }
+
+    /// Vrai si la relation `type_a → type_b` est figée
+    pub fn is_locked(&self, type_a: usize, type_b: usize) -> bool {
+        self.locked_mask
+            .get(type_a * self.type_count + type_b)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    pub fn set_locked(&mut self, type_a: usize, type_b: usize, locked: bool) {
+        if type_a >= self.type_count || type_b >= self.type_count {
+            return;
+        }
+        if self.locked_mask.len() != self.force_matrix.len() {
+            self.locked_mask = vec![false; self.force_matrix.len()];
+        }
+        self.locked_mask[type_a * self.type_count + type_b] = locked;
+    }
+
+    fn is_index_locked(&self, index: usize) -> bool {
+        self.locked_mask.get(index).copied().unwrap_or(false)
     }
 
     /// Obtient la force entre deux types
@@ -69,42 +154,187 @@ impl Genotype {
         self.food_forces.get(particle_type).copied().unwrap_or(0.0)
     }
 
-    /// Crossover avec un autre génome
-    pub fn crossover(&self, other: &Self, rng: &mut impl Rng) -> Self {
-        let mut new_force_matrix = Vec::with_capacity(self.force_matrix.len());
-        let mut new_food_forces = Vec::with_capacity(self.food_forces.len());
+    /// Définit la force de nourriture pour un type
+    pub fn set_food_force(&mut self, particle_type: usize, force: f32) {
+        if let Some(food_force) = self.food_forces.get_mut(particle_type) {
+            *food_force = force;
+        }
+    }
 
-        // Crossover uniforme pour la matrice des forces
-        for i in 0..self.force_matrix.len() {
-            if rng.random_bool(0.5) {
-                new_force_matrix.push(self.force_matrix[i]);
-            } else {
-                new_force_matrix.push(other.force_matrix[i]);
-            }
+    /// Enregistre le score d'une époque en ne gardant que les plus récents
+    pub fn update_fitness_history(&mut self, score: f32) {
+        self.fitness_history.push(score);
+        if self.fitness_history.len() > FITNESS_HISTORY_LENGTH {
+            let excess = self.fitness_history.len() - FITNESS_HISTORY_LENGTH;
+            self.fitness_history.drain(..excess);
+        }
+    }
+
+    /// Pente (moindres carrés) de l'historique de fitness par époque, `None` sous deux scores
+    pub fn fitness_trend(&self) -> Option<f32> {
+        let count = self.fitness_history.len();
+        if count < 2 {
+            return None;
+        }
+        let mean_x = (count - 1) as f32 / 2.0;
+        let mean_y = self.fitness_history.iter().sum::<f32>() / count as f32;
+        let (covariance, variance) = self.fitness_history.iter().enumerate().fold(
+            (0.0, 0.0),
+            |(covariance, variance), (epoch, &score)| {
+                let dx = epoch as f32 - mean_x;
+                (covariance + dx * (score - mean_y), variance + dx * dx)
+            },
+        );
+        Some(covariance / variance)
+    }
+
+    /// Historique moyen de deux parents, aligné sur leurs époques les plus récentes : là où
+    /// un seul parent a un score, c'est le sien qui est repris
+    pub fn inherit_fitness_history(&mut self, parent_a: &Self, parent_b: &Self) {
+        let (a, b) = (&parent_a.fitness_history, &parent_b.fitness_history);
+        let length = a.len().max(b.len()).min(FITNESS_HISTORY_LENGTH);
+        // Du plus récent au plus ancien, puis remis dans l'ordre chronologique
+        let mut inherited: Vec<f32> = (0..length)
+            .map(|age| match (a.iter().rev().nth(age), b.iter().rev().nth(age)) {
+                (Some(x), Some(y)) => (x + y) / 2.0,
+                (Some(x), None) | (None, Some(x)) => *x,
+                (None, None) => 0.0,
+            })
+            .collect();
+        inherited.reverse();
+        self.fitness_history = inherited;
+    }
+
+    /// Distance génétique (RMS) entre deux génomes de même taille
+    pub fn genetic_distance(&self, other: &Self) -> f32 {
+        let squared_sum: f32 = self
+            .force_matrix
+            .iter()
+            .zip(&other.force_matrix)
+            .chain(self.food_forces.iter().zip(&other.food_forces))
+            .map(|(a, b)| (a - b).powi(2))
+            .sum();
+        let count = self.force_matrix.len() + self.food_forces.len();
+
+        if count == 0 {
+            0.0
+        } else {
+            (squared_sum / count as f32).sqrt()
         }
+    }
+
+    /// Identifiant compact du génome (FNV-1a sur les forces), pour l'affichage
+    pub fn genome_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        self.force_matrix
+            .iter()
+            .chain(&self.food_forces)
+            .flat_map(|force| force.to_bits().to_le_bytes())
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Crossover avec un autre génome selon une stratégie concrète (`Auto` doit être résolu avant)
+    pub fn crossover(&self, other: &Self, strategy: CrossoverStrategy, rng: &mut impl Rng) -> Self {
+        let type_count = self.type_count;
+        let mut child = self.clone();
+        child.fitness_history.clear();
 
-        // Crossover uniforme pour les forces de nourriture
-        for i in 0..self.food_forces.len() {
-            if rng.random_bool(0.5) {
-                new_food_forces.push(self.food_forces[i]);
-            } else {
-                new_food_forces.push(other.food_forces[i]);
+        match strategy {
+            CrossoverStrategy::Uniform | CrossoverStrategy::Auto => {
+                for (gene, other_gene) in child.force_matrix.iter_mut().zip(&other.force_matrix) {
+                    if rng.random_bool(0.5) {
+                        *gene = *other_gene;
+                    }
+                }
+                for (gene, other_gene) in child.food_forces.iter_mut().zip(&other.food_forces) {
+                    if rng.random_bool(0.5) {
+                        *gene = *other_gene;
+                    }
+                }
+            }
+            CrossoverStrategy::SymmetricRelations => {
+                for a in 0..type_count {
+                    for b in a..type_count {
+                        if rng.random_bool(0.5) {
+                            child.set_force(a, b, other.get_force(a, b));
+                            child.set_force(b, a, other.get_force(b, a));
+                        }
+                    }
+                }
+                for (gene, other_gene) in child.food_forces.iter_mut().zip(&other.food_forces) {
+                    if rng.random_bool(0.5) {
+                        *gene = *other_gene;
+                    }
+                }
+            }
+            CrossoverStrategy::TypeBlocks => {
+                for a in 0..type_count {
+                    if rng.random_bool(0.5) {
+                        for b in 0..type_count {
+                            child.set_force(a, b, other.get_force(a, b));
+                        }
+                        child.set_food_force(a, other.get_food_force(a));
+                    }
+                }
+            }
+            CrossoverStrategy::Blend { alpha, auto_weight } => {
+                let alpha = if auto_weight {
+                    self.blend_weight(other).unwrap_or(alpha)
+                } else {
+                    alpha
+                }
+                .clamp(0.0, 1.0);
+
+                for (gene, other_gene) in child
+                    .force_matrix
+                    .iter_mut()
+                    .zip(&other.force_matrix)
+                    .chain(child.food_forces.iter_mut().zip(&other.food_forces))
+                {
+                    *gene = (alpha * *gene + (1.0 - alpha) * other_gene).clamp(-2.0, 2.0);
+                }
             }
         }
 
-        Self {
-            force_matrix: new_force_matrix,
-            food_forces: new_food_forces,
-            type_count: self.type_count,
+        // Les cellules figées chez un parent sont transmises telles quelles (priorité à `self`)
+        if !self.locked_mask.is_empty() || !other.locked_mask.is_empty() {
+            child.locked_mask = vec![false; child.force_matrix.len()];
+            for index in 0..child.force_matrix.len() {
+                if self.is_index_locked(index) {
+                    child.force_matrix[index] = self.force_matrix[index];
+                    child.locked_mask[index] = true;
+                } else if other.is_index_locked(index) {
+                    child.force_matrix[index] = other.force_matrix[index];
+                    child.locked_mask[index] = true;
+                }
+            }
         }
+
+        child
+    }
+
+    /// Part de `self` dans un mélange d'après la dernière fitness des deux parents
+    /// (négatives ramenées à 0) ; `None` si l'une manque ou si les deux sont nulles
+    fn blend_weight(&self, other: &Self) -> Option<f32> {
+        let own = self.fitness_history.last()?.max(0.0);
+        let theirs = other.fitness_history.last()?.max(0.0);
+        let total = own + theirs;
+        (total > 0.0).then(|| own / total)
     }
 
     /// Applique une mutation
-    pub fn mutate(&mut self, mutation_rate: f32, rng: &mut impl Rng) {
-        // Mutation de la matrice des forces
-        for force in &mut self.force_matrix {
+    pub fn mutate(&mut self, mutation_rate: f32, kind: MutationKind, rng: &mut impl Rng) {
+        // Mutation de la matrice des forces (cellules figées exclues)
+        for (index, force) in self.force_matrix.iter_mut().enumerate() {
+            if self.locked_mask.get(index).copied().unwrap_or(false) {
+                continue;
+            }
             if rng.random::<f32>() < mutation_rate {
-                *force += rng.random_range(-0.2..=0.2);
+                *force += Self::mutation_step(kind, mutation_rate, rng);
                 *force = force.clamp(-2.0, 2.0);
             }
         }
@@ -112,27 +342,39 @@ impl Genotype {
         // Mutation des forces de nourriture
         for force in &mut self.food_forces {
             if rng.random::<f32>() < mutation_rate * 0.5 {
-                *force += rng.random_range(-0.2..=0.2);
+                *force += Self::mutation_step(kind, mutation_rate, rng);
                 *force = force.clamp(-2.0, 2.0);
             }
         }
     }
 
-    /// Retourne une matrice de toutes les forces d'interaction
-    pub fn get_force_matrix(&self) -> Vec<Vec<f32>> {
-        let mut matrix = vec![vec![0.0; self.type_count]; self.type_count];
-
-        for i in 0..self.type_count {
-            for j in 0..self.type_count {
-                matrix[i][j] = self.get_force(i, j);
+    /// Tire une perturbation. En gaussien, l'écart-type suit le taux de mutation
+    /// (déjà adapté à la diversité) relativement au taux par défaut
+    fn mutation_step(kind: MutationKind, mutation_rate: f32, rng: &mut impl Rng) -> f32 {
+        match kind {
+            MutationKind::Uniform => rng.random_range(-0.2..=0.2),
+            MutationKind::Gaussian => {
+                let sigma = GAUSSIAN_MUTATION_SIGMA
+                    * (mutation_rate / DEFAULT_MUTATION_RATE).clamp(0.5, 2.0);
+                // Box-Muller : 1 - u évite ln(0)
+                let u1 = 1.0 - rng.random::<f32>();
+                let u2 = rng.random::<f32>();
+                sigma * (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
             }
         }
+    }
 
-        matrix
+    /// Retourne une matrice de toutes les forces d'interaction
+    pub fn get_force_matrix(&self) -> Vec<Vec<f32>> {
+        (0..self.type_count)
+            .map(|i| (0..self.type_count).map(|j| self.get_force(i, j)).collect())
+            .collect()
     }
 
-    /// Génère des forces intéressantes prédéfinies
-    pub fn set_interesting_forces(&mut self) {
+    /// Génère des forces intéressantes prédéfinies. Les préréglages à 3 et 4 types gardent
+    /// leur auto-répulsion ; `allow_self_attraction` ne concerne que le tirage des autres,
+    /// fait avec `rng`
+    pub fn set_interesting_forces(&mut self, allow_self_attraction: bool, rng: &mut impl Rng) {
         // Efface les forces actuelles
         self.force_matrix.fill(0.0);
         self.food_forces.fill(0.0);
@@ -178,10 +420,9 @@ impl Genotype {
             },
             _ => {
                 // Configuration aléatoire pour autres nombres de types
-                let mut rng = rand::rng();
                 for i in 0..self.type_count {
                     for j in 0..self.type_count {
-                        let force = if i == j {
+                        let force = if i == j && !allow_self_attraction {
                             rng.random_range(-0.5..=-0.1)
                         } else {
                             rng.random_range(-1.0..=1.0)
@@ -193,4 +434,20 @@ impl Genotype {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn interesting_forces_follow_the_seed() {
+        // Sans préréglage (5 types), les forces sont tirées du générateur fourni
+        let draw = |seed| Genotype::interesting(5, false, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(draw(50).force_matrix, draw(50).force_matrix);
+        assert_eq!(draw(50).food_forces, draw(50).food_forces);
+        assert_ne!(draw(50).force_matrix, draw(51).force_matrix);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod behavior;
+pub mod clustering;
+pub mod exploration;
 pub mod genotype;
-pub mod score;
\ No newline at end of file
+pub mod score;
@@ -1,41 +1,113 @@
 pub const DEFAULT_PARTICLE_COUNT: usize = 100;
+pub const MAX_PARTICLE_COUNT: usize = 2000;
+/// Particules de chaque type ajoutées ou retirées par clic dans la barre de contrôle
+pub const PARTICLE_COUNT_STEP: usize = 5;
 pub const DEFAULT_PARTICLE_TYPES: usize = 3;
+/// Les matrices de forces ont `types²` valeurs : le coût des génomes, de la mutation et du
+/// buffer GPU croît au carré du nombre de types
+pub const MAX_PARTICLE_TYPES: usize = 12;
 pub const DEFAULT_SIMULATION_COUNT: usize = 6;
+pub const MAX_SIMULATION_COUNT: usize = 20;
 pub const DEFAULT_EPOCH_DURATION: f32 = 60.0; // secondes
-pub const DEFAULT_PARTICLES_PER_TYPE: usize = DEFAULT_PARTICLE_COUNT / DEFAULT_PARTICLE_TYPES;
 
-/// Timestep fixe pour la physique (60 FPS) - indépendant de la vitesse de simulation
+/// Temps simulé par frame à vitesse normale, et timestep par défaut
 pub const PHYSICS_TIMESTEP: f32 = 0.008;
+pub const MIN_PHYSICS_TIMESTEP: f32 = 0.002;
+pub const MAX_PHYSICS_TIMESTEP: f32 = 0.02;
+/// Au-delà, les forces fortes font diverger l'intégration
+pub const UNSTABLE_PHYSICS_TIMESTEP: f32 = 0.012;
+/// Temps de calcul max par frame en avance rapide
+pub const FAST_FORWARD_FRAME_BUDGET_MS: u64 = 30;
 
 // Paramètres de la grille
 pub const DEFAULT_GRID_WIDTH: f32 = 800.0;
 pub const DEFAULT_GRID_HEIGHT: f32 = 800.0;
 pub const DEFAULT_GRID_DEPTH: f32 = 800.0;
+pub const MIN_GRID_SIZE: f32 = 100.0;
+pub const MAX_GRID_SIZE: f32 = 2000.0;
 
 // Paramètres de la nourriture
 pub const DEFAULT_FOOD_COUNT: usize = 50;
+pub const MAX_FOOD_COUNT: usize = 200;
 pub const DEFAULT_FOOD_RESPAWN_TIME: f32 = 5.0; // secondes
 pub const DEFAULT_FOOD_VALUE: f32 = 1.0;
+pub const DEFAULT_FOOD_CLUSTERS: usize = 4;
+pub const DEFAULT_FOOD_CLUSTER_SPREAD: f32 = 60.0;
+/// Nombre de cellules de la heatmap de nourriture sur le plus grand axe horizontal
+pub const FOOD_HEATMAP_RESOLUTION: usize = 16;
 pub const FOOD_RADIUS: f32 = 2.0;
 
 // Paramètres des particules
 pub const PARTICLE_RADIUS: f32 = 4.0;
 pub const PARTICLE_MASS: f32 = 1.0;
-pub const MAX_VELOCITY: f32 = 200.0;
-pub const COLLISION_DAMPING: f32 = 0.5;
+pub const DEFAULT_MAX_VELOCITY: f32 = 200.0;
+/// Fraction de la vitesse conservée lors d'un rebond (0 = collé au mur, 1 = rebond parfait)
+pub const DEFAULT_COLLISION_DAMPING: f32 = 0.5;
 
 // Paramètres des forces
 pub const DEFAULT_MAX_FORCE_RANGE: f32 = 300.0;
+pub const EXPLORATION_CELL_SIZE: f32 = 50.0; // Taille des cellules pour mesurer l'exploration
+/// Nombre max de voisins pris en compte par particule (0 = illimité)
+pub const DEFAULT_MAX_INTERACTIONS: usize = 100;
+/// Plafond d'interactions par particule sur GPU : les voisines retenues tiennent dans un
+/// tableau de taille fixe du shader, un plafond plus haut y est ramené
+pub const GPU_MAX_INTERACTIONS: usize = 128;
 
 pub const FORCE_SCALE_FACTOR: f32 = 80.0;
+/// Temps (s) pour que la vitesse d'une particule soit divisée par deux
+pub const DEFAULT_VELOCITY_HALF_LIFE: f32 = 0.043;
 
-pub const MIN_DISTANCE: f32 = 0.001;
-pub const PARTICLE_REPULSION_STRENGTH: f32 = 100.0;
+/// Durée max d'exécution asynchrone du compute shader avant de bloquer la frame
+pub const GPU_MAX_ASYNC_MS: u64 = 250;
+
+/// Frames entre deux images d'un enregistrement vidéo
+pub const DEFAULT_VIDEO_FRAME_INTERVAL: u32 = 2;
+/// Captures en cours au-delà desquelles les images suivantes sont abandonnées
+pub const VIDEO_MAX_PENDING_FRAMES: usize = 4;
+
+/// Taille de workgroup du compute shader, sûre sur tous les GPU
+pub const DEFAULT_GPU_WORKGROUP_SIZE: u32 = 64;
+/// Tailles proposées via `--workgroup-size=N`
+pub const GPU_WORKGROUP_SIZES: [u32; 3] = [64, 128, 256];
+/// Capacité des buffers de particules du compute shader (toutes simulations confondues)
+pub const GPU_MAX_PARTICLES: usize = 16384;
+/// Cellules max par axe de la grille spatiale GPU, les cellules grandissent au-delà
+pub const GPU_GRID_MAX_CELLS_PER_AXIS: usize = 16;
+/// Les scores GPU sont accumulés en entiers (atomiques) : valeur × ce facteur
+pub const GPU_SCORE_FIXED_POINT: f32 = 1000.0;
+/// Passes de compute encodées par dispatch, une par pas de physique : de quoi jouer
+/// VeryFast (×4) au plus petit timestep
+pub const GPU_MAX_STEPS_PER_FRAME: usize = 16;
+/// Pas simulés par la validation CPU/GPU
+pub const DEFAULT_GPU_VALIDATION_STEPS: usize = 10;
+/// Écart de position toléré par la validation CPU/GPU, en unités du monde
+pub const DEFAULT_GPU_VALIDATION_TOLERANCE: f32 = 0.05;
+
+/// Au-delà de ce nombre de particules, la physique CPU devient le goulot d'étranglement
+pub const GPU_RECOMMENDED_PARTICLES: usize = 500;
 
 // Paramètres génétiques
 pub const DEFAULT_ELITE_RATIO: f32 = 0.1; // 10% des génomes gardés
 pub const DEFAULT_MUTATION_RATE: f32 = 0.1; // 10% de chance de mutation
+pub const DEFAULT_ELITE_MUTATION_RATE: f32 = DEFAULT_MUTATION_RATE * 0.1; // Légère perturbation des élites, 0 = copie exacte
 pub const DEFAULT_CROSSOVER_RATE: f32 = 0.7; // 70% de crossover
+pub const DEFAULT_TOURNAMENT_SIZE: usize = 3; // Candidats par tournoi de sélection
+pub const DEFAULT_BLEND_ALPHA: f32 = 0.5; // Part du premier parent dans un crossover par mélange
+pub const GAUSSIAN_MUTATION_SIGMA: f32 = 0.1; // Écart-type de base de la mutation gaussienne
+pub const DEFAULT_SHARING_RADIUS: f32 = 0.3; // Distance génétique définissant une niche
+pub const MAX_SHARING_DENOMINATOR: f32 = 3.0; // Pénalité max d'une niche, même si tout a convergé
+pub const DEFAULT_HALL_OF_FAME_SIZE: usize = 10; // Génomes conservés sur tout le run
+pub const DEFAULT_GENOME_ARCHIVE_DEPTH: usize = 1; // Meilleurs génomes archivés par époque
+pub const GENOME_ARCHIVE_CAPACITY: usize = 1000; // Époques archivées avant d'oublier les plus anciennes
+pub const FITNESS_HISTORY_LENGTH: usize = 10; // Scores conservés par génome
+pub const DEFAULT_STAGNATION_THRESHOLD: usize = 3; // Époques sans amélioration avant adaptation
+pub const DEFAULT_ADAPT_MUTATION_STEP: f32 = 0.05; // Hausse du taux de mutation par adaptation
+pub const DEFAULT_ADAPT_ELITE_STEP: f32 = 0.02; // Baisse du ratio d'élites par adaptation
+pub const ADAPT_MAX_MUTATION_RATE: f32 = 0.5; // Plafond du taux de mutation adapté
+pub const ADAPT_MIN_ELITE_RATIO: f32 = 0.01; // Plancher du ratio d'élites adapté
+pub const DEFAULT_DIVERSITY_COLLAPSE_THRESHOLD: f32 = 0.05; // Diversité sous laquelle la population est jugée convergée
+pub const DEFAULT_DIVERSITY_COLLAPSE_PATIENCE: usize = 5; // Époques consécutives sous le seuil avant d'agir
+pub const DIVERSITY_INJECTION_RATIO: f32 = 0.5; // Part des non-élites remplacées par des génomes aléatoires
 
 // Paramètres de rendu
-pub const PARTICLE_SUBDIVISIONS: u32 = 8;
\ No newline at end of file
+pub const PARTICLE_SUBDIVISIONS: u32 = 8; // Icosphères en qualité haute
\ No newline at end of file
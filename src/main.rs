@@ -1,8 +1,14 @@
 // src/main.rs
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::asset::AssetPlugin;
 use bevy::diagnostic::{FrameCount, FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
+use bevy::input::InputPlugin;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
 use bevy::window::{PresentMode, WindowMode};
 use bevy_app_compute::prelude::*;
+use std::time::Duration;
 
 mod components;
 mod globals;
@@ -16,11 +22,18 @@ use crate::states::app::AppState;
 use crate::plugins::core::camera::CameraPlugin;
 use crate::plugins::core::setup::SetupPlugin;
 use crate::plugins::simulation::compute::ParticleComputePlugin;
+use crate::plugins::simulation::headless::HeadlessPlugin;
 use crate::plugins::simulation::simulation::SimulationPlugin;
 use crate::plugins::simulation::visualizer::VisualizerPlugin;
 use crate::plugins::ui::ui_plugin::UIPlugin;
 
 fn main() {
+    // --headless : évolution en batch, sans fenêtre ni rendu
+    if std::env::args().any(|arg| arg == "--headless") {
+        run_headless();
+        return;
+    }
+
     App::new()
         .add_plugins((
             DefaultPlugins.set(WindowPlugin {
@@ -56,6 +69,19 @@ fn main() {
         .run();
 }
 
+fn run_headless() {
+    App::new()
+        .add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+            LogPlugin::default(),
+            StatesPlugin,
+            AssetPlugin::default(),
+            InputPlugin,
+        ))
+        .add_plugins((SetupPlugin, SimulationPlugin, HeadlessPlugin))
+        .run();
+}
+
 fn make_visible(mut window: Single<&mut Window>, frames: Res<FrameCount>) {
     if frames.0 == 3 {
         window.visible = true;
@@ -82,6 +108,9 @@ fn exit_game(
             AppState::Visualizer => {
                 next_state.set(AppState::MainMenu);
             }
+            AppState::Finished => {
+                next_state.set(AppState::MainMenu);
+            }
         }
     }
 }
\ No newline at end of file
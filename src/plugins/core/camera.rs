@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use crate::resources::world::camera::CameraSettings;
 use crate::resources::world::grid::GridParameters;
+use crate::states::app::AppState;
+use crate::systems::rendering::camera::{follow_camera_targets, orbit, orbit_viewport_cameras};
 use crate::systems::rendering::viewport_manager::ViewportCamera;
 
 pub struct CameraPlugin;
@@ -10,23 +12,38 @@ impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CameraSettings>();
         app.add_systems(Startup, setup_default_camera);
-        app.add_systems(Update, (manage_default_camera, update_default_camera_distance)); 
+        app.add_systems(
+            Update,
+            (
+                manage_default_camera,
+                update_default_camera_distance,
+                orbit.after(update_default_camera_distance),
+                orbit_viewport_cameras.run_if(in_state(AppState::Simulation)),
+                follow_camera_targets
+                    .after(orbit)
+                    .after(orbit_viewport_cameras)
+                    .run_if(in_state(AppState::Simulation).or(in_state(AppState::Visualization))),
+            ),
+        );
+        app.add_systems(OnEnter(AppState::Simulation), reframe_default_camera);
+        app.add_systems(OnEnter(AppState::Visualization), reframe_default_camera);
     }
 }
 
 /// Marqueur pour la caméra par défaut
 #[derive(Component)]
-struct DefaultCamera;
+pub struct DefaultCamera;
 
 /// NOUVEAU : Calcule la distance adaptative pour la caméra par défaut
 fn calculate_default_camera_distance(grid: &GridParameters) -> f32 {
-    // Calculer la diagonale 3D de la grille
-    let diagonal_3d = (grid.width.powi(2) + grid.height.powi(2) + grid.depth.powi(2)).sqrt();
-
-    // Distance pour voir confortablement toute la grille
-    let distance = diagonal_3d * 0.85;
+    CameraSettings::ideal_orbit_distance(grid)
+}
 
-    distance.max(300.0) // Distance minimale de sécurité
+/// Place la caméra par défaut à `distance` de l'origine, dans la direction initiale : la
+/// même distance que `orbit`, pour que le premier zoom ne fasse pas sauter la vue
+fn default_camera_transform(distance: f32) -> Transform {
+    let position = Vec3::new(0.7, 0.8, 0.7).normalize() * distance;
+    Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y)
 }
 
 /// Configure une caméra par défaut au démarrage - AMÉLIORÉ
@@ -36,16 +53,9 @@ fn setup_default_camera(
 ) {
     let camera_distance = calculate_default_camera_distance(&grid_params);
 
-    let camera_position = Vec3::new(
-        camera_distance * 0.7,
-        camera_distance * 0.8,
-        camera_distance * 0.7
-    );
-
     commands.spawn((
         Camera3d::default(),
-        Transform::from_translation(camera_position)
-            .looking_at(Vec3::ZERO, Vec3::Y),
+        default_camera_transform(camera_distance),
         DefaultCamera,
         RenderLayers::from_layers(&[0, 1]),
     ));
@@ -59,37 +69,43 @@ fn update_default_camera_distance(
     mut default_cameras: Query<&mut Transform, With<DefaultCamera>>,
     mut camera_settings: ResMut<CameraSettings>,
 ) {
-    // Ne s'exécute que si les paramètres de grille ont changé
-    if !grid_params.is_changed() {
+    // Ne s'exécute que si les paramètres de grille ont changé, et ne recadre que si
+    // leurs dimensions diffèrent (le zoom à la molette est conservé sinon)
+    if !grid_params.is_changed() || !camera_settings.update_for_grid(&grid_params) {
         return;
     }
 
-    let new_distance = calculate_default_camera_distance(&grid_params);
-
-    // Mettre à jour la distance d'orbite dans les paramètres
-    camera_settings.orbit_distance = new_distance;
-
-    // Mettre à jour la position de la caméra par défaut si elle existe
+    let new_distance = camera_settings.orbit_distance;
+    camera_settings.focus = Vec3::ZERO;
     for mut transform in default_cameras.iter_mut() {
-        let new_position = Vec3::new(
-            new_distance * 0.7,
-            new_distance * 0.8,
-            new_distance * 0.7
-        );
-
-        *transform = Transform::from_translation(new_position)
-            .looking_at(Vec3::ZERO, Vec3::Y);
+        *transform = default_camera_transform(new_distance);
     }
 
     info!("🔄 Caméra par défaut adaptée à la nouvelle grille - Distance: {:.0}", new_distance);
 }
 
+/// Recadre la caméra par défaut à l'entrée en simulation ou en visualisation
+fn reframe_default_camera(
+    grid_params: Res<GridParameters>,
+    mut default_cameras: Query<&mut Transform, With<DefaultCamera>>,
+    mut camera_settings: ResMut<CameraSettings>,
+) {
+    camera_settings.framed_grid = None;
+    camera_settings.update_for_grid(&grid_params);
+    camera_settings.focus = Vec3::ZERO;
+
+    for mut transform in default_cameras.iter_mut() {
+        *transform = default_camera_transform(camera_settings.orbit_distance);
+    }
+}
+
 /// Désactive la caméra par défaut quand des viewports sont créés
 fn manage_default_camera(
     mut commands: Commands,
     default_camera: Query<Entity, With<DefaultCamera>>,
     viewport_cameras: Query<Entity, With<ViewportCamera>>,
     grid_params: Res<GridParameters>,
+    mut camera_settings: ResMut<CameraSettings>,
 ) {
     // S'il y a des caméras de viewport, supprimer la caméra par défaut
     if !viewport_cameras.is_empty() {
@@ -101,19 +117,14 @@ fn manage_default_camera(
     else if viewport_cameras.is_empty() && default_camera.is_empty() {
         let camera_distance = calculate_default_camera_distance(&grid_params);
 
-        let camera_position = Vec3::new(
-            camera_distance * 0.7,
-            camera_distance * 0.8,
-            camera_distance * 0.7
-        );
-
         commands.spawn((
             Camera3d::default(),
-            Transform::from_translation(camera_position)
-                .looking_at(Vec3::ZERO, Vec3::Y),
+            default_camera_transform(camera_distance),
             DefaultCamera,
             RenderLayers::from_layers(&[0, 1]),
         ));
+        camera_settings.orbit_distance = camera_distance;
+        camera_settings.focus = Vec3::ZERO;
 
         info!("🎥 Caméra par défaut recréée avec distance adaptée: {:.0}", camera_distance);
     }
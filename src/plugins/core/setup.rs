@@ -1,5 +1,7 @@
 use crate::resources::config::food::FoodParameters;
 use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
 use crate::resources::config::simulation::SimulationParameters;
 use crate::resources::world::boundary::BoundaryMode;
 use crate::resources::world::grid::GridParameters;
@@ -16,5 +18,7 @@ impl Plugin for SetupPlugin {
         app.init_resource::<SimulationParameters>();
         app.init_resource::<FoodParameters>();
         app.init_resource::<BoundaryMode>();
+        app.init_resource::<RenderQuality>();
+        app.init_resource::<RenderStyle>();
     }
 }
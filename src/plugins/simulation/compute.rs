@@ -1,14 +1,32 @@
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
+use bevy::render::render_resource::{ShaderDefVal, WgpuFeatures};
+use bevy::render::renderer::RenderDevice;
 use bevy_app_compute::prelude::*;
-use crate::components::entities::food::Food;
+use crate::components::entities::food::{Food, FoodRespawnTimer, FoodValue};
 use crate::components::entities::particle::{Particle, ParticleType, Velocity};
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
-use crate::resources::config::simulation::{SimulationParameters, SimulationSpeed};
+use crate::components::genetics::score::Score;
+use crate::globals::{
+    DEFAULT_GPU_WORKGROUP_SIZE, GPU_GRID_MAX_CELLS_PER_AXIS, GPU_MAX_ASYNC_MS,
+    GPU_MAX_INTERACTIONS, GPU_MAX_PARTICLES, GPU_MAX_STEPS_PER_FRAME, GPU_SCORE_FIXED_POINT,
+    GPU_WORKGROUP_SIZES, MAX_FOOD_COUNT, MAX_PARTICLE_TYPES, MAX_SIMULATION_COUNT,
+};
+use crate::plugins::simulation::compute_validation::{
+    ComputeValidation, advance_compute_validation, start_compute_validation,
+    validation_requested, validation_running,
+};
+use crate::systems::simulation::collision::consume_food;
+use crate::systems::simulation::spatial::SpatialGrid;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::{ScoringEnabled, SimulationParameters};
 use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::food_heatmap::FoodHeatmap;
 use crate::resources::world::grid::GridParameters;
 use crate::states::app::AppState;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 pub struct ParticleComputePlugin;
 
@@ -16,20 +34,204 @@ pub struct ParticleComputePlugin;
 #[derive(Resource, Default)]
 pub struct ComputeEnabled(pub bool);
 
+/// Suivi de la nourriture envoyée au GPU, pour rendre les collisions relues à leurs entités
+#[derive(Resource, Default)]
+pub struct GpuFoodReadback {
+    /// Entités dans l'ordre du buffer `food_positions`
+    pub food_order: Vec<Entity>,
+}
+
+/// Particules envoyées au GPU : chaque résultat revient à sa particule même si des
+/// particules ont été ajoutées ou retirées entre l'envoi et la relecture
+#[derive(Resource, Default)]
+pub struct GpuParticleReadback {
+    /// Entités dans l'ordre du buffer `positions` au dernier envoi
+    pub uploaded: Vec<Entity>,
+    /// Ordre de l'envoi précédent, dont les résultats sont relus par `apply_compute_results`
+    pub in_flight: Vec<Entity>,
+}
+
+/// Nombre de frames écoulées sans résultat GPU disponible
+#[derive(Resource, Default)]
+pub struct GpuReadbackLatency {
+    pub pending_frames: u32,
+}
+
+/// Chronométrage du compute (`--gpu-timing`) : temps entre la soumission et la
+/// disponibilité des résultats, lecture comprise
+#[derive(Resource, Debug)]
+pub struct GpuComputeTiming {
+    pub enabled: bool,
+    dispatched_at: Option<Instant>,
+    dispatched_iterations: u32,
+    pub last_round_trip: Option<Duration>,
+    pub last_iterations: u32,
+}
+
+impl Default for GpuComputeTiming {
+    fn default() -> Self {
+        Self {
+            enabled: std::env::args().any(|arg| arg == "--gpu-timing"),
+            dispatched_at: None,
+            dispatched_iterations: 0,
+            last_round_trip: None,
+            last_iterations: 0,
+        }
+    }
+}
+
+impl GpuComputeTiming {
+    /// Durée moyenne d'un pas du dernier dispatch (Fast/VeryFast en enchaînent plusieurs),
+    /// passes inactives comprises
+    pub fn per_iteration(&self) -> Option<Duration> {
+        self.last_round_trip
+            .filter(|_| self.last_iterations > 0)
+            .map(|round_trip| round_trip / self.last_iterations)
+    }
+}
+
+/// Recherche des voisines dans le compute shader
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NeighborSearch {
+    /// Grille spatiale construite côté CPU : seules les 27 cellules voisines sont parcourues
+    Grid,
+    /// Toutes les particules (`--gpu-neighbors=brute`), plus simple pour les petits effectifs
+    BruteForce,
+}
+
+impl NeighborSearch {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NeighborSearch::Grid => "Grille spatiale",
+            NeighborSearch::BruteForce => "Exhaustive",
+        }
+    }
+}
+
+/// Réglages de dispatch du compute shader, figés à la création du pipeline
+#[derive(Resource, Debug)]
+pub struct GpuDispatchSettings {
+    /// Taille demandée (`--workgroup-size=N`), parmi `GPU_WORKGROUP_SIZES`
+    pub requested_workgroup_size: u32,
+    /// Taille retenue après validation contre les limites du GPU
+    pub workgroup_size: u32,
+    pub neighbor_search: NeighborSearch,
+}
+
+use gpu_layout::{GpuGridParams, GpuTypeProperties};
+
+mod gpu_layout {
+    // Les fonctions `check` générées par `ShaderType` pour chaque champ ne sont jamais
+    // appelées : le lint ne peut être levé qu'au niveau du module
+    #![allow(dead_code)]
+
+    use crate::globals::MAX_PARTICLE_TYPES;
+    use bevy::prelude::{UVec4, Vec4};
+    use bevy::render::render_resource::ShaderType;
+
+    /// Paramètres de la grille spatiale, miroir de `SpatialGridParams` dans le shader
+    /// (vec4 pour une disposition identique côté Rust et WGSL)
+    #[repr(C)]
+    #[derive(ShaderType, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct GpuGridParams {
+        /// Cellules par axe, w = nombre total de cellules
+        pub dims: UVec4,
+        pub cell_extent: Vec4,
+        pub half_extents: Vec4,
+    }
+
+    /// Rayon et masse de chaque type, miroir de `ParticleTypeProperties` dans le shader :
+    /// quatre types par vec4, un tableau uniforme ayant un pas de 16 octets
+    #[repr(C)]
+    #[derive(ShaderType, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct GpuTypeProperties {
+        pub radii: [Vec4; MAX_PARTICLE_TYPES.div_ceil(4)],
+        pub masses: [Vec4; MAX_PARTICLE_TYPES.div_ceil(4)],
+    }
+}
+
+impl Default for GpuDispatchSettings {
+    fn default() -> Self {
+        let requested_workgroup_size = std::env::args()
+            .find_map(|arg| {
+                arg.strip_prefix("--workgroup-size=")
+                    .and_then(|value| value.parse().ok())
+            })
+            .unwrap_or(DEFAULT_GPU_WORKGROUP_SIZE);
+
+        let neighbor_search = if std::env::args().any(|arg| arg == "--gpu-neighbors=brute") {
+            NeighborSearch::BruteForce
+        } else {
+            NeighborSearch::Grid
+        };
+
+        Self {
+            requested_workgroup_size,
+            workgroup_size: DEFAULT_GPU_WORKGROUP_SIZE,
+            neighbor_search,
+        }
+    }
+}
+
+/// Shader defs du pipeline : `ComputeShader::shader_defs` est statique
+static WORKGROUP_SHADER_DEFS: OnceLock<Vec<ShaderDefVal>> = OnceLock::new();
+
+/// Garde la taille demandée si elle est proposée et compatible avec le GPU, sinon 64
+fn validate_workgroup_size(requested: u32, max_invocations: u32, max_size_x: u32) -> u32 {
+    if !GPU_WORKGROUP_SIZES.contains(&requested) {
+        warn!(
+            "GPU: workgroup de {} non proposé ({:?}), repli sur {}",
+            requested, GPU_WORKGROUP_SIZES, DEFAULT_GPU_WORKGROUP_SIZE
+        );
+        return DEFAULT_GPU_WORKGROUP_SIZE;
+    }
+
+    if requested > max_size_x || !max_invocations.is_multiple_of(requested) {
+        warn!(
+            "GPU: workgroup de {} incompatible (max {} invocations, {} en x), repli sur {}",
+            requested, max_invocations, max_size_x, DEFAULT_GPU_WORKGROUP_SIZE
+        );
+        return DEFAULT_GPU_WORKGROUP_SIZE;
+    }
+
+    info!("GPU: workgroup de {} invocations", requested);
+    requested
+}
+
 impl Plugin for ParticleComputePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ComputeEnabled>()
+            .init_resource::<GpuDispatchSettings>()
+            .init_resource::<GpuComputeTiming>()
+            .init_resource::<GpuFoodReadback>()
+            .init_resource::<GpuParticleReadback>()
+            .init_resource::<GpuReadbackLatency>()
+            .init_resource::<ComputeValidation>()
             .add_plugins(AppComputeWorkerPlugin::<ParticleComputeWorker>::default())
             .add_systems(
                 Update,
                 (
+                    track_readback_latency,
+                    // Avant la mise à jour des buffers, qui réécrit l'ordre de la nourriture
+                    apply_compute_food_results,
                     update_compute_buffers,
-                    run_compute_simulation.after(update_compute_buffers),
+                    run_compute_simulation
+                        .after(update_compute_buffers)
+                        .run_if(not(validation_requested)),
                     apply_compute_results.after(run_compute_simulation),
                 )
                     .chain()
                     .run_if(in_state(AppState::Simulation))
-                    .run_if(compute_enabled),
+                    .run_if(compute_enabled)
+                    .run_if(not(validation_running)),
+            )
+            // Après la relecture normale, que la validation ne fait pas perdre
+            .add_systems(
+                Update,
+                (start_compute_validation, advance_compute_validation)
+                    .chain()
+                    .after(apply_compute_results)
+                    .run_if(in_state(AppState::Simulation)),
             );
     }
 }
@@ -41,52 +243,100 @@ impl ComputeShader for ParticleComputeShader {
     fn shader() -> ShaderRef {
         "shaders/particle_compute.wgsl".into()
     }
+
+    fn shader_defs<'a>() -> &'a [ShaderDefVal] {
+        WORKGROUP_SHADER_DEFS.get().map(Vec::as_slice).unwrap_or_default()
+    }
 }
 
 #[derive(Resource)]
-struct ParticleComputeWorker;
+pub(crate) struct ParticleComputeWorker;
 
 impl ComputeWorker for ParticleComputeWorker {
     fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let render_device = world.resource::<RenderDevice>();
+        let limits = render_device.limits();
+        if world.resource::<GpuComputeTiming>().enabled {
+            // Le dispatch est encapsulé par bevy_app_compute : pas de timestamp_writes
+            // possible, on mesure l'aller-retour côté CPU
+            let timestamp_support = render_device
+                .features()
+                .contains(WgpuFeatures::TIMESTAMP_QUERY);
+            info!(
+                "GPU: chronométrage du compute activé (aller-retour CPU, timestamps GPU {})",
+                if timestamp_support { "supportés mais non exposés" } else { "non supportés" }
+            );
+        }
+        let workgroup_size = validate_workgroup_size(
+            world.resource::<GpuDispatchSettings>().requested_workgroup_size,
+            limits.max_compute_invocations_per_workgroup,
+            limits.max_compute_workgroup_size_x,
+        );
+        world.resource_mut::<GpuDispatchSettings>().workgroup_size = workgroup_size;
+        let neighbor_search = world.resource::<GpuDispatchSettings>().neighbor_search;
+        info!("GPU: recherche de voisines {}", neighbor_search.label());
+        WORKGROUP_SHADER_DEFS.get_or_init(|| {
+            let mut shader_defs = vec![ShaderDefVal::UInt("WORKGROUP_SIZE".into(), workgroup_size)];
+            if neighbor_search == NeighborSearch::Grid {
+                shader_defs.push("SPATIAL_GRID".into());
+            }
+            shader_defs
+        });
+
         let sim_params = world.resource::<SimulationParameters>();
         let grid_params = world.resource::<GridParameters>();
         let boundary_mode = world.resource::<BoundaryMode>();
 
-        let num_particles = sim_params.particle_count as u32;
-        let dt = 1.0f32 / 60.0; // 60 FPS
-        let world_size = grid_params
-            .width
-            .max(grid_params.height)
-            .max(grid_params.depth);
+        let num_particles = 0u32;
+        let world_extents = world_extents(grid_params);
         let num_types = sim_params.particle_types as u32;
-        let max_force_range = sim_params.max_force_range;
-        let boundary_mode_u32 = match boundary_mode {
-            BoundaryMode::Bounce => 0u32,
-            BoundaryMode::Teleport => 1u32,
-        };
+        let force_ranges = vec![sim_params.max_force_range; MAX_SIMULATION_COUNT];
+        let boundary_mode_u32 = boundary_mode.gpu_mask();
 
-        // Buffers initiaux vides
-        let positions = vec![[0.0f32; 4]; num_particles as usize];
-        let velocities = vec![[0.0f32; 4]; num_particles as usize];
-        let force_matrix = vec![0.0f32; (num_types * num_types) as usize];
-        let food_positions = vec![[0.0f32; 4]; 1]; // Au moins 1 élément
-        let food_forces = vec![0.0f32; num_types as usize];
+        // Buffers initiaux vides, dimensionnés pour la plus grande configuration du menu :
+        // ils ne sont pas redimensionnés après la création, seuls les compteurs changent
+        let positions = vec![[0.0f32; 4]; GPU_MAX_PARTICLES];
+        let velocities = vec![[0.0f32; 4]; GPU_MAX_PARTICLES];
+        let force_matrix = vec![0.0f32; MAX_SIMULATION_COUNT * MAX_PARTICLE_TYPES * MAX_PARTICLE_TYPES];
+        let food_positions = vec![[0.0f32; 4]; MAX_FOOD_COUNT];
+        let food_forces = vec![0.0f32; MAX_SIMULATION_COUNT * MAX_PARTICLE_TYPES];
         let food_count = 0u32;
+        let food_eaten = vec![0u32; MAX_FOOD_COUNT];
+        let score_accumulators = vec![0i32; MAX_SIMULATION_COUNT];
+        let grid_cell_capacity = GPU_GRID_MAX_CELLS_PER_AXIS.pow(3);
+        let spatial_grid = vec![0u32; grid_cell_capacity + 1 + GPU_MAX_PARTICLES];
+        let grid_params = GpuGridParams::default();
+        let max_interactions = gpu_max_interactions(sim_params);
+        let force_scale_factor = sim_params.force_scale_factor;
+        let max_velocity = sim_params.max_velocity;
+        let collision_damping = sim_params.collision_damping;
+        let force_curve = sim_params.force_curve.gpu_index();
+        let min_distance = sim_params.min_distance();
+        let velocity_half_life = sim_params.velocity_half_life;
+        let type_properties = GpuTypeProperties::new(world.resource::<ParticleTypesConfig>());
 
         info!(
-            "Initializing compute worker with {} particles, {} types",
-            num_particles, num_types
+            "Initializing compute worker for up to {} particles, {} simulations",
+            GPU_MAX_PARTICLES, MAX_SIMULATION_COUNT
         );
 
-        AppComputeWorkerBuilder::new(world)
+        let mut builder = AppComputeWorkerBuilder::new(world);
+        builder
             // Paramètres uniformes
             .add_uniform("num_particles", &num_particles)
-            .add_uniform("dt", &dt)
-            .add_uniform("world_size", &world_size)
+            .add_uniform("world_extents", &world_extents)
             .add_uniform("num_types", &num_types)
-            .add_uniform("max_force_range", &max_force_range)
             .add_uniform("boundary_mode", &boundary_mode_u32)
             .add_uniform("food_count", &food_count)
+            .add_uniform("max_interactions", &max_interactions)
+            .add_uniform("force_scale_factor", &force_scale_factor)
+            .add_uniform("max_velocity", &max_velocity)
+            .add_uniform("collision_damping", &collision_damping)
+            .add_uniform("grid_params", &grid_params)
+            .add_uniform("force_curve", &force_curve)
+            .add_uniform("min_distance", &min_distance)
+            .add_uniform("velocity_half_life", &velocity_half_life)
+            .add_uniform("type_properties", &type_properties)
             // Buffers de données
             .add_staging("positions", &positions)
             .add_staging("velocities", &velocities)
@@ -95,43 +345,136 @@ impl ComputeWorker for ParticleComputeWorker {
             .add_staging("force_matrix", &force_matrix)
             .add_staging("food_positions", &food_positions)
             .add_staging("food_forces", &food_forces)
-            // Passe de calcul
-            .add_pass::<ParticleComputeShader>(
-                [((num_particles + 63) / 64) as u32, 1, 1],
-                &[
-                    "num_particles",
-                    "dt",
-                    "world_size",
-                    "num_types",
-                    "max_force_range",
-                    "boundary_mode",
-                    "positions",
-                    "velocities",
-                    "new_positions",
-                    "new_velocities",
-                    "force_matrix",
-                    "food_positions",
-                    "food_count",
-                    "food_forces",
-                ],
-            )
+            .add_staging("force_ranges", &force_ranges)
+            .add_staging("food_eaten", &food_eaten)
+            .add_staging("score_accumulators", &score_accumulators)
+            .add_storage("spatial_grid", &spatial_grid);
+
+        // Une passe par pas de physique, chacune avec son timestep (voir
+        // `write_pass_timesteps`) ; le nombre de passes est figé à la création du pipeline
+        let workgroups = [(GPU_MAX_PARTICLES as u32).div_ceil(workgroup_size), 1, 1];
+        for pass in 0..GPU_MAX_STEPS_PER_FRAME {
+            let pass_dt = pass_timestep_name(pass);
+            builder.add_uniform(&pass_dt, &0.0f32);
+            let mut bindings = PASS_BINDINGS.to_vec();
+            bindings[1] = pass_dt.as_str();
+            builder.add_pass::<ParticleComputeShader>(workgroups, &bindings);
+            // La passe suivante part des résultats de celle-ci ; la dernière laisse les
+            // siens dans `new_*`, relus par `apply_compute_results`
+            if pass + 1 < GPU_MAX_STEPS_PER_FRAME {
+                builder
+                    .add_swap("positions", "new_positions")
+                    .add_swap("velocities", "new_velocities");
+            }
+        }
+
+        builder
+            // Lecture asynchrone : on ne bloque la frame qu'au-delà de GPU_MAX_ASYNC_MS
+            .asynchronous(Some(Duration::from_millis(GPU_MAX_ASYNC_MS)))
             .build()
     }
 }
 
+/// Buffers liés à chaque passe, dans l'ordre des bindings du shader ; le timestep (binding 1)
+/// est propre à chaque passe
+const PASS_BINDINGS: [&str; 26] = [
+    "num_particles",
+    "dt",
+    "world_extents",
+    "num_types",
+    "force_ranges",
+    "boundary_mode",
+    "positions",
+    "velocities",
+    "new_positions",
+    "new_velocities",
+    "force_matrix",
+    "food_positions",
+    "food_count",
+    "food_forces",
+    "max_interactions",
+    "force_scale_factor",
+    "max_velocity",
+    "collision_damping",
+    "food_eaten",
+    "score_accumulators",
+    "spatial_grid",
+    "grid_params",
+    "force_curve",
+    "min_distance",
+    "velocity_half_life",
+    "type_properties",
+];
+
+fn pass_timestep_name(pass: usize) -> String {
+    format!("dt_{}", pass)
+}
+
+/// Timestep de chaque passe pour avancer de `steps` pas : des pas entiers, un pas raccourci
+/// pour le reste, puis des passes à 0 qui recopient leur entrée. Le temps simulé par
+/// dispatch vaut exactement `steps` × `timestep`, sans report d'une frame à l'autre
+fn pass_timesteps(steps: f32, timestep: f32) -> [f32; GPU_MAX_STEPS_PER_FRAME] {
+    std::array::from_fn(|pass| {
+        let fraction = (steps - pass as f32).clamp(0.0, 1.0);
+        // Les résidus d'arrondi de `physics_steps_per_frame` ne valent pas une passe
+        if fraction < 1e-3 { 0.0 } else { fraction * timestep }
+    })
+}
+
+/// Écrit les timesteps du prochain dispatch ; renvoie le nombre de passes actives
+pub(crate) fn write_pass_timesteps(
+    compute_worker: &mut AppComputeWorker<ParticleComputeWorker>,
+    steps: f32,
+    timestep: f32,
+) -> u32 {
+    let timesteps = pass_timesteps(steps, timestep);
+    for (pass, dt) in timesteps.iter().enumerate() {
+        compute_worker.write(&pass_timestep_name(pass), dt);
+    }
+    timesteps.iter().filter(|dt| **dt > 0.0).count() as u32
+}
+
 fn compute_enabled(compute: Res<ComputeEnabled>) -> bool {
     compute.0
 }
 
+/// Compte les frames en attente de résultats GPU ; les particules gardent leur dernière position
+fn track_readback_latency(
+    compute_worker: Res<AppComputeWorker<ParticleComputeWorker>>,
+    mut latency: ResMut<GpuReadbackLatency>,
+    mut timing: ResMut<GpuComputeTiming>,
+) {
+    if compute_worker.ready() {
+        if let Some(dispatched_at) = timing.dispatched_at.take() {
+            timing.last_round_trip = Some(dispatched_at.elapsed());
+            timing.last_iterations = timing.dispatched_iterations;
+        }
+        if latency.pending_frames > 0 {
+            debug!(
+                "GPU readback: résultats reçus après {} frame(s) d'attente",
+                latency.pending_frames
+            );
+        }
+        latency.pending_frames = 0;
+    } else {
+        latency.pending_frames += 1;
+    }
+}
+
 /// Met à jour les buffers GPU avec les données actuelles des entités
+#[allow(clippy::too_many_arguments)]
 fn update_compute_buffers(
     mut compute_worker: ResMut<AppComputeWorker<ParticleComputeWorker>>,
     sim_params: Res<SimulationParameters>,
+    particles: Query<(Entity, &Transform, &Velocity, &ParticleType, &ChildOf), With<Particle>>,
+    simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    food_query: Query<(Entity, &Transform, &FoodValue, &Visibility), With<Food>>,
+    mut food_readback: ResMut<GpuFoodReadback>,
+    mut particle_readback: ResMut<GpuParticleReadback>,
+    dispatch_settings: Res<GpuDispatchSettings>,
     grid_params: Res<GridParameters>,
     boundary_mode: Res<BoundaryMode>,
-    particles: Query<(&Transform, &Velocity, &ParticleType, &ChildOf), With<Particle>>,
-    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
-    food_query: Query<(&Transform, &ViewVisibility), With<Food>>,
+    particle_config: Res<ParticleTypesConfig>,
 ) {
     if !compute_worker.ready() {
         return;
@@ -140,16 +483,19 @@ fn update_compute_buffers(
     // Collecte des positions et vélocités des particules
     let mut positions = Vec::new();
     let mut velocities = Vec::new();
+    let mut particle_order = Vec::new();
 
-    for (transform, velocity, particle_type, parent) in particles.iter() {
-        if simulations.get(parent.parent()).is_ok() {
+    for (entity, transform, velocity, particle_type, parent) in particles.iter() {
+        if let Ok((sim_id, _, _)) = simulations.get(parent.parent()) {
+            particle_order.push(entity);
             positions.push([
                 transform.translation.x,
                 transform.translation.y,
                 transform.translation.z,
                 particle_type.0 as f32,
             ]);
-            velocities.push([velocity.0.x, velocity.0.y, velocity.0.z, 0.0]);
+            // La composante w porte l'ID de simulation pour isoler les populations
+            velocities.push([velocity.0.x, velocity.0.y, velocity.0.z, sim_id.0 as f32]);
         }
     }
 
@@ -158,91 +504,283 @@ fn update_compute_buffers(
         return;
     }
 
+    if positions.len() > GPU_MAX_PARTICLES {
+        warn!(
+            "GPU: {} particules, seules les {} premières sont simulées",
+            positions.len(),
+            GPU_MAX_PARTICLES
+        );
+        positions.truncate(GPU_MAX_PARTICLES);
+        velocities.truncate(GPU_MAX_PARTICLES);
+        particle_order.truncate(GPU_MAX_PARTICLES);
+    }
+    particle_readback.in_flight =
+        std::mem::replace(&mut particle_readback.uploaded, particle_order);
+
     // Mettre à jour seulement les données qui changent
     compute_worker.write_slice("positions", &positions);
     compute_worker.write_slice("velocities", &velocities);
+    compute_worker.write("num_particles", &(positions.len() as u32));
+    write_simulation_parameters(
+        &mut compute_worker,
+        &sim_params,
+        &grid_params,
+        *boundary_mode,
+        &particle_config,
+    );
 
-    // Forces des simulations (peuvent changer entre époques)
-    if let Some((_, genotype)) = simulations.iter().next() {
-        compute_worker.write_slice("force_matrix", &genotype.force_matrix);
-        compute_worker.write_slice("food_forces", &genotype.food_forces);
-    } else {
+    // Forces des simulations (peuvent changer entre époques), une matrice par simulation
+    let Some((force_matrix, food_forces, force_ranges)) =
+        flatten_genotypes(&simulations, sim_params.particle_types)
+    else {
         warn!("GPU: Aucune simulation trouvée!");
         return;
+    };
+    compute_worker.write_slice("force_matrix", &force_matrix);
+    compute_worker.write_slice("food_forces", &food_forces);
+    compute_worker.write_slice("force_ranges", &force_ranges);
+
+    if dispatch_settings.neighbor_search == NeighborSearch::Grid {
+        write_spatial_grid(
+            &mut compute_worker,
+            &positions,
+            &force_ranges,
+            &grid_params,
+            *boundary_mode,
+            grid_drift(&sim_params),
+        );
     }
 
-    // Nourriture
+    // Nourriture : la composante w porte la valeur, 0 pour une nourriture cachée
     let mut food_positions = Vec::new();
-    for (transform, visibility) in food_query.iter() {
+    food_readback.food_order.clear();
+    for (entity, transform, food_value, visibility) in food_query.iter() {
+        if food_positions.len() == MAX_FOOD_COUNT {
+            warn!("GPU: plus de {} nourritures, les suivantes sont ignorées", MAX_FOOD_COUNT);
+            break;
+        }
         food_positions.push([
             transform.translation.x,
             transform.translation.y,
             transform.translation.z,
-            if visibility.get() { 1.0 } else { 0.0 },
+            if *visibility != Visibility::Hidden { food_value.0 } else { 0.0 },
         ]);
+        food_readback.food_order.push(entity);
     }
 
-    if food_positions.is_empty() {
-        food_positions.push([0.0, 0.0, 0.0, 0.0]);
+    compute_worker.write("food_count", &(food_positions.len() as u32));
+    if !food_positions.is_empty() {
+        compute_worker.write_slice("food_positions", &food_positions);
     }
 
-    compute_worker.write_slice("food_positions", &food_positions);
+    // Remise à zéro des accumulateurs du dispatch
+    compute_worker.write_slice("food_eaten", &[0u32; MAX_FOOD_COUNT]);
+    compute_worker.write_slice("score_accumulators", &[0i32; MAX_SIMULATION_COUNT]);
 
     info!(
         "GPU Update: {} particules, forces={}, nourriture={}",
         positions.len(),
-        simulations
-            .iter()
-            .next()
-            .map_or(0, |(_, g)| g.force_matrix.len()),
+        force_matrix.len(),
         food_positions.len()
     );
 }
 
-/// Exécute la simulation compute selon la vitesse de simulation
+/// Paramètres uniformes partagés par tous les dispatchs, hors nombre de particules et de
+/// nourritures
+pub(crate) fn write_simulation_parameters(
+    compute_worker: &mut AppComputeWorker<ParticleComputeWorker>,
+    sim_params: &SimulationParameters,
+    grid_params: &GridParameters,
+    boundary_mode: BoundaryMode,
+    particle_config: &ParticleTypesConfig,
+) {
+    compute_worker.write("num_types", &(sim_params.particle_types as u32));
+    compute_worker.write("world_extents", &world_extents(grid_params));
+    compute_worker.write("boundary_mode", &boundary_mode.gpu_mask());
+    compute_worker.write("max_interactions", &gpu_max_interactions(sim_params));
+    compute_worker.write("force_scale_factor", &sim_params.force_scale_factor);
+    compute_worker.write("max_velocity", &sim_params.max_velocity);
+    compute_worker.write("collision_damping", &sim_params.collision_damping);
+    compute_worker.write("force_curve", &sim_params.force_curve.gpu_index());
+    compute_worker.write("min_distance", &sim_params.min_distance());
+    compute_worker.write("velocity_half_life", &sim_params.velocity_half_life);
+    compute_worker.write("type_properties", &GpuTypeProperties::new(particle_config));
+}
+
+/// Reconstruit la grille spatiale du shader à partir des positions envoyées ; les cellules
+/// s'élargissent de `drift` pour rester valables pendant tous les pas du dispatch
+pub(crate) fn write_spatial_grid(
+    compute_worker: &mut AppComputeWorker<ParticleComputeWorker>,
+    positions: &[[f32; 4]],
+    force_ranges: &[f32],
+    grid_params: &GridParameters,
+    boundary_mode: BoundaryMode,
+    drift: f32,
+) {
+    let max_force_range = force_ranges.iter().copied().fold(0.0f32, f32::max);
+    let (spatial_grid, params) =
+        build_spatial_grid(positions, grid_params, max_force_range + drift, boundary_mode);
+    compute_worker.write_slice("spatial_grid", &spatial_grid);
+    compute_worker.write("grid_params", &params);
+}
+
+/// Rapprochement max de deux particules après le premier pas d'un dispatch, la grille étant
+/// construite sur les positions de départ
+fn grid_drift(sim_params: &SimulationParameters) -> f32 {
+    let later_steps = (sim_params.physics_steps_per_frame() - 1.0).max(0.0);
+    2.0 * sim_params.max_velocity * later_steps * sim_params.physics_timestep()
+}
+
+/// Dimensions de la grille par axe : le tore et les murs du shader suivent la grille CPU
+fn world_extents(grid_params: &GridParameters) -> Vec4 {
+    Vec4::new(grid_params.width, grid_params.height, grid_params.depth, 0.0)
+}
+
+/// Grille des particules au format du shader ; les cellules couvrent au moins la plus grande
+/// portée pour que les 27 cellules voisines suffisent, et grandissent au-delà de la capacité
+fn build_spatial_grid(
+    positions: &[[f32; 4]],
+    grid_params: &GridParameters,
+    max_force_range: f32,
+    boundary_mode: BoundaryMode,
+) -> (Vec<u32>, GpuGridParams) {
+    let largest_side = grid_params.width.max(grid_params.height).max(grid_params.depth);
+    let cell_size = max_force_range.max(largest_side / GPU_GRID_MAX_CELLS_PER_AXIS as f32);
+
+    let mut grid = SpatialGrid::new(grid_params, cell_size, boundary_mode);
+    for (index, position) in positions.iter().enumerate() {
+        grid.insert(index, Vec3::new(position[0], position[1], position[2]));
+    }
+
+    let dims = grid.dims();
+    let params = GpuGridParams {
+        dims: UVec4::new(
+            dims[0] as u32,
+            dims[1] as u32,
+            dims[2] as u32,
+            grid.cell_count() as u32,
+        ),
+        cell_extent: grid.cell_extent().extend(0.0),
+        half_extents: grid.half_extents().extend(0.0),
+    };
+    (grid.to_compact(), params)
+}
+
+/// Concatène les génomes et portées de toutes les simulations, indexés par ID de simulation
+pub(crate) fn flatten_genotypes(
+    simulations: &Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    type_count: usize,
+) -> Option<(Vec<f32>, Vec<f32>, Vec<f32>)> {
+    let simulation_count = simulations.iter().map(|(sim_id, _, _)| sim_id.0 + 1).max()?;
+    let matrix_size = type_count * type_count;
+
+    let mut force_matrix = vec![0.0f32; simulation_count * matrix_size];
+    let mut food_forces = vec![0.0f32; simulation_count * type_count];
+    let mut force_ranges = vec![0.0f32; simulation_count];
+
+    for (sim_id, genotype, force_range) in simulations.iter() {
+        force_ranges[sim_id.0] = force_range.0;
+
+        let matrix_offset = sim_id.0 * matrix_size;
+        let food_offset = sim_id.0 * type_count;
+
+        for (dst, src) in force_matrix[matrix_offset..matrix_offset + matrix_size]
+            .iter_mut()
+            .zip(&genotype.force_matrix)
+        {
+            *dst = *src;
+        }
+        for (dst, src) in food_forces[food_offset..food_offset + type_count]
+            .iter_mut()
+            .zip(&genotype.food_forces)
+        {
+            *dst = *src;
+        }
+    }
+
+    Some((force_matrix, food_forces, force_ranges))
+}
+
+impl GpuTypeProperties {
+    fn new(particle_config: &ParticleTypesConfig) -> Self {
+        let per_type = |value: fn(&ParticleTypesConfig, usize) -> f32| {
+            std::array::from_fn(|group| {
+                Vec4::from_array(std::array::from_fn(|lane| value(particle_config, group * 4 + lane)))
+            })
+        };
+        Self {
+            radii: per_type(ParticleTypesConfig::radius_for),
+            masses: per_type(ParticleTypesConfig::mass_for),
+        }
+    }
+}
+
+/// Plafond d'interactions envoyé au shader, ramené à la capacité de son tableau de voisines
+fn gpu_max_interactions(sim_params: &SimulationParameters) -> u32 {
+    sim_params.max_interactions_per_particle.min(GPU_MAX_INTERACTIONS) as u32
+}
+
+/// Règle le dispatch que le worker lance à la fin de chaque frame où ses résultats ont été
+/// relus : même temps simulé que la physique CPU par frame, quel que soit le timestep
 fn run_compute_simulation(
     mut compute_worker: ResMut<AppComputeWorker<ParticleComputeWorker>>,
     sim_params: Res<SimulationParameters>,
-    time: Res<Time>,
-    mut timer: Local<Timer>,
+    mut timing: ResMut<GpuComputeTiming>,
 ) {
     if !compute_worker.ready() {
         return;
     }
 
-    // Initialiser le timer pour 60 FPS
-    if timer.duration().is_zero() {
-        *timer = Timer::from_seconds(1.0 / 60.0, TimerMode::Repeating);
+    let steps = write_pass_timesteps(
+        &mut compute_worker,
+        sim_params.physics_steps_per_frame(),
+        sim_params.physics_timestep(),
+    );
+    if timing.enabled && steps > 0 {
+        timing.dispatched_at = Some(Instant::now());
+        timing.dispatched_iterations = steps;
     }
+}
 
-    timer.tick(time.delta());
-
-    if !timer.just_finished() {
+/// Reporte les scores accumulés par le shader et retire la nourriture mangée. Le worker
+/// relance le shader à chaque frame où ses résultats ont été relus : chaque dispatch est
+/// lu ici une fois, avant que `update_compute_buffers` remette les accumulateurs à zéro
+#[allow(clippy::too_many_arguments)]
+fn apply_compute_food_results(
+    mut commands: Commands,
+    compute_worker: Res<AppComputeWorker<ParticleComputeWorker>>,
+    food_readback: Res<GpuFoodReadback>,
+    mut food_query: Query<(&Transform, &mut FoodRespawnTimer), With<Food>>,
+    mut simulations: Query<(&SimulationId, &mut Score), With<Simulation>>,
+    mut heatmap: ResMut<FoodHeatmap>,
+    grid_params: Res<GridParameters>,
+    sim_params: Res<SimulationParameters>,
+    scoring_enabled: Res<ScoringEnabled>,
+) {
+    if !compute_worker.ready() {
         return;
     }
 
-    // Calculer le nombre d'itérations selon la vitesse
-    let iterations = match sim_params.simulation_speed {
-        SimulationSpeed::Paused => 0,
-        SimulationSpeed::Normal => 1,
-        SimulationSpeed::Fast => 2,
-        SimulationSpeed::VeryFast => 4,
-    };
-
-    // Debug: afficher le nombre d'itérations
-    if iterations > 0 {
-        // Exécuter les itérations
-        for _ in 0..iterations {
-            compute_worker.execute();
-
-            // Copier les résultats pour la prochaine itération
-            if iterations > 1 {
-                let new_positions: Vec<[f32; 4]> = compute_worker.read_vec("new_positions");
-                let new_velocities: Vec<[f32; 4]> = compute_worker.read_vec("new_velocities");
+    let score_accumulators: Vec<i32> = compute_worker.read_vec("score_accumulators");
+    for (sim_id, mut score) in simulations.iter_mut().filter(|_| scoring_enabled.0) {
+        if let Some(gained) = score_accumulators.get(sim_id.0).filter(|gained| **gained != 0) {
+            score.add(*gained as f32 / GPU_SCORE_FIXED_POINT);
+        }
+    }
 
-                compute_worker.write_slice("positions", &new_positions);
-                compute_worker.write_slice("velocities", &new_velocities);
-            }
+    let food_eaten: Vec<u32> = compute_worker.read_vec("food_eaten");
+    for (food_entity, eaten) in food_readback.food_order.iter().zip(&food_eaten) {
+        if *eaten == 0 {
+            continue;
+        }
+        if let Ok((transform, mut respawn_timer)) = food_query.get_mut(*food_entity) {
+            heatmap.record_consumption(
+                &grid_params,
+                sim_params.current_epoch,
+                (*eaten - 1) as usize,
+                transform.translation,
+            );
+            consume_food(&mut commands, *food_entity, &mut respawn_timer);
         }
     }
 }
@@ -250,7 +788,8 @@ fn run_compute_simulation(
 /// Applique les résultats du compute aux entités
 fn apply_compute_results(
     compute_worker: Res<AppComputeWorker<ParticleComputeWorker>>,
-    mut particles: Query<(Entity, &mut Transform, &mut Velocity), With<Particle>>,
+    particle_readback: Res<GpuParticleReadback>,
+    mut particles: Query<(&mut Transform, &mut Velocity), With<Particle>>,
 ) {
     if !compute_worker.ready() {
         return;
@@ -264,8 +803,11 @@ fn apply_compute_results(
         return;
     }
 
-    // Appliquer les résultats aux entités avec index sécurisé
-    for (i, (_, mut transform, mut velocity)) in particles.iter_mut().enumerate() {
+    // Les particules retirées depuis l'envoi sont ignorées, les nouvelles attendent le suivant
+    for (i, entity) in particle_readback.in_flight.iter().enumerate() {
+        let Ok((mut transform, mut velocity)) = particles.get_mut(*entity) else {
+            continue;
+        };
         if let (Some(pos), Some(vel)) = (new_positions.get(i), new_velocities.get(i)) {
             let new_pos = Vec3::new(pos[0], pos[1], pos[2]);
             let new_vel = Vec3::new(vel[0], vel[1], vel[2]);
@@ -278,3 +820,486 @@ fn apply_compute_results(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::globals::{DEFAULT_PARTICLE_TYPES, PARTICLE_MASS, PHYSICS_TIMESTEP};
+    use crate::resources::config::simulation::{NeighborBackend, StepRequested};
+    use crate::resources::world::boundary::AxisBoundary;
+    use crate::systems::simulation::physics::physics_simulation_system;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::tasks::block_on;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeMap;
+    use wgpu::util::DeviceExt;
+
+    fn flatten_system(
+        In(type_count): In<usize>,
+        simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    ) -> Option<(Vec<f32>, Vec<f32>, Vec<f32>)> {
+        flatten_genotypes(&simulations, type_count)
+    }
+
+    /// Génome dont chaque force encode sa position : simulation, type source, type cible
+    fn tagged_genotype(sim_id: usize, type_count: usize) -> Genotype {
+        let mut genotype = Genotype::new(type_count);
+        for a in 0..type_count {
+            for b in 0..type_count {
+                genotype.set_force(a, b, (sim_id * 100 + a * 10 + b) as f32);
+            }
+            genotype.food_forces[a] = -((sim_id * 10 + a) as f32);
+        }
+        genotype
+    }
+
+    #[test]
+    fn flatten_genotypes_layout() {
+        let type_count = 4;
+        let mut world = World::new();
+        // Ordre de création différent de l'ordre des ID : seul l'ID compte
+        for sim_id in [2, 0, 1] {
+            world.spawn((
+                Simulation,
+                SimulationId(sim_id),
+                tagged_genotype(sim_id, type_count),
+                ForceRange(100.0 + sim_id as f32),
+            ));
+        }
+
+        let (force_matrix, food_forces, force_ranges) = world
+            .run_system_once_with(flatten_system, type_count)
+            .unwrap()
+            .unwrap();
+
+        let n = type_count;
+        assert_eq!(force_matrix.len(), 3 * n * n);
+        assert_eq!(food_forces.len(), 3 * n);
+        for sim_id in 0..3 {
+            assert_eq!(force_ranges[sim_id], 100.0 + sim_id as f32);
+            for a in 0..n {
+                for b in 0..n {
+                    let expected = (sim_id * 100 + a * 10 + b) as f32;
+                    assert_eq!(force_matrix[sim_id * n * n + a * n + b], expected);
+                }
+                assert_eq!(food_forces[sim_id * n + a], -((sim_id * 10 + a) as f32));
+            }
+        }
+    }
+
+    #[test]
+    fn pass_timesteps_cover_the_frame() {
+        use crate::globals::{MAX_PHYSICS_TIMESTEP, MIN_PHYSICS_TIMESTEP, PHYSICS_TIMESTEP};
+        use crate::resources::config::simulation::SimulationSpeed;
+
+        let speeds = [
+            SimulationSpeed::Paused,
+            SimulationSpeed::Normal,
+            SimulationSpeed::Fast,
+            SimulationSpeed::VeryFast,
+        ];
+        for simulation_speed in speeds {
+            let multiplier = simulation_speed.multiplier();
+            for physics_timestep in [MIN_PHYSICS_TIMESTEP, 0.005, PHYSICS_TIMESTEP, MAX_PHYSICS_TIMESTEP] {
+                let sim_params = SimulationParameters {
+                    simulation_speed: simulation_speed.clone(),
+                    physics_timestep,
+                    ..default()
+                };
+                let timestep = sim_params.physics_timestep();
+                let timesteps = pass_timesteps(sim_params.physics_steps_per_frame(), timestep);
+
+                // Même temps simulé qu'une frame CPU, sans pas plus long que le timestep
+                let simulated: f32 = timesteps.iter().sum();
+                let expected = multiplier * PHYSICS_TIMESTEP;
+                assert!(
+                    (simulated - expected).abs() < 1e-5,
+                    "×{} à {}: {} simulées, {} attendues",
+                    multiplier,
+                    physics_timestep,
+                    simulated,
+                    expected
+                );
+                assert!(timesteps.iter().all(|dt| *dt <= timestep));
+                // Les passes actives viennent en premier : les inactives recopient le résultat
+                let active = timesteps.iter().filter(|dt| **dt > 0.0).count();
+                assert!(timesteps[active..].iter().all(|dt| *dt == 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn type_properties_packed_by_four() {
+        let radii: Vec<f32> = (0..5).map(|particle_type| 1.0 + particle_type as f32).collect();
+        let masses: Vec<f32> = (0..5).map(|particle_type| 0.5 * particle_type as f32).collect();
+        let particle_config =
+            ParticleTypesConfig::new(5, vec![1; 5]).with_physical_properties(&radii, &masses);
+        let packed = GpuTypeProperties::new(&particle_config);
+        // Indexation du shader : radii[t / 4][t % 4], de même pour les masses
+        for particle_type in 0..MAX_PARTICLE_TYPES {
+            let (group, lane) = (particle_type / 4, particle_type % 4);
+            assert_eq!(packed.radii[group][lane], particle_config.radius_for(particle_type));
+            assert_eq!(packed.masses[group][lane], particle_config.mass_for(particle_type));
+        }
+        assert_eq!(packed.radii[1][0], 5.0);
+        // Masse nulle remplacée par la masse par défaut, comme sur CPU
+        assert_eq!(packed.masses[0][0], PARTICLE_MASS);
+    }
+
+    /// Préprocesseur minimal du shader : taille de workgroup fixée, parcours exhaustif
+    fn exhaustive_shader_source() -> String {
+        let source = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/shaders/particle_compute.wgsl"
+        ))
+        .unwrap()
+        .replace("#{WORKGROUP_SIZE}", &DEFAULT_GPU_WORKGROUP_SIZE.to_string());
+
+        let mut kept = Vec::new();
+        let mut in_grid_branch = false;
+        for line in source.lines() {
+            match line.trim() {
+                "#ifdef SPATIAL_GRID" => in_grid_branch = true,
+                "#else" => in_grid_branch = false,
+                "#endif" => {}
+                _ if !in_grid_branch => kept.push(line),
+                _ => {}
+            }
+        }
+        kept.join("\n")
+    }
+
+    /// Uniform scalaire ou vectoriel, complété à 16 octets
+    fn uniform_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut padded = bytes.to_vec();
+        padded.resize(bytes.len().next_multiple_of(16), 0);
+        padded
+    }
+
+    /// Avance `particles` (position, vitesse, type, simulation) de `steps` pas avec le compute
+    /// shader, hors de Bevy et sans nourriture ; None sans adaptateur GPU
+    fn run_gpu_steps(
+        particles: &[(Vec3, Vec3, usize, usize)],
+        steps: usize,
+        flattened: &(Vec<f32>, Vec<f32>, Vec<f32>),
+        sim_params: &SimulationParameters,
+        grid: &GridParameters,
+        boundary_mode: BoundaryMode,
+        particle_config: &ParticleTypesConfig,
+    ) -> Option<Vec<Vec3>> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::PRIMARY,
+            ..default()
+        });
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        // Le shader dépasse les limites par défaut en buffers de stockage, comme sous Bevy
+        let descriptor = wgpu::DeviceDescriptor {
+            required_limits: adapter.limits(),
+            ..default()
+        };
+        let (device, queue) = block_on(adapter.request_device(&descriptor, None)).ok()?;
+
+        let source = exhaustive_shader_source();
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("particle_compute"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("particle_compute"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let (force_matrix, food_forces, force_ranges) = flattened;
+        let positions: Vec<[f32; 4]> = particles
+            .iter()
+            .map(|(position, _, particle_type, _)| position.extend(*particle_type as f32).to_array())
+            .collect();
+        let velocities: Vec<[f32; 4]> = particles
+            .iter()
+            .map(|(_, velocity, _, sim_id)| velocity.extend(*sim_id as f32).to_array())
+            .collect();
+        let particle_bytes = std::mem::size_of_val(positions.as_slice()) as u64;
+
+        let storage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST;
+        let contents: Vec<(u32, wgpu::BufferUsages, Vec<u8>)> = vec![
+            (0, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&(particles.len() as u32)))),
+            (1, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&PHYSICS_TIMESTEP))),
+            (2, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&world_extents(grid)))),
+            (3, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&(sim_params.particle_types as u32)))),
+            (4, storage, bytemuck::cast_slice(force_ranges).to_vec()),
+            (5, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&boundary_mode.gpu_mask()))),
+            (6, storage, bytemuck::cast_slice(&positions).to_vec()),
+            (7, storage, bytemuck::cast_slice(&velocities).to_vec()),
+            (8, storage, bytemuck::cast_slice(&positions).to_vec()),
+            (9, storage, bytemuck::cast_slice(&velocities).to_vec()),
+            (10, storage, bytemuck::cast_slice(force_matrix).to_vec()),
+            (11, storage, vec![0; 16]),
+            (12, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&0u32))),
+            (13, storage, bytemuck::cast_slice(food_forces).to_vec()),
+            (14, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&gpu_max_interactions(sim_params)))),
+            (15, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.force_scale_factor))),
+            (16, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.max_velocity))),
+            (17, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.collision_damping))),
+            (18, storage, vec![0; 4]),
+            (19, storage, vec![0; 4 * MAX_SIMULATION_COUNT]),
+            (20, storage, vec![0; 4]),
+            (21, wgpu::BufferUsages::UNIFORM, bytemuck::bytes_of(&GpuGridParams::default()).to_vec()),
+            (22, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.force_curve.gpu_index()))),
+            (23, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.min_distance()))),
+            (24, wgpu::BufferUsages::UNIFORM, uniform_bytes(bytemuck::bytes_of(&sim_params.velocity_half_life))),
+            (25, wgpu::BufferUsages::UNIFORM, bytemuck::bytes_of(&GpuTypeProperties::new(particle_config)).to_vec()),
+        ];
+        let buffers: BTreeMap<u32, wgpu::Buffer> = contents
+            .into_iter()
+            .map(|(binding, usage, contents)| {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: &contents,
+                    usage,
+                });
+                (binding, buffer)
+            })
+            .collect();
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: *binding,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: particle_bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&Default::default());
+        let workgroups = (particles.len() as u32).div_ceil(64);
+        for _ in 0..steps {
+            {
+                let mut pass = encoder.begin_compute_pass(&Default::default());
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+            // Le résultat d'un pas devient l'entrée du suivant
+            encoder.copy_buffer_to_buffer(&buffers[&8], 0, &buffers[&6], 0, particle_bytes);
+            encoder.copy_buffer_to_buffer(&buffers[&9], 0, &buffers[&7], 0, particle_bytes);
+        }
+        encoder.copy_buffer_to_buffer(&buffers[&8], 0, &readback, 0, particle_bytes);
+        queue.submit([encoder.finish()]);
+
+        readback.slice(..).map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::Maintain::Wait);
+        let data: Vec<[f32; 4]> = bytemuck::cast_slice(&readback.slice(..).get_mapped_range()).to_vec();
+        Some(data.iter().map(|position| Vec4::from(*position).truncate()).collect())
+    }
+
+    /// 60 pas du compute shader comparés à la physique CPU sur la même population ;
+    /// nécessite un adaptateur GPU (`cargo test -- --ignored`)
+    #[test]
+    #[ignore]
+    fn gpu_matches_cpu_over_60_steps() {
+        let type_count = DEFAULT_PARTICLE_TYPES;
+        // Demi-vie hors défaut : le shader doit suivre le paramètre, pas une constante
+        let sim_params = SimulationParameters {
+            particle_types: type_count,
+            velocity_half_life: 0.2,
+            ..default()
+        };
+        let grid = GridParameters::default();
+        // Rayons et masses différents par type : répulsion et accélération en dépendent
+        let particle_config = ParticleTypesConfig::new(type_count, vec![1; type_count])
+            .with_physical_properties(&[3.0, 4.0, 6.0], &[0.5, 1.0, 2.5]);
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut world = World::new();
+        world.insert_resource(sim_params.clone());
+        world.insert_resource(GridParameters::default());
+        world.insert_resource(BoundaryMode::TELEPORT);
+        world.init_resource::<StepRequested>();
+        world.init_resource::<NeighborBackend>();
+        let simulations: Vec<Entity> = (0..2)
+            .map(|sim_id| {
+                world
+                    .spawn((
+                        Simulation,
+                        SimulationId(sim_id),
+                        Genotype::random(type_count, true, &mut rng),
+                        ForceRange(sim_params.max_force_range),
+                    ))
+                    .id()
+            })
+            .collect();
+        let flattened = world
+            .run_system_once_with(flatten_system, type_count)
+            .unwrap()
+            .unwrap();
+
+        let half_size = grid.width.min(grid.height).min(grid.depth) / 2.0;
+        let particles: Vec<(Vec3, Vec3, usize, usize)> = (0..128)
+            .map(|index| {
+                let position = Vec3::new(
+                    rng.random_range(-half_size..half_size),
+                    rng.random_range(-half_size..half_size),
+                    rng.random_range(-half_size..half_size),
+                );
+                (position, Vec3::ZERO, index % type_count, index % 2)
+            })
+            .collect();
+        let entities: Vec<Entity> = particles
+            .iter()
+            .map(|(position, _, particle_type, sim_id)| {
+                world
+                    .spawn((
+                        Particle,
+                        ParticleType(*particle_type),
+                        Transform::from_translation(*position),
+                        ChildOf(simulations[*sim_id]),
+                    ))
+                    .id()
+            })
+            .collect();
+
+        let Some(gpu) = run_gpu_steps(
+            &particles,
+            60,
+            &flattened,
+            &sim_params,
+            &grid,
+            BoundaryMode::TELEPORT,
+            &particle_config,
+        ) else {
+            eprintln!("Aucun adaptateur GPU : comparaison ignorée");
+            return;
+        };
+        world.insert_resource(particle_config);
+        for _ in 0..60 {
+            world.run_system_once(physics_simulation_system).unwrap();
+        }
+
+        let size = Vec3::new(grid.width, grid.height, grid.depth);
+        for (entity, gpu_position) in entities.iter().zip(&gpu) {
+            let cpu_position = world.get::<Transform>(*entity).unwrap().translation;
+            // Écart mesuré à travers le tore : une particule peut avoir traversé un bord
+            let delta = *gpu_position - cpu_position;
+            let error = (delta - (delta / size).round() * size).length();
+            assert!(error < 0.05, "écart {error} pour la particule {entity:?}");
+        }
+    }
+
+    /// Deux particules de part et d'autre des bords d'une grille non cubique, pour chaque
+    /// mélange d'axes : elles ne sont à portée qu'à travers les axes en téléportation, chacun
+    /// replié sur sa propre taille, et doivent finir aux mêmes positions sur GPU et sur CPU
+    /// (`cargo test -- --ignored`)
+    #[test]
+    #[ignore]
+    fn gpu_matches_cpu_across_torus_seam() {
+        let type_count = 1;
+        let sim_params = SimulationParameters {
+            particle_types: type_count,
+            ..default()
+        };
+        let grid = GridParameters {
+            width: 800.0,
+            height: 400.0,
+            depth: 600.0,
+        };
+        let mut genotype = Genotype::new(type_count);
+        genotype.set_force(0, 0, 1.0);
+        let flattened = (
+            genotype.force_matrix.clone(),
+            genotype.food_forces.clone(),
+            vec![sim_params.max_force_range],
+        );
+
+        // Écart direct hors de portée sur X et Y, 40 unités à travers chaque bord
+        let half_extents = world_extents(&grid).truncate() / 2.0;
+        let corner = Vec3::new(half_extents.x - 20.0, half_extents.y - 20.0, 0.0);
+        let particles = [
+            (corner, Vec3::ZERO, 0, 0),
+            (Vec3::new(-corner.x, -corner.y, 30.0), Vec3::ZERO, 0, 0),
+        ];
+
+        let particle_config = ParticleTypesConfig::new(type_count, vec![2]);
+        let size = world_extents(&grid).truncate();
+        for mask in 0..8usize {
+            let axis = |bit: usize| {
+                if mask & bit != 0 { AxisBoundary::Teleport } else { AxisBoundary::Bounce }
+            };
+            let boundary_mode = BoundaryMode {
+                axes: [axis(1), axis(2), axis(4)],
+            };
+
+            let mut world = World::new();
+            world.insert_resource(sim_params.clone());
+            world.insert_resource(GridParameters {
+                width: grid.width,
+                height: grid.height,
+                depth: grid.depth,
+            });
+            world.insert_resource(boundary_mode);
+            world.insert_resource(ParticleTypesConfig::new(type_count, vec![2]));
+            world.init_resource::<StepRequested>();
+            world.init_resource::<NeighborBackend>();
+            let simulation = world
+                .spawn((
+                    Simulation,
+                    SimulationId(0),
+                    genotype.clone(),
+                    ForceRange(sim_params.max_force_range),
+                ))
+                .id();
+            let entities: Vec<Entity> = particles
+                .iter()
+                .map(|(position, ..)| {
+                    world
+                        .spawn((
+                            Particle,
+                            ParticleType(0),
+                            Transform::from_translation(*position),
+                            ChildOf(simulation),
+                        ))
+                        .id()
+                })
+                .collect();
+
+            let Some(gpu) = run_gpu_steps(
+                &particles,
+                60,
+                &flattened,
+                &sim_params,
+                &grid,
+                boundary_mode,
+                &particle_config,
+            ) else {
+                eprintln!("Aucun adaptateur GPU : comparaison ignorée");
+                return;
+            };
+            for _ in 0..60 {
+                world.run_system_once(physics_simulation_system).unwrap();
+            }
+
+            // X et Y doivent tous deux se replier pour que les particules soient à portée
+            let interacting = mask & 3 == 3;
+            for ((entity, gpu_position), (initial, ..)) in entities.iter().zip(&gpu).zip(&particles) {
+                let cpu_position = world.get::<Transform>(*entity).unwrap().translation;
+                assert_eq!(cpu_position != *initial, interacting, "{boundary_mode:?}");
+                let delta = *gpu_position - cpu_position;
+                let error = (delta - (delta / size).round() * size).length();
+                assert!(error < 0.05, "{boundary_mode:?} : écart {error} pour la particule {entity:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::components::entities::food::Food;
+use crate::components::entities::particle::{Particle, ParticleType, Velocity};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
+use crate::components::genetics::genotype::Genotype;
+use crate::globals::{
+    DEFAULT_GPU_VALIDATION_STEPS, DEFAULT_GPU_VALIDATION_TOLERANCE, GPU_MAX_PARTICLES,
+    MAX_FOOD_COUNT, MAX_SIMULATION_COUNT,
+};
+use crate::plugins::simulation::compute::{
+    GpuDispatchSettings, GpuParticleReadback, NeighborSearch,
+    ParticleComputeWorker,
+    flatten_genotypes, write_pass_timesteps, write_simulation_parameters, write_spatial_grid,
+};
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::systems::simulation::physics::{DetachedParticle, simulate_detached};
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+use std::collections::BTreeMap;
+
+/// Comparaison de la physique CPU et du compute shader sur une copie des simulations
+#[derive(Resource)]
+pub struct ComputeValidation {
+    pub steps: usize,
+    /// Écart de position au-delà duquel la validation échoue
+    pub tolerance: f32,
+    /// Demande de lancement, prise en compte dès que le worker est libre
+    pub requested: bool,
+    run: Option<ValidationRun>,
+    pub report: Option<ValidationReport>,
+}
+
+impl Default for ComputeValidation {
+    fn default() -> Self {
+        Self {
+            steps: DEFAULT_GPU_VALIDATION_STEPS,
+            tolerance: DEFAULT_GPU_VALIDATION_TOLERANCE,
+            requested: false,
+            run: None,
+            report: None,
+        }
+    }
+}
+
+impl ComputeValidation {
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+}
+
+/// Validation en cours : le GPU avance pas à pas, une frame par aller-retour
+struct ValidationRun {
+    /// Résultat CPU après `steps` pas, dans l'ordre des buffers GPU
+    reference: Vec<DetachedParticle>,
+    /// Buffers de départ, restaurés à la fin pour que la simulation reprenne là où elle était
+    initial_positions: Vec<[f32; 4]>,
+    initial_velocities: Vec<[f32; 4]>,
+    force_ranges: Vec<f32>,
+    dispatched_steps: usize,
+}
+
+/// Écarts maximaux entre CPU et GPU après `steps` pas
+#[derive(Clone, Debug)]
+pub struct ValidationReport {
+    pub steps: usize,
+    pub particle_count: usize,
+    pub max_position_error: f32,
+    pub mean_position_error: f32,
+    pub max_velocity_error: f32,
+    /// Particule la plus divergente et sa simulation
+    pub worst_particle: Option<(Entity, usize)>,
+    pub tolerance: f32,
+}
+
+impl ValidationReport {
+    pub fn passed(&self) -> bool {
+        self.max_position_error <= self.tolerance
+    }
+}
+
+/// Pendant une validation, le worker appartient à la copie : le chemin normal est suspendu
+pub fn validation_running(validation: Res<ComputeValidation>) -> bool {
+    validation.is_running()
+}
+
+/// Validation demandée : plus de nouveau dispatch normal, pour libérer le worker
+pub fn validation_requested(validation: Res<ComputeValidation>) -> bool {
+    validation.requested
+}
+
+/// Copie les particules, calcule la référence CPU puis lance le premier pas GPU sur la copie
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn start_compute_validation(
+    mut validation: ResMut<ComputeValidation>,
+    mut compute_worker: ResMut<AppComputeWorker<ParticleComputeWorker>>,
+    mut particle_readback: ResMut<GpuParticleReadback>,
+    sim_params: Res<SimulationParameters>,
+    grid_params: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    dispatch_settings: Res<GpuDispatchSettings>,
+    particle_config: Res<ParticleTypesConfig>,
+    particles: Query<(Entity, &Transform, &Velocity, &ParticleType, &ChildOf), With<Particle>>,
+    simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+) {
+    if !validation.requested || validation.is_running() || !compute_worker.ready() {
+        return;
+    }
+    validation.requested = false;
+
+    // Même ordre que `update_compute_buffers`
+    let mut detached: Vec<DetachedParticle> = particles
+        .iter()
+        .filter_map(|(entity, transform, velocity, particle_type, parent)| {
+            let (sim_id, _, _) = simulations.get(parent.parent()).ok()?;
+            Some(DetachedParticle {
+                entity,
+                position: transform.translation,
+                velocity: velocity.0,
+                particle_type: particle_type.0,
+                sim_id: sim_id.0,
+            })
+        })
+        .collect();
+    detached.truncate(GPU_MAX_PARTICLES);
+
+    let Some((force_matrix, food_forces, force_ranges)) =
+        flatten_genotypes(&simulations, sim_params.particle_types)
+    else {
+        warn!("Validation CPU/GPU : aucune simulation");
+        return;
+    };
+    if detached.is_empty() {
+        warn!("Validation CPU/GPU : aucune particule");
+        return;
+    }
+
+    // L'état de départ remis dans `new_*` à la fin sera relu dans cet ordre
+    particle_readback.uploaded = detached.iter().map(|particle| particle.entity).collect();
+
+    let food_positions: Vec<Vec3> = food_query
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(transform, _)| transform.translation)
+        .take(MAX_FOOD_COUNT)
+        .collect();
+
+    let initial_positions: Vec<[f32; 4]> = detached
+        .iter()
+        .map(|particle| particle.position.extend(particle.particle_type as f32).to_array())
+        .collect();
+    let initial_velocities: Vec<[f32; 4]> = detached
+        .iter()
+        .map(|particle| particle.velocity.extend(particle.sim_id as f32).to_array())
+        .collect();
+
+    let genotypes: BTreeMap<usize, (Genotype, f32)> = simulations
+        .iter()
+        .map(|(sim_id, genotype, force_range)| (sim_id.0, (genotype.clone(), force_range.0)))
+        .collect();
+    let mut reference = detached;
+    simulate_detached(
+        &mut reference,
+        validation.steps,
+        &genotypes,
+        &food_positions,
+        &sim_params,
+        &grid_params,
+        &boundary_mode,
+        &particle_config,
+    );
+
+    // La nourriture est figée : le shader signale ce qu'il mange sans rien retirer
+    let gpu_food: Vec<[f32; 4]> = food_positions.iter().map(|food| food.extend(1.0).to_array()).collect();
+    compute_worker.write_slice("positions", &initial_positions);
+    compute_worker.write_slice("velocities", &initial_velocities);
+    compute_worker.write("num_particles", &(initial_positions.len() as u32));
+    write_simulation_parameters(
+        &mut compute_worker,
+        &sim_params,
+        &grid_params,
+        *boundary_mode,
+        &particle_config,
+    );
+    // Un pas par dispatch, comparé à la référence après chaque relecture
+    write_pass_timesteps(&mut compute_worker, 1.0, sim_params.physics_timestep());
+    compute_worker.write_slice("force_matrix", &force_matrix);
+    compute_worker.write_slice("food_forces", &food_forces);
+    compute_worker.write_slice("force_ranges", &force_ranges);
+    compute_worker.write("food_count", &(gpu_food.len() as u32));
+    if !gpu_food.is_empty() {
+        compute_worker.write_slice("food_positions", &gpu_food);
+    }
+    if dispatch_settings.neighbor_search == NeighborSearch::Grid {
+        write_spatial_grid(
+            &mut compute_worker,
+            &initial_positions,
+            &force_ranges,
+            &grid_params,
+            *boundary_mode,
+            0.0,
+        );
+    }
+
+    info!(
+        "Validation CPU/GPU : {} particules, {} pas",
+        initial_positions.len(),
+        validation.steps
+    );
+    validation.run = Some(ValidationRun {
+        reference,
+        initial_positions,
+        initial_velocities,
+        force_ranges,
+        dispatched_steps: 1,
+    });
+}
+
+/// Enchaîne les pas GPU en relisant chaque résultat, puis compare à la référence CPU :
+/// le chemin d'extraction et de relecture est exercé à chaque pas
+pub fn advance_compute_validation(
+    mut validation: ResMut<ComputeValidation>,
+    mut compute_worker: ResMut<AppComputeWorker<ParticleComputeWorker>>,
+    grid_params: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    dispatch_settings: Res<GpuDispatchSettings>,
+) {
+    if !compute_worker.ready() {
+        return;
+    }
+    let (steps, tolerance) = (validation.steps, validation.tolerance);
+    let Some(run) = validation.run.as_mut() else {
+        return;
+    };
+
+    let new_positions: Vec<[f32; 4]> = compute_worker.read_vec("new_positions");
+    let new_velocities: Vec<[f32; 4]> = compute_worker.read_vec("new_velocities");
+    let particle_count = run.initial_positions.len();
+
+    if run.dispatched_steps < steps {
+        compute_worker.write_slice("positions", &new_positions[..particle_count]);
+        compute_worker.write_slice("velocities", &new_velocities[..particle_count]);
+        if dispatch_settings.neighbor_search == NeighborSearch::Grid {
+            write_spatial_grid(
+                &mut compute_worker,
+                &new_positions[..particle_count],
+                &run.force_ranges,
+                &grid_params,
+                *boundary_mode,
+                0.0,
+            );
+        }
+        compute_worker.write_slice("food_eaten", &[0u32; MAX_FOOD_COUNT]);
+        run.dispatched_steps += 1;
+        return;
+    }
+
+    let mut report = ValidationReport {
+        steps,
+        particle_count,
+        max_position_error: 0.0,
+        mean_position_error: 0.0,
+        max_velocity_error: 0.0,
+        worst_particle: None,
+        tolerance,
+    };
+    for ((reference, position), velocity) in
+        run.reference.iter().zip(&new_positions).zip(&new_velocities)
+    {
+        let position_error = reference
+            .position
+            .distance(Vec3::new(position[0], position[1], position[2]));
+        let velocity_error = reference
+            .velocity
+            .distance(Vec3::new(velocity[0], velocity[1], velocity[2]));
+        // NaN compte comme une divergence maximale
+        let position_error = if position_error.is_finite() { position_error } else { f32::INFINITY };
+
+        report.mean_position_error += position_error;
+        report.max_velocity_error = report.max_velocity_error.max(velocity_error);
+        if position_error > report.max_position_error || report.worst_particle.is_none() {
+            report.max_position_error = position_error;
+            report.worst_particle = Some((reference.entity, reference.sim_id));
+        }
+    }
+    report.mean_position_error /= particle_count.max(1) as f32;
+
+    // Le chemin normal relit `new_*` et les collisions du dispatch lancé à la fin de cette
+    // frame : sans particule, il ne touche à rien et laisse l'état de départ
+    compute_worker.write_slice("new_positions", &run.initial_positions);
+    compute_worker.write_slice("new_velocities", &run.initial_velocities);
+    compute_worker.write("num_particles", &0u32);
+    compute_worker.write_slice("food_eaten", &[0u32; MAX_FOOD_COUNT]);
+    compute_worker.write_slice("score_accumulators", &[0i32; MAX_SIMULATION_COUNT]);
+
+    if report.passed() {
+        info!(
+            "Validation CPU/GPU réussie : écart max {:.4} après {} pas",
+            report.max_position_error, steps
+        );
+    } else {
+        warn!(
+            "Validation CPU/GPU : divergence {:.4} > tolérance {:.4} après {} pas",
+            report.max_position_error, report.tolerance, steps
+        );
+    }
+    validation.run = None;
+    validation.report = Some(report);
+}
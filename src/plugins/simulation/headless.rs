@@ -0,0 +1,88 @@
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::score::Score;
+use crate::globals::*;
+use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::resources::config::seed::SimulationSeed;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::states::app::AppState;
+use crate::states::simulation::SimulationState;
+use crate::systems::lifecycle::check_epoch_end;
+use crate::systems::persistence::population_save::{
+    process_save_requests, SavePopulationEvent,
+};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+/// Mode batch sans rendu : enchaîne les époques au plus vite puis sauvegarde et quitte
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        // Chaque frame simule exactement PHYSICS_TIMESTEP : une époque dure
+        // epoch_duration / PHYSICS_TIMESTEP frames, quelle que soit l'horloge réelle
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            PHYSICS_TIMESTEP,
+        )))
+        .insert_resource(ComputeEnabled(false))
+        // Le spawn crée meshes et matériaux même sans rendu
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .add_systems(Startup, start_headless_run)
+        .add_systems(
+            Update,
+            finish_headless_run
+                .after(check_epoch_end)
+                .before(process_save_requests)
+                .run_if(in_state(SimulationState::Running))
+                .run_if(in_state(AppState::Simulation)),
+        );
+    }
+}
+
+fn start_headless_run(
+    sim_params: Res<SimulationParameters>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    info!(
+        "Mode headless: {} simulations, {} époques de {} frames",
+        sim_params.simulation_count,
+        sim_params.max_epochs,
+        (sim_params.epoch_duration / PHYSICS_TIMESTEP).round() as u32
+    );
+    next_state.set(AppState::Simulation);
+}
+
+/// À la fin de la dernière époque, sauvegarde le génome de chaque simulation puis quitte
+fn finish_headless_run(
+    sim_params: Res<SimulationParameters>,
+    seed: Res<SimulationSeed>,
+    simulations: Query<(&SimulationId, &Score), With<Simulation>>,
+    mut save_events: EventWriter<SavePopulationEvent>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    if sim_params.current_epoch < sim_params.max_epochs {
+        return;
+    }
+
+    for (sim_id, score) in simulations.iter() {
+        save_events.write(SavePopulationEvent {
+            simulation_id: sim_id.0,
+            name: format!("headless_seed{}_sim{}", seed.0, sim_id.0),
+            description: Some(format!(
+                "Run headless de {} époques, score final {:.1}",
+                sim_params.max_epochs,
+                score.get()
+            )),
+            tags: vec!["headless".to_string()],
+            genome: None,
+        });
+    }
+
+    info!(
+        "Mode headless terminé après {} époques, sauvegarde de {} génomes",
+        sim_params.current_epoch,
+        simulations.iter().len()
+    );
+    app_exit.write(AppExit::Success);
+}
@@ -1,3 +1,6 @@
 pub mod compute;
+pub mod compute_validation;
+pub mod headless;
+#[allow(clippy::module_inception)]
 pub mod simulation;
-pub mod visualizer;
\ No newline at end of file
+pub mod visualizer;
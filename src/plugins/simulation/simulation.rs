@@ -1,12 +1,37 @@
 use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::resources::genetics::auto_adapt::AutoAdapt;
+use crate::resources::genetics::diversity_guard::DiversityGuard;
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::genetics::genome_archive::GenomeArchive;
+use crate::resources::genetics::hall_of_fame::HallOfFame;
+use crate::resources::genetics::history::EvolutionHistory;
 use crate::states::app::AppState;
 use crate::states::simulation::SimulationState;
-use crate::systems::lifecycle::{check_epoch_end, handle_pause_input};
+use crate::resources::config::seed::{SimulationRng, SimulationSeed};
+use crate::resources::config::simulation::{
+    AutoPauseOnEpochEnd, AutoSaveOnFinish, EpochEndPending, FastForward, NeighborBackend,
+    ScoringEnabled, StepRequested,
+};
+use crate::systems::lifecycle::{
+    check_epoch_end, clear_step_request, handle_pause_input, running_or_step_requested,
+};
 use crate::systems::persistence::population_save::{
-    load_available_populations, process_save_requests, AvailablePopulations, PopulationSaveEvents,
+    load_available_populations, process_bulk_save_requests, process_save_requests,
+    AvailablePopulations, BulkSaveSettings, SavePopulationEvent, SaveTopGenomesEvent,
 };
+use crate::systems::persistence::run_report::{ExportStatsEvent, process_export_requests};
 use crate::systems::rendering::viewport_manager::ViewportCamera;
-use crate::systems::simulation::collision::detect_food_collision;
+use crate::resources::world::food_heatmap::FoodHeatmap;
+use crate::resources::world::mouse_interaction::MouseInteraction;
+use crate::systems::simulation::collision::{detect_food_collision, tick_food_respawn};
+use crate::systems::simulation::exploration::track_exploration;
+use crate::systems::simulation::food_proximity::accumulate_food_proximity;
+use crate::systems::simulation::grid_resize::{apply_grid_resize, ResizeGridEvent};
+use crate::systems::simulation::particle_count::{
+    apply_particle_count_change, AdjustParticleCountEvent,
+};
+use crate::systems::simulation::fast_forward::{fast_forward_inactive, run_fast_forward};
+use crate::systems::simulation::interaction::update_mouse_interaction;
 use crate::systems::simulation::physics::physics_simulation_system;
 use crate::systems::simulation::reset::reset_for_new_epoch;
 use crate::systems::simulation::spawning::{spawn_food, spawn_simulations_with_particles, EntitiesSpawned};
@@ -20,8 +45,30 @@ impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<SimulationState>()
             .init_resource::<EntitiesSpawned>()
-            .init_resource::<PopulationSaveEvents>()
+            .add_event::<SavePopulationEvent>()
+            .add_event::<SaveTopGenomesEvent>()
+            .init_resource::<BulkSaveSettings>()
+            .add_event::<ExportStatsEvent>()
+            .add_event::<ResizeGridEvent>()
+            .add_event::<AdjustParticleCountEvent>()
             .init_resource::<AvailablePopulations>()
+            .init_resource::<EvolutionHistory>()
+            .init_resource::<HallOfFame>()
+            .init_resource::<GenomeArchive>()
+            .init_resource::<FitnessWeights>()
+            .init_resource::<AutoAdapt>()
+            .init_resource::<DiversityGuard>()
+            .init_resource::<StepRequested>()
+            .init_resource::<ScoringEnabled>()
+            .init_resource::<NeighborBackend>()
+            .init_resource::<AutoPauseOnEpochEnd>()
+            .init_resource::<AutoSaveOnFinish>()
+            .init_resource::<EpochEndPending>()
+            .init_resource::<FastForward>()
+            .init_resource::<SimulationSeed>()
+            .init_resource::<SimulationRng>()
+            .init_resource::<MouseInteraction>()
+            .init_resource::<FoodHeatmap>()
             .add_systems(Startup, load_available_populations)
             .add_systems(
                 OnEnter(AppState::Simulation),
@@ -47,25 +94,78 @@ impl Plugin for SimulationPlugin {
             .add_systems(
                 Update,
                 physics_simulation_system
-                    .run_if(in_state(SimulationState::Running))
+                    .run_if(running_or_step_requested)
                     .run_if(in_state(AppState::Simulation))
-                    .run_if(compute_disabled),
+                    .run_if(compute_disabled)
+                    .run_if(fast_forward_inactive),
+            )
+            // Avance rapide : pas de physique enchaînés hors de l'horloge réelle
+            .add_systems(
+                Update,
+                run_fast_forward
+                    .run_if(in_state(SimulationState::Running))
+                    .run_if(in_state(AppState::Simulation)),
             )
             // Systèmes généraux
             .add_systems(
                 Update,
                 (
-                    detect_food_collision,
-                    check_epoch_end,
-                    process_save_requests,
+                    detect_food_collision.run_if(compute_disabled),
+                    tick_food_respawn.run_if(not(compute_disabled)),
                 )
+                    .after(physics_simulation_system)
+                    .run_if(running_or_step_requested)
+                    .run_if(in_state(AppState::Simulation))
+                    .run_if(fast_forward_inactive),
+            )
+            .add_systems(
+                Update,
+                (track_exploration, accumulate_food_proximity)
+                    .after(physics_simulation_system)
+                    .run_if(running_or_step_requested)
+                    .run_if(in_state(AppState::Simulation))
+                    .run_if(fast_forward_inactive),
+            )
+            // Le timer d'époque n'avance pas pendant un pas unique
+            .add_systems(
+                Update,
+                check_epoch_end
+                    .run_if(fast_forward_inactive)
                     .run_if(in_state(SimulationState::Running))
                     .run_if(in_state(AppState::Simulation)),
             )
+            // Avant toute la physique de la frame, CPU comme GPU
+            .add_systems(
+                PreUpdate,
+                apply_grid_resize
+                    .run_if(in_state(AppState::Simulation).or(in_state(AppState::Visualization))),
+            )
+            .add_systems(
+                PreUpdate,
+                apply_particle_count_change.run_if(in_state(AppState::Simulation)),
+            )
+            // Sauvegardes traitées aussi en pause et depuis le rapport de fin de run
+            .add_systems(
+                Update,
+                (
+                    process_bulk_save_requests.before(process_save_requests),
+                    process_save_requests,
+                    process_export_requests,
+                )
+                    .after(check_epoch_end)
+                    .run_if(in_state(AppState::Simulation).or(in_state(AppState::Finished))),
+            )
             // AJOUT DU SYSTÈME handle_pause_input
             .add_systems(
                 Update,
-                handle_pause_input.run_if(in_state(AppState::Simulation)),
+                (
+                    handle_pause_input.before(physics_simulation_system),
+                    update_mouse_interaction
+                        .before(physics_simulation_system)
+                        .run_if(any_with_component::<ViewportCamera>),
+                    clear_step_request.after(detect_food_collision),
+                )
+                    .run_if(in_state(AppState::Simulation)),
             )
             .add_systems(OnExit(AppState::Simulation), cleanup_all);
     }
@@ -92,6 +192,8 @@ fn cleanup_all(
     food: Query<Entity, With<Food>>,
     cameras: Query<Entity, With<ViewportCamera>>,
     mut entities_spawned: ResMut<EntitiesSpawned>,
+    mut fast_forward: ResMut<FastForward>,
+    mut epoch_end_pending: ResMut<EpochEndPending>,
 ) {
     for entity in simulations.iter() {
         commands.entity(entity).despawn();
@@ -106,6 +208,78 @@ fn cleanup_all(
     }
 
     entities_spawned.0 = false;
+    *fast_forward = FastForward::default();
+    epoch_end_pending.0 = false;
 
     info!("Nettoyage complet de la simulation");
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::entities::simulation::SimulationId;
+    use crate::components::genetics::genotype::Genotype;
+    use crate::plugins::core::setup::SetupPlugin;
+    use crate::plugins::simulation::headless::HeadlessPlugin;
+    use crate::resources::config::food::FoodParameters;
+    use crate::resources::config::particle_types::ParticleTypesConfig;
+    use crate::resources::config::simulation::SimulationParameters;
+    use bevy::input::InputPlugin;
+    use bevy::state::app::StatesPlugin;
+
+    /// Run headless réduit (pas de temps fixe, aucun GPU) avec la graine donnée
+    fn seeded_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default(), InputPlugin))
+            .add_plugins((SetupPlugin, SimulationPlugin, HeadlessPlugin))
+            .insert_resource(SimulationSeed(seed))
+            .insert_resource(SimulationRng::from_seed(seed))
+            .insert_resource(SimulationParameters {
+                simulation_count: 4,
+                particle_count: 30,
+                epoch_duration: 0.2,
+                epoch_timer: Timer::from_seconds(0.2, TimerMode::Once),
+                ..default()
+            })
+            .insert_resource(ParticleTypesConfig::new(3, vec![10; 3]))
+            .insert_resource(FoodParameters {
+                food_count: 10,
+                ..default()
+            });
+        app
+    }
+
+    /// Avance jusqu'à ce que l'époque `epoch` tourne, puis relève les matrices de forces
+    /// par ID de simulation, bit à bit
+    fn force_matrices_at_epoch(app: &mut App, epoch: usize) -> Vec<(usize, Vec<u32>)> {
+        for _ in 0..1000 {
+            app.update();
+            let running = *app.world().resource::<State<SimulationState>>() == SimulationState::Running;
+            if running && app.world().resource::<SimulationParameters>().current_epoch == epoch {
+                let world = app.world_mut();
+                let mut matrices: Vec<(usize, Vec<u32>)> = world
+                    .query::<(&SimulationId, &Genotype)>()
+                    .iter(world)
+                    .map(|(sim_id, genotype)| {
+                        (sim_id.0, genotype.force_matrix.iter().map(|force| force.to_bits()).collect())
+                    })
+                    .collect();
+                matrices.sort_unstable_by_key(|(sim_id, _)| *sim_id);
+                return matrices;
+            }
+        }
+        panic!("époque {epoch} jamais atteinte");
+    }
+
+    /// Même graine, même configuration : mêmes génomes de départ, et mêmes génomes après
+    /// la sélection de fin d'époque
+    #[test]
+    fn same_seed_gives_identical_genomes_across_epochs() {
+        let mut first = seeded_app(64);
+        let mut second = seeded_app(64);
+        for epoch in [0, 1] {
+            let expected = force_matrices_at_epoch(&mut first, epoch);
+            assert_eq!(expected.len(), 4);
+            assert_eq!(force_matrices_at_epoch(&mut second, epoch), expected, "époque {epoch}");
+        }
+    }
+}
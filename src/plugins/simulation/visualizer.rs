@@ -1,16 +1,20 @@
 use crate::plugins::simulation::compute::ComputeEnabled;
-use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::{NeighborBackend, SimulationParameters, StepRequested};
 use crate::resources::world::boundary::BoundaryMode;
 use crate::resources::world::grid::GridParameters;
 use crate::states::app::AppState;
+use crate::systems::rendering::neighbor_coloring::NeighborColoring;
 use crate::systems::simulation::collision::detect_food_collision;
 use crate::systems::simulation::physics::physics_simulation_system;
 use crate::systems::simulation::spawning::spawn_food;
 use crate::systems::simulation::visualizer_spawning::spawn_visualizer_simulation;
 use bevy::prelude::*;
 use crate::components::entities::food::Food;
-use crate::components::entities::particle::{Particle, ParticleType, Velocity};
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::particle::{
+    Particle, ParticleType, PreviousAcceleration, Velocity,
+};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
 
 pub struct VisualizerPlugin;
@@ -51,30 +55,43 @@ fn compute_disabled(compute: Res<ComputeEnabled>) -> bool {
 }
 
 /// Wrapper pour le système physique du visualizer (évite les conflits de noms)
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn visualizer_physics_system(
     sim_params: Res<SimulationParameters>,
+    step_requested: Res<StepRequested>,
     grid: Res<GridParameters>,
     boundary_mode: Res<BoundaryMode>,
-    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
-    mut particles: Query<
+    simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    particles: Query<
         (
             Entity,
             &mut Transform,
             &mut Velocity,
+            &mut PreviousAcceleration,
             &ParticleType,
             &ChildOf,
         ),
         With<Particle>,
     >,
-    food_query: Query<(&Transform, &ViewVisibility), (With<Food>, Without<Particle>)>,
+    food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    particle_config: Res<ParticleTypesConfig>,
+    neighbor_coloring: Option<ResMut<NeighborColoring>>,
+    neighbor_backend: Res<NeighborBackend>,
+    step_accumulator: Local<f32>,
 ) {
     physics_simulation_system(
         sim_params,
+        step_requested,
         grid,
         boundary_mode,
+        None,
         simulations,
         particles,
         food_query,
+        particle_config,
+        neighbor_coloring,
+        neighbor_backend,
+        step_accumulator,
     );
 }
 
@@ -1,15 +1,48 @@
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::trail::TrailConfig;
+use crate::resources::world::grid::{GridBoundsDisplay, GridResizeMode};
 use crate::states::app::AppState;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::systems::persistence::population_save::process_save_requests;
+use crate::systems::persistence::run_report::{RunSummary, clear_run_summary, finish_run};
+use crate::systems::lifecycle::check_epoch_end;
+use crate::systems::rendering::food_heatmap::draw_food_heatmap;
+use crate::systems::rendering::grid_bounds::draw_grid_bounds;
+use crate::systems::rendering::neighbor_coloring::{NeighborColoring, apply_neighbor_coloring};
+use crate::systems::rendering::particle_colors::sync_particle_materials;
+use crate::systems::rendering::render_quality::{apply_render_quality, orient_billboards};
+use crate::systems::rendering::render_style::apply_render_style;
+use crate::systems::rendering::trails::{draw_particle_trails, record_particle_trails};
+use crate::systems::rendering::video_recording::{VideoRecording, record_video_frames, stop_video_recording};
+use crate::systems::rendering::viewport_capture::{ViewportCapture, capture_selected_viewport};
 use crate::systems::rendering::viewport_manager::{
-    UISpace, assign_render_layers, delayed_viewport_update, force_viewport_update_after_startup,
+    UISpace, ViewportLayout, assign_render_layers, delayed_viewport_update, force_viewport_update_after_startup,
     update_viewports,
 };
 use crate::systems::rendering::viewport_overlay::draw_viewport_overlays;
+use crate::systems::simulation::inspector::{
+    InspectorGizmos, draw_inspector_gizmos, pick_inspected_particle, update_force_breakdown,
+};
 use crate::ui::dialogs::save_population::{
     SavePopulationUI, save_population_ui, simulations_list_ui,
 };
-use crate::ui::menus::main_menu::{MenuConfig, main_menu_ui};
-use crate::ui::menus::visualizer_menu::{VisualizerSelection, visualizer_ui};
-use crate::ui::panels::force_matrix::{ForceMatrixUI, force_matrix_window, speed_control_ui};
+use crate::ui::menus::main_menu::{MenuConfig, RunConfigUI, main_menu_ui};
+use crate::ui::menus::visualizer_menu::{
+    VisualizerSelection, refresh_diversity_cache, visualizer_ui,
+};
+use crate::ui::panels::epoch_end::epoch_end_prompt;
+use crate::ui::panels::fitness_history::{FitnessHistoryUI, fitness_history_window};
+use crate::ui::panels::force_matrix::{
+    ForceMatrixUI, force_matrix_keyboard_selection, force_matrix_window, speed_control_ui,
+};
+use crate::ui::panels::genome_archive::{GenomeArchiveUI, genome_archive_window};
+use crate::ui::panels::hall_of_fame::{HallOfFameUI, hall_of_fame_window};
+use crate::ui::panels::interaction_graph::{InteractionGraphUI, interaction_graph_window};
+use crate::ui::panels::particle_inspector::{ParticleInspectorUI, particle_inspector_window};
+use crate::ui::panels::performance::{PerformanceUI, performance_window};
+use crate::ui::panels::run_summary::run_report_screen;
+use crate::ui::panels::type_colors::{TypeColorsUI, type_colors_window};
 use bevy::prelude::*;
 use bevy_egui::{EguiContextPass, EguiPlugin};
 
@@ -24,9 +57,25 @@ impl Plugin for UIPlugin {
         // Resources
         app.init_resource::<ForceMatrixUI>();
         app.init_resource::<UISpace>();
+        app.init_resource::<ViewportLayout>();
         app.init_resource::<MenuConfig>();
+        app.init_resource::<RunConfigUI>();
         app.init_resource::<SavePopulationUI>();
         app.init_resource::<VisualizerSelection>();
+        app.init_resource::<FitnessHistoryUI>();
+        app.init_resource::<HallOfFameUI>();
+        app.init_resource::<GenomeArchiveUI>();
+        app.init_resource::<TypeColorsUI>();
+        app.init_resource::<InteractionGraphUI>();
+        app.init_resource::<PerformanceUI>();
+        app.init_resource::<GridBoundsDisplay>();
+        app.init_resource::<GridResizeMode>();
+        app.init_resource::<TrailConfig>();
+        app.init_resource::<NeighborColoring>();
+        app.init_resource::<ViewportCapture>();
+        app.init_resource::<VideoRecording>();
+        app.init_resource::<ParticleInspectorUI>();
+        app.init_gizmo_group::<InspectorGizmos>();
 
         // Système pour forcer la mise à jour des viewports après le démarrage
         app.add_systems(Startup, force_viewport_update_after_startup);
@@ -43,16 +92,64 @@ impl Plugin for UIPlugin {
                 .run_if(in_state(AppState::Simulation)),
         );
 
+        // Limites de la grille
+        app.add_systems(
+            Update,
+            (
+                draw_grid_bounds,
+                draw_food_heatmap,
+                (record_particle_trails, draw_particle_trails).chain(),
+                apply_neighbor_coloring,
+                sync_particle_materials.run_if(
+                    resource_changed::<ParticleTypesConfig>.or(resource_changed::<RenderStyle>),
+                ),
+                apply_render_quality.run_if(resource_changed::<RenderQuality>),
+                apply_render_style.run_if(resource_changed::<RenderStyle>),
+                orient_billboards,
+            )
+                .run_if(in_state(AppState::Simulation).or(in_state(AppState::Visualization))),
+        );
+
+        // Inspecteur de particule : le pointage passe par les viewports de simulation
+        app.add_systems(
+            Update,
+            (pick_inspected_particle, update_force_breakdown, draw_inspector_gizmos)
+                .chain()
+                .run_if(in_state(AppState::Simulation)),
+        );
+
+        // Fin de run avec bilan ; en headless, `finish_headless_run` prend le relais
+        app.add_systems(
+            Update,
+            finish_run
+                .after(check_epoch_end)
+                .before(process_save_requests)
+                .run_if(in_state(AppState::Simulation)),
+        );
+
         // Systèmes UI du menu principal
         app.add_systems(
             EguiContextPass,
             main_menu_ui.run_if(in_state(AppState::MainMenu)),
         );
 
+        // Rapport de fin de run
+        app.add_systems(
+            EguiContextPass,
+            run_report_screen
+                .run_if(in_state(AppState::Finished))
+                .run_if(resource_exists::<RunSummary>),
+        )
+        .add_systems(OnExit(AppState::Finished), clear_run_summary)
+        .add_systems(OnExit(AppState::Simulation), stop_video_recording)
+        .add_systems(OnExit(AppState::Visualization), stop_video_recording);
+
         // Systèmes UI du visualiseur
         app.add_systems(
             EguiContextPass,
-            visualizer_ui.run_if(in_state(AppState::Visualizer)),
+            (visualizer_ui, refresh_diversity_cache)
+                .chain()
+                .run_if(in_state(AppState::Visualizer)),
         );
 
         // Systèmes UI et viewport pour la simulation
@@ -60,18 +157,46 @@ impl Plugin for UIPlugin {
             EguiContextPass,
             (
                 speed_control_ui,
-                (simulations_list_ui, force_matrix_window, save_population_ui),
+                (
+                    simulations_list_ui,
+                    force_matrix_window.after(force_matrix_keyboard_selection),
+                    force_matrix_keyboard_selection,
+                    save_population_ui,
+                    fitness_history_window,
+                    hall_of_fame_window,
+                    genome_archive_window,
+                    type_colors_window,
+                    interaction_graph_window,
+                    particle_inspector_window,
+                    performance_window,
+                    epoch_end_prompt,
+                ),
                 update_viewports
                     .after(simulations_list_ui)
                     .after(force_matrix_window),
                 draw_viewport_overlays.after(update_viewports),
+                capture_selected_viewport
+                    .after(speed_control_ui)
+                    .after(update_viewports),
+                record_video_frames
+                    .after(speed_control_ui)
+                    .after(update_viewports),
             )
                 .run_if(in_state(AppState::Simulation)),
         );
 
         app.add_systems(
             EguiContextPass,
-            (speed_control_ui, draw_viewport_overlays).run_if(in_state(AppState::Visualization)),
+            (
+                speed_control_ui,
+                draw_viewport_overlays,
+                record_video_frames.after(speed_control_ui),
+                genome_archive_window,
+                type_colors_window,
+                interaction_graph_window,
+                performance_window,
+            )
+                .run_if(in_state(AppState::Visualization)),
         );
     }
 }
\ No newline at end of file
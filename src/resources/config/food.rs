@@ -1,12 +1,58 @@
 use bevy::prelude::*;
+use rand::Rng;
 use crate::globals::*;
+use crate::resources::world::grid::GridParameters;
+
+/// Répartition spatiale de la nourriture
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum FoodDistribution {
+    #[default]
+    Uniform,
+    /// Nourriture regroupée autour de `clusters` centres, à ± `spread` unités par axe
+    Clustered { clusters: usize, spread: f32 },
+}
+
+impl FoodDistribution {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FoodDistribution::Uniform => "Uniforme",
+            FoodDistribution::Clustered { .. } => "Amas",
+        }
+    }
+}
+
+/// Position d'une nourriture quand son timer de réapparition se termine
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FoodRespawnMode {
+    /// Réapparaît là où elle a été mangée
+    #[default]
+    SamePosition,
+    /// Réapparaît à une position tirée dans toute la grille
+    RandomPosition,
+}
+
+impl FoodRespawnMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FoodRespawnMode::SamePosition => "Sur place",
+            FoodRespawnMode::RandomPosition => "Position aléatoire",
+        }
+    }
+}
 
 #[derive(Resource)]
 pub struct FoodParameters {
     pub food_count: usize,
     pub respawn_enabled: bool,
     pub respawn_cooldown: f32,
+    /// Écart max (± secondes) tiré par nourriture autour de `respawn_cooldown`, pour que les
+    /// réapparitions ne se fassent pas toutes en même temps
+    pub respawn_jitter: f32,
+    pub respawn_mode: FoodRespawnMode,
     pub food_value: f32,
+    /// Valeur max d'une nourriture ; `None` = toutes valent `food_value`
+    pub max_food_value: Option<f32>,
+    pub distribution: FoodDistribution,
 }
 
 impl Default for FoodParameters {
@@ -15,7 +61,79 @@ impl Default for FoodParameters {
             food_count: DEFAULT_FOOD_COUNT,
             respawn_enabled: true,
             respawn_cooldown: DEFAULT_FOOD_RESPAWN_TIME,
+            respawn_jitter: 0.0,
+            respawn_mode: FoodRespawnMode::default(),
             food_value: DEFAULT_FOOD_VALUE,
+            max_food_value: None,
+            distribution: FoodDistribution::default(),
+        }
+    }
+}
+
+impl FoodParameters {
+    /// Positions de `food_count` nourritures, toujours à l'intérieur de la grille
+    pub fn generate_positions(&self, grid: &GridParameters, rng: &mut impl Rng) -> Vec<Vec3> {
+        let half_extents = Vec3::new(grid.width, grid.height, grid.depth) / 2.0;
+
+        match self.distribution {
+            FoodDistribution::Uniform => (0..self.food_count)
+                .map(|_| random_offset(half_extents, rng))
+                .collect(),
+            FoodDistribution::Clustered { clusters, spread } => {
+                let centers: Vec<Vec3> = (0..clusters.max(1))
+                    .map(|_| random_offset(half_extents, rng))
+                    .collect();
+                let spread = Vec3::splat(spread.max(f32::EPSILON));
+                let limit = (half_extents - Vec3::splat(FOOD_RADIUS)).max(Vec3::ZERO);
+
+                (0..self.food_count)
+                    .map(|_| {
+                        let center = centers[rng.random_range(0..centers.len())];
+                        (center + random_offset(spread, rng)).clamp(-limit, limit)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Délai de réapparition propre à une nourriture : `respawn_cooldown` ± `respawn_jitter`,
+    /// jamais négatif. Sans écart, aucun tirage n'est fait : les runs à graine fixe déjà
+    /// enregistrés restent reproductibles
+    pub fn jittered_cooldown(&self, rng: &mut impl Rng) -> f32 {
+        let jitter = self.respawn_jitter.max(0.0);
+        if jitter == 0.0 {
+            return self.respawn_cooldown.max(0.0);
         }
+        (self.respawn_cooldown + rng.random_range(-jitter..=jitter)).max(0.0)
     }
-}
\ No newline at end of file
+
+    /// Position d'une nourriture qui réapparaît, `None` si elle reste sur place
+    pub fn respawn_position(&self, grid: &GridParameters, rng: &mut impl Rng) -> Option<Vec3> {
+        match self.respawn_mode {
+            FoodRespawnMode::SamePosition => None,
+            FoodRespawnMode::RandomPosition => {
+                let half_extents = Vec3::new(grid.width, grid.height, grid.depth) / 2.0;
+                Some(random_offset(half_extents, rng))
+            }
+        }
+    }
+
+    /// Valeur d'une nouvelle nourriture ; le tirage au carré rend les fortes valeurs rares
+    pub fn random_value(&self, rng: &mut impl Rng) -> f32 {
+        match self.max_food_value {
+            Some(max_value) if max_value > self.food_value => {
+                let t: f32 = rng.random();
+                self.food_value + (max_value - self.food_value) * t * t
+            }
+            _ => self.food_value,
+        }
+    }
+}
+
+fn random_offset(half_extents: Vec3, rng: &mut impl Rng) -> Vec3 {
+    Vec3::new(
+        rng.random_range(-half_extents.x..half_extents.x),
+        rng.random_range(-half_extents.y..half_extents.y),
+        rng.random_range(-half_extents.z..half_extents.z),
+    )
+}
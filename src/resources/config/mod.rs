@@ -1,3 +1,7 @@
 pub mod food;
 pub mod particle_types;
-pub mod simulation;
\ No newline at end of file
+pub mod render_quality;
+pub mod render_style;
+pub mod seed;
+pub mod simulation;
+pub mod trail;
\ No newline at end of file
@@ -5,6 +5,12 @@ use crate::globals::*;
 pub struct ParticleTypesConfig {
     pub type_count: usize,
     pub colors: Vec<(Color, LinearRgba)>, 
+    /// Nombre de particules de chaque type (un type peut être vide)
+    pub counts_per_type: Vec<usize>,
+    /// Rayon de chaque type : collisions, distance de répulsion et taille du mesh
+    pub radii: Vec<f32>,
+    /// Masse de chaque type : l'accélération vaut force / masse
+    pub masses: Vec<f32>,
 }
 
 impl Default for ParticleTypesConfig {
@@ -12,26 +18,131 @@ impl Default for ParticleTypesConfig {
         Self {
             type_count: DEFAULT_PARTICLE_TYPES,
             colors: Self::generate_colors(DEFAULT_PARTICLE_TYPES),
+            counts_per_type: Self::even_split(DEFAULT_PARTICLE_COUNT, DEFAULT_PARTICLE_TYPES),
+            radii: vec![PARTICLE_RADIUS; DEFAULT_PARTICLE_TYPES],
+            masses: vec![PARTICLE_MASS; DEFAULT_PARTICLE_TYPES],
         }
     }
 }
 
 impl ParticleTypesConfig {
-    pub fn new(type_count: usize) -> Self {
+    pub fn new(type_count: usize, counts_per_type: Vec<usize>) -> Self {
         Self {
             type_count,
             colors: Self::generate_colors(type_count),
+            counts_per_type: Self::normalize_counts(counts_per_type, type_count),
+            radii: vec![PARTICLE_RADIUS; type_count],
+            masses: vec![PARTICLE_MASS; type_count],
         }
     }
 
+    /// Remplace rayons et masses ; les types manquants gardent les valeurs globales
+    pub fn with_physical_properties(mut self, radii: &[f32], masses: &[f32]) -> Self {
+        self.radii = radii.to_vec();
+        self.masses = masses.to_vec();
+        Self::resize_physical_properties(&mut self.radii, &mut self.masses, self.type_count);
+        self
+    }
+
+    /// Ajuste rayons et masses à `type_count` entrées, les nouveaux types prenant
+    /// `PARTICLE_RADIUS` et `PARTICLE_MASS`
+    pub fn resize_physical_properties(radii: &mut Vec<f32>, masses: &mut Vec<f32>, type_count: usize) {
+        radii.resize(type_count, PARTICLE_RADIUS);
+        masses.resize(type_count, PARTICLE_MASS);
+    }
+
+    pub fn radius_for(&self, particle_type: usize) -> f32 {
+        self.radii.get(particle_type).copied().unwrap_or(PARTICLE_RADIUS)
+    }
+
+    pub fn mass_for(&self, particle_type: usize) -> f32 {
+        self.masses
+            .get(particle_type)
+            .copied()
+            .filter(|mass| *mass > 0.0)
+            .unwrap_or(PARTICLE_MASS)
+    }
+
+    /// Échelle à appliquer au mesh partagé (sphère de rayon `PARTICLE_RADIUS`)
+    pub fn mesh_scale_for(&self, particle_type: usize) -> Vec3 {
+        Vec3::splat(self.radius_for(particle_type) / PARTICLE_RADIUS)
+    }
+
+    /// Répartition équitable, le reste allant aux premiers types
+    pub fn even_split(total: usize, type_count: usize) -> Vec<usize> {
+        if type_count == 0 {
+            return Vec::new();
+        }
+
+        let base = total / type_count;
+        let remainder = total % type_count;
+        (0..type_count)
+            .map(|i| base + usize::from(i < remainder))
+            .collect()
+    }
+
+    /// Ajuste la liste à `type_count` entrées (types manquants vides)
+    pub fn normalize_counts(mut counts: Vec<usize>, type_count: usize) -> Vec<usize> {
+        counts.resize(type_count, 0);
+        counts
+    }
+
+    pub fn total_particles(&self) -> usize {
+        self.counts_per_type.iter().sum()
+    }
+
+    /// Type de chaque particule, dans l'ordre de spawn
+    pub fn type_sequence(&self) -> impl Iterator<Item = usize> + '_ {
+        self.counts_per_type
+            .iter()
+            .enumerate()
+            .flat_map(|(particle_type, &count)| std::iter::repeat_n(particle_type, count))
+    }
+
+    /// Remplace les couleurs de base ; l'émissive est recalculée
+    pub fn with_colors(mut self, base_colors: &[Color]) -> Self {
+        for (index, base_color) in base_colors.iter().take(self.type_count).enumerate() {
+            self.set_color(index, *base_color);
+        }
+        self
+    }
+
+    pub fn set_color(&mut self, type_index: usize, base_color: Color) {
+        if let Some(color) = self.colors.get_mut(type_index) {
+            *color = (base_color, Self::emissive_for(base_color));
+        }
+    }
+
+    pub fn emissive_for(base_color: Color) -> LinearRgba {
+        base_color.to_linear() * 0.5 // Émission modérée
+    }
+
+    /// Teinte par défaut du type `index` parmi `count` (teintes HSL régulièrement espacées).
+    /// Au-delà de 6 types les teintes voisines se ressemblent : un type sur deux est plus
+    /// sombre et plus saturé pour rester distinguable
+    pub fn default_color(index: usize, count: usize) -> Color {
+        let hue = (index as f32 / count.max(1) as f32) * 360.0;
+        if count > 6 && index % 2 == 1 {
+            Color::hsl(hue, 0.95, 0.45)
+        } else {
+            Color::hsl(hue, 0.8, 0.6)
+        }
+    }
+
+    /// Ajuste la liste à `type_count` couleurs : les existantes sont conservées,
+    /// les nouveaux types reçoivent leur teinte par défaut
+    pub fn resize_colors(colors: &mut Vec<Color>, type_count: usize) {
+        let existing = colors.len();
+        colors.truncate(type_count);
+        colors.extend((existing..type_count).map(|index| Self::default_color(index, type_count)));
+    }
+
     /// Génère des couleurs distinctes pour chaque type avec émissive
     fn generate_colors(count: usize) -> Vec<(Color, LinearRgba)> {
         (0..count)
             .map(|i| {
-                let hue = (i as f32 / count as f32) * 360.0;
-                let base_color = Color::hsl(hue, 0.8, 0.6);
-                let emissive = base_color.to_linear() * 0.5; // Émission modérée
-                (base_color, emissive)
+                let base_color = Self::default_color(i, count);
+                (base_color, Self::emissive_for(base_color))
             })
             .collect()
     }
@@ -39,4 +150,4 @@ impl ParticleTypesConfig {
     pub fn get_color_for_type(&self, type_index: usize) -> (Color, LinearRgba) {
         self.colors[type_index % self.colors.len()]
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,66 @@
+use crate::globals::{FOOD_RADIUS, PARTICLE_RADIUS, PARTICLE_SUBDIVISIONS};
+use bevy::prelude::*;
+
+/// Nombre de segments des disques du mode billboard
+const BILLBOARD_RESOLUTION: u32 = 12;
+
+/// Finesse des meshes partagés des particules et de la nourriture
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenderQuality {
+    /// Disques tournés vers la caméra : quelques sommets par particule
+    Billboard,
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl RenderQuality {
+    pub const ALL: [RenderQuality; 4] = [
+        RenderQuality::Billboard,
+        RenderQuality::Low,
+        RenderQuality::Medium,
+        RenderQuality::High,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RenderQuality::Billboard => "Billboard",
+            RenderQuality::Low => "Basse",
+            RenderQuality::Medium => "Moyenne",
+            RenderQuality::High => "Haute",
+        }
+    }
+
+    /// Subdivisions de l'icosphère ; en billboard, la nourriture reste une sphère grossière
+    fn subdivisions(&self) -> u32 {
+        match self {
+            RenderQuality::Billboard | RenderQuality::Low => 1,
+            RenderQuality::Medium => 4,
+            RenderQuality::High => PARTICLE_SUBDIVISIONS,
+        }
+    }
+
+    pub fn particle_mesh(&self) -> Mesh {
+        match self {
+            // En style plat, un disque face caméra se confond avec une sphère ; éclairé, il
+            // reste uniforme faute de normales courbes
+            RenderQuality::Billboard => Circle::new(PARTICLE_RADIUS)
+                .mesh()
+                .resolution(BILLBOARD_RESOLUTION)
+                .build(),
+            _ => Sphere::new(PARTICLE_RADIUS)
+                .mesh()
+                .ico(self.subdivisions())
+                .unwrap(),
+        }
+    }
+
+    /// La nourriture est vue par toutes les caméras à la fois, elle n'est jamais en billboard
+    pub fn food_mesh(&self) -> Mesh {
+        Sphere::new(FOOD_RADIUS)
+            .mesh()
+            .ico(self.subdivisions())
+            .unwrap()
+    }
+}
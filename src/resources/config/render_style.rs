@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+/// Part de l'émissive conservée en mode éclairé : assez pour garder les couleurs vives,
+/// pas assez pour écraser l'ombrage
+const LIT_EMISSIVE_SCALE: f32 = 0.2;
+
+/// Ombrage des particules et de la nourriture
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenderStyle {
+    /// Couleurs plates, sans lumière : le plus lisible et le plus rapide
+    #[default]
+    Unlit,
+    /// Lumière directionnelle et ombrage, pour percevoir la profondeur
+    Lit,
+}
+
+impl RenderStyle {
+    pub const ALL: [RenderStyle; 2] = [RenderStyle::Unlit, RenderStyle::Lit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RenderStyle::Unlit => "Plat",
+            RenderStyle::Lit => "Éclairé",
+        }
+    }
+
+    pub fn is_lit(&self) -> bool {
+        *self == RenderStyle::Lit
+    }
+
+    /// Matériau d'une couleur dans ce style ; `emissive` est l'émissive du mode plat
+    pub fn material(&self, base_color: Color, emissive: LinearRgba) -> StandardMaterial {
+        let mut material = StandardMaterial {
+            base_color,
+            ..default()
+        };
+        self.apply(&mut material, emissive);
+        material
+    }
+
+    /// Met un matériau existant au style, sans changer son handle
+    pub fn apply(&self, material: &mut StandardMaterial, emissive: LinearRgba) {
+        match self {
+            RenderStyle::Unlit => {
+                material.unlit = true;
+                material.emissive = emissive;
+            }
+            RenderStyle::Lit => {
+                material.unlit = false;
+                material.emissive = emissive * LIT_EMISSIVE_SCALE;
+            }
+        }
+    }
+}
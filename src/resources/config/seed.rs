@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Graine du run : même graine + même configuration = mêmes conditions initiales
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SimulationSeed(pub u64);
+
+impl Default for SimulationSeed {
+    fn default() -> Self {
+        Self(random_seed())
+    }
+}
+
+/// Générateur partagé par le spawn et les opérateurs génétiques
+#[derive(Resource)]
+pub struct SimulationRng(pub StdRng);
+
+impl SimulationRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl FromWorld for SimulationRng {
+    fn from_world(world: &mut World) -> Self {
+        let seed = world
+            .get_resource::<SimulationSeed>()
+            .copied()
+            .unwrap_or_default();
+        Self::from_seed(seed.0)
+    }
+}
+
+/// Graine aléatoire restreinte à 32 bits pour rester saisissable dans l'UI
+pub fn random_seed() -> u64 {
+    rand::random::<u32>() as u64
+}
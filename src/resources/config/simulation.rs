@@ -21,6 +21,315 @@ impl SimulationSpeed {
     }
 }
 
+/// Loi de tirage des perturbations appliquées lors d'une mutation
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MutationKind {
+    /// Pas uniforme dans ±0.2
+    #[default]
+    Uniform,
+    /// Pas normal, écart-type adapté au taux de mutation courant
+    Gaussian,
+}
+
+/// Nombre d'élites conservées d'une époque à l'autre
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Elitism {
+    /// Fraction `elite_ratio` des simulations, arrondie au supérieur
+    #[default]
+    Ratio,
+    /// Nombre fixe, indépendant du nombre de simulations
+    Count(usize),
+}
+
+impl Elitism {
+    /// Nombre demandé, avant la borne de `resolve`
+    pub fn requested(&self, elite_ratio: f32, simulation_count: usize) -> usize {
+        match self {
+            Elitism::Ratio => (simulation_count as f32 * elite_ratio).ceil() as usize,
+            Elitism::Count(count) => *count,
+        }
+    }
+
+    /// Nombre d'élites entre 1 et `simulation_count - 1`
+    pub fn resolve(&self, elite_ratio: f32, simulation_count: usize) -> usize {
+        self.requested(elite_ratio, simulation_count)
+            .clamp(1, simulation_count.saturating_sub(1).max(1))
+    }
+}
+
+/// Schéma d'intégration de la physique CPU
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Integrator {
+    /// Euler semi-implicite : vitesse puis position
+    #[default]
+    Euler,
+    /// Verlet vitesse : moins de dépassements quand les forces sont fortes
+    Verlet,
+}
+
+impl Integrator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Integrator::Euler => "Euler",
+            Integrator::Verlet => "Verlet",
+        }
+    }
+}
+
+/// Profil de la force entre deux particules en fonction de leur distance. Toutes les courbes
+/// sont nulles au-delà de la portée de la simulation ; sous `min_r`, les particules se
+/// repoussent quel que soit le génome
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ForceCurve {
+    /// Répulsion linéaire jusqu'à `min_r`, puis triangle d'attraction culminant à mi-chemin
+    /// entre `min_r` et la portée
+    #[default]
+    Tent,
+    /// Répulsion linéaire jusqu'à `min_r`, puis attraction maximale à `min_r` qui décroît
+    /// linéairement jusqu'à s'annuler à la portée
+    Linear,
+    /// Potentiel de Lennard-Jones de paramètre `min_r` : répulsion raide sous `min_r`, puis
+    /// puits d'attraction le plus profond vers 1,12 × `min_r`, coupé à la portée
+    LennardJones,
+    /// Répulsion linéaire jusqu'à `min_r`, puis arche sinusoïdale sans angle, maximale à
+    /// mi-chemin entre `min_r` et la portée
+    Smooth,
+}
+
+impl ForceCurve {
+    pub const ALL: [ForceCurve; 4] = [
+        ForceCurve::Tent,
+        ForceCurve::Linear,
+        ForceCurve::LennardJones,
+        ForceCurve::Smooth,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ForceCurve::Tent => "Triangle",
+            ForceCurve::Linear => "Linéaire",
+            ForceCurve::LennardJones => "Lennard-Jones",
+            ForceCurve::Smooth => "Lisse",
+        }
+    }
+
+    /// Index lu par le shader (`curve_force` dans `compute.wgsl`)
+    pub fn gpu_index(&self) -> u32 {
+        match self {
+            ForceCurve::Tent => 0,
+            ForceCurve::Linear => 1,
+            ForceCurve::LennardJones => 2,
+            ForceCurve::Smooth => 3,
+        }
+    }
+
+    /// Force signée (positive : attraction) pour une distance `r` et une distance de
+    /// répulsion `min_r` normalisées par la portée, `attraction` venant du génome
+    pub fn force(&self, r: f32, min_r: f32, attraction: f32) -> f32 {
+        if r >= 1.0 {
+            return 0.0;
+        }
+        let span = 1.0 - min_r;
+        match self {
+            ForceCurve::LennardJones => {
+                let s6 = (min_r / r).powi(6);
+                if r < min_r {
+                    // Bornée comme les autres courbes pour ne pas éjecter les particules
+                    // qui se chevauchent
+                    (1.0 - s6).max(-1.0)
+                } else {
+                    attraction * 4.0 * (s6 - s6 * s6)
+                }
+            }
+            _ if r < min_r => r / min_r - 1.0,
+            ForceCurve::Tent => attraction * (1.0 - (1.0 + min_r - 2.0 * r).abs() / span),
+            ForceCurve::Linear => attraction * (1.0 - r) / span,
+            ForceCurve::Smooth => attraction * (std::f32::consts::PI * (r - min_r) / span).sin(),
+        }
+    }
+}
+
+/// Placement des particules au début de chaque époque
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SpawnMode {
+    /// Même disposition pour toutes les simulations : les écarts de score ne viennent que des
+    /// génomes, pas de la chance du placement
+    #[default]
+    SharedInitial,
+    /// Disposition tirée séparément pour chaque simulation : évite qu'un génome ne soit
+    /// sélectionné pour une disposition de départ commune favorable
+    IndependentRandom,
+}
+
+impl SpawnMode {
+    pub const ALL: [SpawnMode; 2] = [SpawnMode::SharedInitial, SpawnMode::IndependentRandom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpawnMode::SharedInitial => "Positions communes",
+            SpawnMode::IndependentRandom => "Positions indépendantes",
+        }
+    }
+}
+
+/// Recherche des voisines de la physique CPU, à comparer depuis la fenêtre Performances
+#[derive(Resource, Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum NeighborBackend {
+    /// Parcours de toutes les particules : O(n²), aucune construction
+    #[default]
+    Linear,
+    /// Grille uniforme de cellules de la portée max : construction O(n), idéale quand la
+    /// densité est homogène, mais une cellule surpeuplée ramène au parcours complet
+    UniformGrid,
+    /// Arbre k-d : construction O(n log n), requêtes qui restent efficaces quand les
+    /// particules s'agglutinent en amas très denses
+    KdTree,
+}
+
+impl NeighborBackend {
+    pub const ALL: [NeighborBackend; 3] = [
+        NeighborBackend::Linear,
+        NeighborBackend::UniformGrid,
+        NeighborBackend::KdTree,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NeighborBackend::Linear => "Parcours complet",
+            NeighborBackend::UniformGrid => "Grille uniforme",
+            NeighborBackend::KdTree => "Arbre k-d",
+        }
+    }
+}
+
+/// Façon de combiner les gènes de deux parents
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum CrossoverStrategy {
+    /// Chaque force vient de l'un ou l'autre parent, au hasard
+    #[default]
+    Uniform,
+    /// Les relations A→B et B→A sont héritées ensemble du même parent
+    SymmetricRelations,
+    /// Toutes les forces d'un type (ligne + nourriture) viennent du même parent
+    TypeBlocks,
+    /// Moyenne pondérée des deux parents : `alpha` × parent 1 + (1 - `alpha`) × parent 2.
+    /// Avec `auto_weight`, `alpha` suit la fitness relative des parents
+    Blend { alpha: f32, auto_weight: bool },
+    /// Choisie pour chaque couple selon la distance génétique des parents
+    Auto,
+}
+
+impl CrossoverStrategy {
+    /// Stratégies effectivement appliquées (hors `Auto`)
+    pub const CONCRETE: [CrossoverStrategy; 4] = [
+        CrossoverStrategy::Uniform,
+        CrossoverStrategy::SymmetricRelations,
+        CrossoverStrategy::TypeBlocks,
+        CrossoverStrategy::Blend {
+            alpha: DEFAULT_BLEND_ALPHA,
+            auto_weight: false,
+        },
+    ];
+
+    /// Même stratégie, indépendamment des paramètres de `Blend`
+    pub fn same_kind(&self, other: &CrossoverStrategy) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CrossoverStrategy::Uniform => "Uniforme",
+            CrossoverStrategy::SymmetricRelations => "Relations symétriques",
+            CrossoverStrategy::TypeBlocks => "Blocs par type",
+            CrossoverStrategy::Blend { .. } => "Mélange",
+            CrossoverStrategy::Auto => "Auto",
+        }
+    }
+
+    /// Résout `Auto` : des parents proches se mélangent gène à gène, des parents
+    /// éloignés transmettent des blocs entiers pour ne pas casser leurs structures
+    pub fn resolve(self, genetic_distance: f32) -> CrossoverStrategy {
+        match self {
+            CrossoverStrategy::Auto if genetic_distance < 0.25 => CrossoverStrategy::Uniform,
+            CrossoverStrategy::Auto if genetic_distance < 0.75 => {
+                CrossoverStrategy::SymmetricRelations
+            }
+            CrossoverStrategy::Auto => CrossoverStrategy::TypeBlocks,
+            concrete => concrete,
+        }
+    }
+}
+
+/// Demande d'avancer d'un seul pas de physique pendant la pause
+#[derive(Resource, Default)]
+pub struct StepRequested(pub bool);
+
+/// Mode observation quand `false` : la nourriture est mangée sans rapporter de points
+/// et les génomes ne changent pas d'une époque à l'autre
+#[derive(Resource)]
+pub struct ScoringEnabled(pub bool);
+
+impl Default for ScoringEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Met la simulation en pause à la fin de chaque époque, avant la sélection génétique
+#[derive(Resource, Default)]
+pub struct AutoPauseOnEpochEnd(pub bool);
+
+/// Sauvegarde le génome de chaque simulation quand `max_epochs` est atteint
+#[derive(Resource, Default)]
+pub struct AutoSaveOnFinish(pub bool);
+
+/// Époque terminée en attente de confirmation avant `reset_for_new_epoch`
+#[derive(Resource, Default)]
+pub struct EpochEndPending(pub bool);
+
+/// Avance rapide : enchaîne des époques sans attendre l'horloge réelle
+#[derive(Resource)]
+pub struct FastForward {
+    /// Nombre d'époques demandé depuis la barre de contrôle
+    pub requested_epochs: usize,
+    pub total_epochs: usize,
+    pub remaining_epochs: usize,
+    /// Vitesse à restaurer à la fin de l'avance rapide
+    pub previous_speed: SimulationSpeed,
+}
+
+impl Default for FastForward {
+    fn default() -> Self {
+        Self {
+            requested_epochs: 5,
+            total_epochs: 0,
+            remaining_epochs: 0,
+            previous_speed: SimulationSpeed::Normal,
+        }
+    }
+}
+
+impl FastForward {
+    pub fn is_active(&self) -> bool {
+        self.remaining_epochs > 0
+    }
+
+    pub fn start(&mut self, epochs: usize, current_speed: SimulationSpeed) {
+        self.total_epochs = epochs;
+        self.remaining_epochs = epochs;
+        self.previous_speed = current_speed;
+    }
+
+    /// Progression globale, `epoch_fraction` étant l'avancement de l'époque en cours
+    pub fn progress(&self, epoch_fraction: f32) -> f32 {
+        if self.total_epochs == 0 {
+            return 0.0;
+        }
+        let done = (self.total_epochs - self.remaining_epochs) as f32 + epoch_fraction;
+        (done / self.total_epochs as f32).clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Resource, Clone)]
 pub struct SimulationParameters {
     // Paramètres d'époque
@@ -35,14 +344,46 @@ pub struct SimulationParameters {
     pub particle_types: usize,
     pub simulation_speed: SimulationSpeed,
 
+    /// Durée d'un pas de physique, bornée à [MIN_PHYSICS_TIMESTEP, MAX_PHYSICS_TIMESTEP]
+    pub physics_timestep: f32,
+
     // Paramètres des forces
     pub max_force_range: f32,
     pub velocity_half_life: f32,
+    pub max_velocity: f32,
+    pub integrator: Integrator,
+    pub force_curve: ForceCurve,
+    /// Distance sous laquelle deux particules se repoussent, `None` pour la valeur historique
+    /// `particle_types × PARTICLE_RADIUS`. Plus grande, elle espace les particules et donne
+    /// des amas lâches ; plus petite, elle les tasse en amas denses. Elle doit rester sous la
+    /// portée des forces pour laisser place à l'attraction
+    pub min_distance: Option<f32>,
+    /// Fraction de la vitesse conservée au rebond sur un mur (0 à 1)
+    pub collision_damping: f32,
+    /// Facteur multiplicatif appliqué aux forces du génome
+    pub force_scale_factor: f32,
+    /// Nombre max de voisins (les plus proches) par particule, 0 = illimité
+    pub max_interactions_per_particle: usize,
 
     // Paramètres génétiques
+    pub elitism: Elitism,
     pub elite_ratio: f32,
     pub mutation_rate: f32,
+    /// Taux de mutation appliqué aux élites conservées, 0 = élitisme pur
+    pub elite_mutation_rate: f32,
     pub crossover_rate: f32,
+    /// Candidats tirés par tournoi : plus grand favorise les meilleurs, plus petit la diversité
+    pub tournament_size: usize,
+    pub mutation_kind: MutationKind,
+    pub crossover_strategy: CrossoverStrategy,
+    /// Partage de fitness : le score est divisé par la taille de la niche
+    pub sharing_enabled: bool,
+    pub sharing_radius: f32,
+    /// Génomes aléatoires dont les types peuvent s'attirer eux-mêmes (amas cohésifs)
+    pub allow_self_attraction: bool,
+    /// Les enfants d'un crossover héritent de l'historique de fitness moyen de leurs parents
+    pub inherit_fitness_history: bool,
+    pub spawn_mode: SpawnMode,
 }
 
 impl Default for SimulationParameters {
@@ -57,18 +398,62 @@ impl Default for SimulationParameters {
             particle_count: DEFAULT_PARTICLE_COUNT,
             particle_types: DEFAULT_PARTICLE_TYPES,
             simulation_speed: SimulationSpeed::default(),
+            physics_timestep: PHYSICS_TIMESTEP,
 
             max_force_range: DEFAULT_MAX_FORCE_RANGE,
-            velocity_half_life: 0.043,
+            velocity_half_life: DEFAULT_VELOCITY_HALF_LIFE,
+            max_velocity: DEFAULT_MAX_VELOCITY,
+            integrator: Integrator::default(),
+            force_curve: ForceCurve::default(),
+            min_distance: None,
+            collision_damping: DEFAULT_COLLISION_DAMPING,
+            force_scale_factor: FORCE_SCALE_FACTOR,
+            max_interactions_per_particle: DEFAULT_MAX_INTERACTIONS,
 
+            elitism: Elitism::default(),
             elite_ratio: DEFAULT_ELITE_RATIO,
             mutation_rate: DEFAULT_MUTATION_RATE,
+            elite_mutation_rate: DEFAULT_ELITE_MUTATION_RATE,
             crossover_rate: DEFAULT_CROSSOVER_RATE,
+            tournament_size: DEFAULT_TOURNAMENT_SIZE,
+            mutation_kind: MutationKind::default(),
+            crossover_strategy: CrossoverStrategy::default(),
+            sharing_enabled: false,
+            sharing_radius: DEFAULT_SHARING_RADIUS,
+            allow_self_attraction: false,
+            inherit_fitness_history: false,
+            spawn_mode: SpawnMode::default(),
         }
     }
 }
 
 impl SimulationParameters {
+    /// Élites conservées, entre 1 et `simulation_count - 1` pour laisser place à au moins
+    /// un descendant
+    pub fn elite_count(&self) -> usize {
+        self.elitism.resolve(self.elite_ratio, self.simulation_count)
+    }
+
+    /// Taille de tournoi effective, entre 1 et `simulation_count`
+    pub fn tournament_size(&self) -> usize {
+        self.tournament_size.clamp(1, self.simulation_count.max(1))
+    }
+
+    /// Distance de répulsion effective pour des particules de rayon `PARTICLE_RADIUS`
+    pub fn min_distance(&self) -> f32 {
+        self.min_distance
+            .unwrap_or(self.particle_types as f32 * PARTICLE_RADIUS)
+    }
+
+    /// Distance de répulsion d'un couple de rayon moyen `pair_radius`, proportionnelle à ce
+    /// rayon ; le shader la recalcule depuis `min_distance()` et les rayons par type
+    pub fn pair_min_distance(&self, pair_radius: f32) -> f32 {
+        match self.min_distance {
+            Some(min_distance) => min_distance * pair_radius / PARTICLE_RADIUS,
+            None => self.particle_types as f32 * pair_radius,
+        }
+    }
+
     /// Met à jour le timer avec le delta time
     pub fn tick(&mut self, delta: std::time::Duration) {
         if self.simulation_speed != SimulationSpeed::Paused {
@@ -77,11 +462,28 @@ impl SimulationParameters {
         }
     }
 
+    /// Pas de physique par frame : le temps simulé par frame reste `PHYSICS_TIMESTEP` × vitesse
+    /// quel que soit le timestep. La partie fractionnaire est reportée à la frame suivante
+    /// côté CPU, jouée en un pas raccourci côté GPU
+    pub fn physics_steps_per_frame(&self) -> f32 {
+        self.simulation_speed.multiplier() * PHYSICS_TIMESTEP / self.physics_timestep()
+    }
+
+    pub fn physics_timestep(&self) -> f32 {
+        self.physics_timestep
+            .clamp(MIN_PHYSICS_TIMESTEP, MAX_PHYSICS_TIMESTEP)
+    }
+
     /// Vérifie si l'époque est terminée
     pub fn is_epoch_finished(&self) -> bool {
         self.epoch_timer.finished()
     }
 
+    /// Toutes les époques du run ont été jouées
+    pub fn all_epochs_done(&self) -> bool {
+        self.current_epoch >= self.max_epochs
+    }
+
     /// Démarre une nouvelle époque
     pub fn start_new_epoch(&mut self) {
         self.current_epoch += 1;
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+/// Paramètres des traînées de particules
+#[derive(Resource)]
+pub struct TrailConfig {
+    pub enabled: bool,
+    /// Nombre de positions mémorisées par particule
+    pub length: usize,
+    /// Opacité en tête de traînée, décroissant jusqu'à 0 en queue
+    pub fade: f32,
+    /// Nombre total de segments dessinés par simulation
+    pub max_segments: usize,
+}
+
+impl Default for TrailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            length: 20,
+            fade: 0.8,
+            max_segments: 20_000,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::globals::*;
+use crate::resources::config::simulation::SimulationParameters;
+use bevy::prelude::*;
+
+/// Adaptation automatique des paramètres génétiques : quand le meilleur score stagne,
+/// le taux de mutation monte et le ratio d'élites baisse, puis les deux reviennent à leur
+/// valeur d'origine dès que l'amélioration reprend
+#[derive(Resource, Clone, Debug)]
+pub struct AutoAdapt {
+    pub enabled: bool,
+    /// Époques consécutives sans amélioration avant chaque ajustement
+    pub stagnation_threshold: usize,
+    /// Hausse du taux de mutation par ajustement
+    pub mutation_step: f32,
+    /// Baisse du ratio d'élites par ajustement
+    pub elite_step: f32,
+    /// Époques sans amélioration depuis le dernier ajustement
+    pub stagnation_epochs: usize,
+    /// Taux de mutation et ratio d'élites d'origine, tant qu'une adaptation est en cours
+    pub baseline: Option<(f32, f32)>,
+}
+
+impl Default for AutoAdapt {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stagnation_threshold: DEFAULT_STAGNATION_THRESHOLD,
+            mutation_step: DEFAULT_ADAPT_MUTATION_STEP,
+            elite_step: DEFAULT_ADAPT_ELITE_STEP,
+            stagnation_epochs: 0,
+            baseline: None,
+        }
+    }
+}
+
+impl AutoAdapt {
+    /// Paramètres actuellement relevés : la stagnation est déjà prise en compte dans le
+    /// taux de base et ne doit plus être comptée dans le taux adaptatif par génome
+    pub fn is_adapting(&self) -> bool {
+        self.enabled && self.baseline.is_some()
+    }
+
+    /// Ajuste `sim_params` d'après le résultat de l'époque qui vient de se terminer
+    pub fn observe_epoch(&mut self, improved: bool, sim_params: &mut SimulationParameters) {
+        if !self.enabled {
+            return;
+        }
+
+        if improved {
+            self.stagnation_epochs = 0;
+            if let Some((mutation_rate, elite_ratio)) = self.baseline.take() {
+                info!(
+                    "🔧 Amélioration : paramètres restaurés (mutation {:.0}% → {:.0}%, élites {:.0}% → {:.0}%)",
+                    sim_params.mutation_rate * 100.0,
+                    mutation_rate * 100.0,
+                    sim_params.elite_ratio * 100.0,
+                    elite_ratio * 100.0
+                );
+                sim_params.mutation_rate = mutation_rate;
+                sim_params.elite_ratio = elite_ratio;
+            }
+            return;
+        }
+
+        self.stagnation_epochs += 1;
+        if self.stagnation_epochs < self.stagnation_threshold.max(1) {
+            return;
+        }
+        self.stagnation_epochs = 0;
+
+        let previous = (sim_params.mutation_rate, sim_params.elite_ratio);
+        let mutation_rate = (previous.0 + self.mutation_step).min(ADAPT_MAX_MUTATION_RATE);
+        let elite_ratio = (previous.1 - self.elite_step).max(ADAPT_MIN_ELITE_RATIO);
+        if mutation_rate == previous.0 && elite_ratio == previous.1 {
+            info!("🔧 Stagnation persistante : paramètres déjà à leurs bornes");
+            return;
+        }
+
+        self.baseline.get_or_insert(previous);
+        sim_params.mutation_rate = mutation_rate;
+        sim_params.elite_ratio = elite_ratio;
+        info!(
+            "🔧 Stagnation depuis {} époque(s) : mutation {:.0}% → {:.0}%, élites {:.0}% → {:.0}%",
+            self.stagnation_threshold.max(1),
+            previous.0 * 100.0,
+            mutation_rate * 100.0,
+            previous.1 * 100.0,
+            elite_ratio * 100.0
+        );
+    }
+}
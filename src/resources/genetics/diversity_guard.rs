@@ -0,0 +1,73 @@
+use crate::globals::*;
+use bevy::prelude::*;
+
+/// Réaction à une population dont la diversité s'est effondrée
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CollapseAction {
+    /// Remplace une grande partie des non-élites par des génomes aléatoires
+    #[default]
+    Inject,
+    /// Termine le run, avec la mention « convergé » dans le rapport
+    Stop,
+}
+
+impl CollapseAction {
+    pub const ALL: [CollapseAction; 2] = [CollapseAction::Inject, CollapseAction::Stop];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CollapseAction::Inject => "Injecter de la diversité",
+            CollapseAction::Stop => "Arrêter le run",
+        }
+    }
+}
+
+/// Surveillance de la convergence prématurée : quand la diversité génétique reste sous
+/// `threshold` pendant `patience` époques consécutives, `action` est déclenchée
+#[derive(Resource, Clone, Debug)]
+pub struct DiversityGuard {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub patience: usize,
+    pub action: CollapseAction,
+    /// Époques consécutives sous le seuil
+    pub low_epochs: usize,
+    /// Époque à laquelle le run a été arrêté pour convergence
+    pub converged_at: Option<usize>,
+}
+
+impl Default for DiversityGuard {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: DEFAULT_DIVERSITY_COLLAPSE_THRESHOLD,
+            patience: DEFAULT_DIVERSITY_COLLAPSE_PATIENCE,
+            action: CollapseAction::default(),
+            low_epochs: 0,
+            converged_at: None,
+        }
+    }
+}
+
+impl DiversityGuard {
+    /// Compte l'époque qui vient de se terminer ; renvoie l'action à appliquer quand la
+    /// diversité est restée trop basse assez longtemps. Le compteur repart de zéro après
+    /// chaque déclenchement
+    pub fn observe_epoch(&mut self, diversity: f32) -> Option<CollapseAction> {
+        if !self.enabled {
+            return None;
+        }
+
+        if diversity >= self.threshold {
+            self.low_epochs = 0;
+            return None;
+        }
+
+        self.low_epochs += 1;
+        if self.low_epochs < self.patience.max(1) {
+            return None;
+        }
+        self.low_epochs = 0;
+        Some(self.action)
+    }
+}
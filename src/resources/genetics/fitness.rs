@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+/// Pondération des objectifs combinés dans la fitness d'un génome
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct FitnessWeights {
+    /// Poids de la nourriture mangée (score brut)
+    pub food: f32,
+    /// Poids de l'exploration, exprimée en pourcentage de cellules visitées (0-100)
+    pub exploration: f32,
+    /// Bonus de proximité : points ajoutés au score pour les particules proches de la
+    /// nourriture sans la manger, signal plus dense au début de l'évolution
+    pub proximity_enabled: bool,
+    /// Poids du bonus, qui vaut sur une époque ce poids fois la proximité moyenne
+    pub proximity: f32,
+}
+
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        Self {
+            food: 1.0,
+            exploration: 0.0,
+            proximity_enabled: false,
+            proximity: 0.1,
+        }
+    }
+}
+
+impl FitnessWeights {
+    pub fn combine(&self, food_score: f32, exploration_fraction: f32) -> f32 {
+        self.food * food_score + self.exploration * exploration_fraction * 100.0
+    }
+}
@@ -0,0 +1,67 @@
+use crate::components::genetics::genotype::Genotype;
+use crate::globals::*;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Génome archivé à la fin d'une époque
+#[derive(Clone)]
+pub struct ArchivedGenome {
+    pub genotype: Genotype,
+    pub score: f32,
+    pub simulation_id: usize,
+}
+
+/// Meilleurs génomes d'une époque terminée, triés par score décroissant
+#[derive(Clone)]
+pub struct ArchivedEpoch {
+    /// Numéro (à partir de 1) de l'époque
+    pub epoch: usize,
+    pub genomes: Vec<ArchivedGenome>,
+}
+
+/// Champions de chaque époque, en anneau borné à `GENOME_ARCHIVE_CAPACITY` époques
+#[derive(Resource, Clone)]
+pub struct GenomeArchive {
+    pub epochs: VecDeque<ArchivedEpoch>,
+    /// Nombre de génomes conservés par époque
+    pub depth: usize,
+}
+
+impl Default for GenomeArchive {
+    fn default() -> Self {
+        Self::new(DEFAULT_GENOME_ARCHIVE_DEPTH)
+    }
+}
+
+impl GenomeArchive {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            epochs: VecDeque::new(),
+            depth,
+        }
+    }
+
+    /// Archive les `depth` meilleurs génomes d'une époque terminée
+    pub fn record_epoch(&mut self, epoch: usize, mut genomes: Vec<ArchivedGenome>) {
+        if self.depth == 0 {
+            return;
+        }
+
+        genomes.sort_by(|a, b| b.score.total_cmp(&a.score));
+        genomes.truncate(self.depth);
+
+        self.epochs.push_back(ArchivedEpoch { epoch, genomes });
+        while self.epochs.len() > GENOME_ARCHIVE_CAPACITY {
+            self.epochs.pop_front();
+        }
+    }
+
+    pub fn get(&self, epoch: usize) -> Option<&ArchivedEpoch> {
+        self.epochs.iter().find(|archived| archived.epoch == epoch)
+    }
+
+    /// Première et dernière époques encore archivées
+    pub fn epoch_range(&self) -> Option<(usize, usize)> {
+        Some((self.epochs.front()?.epoch, self.epochs.back()?.epoch))
+    }
+}
@@ -0,0 +1,71 @@
+use crate::components::genetics::genotype::Genotype;
+use crate::globals::*;
+use bevy::prelude::*;
+
+/// Génome remarquable conservé d'une époque à l'autre
+#[derive(Clone)]
+pub struct HallOfFameEntry {
+    pub genotype: Genotype,
+    pub score: f32,
+    /// Numéro (à partir de 1) de l'époque où ce score a été obtenu
+    pub epoch: usize,
+    pub simulation_id: usize,
+}
+
+/// Meilleurs génomes jamais observés sur l'ensemble du run, triés par score décroissant
+#[derive(Resource, Clone)]
+pub struct HallOfFame {
+    pub entries: Vec<HallOfFameEntry>,
+    pub capacity: usize,
+    /// Réinjecte un membre dans chaque nouvelle population
+    pub reinject: bool,
+}
+
+impl Default for HallOfFame {
+    fn default() -> Self {
+        Self::new(DEFAULT_HALL_OF_FAME_SIZE, false)
+    }
+}
+
+impl HallOfFame {
+    pub fn new(capacity: usize, reinject: bool) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity + 1),
+            capacity,
+            reinject,
+        }
+    }
+
+    /// Propose un génome ; retourne `true` s'il entre dans le classement
+    pub fn consider(&mut self, entry: HallOfFameEntry) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+
+        // Un même génome (élite conservée) ne garde que son meilleur score
+        let hash = entry.genotype.genome_hash();
+        if let Some(existing) = self
+            .entries
+            .iter()
+            .position(|e| e.genotype.genome_hash() == hash)
+        {
+            if self.entries[existing].score >= entry.score {
+                return false;
+            }
+            self.entries.remove(existing);
+        }
+
+        let position = self
+            .entries
+            .iter()
+            .position(|e| entry.score > e.score)
+            .unwrap_or(self.entries.len());
+        if position >= self.capacity {
+            return false;
+        }
+
+        self.entries.insert(position, entry);
+        self.entries.truncate(self.capacity);
+        true
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+/// Historique de l'évolution sur l'ensemble des époques du run
+#[derive(Resource, Default, Clone)]
+pub struct EvolutionHistory {
+    pub best_scores: Vec<f32>,
+    pub average_scores: Vec<f32>,
+    pub diversity: Vec<f32>,
+}
+
+impl EvolutionHistory {
+    /// Enregistre les statistiques d'une époque terminée
+    pub fn record_epoch(&mut self, best_score: f32, average_score: f32, diversity: f32) {
+        self.best_scores.push(best_score);
+        self.average_scores.push(average_score);
+        self.diversity.push(diversity);
+    }
+
+    /// Nombre d'époques enregistrées
+    pub fn epoch_count(&self) -> usize {
+        self.best_scores.len()
+    }
+
+    /// Périodes d'au moins `min_length` époques sans dépasser le meilleur score atteint
+    /// jusque-là, en (index de la première époque, durée)
+    pub fn stagnation_episodes(&self, min_length: usize) -> Vec<(usize, usize)> {
+        let mut episodes = Vec::new();
+        let mut record = f32::NEG_INFINITY;
+        let mut start = None;
+        for (epoch, &score) in self.best_scores.iter().enumerate() {
+            if score > record {
+                if let Some(first) = start.take()
+                    && epoch - first >= min_length
+                {
+                    episodes.push((first, epoch - first));
+                }
+                record = score;
+            } else if start.is_none() {
+                start = Some(epoch);
+            }
+        }
+        if let Some(first) = start
+            && self.best_scores.len() - first >= min_length
+        {
+            episodes.push((first, self.best_scores.len() - first));
+        }
+        episodes
+    }
+}
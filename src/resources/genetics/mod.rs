@@ -0,0 +1,6 @@
+pub mod auto_adapt;
+pub mod diversity_guard;
+pub mod fitness;
+pub mod genome_archive;
+pub mod hall_of_fame;
+pub mod history;
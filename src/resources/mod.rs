@@ -1,2 +1,3 @@
 pub mod config;
+pub mod genetics;
 pub mod world;
\ No newline at end of file
@@ -1,8 +1,76 @@
 use bevy::prelude::*;
 
-#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
-pub enum BoundaryMode {
+/// Comportement d'un axe de la grille quand une particule atteint un bord
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AxisBoundary {
     #[default]
     Bounce,
     Teleport,
-}
\ No newline at end of file
+}
+
+impl AxisBoundary {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AxisBoundary::Bounce => "Rebond",
+            AxisBoundary::Teleport => "Téléportation",
+        }
+    }
+}
+
+/// Bords de la grille, axe par axe (X, Y, Z) : tout en rebond, tore complet, ou mélange
+/// (par exemple X et Y qui se rejoignent façon Pac-Man, Z qui rebondit)
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BoundaryMode {
+    pub axes: [AxisBoundary; 3],
+}
+
+impl BoundaryMode {
+    pub const BOUNCE: BoundaryMode = BoundaryMode::uniform(AxisBoundary::Bounce);
+    pub const TELEPORT: BoundaryMode = BoundaryMode::uniform(AxisBoundary::Teleport);
+
+    pub const fn uniform(axis: AxisBoundary) -> Self {
+        Self { axes: [axis; 3] }
+    }
+
+    /// Axes qui se rejoignent (téléportation)
+    pub fn wraps(&self) -> BVec3 {
+        BVec3::new(
+            self.axes[0] == AxisBoundary::Teleport,
+            self.axes[1] == AxisBoundary::Teleport,
+            self.axes[2] == AxisBoundary::Teleport,
+        )
+    }
+
+    pub fn any_wrap(&self) -> bool {
+        self.wraps().any()
+    }
+
+    /// Mode commun aux trois axes, `None` pour un mélange
+    pub fn uniform_axis(&self) -> Option<AxisBoundary> {
+        let [x, y, z] = self.axes;
+        (x == y && y == z).then_some(x)
+    }
+
+    /// Bits des axes en téléportation (1 : X, 2 : Y, 4 : Z), lus par le shader
+    pub fn gpu_mask(&self) -> u32 {
+        self.wraps().bitmask()
+    }
+
+    pub fn label(&self) -> String {
+        match self.uniform_axis() {
+            Some(axis) => axis.label().to_string(),
+            None => {
+                let letter = |axis: AxisBoundary| match axis {
+                    AxisBoundary::Bounce => "R",
+                    AxisBoundary::Teleport => "T",
+                };
+                format!(
+                    "Mixte (X {}, Y {}, Z {})",
+                    letter(self.axes[0]),
+                    letter(self.axes[1]),
+                    letter(self.axes[2])
+                )
+            }
+        }
+    }
+}
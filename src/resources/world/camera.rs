@@ -1,24 +1,79 @@
 use bevy::prelude::*;
+use crate::resources::world::grid::GridParameters;
 use std::{f32::consts::FRAC_PI_2, ops::Range};
 
+/// Point autour duquel les caméras tournent
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CameraTarget {
+    #[default]
+    Origin,
+    /// Position moyenne des particules de la simulation affichée, lissée
+    CenterOfMass,
+}
+
+impl CameraTarget {
+    pub const ALL: [CameraTarget; 2] = [CameraTarget::Origin, CameraTarget::CenterOfMass];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraTarget::Origin => "Origine",
+            CameraTarget::CenterOfMass => "Centre de masse",
+        }
+    }
+}
+
 #[derive(Debug, Resource)]
 pub struct CameraSettings {
     pub orbit_distance: f32,
     pub pitch_speed: f32,
     pub pitch_range: Range<f32>,
-    pub roll_speed: f32,
     pub yaw_speed: f32,
+    /// Fraction de la distance d'orbite parcourue par cran de molette
+    pub zoom_speed: f32,
+    /// Applique la même rotation à tous les viewports
+    pub link_viewports: bool,
+    /// Dimensions de la grille pour laquelle `orbit_distance` a été calculée
+    pub framed_grid: Option<Vec3>,
+    pub target: CameraTarget,
+    /// Point visé par la caméra par défaut (les caméras de viewport ont le leur)
+    pub focus: Vec3,
 }
 
 impl Default for CameraSettings {
     fn default() -> Self {
         let pitch_limit = FRAC_PI_2 - 0.01;
         Self {
-            orbit_distance: 800.0, 
+            orbit_distance: 800.0,
             pitch_speed: 0.003,
             pitch_range: -pitch_limit..pitch_limit,
-            roll_speed: 1.0,
             yaw_speed: 0.003,
+            zoom_speed: 0.1,
+            link_viewports: false,
+            framed_grid: None,
+            target: CameraTarget::default(),
+            focus: Vec3::ZERO,
         }
     }
+}
+
+impl CameraSettings {
+    /// Distance pour voir confortablement toute la grille
+    pub fn ideal_orbit_distance(grid: &GridParameters) -> f32 {
+        let diagonal_3d = (grid.width.powi(2) + grid.height.powi(2) + grid.depth.powi(2)).sqrt();
+        (diagonal_3d * 0.85).max(300.0) // Distance minimale de sécurité
+    }
+
+    /// Recadre l'orbite si les dimensions de la grille ont réellement changé : une
+    /// ressource réinsérée à l'identique garde le zoom de l'utilisateur.
+    /// Retourne `true` si la distance a été recalculée.
+    pub fn update_for_grid(&mut self, grid: &GridParameters) -> bool {
+        let dimensions = Vec3::new(grid.width, grid.height, grid.depth);
+        if self.framed_grid == Some(dimensions) {
+            return false;
+        }
+
+        self.framed_grid = Some(dimensions);
+        self.orbit_distance = Self::ideal_orbit_distance(grid);
+        true
+    }
 }
\ No newline at end of file
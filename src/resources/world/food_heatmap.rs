@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::globals::FOOD_HEATMAP_RESOLUTION;
+use crate::resources::world::grid::GridParameters;
+
+/// Heatmap de la nourriture projetée sur le plan XZ (sol de la grille)
+#[derive(Resource, Default)]
+pub struct FoodHeatmap {
+    pub enabled: bool,
+    /// Nourritures mangées pendant l'époque, par simulation puis par cellule
+    pub consumption: HashMap<usize, HashMap<IVec3, u32>>,
+    /// Époque à laquelle se rapportent les compteurs
+    pub epoch: usize,
+}
+
+impl FoodHeatmap {
+    pub fn cell_size(grid: &GridParameters) -> f32 {
+        grid.width.max(grid.depth) / FOOD_HEATMAP_RESOLUTION as f32
+    }
+
+    /// Clé de cellule projetée : la coordonnée Y est écrasée pour cumuler toute la hauteur
+    pub fn cell_key(grid: &GridParameters, position: Vec3) -> IVec3 {
+        let half_extents = Vec3::new(grid.width, grid.height, grid.depth) / 2.0;
+        ((position + half_extents) / Self::cell_size(grid))
+            .floor()
+            .as_ivec3()
+            .with_y(0)
+    }
+
+    /// Centre de la cellule, posé sur le sol de la grille
+    pub fn cell_center(grid: &GridParameters, key: IVec3) -> Vec3 {
+        let cell_size = Self::cell_size(grid);
+        Vec3::new(
+            (key.x as f32 + 0.5) * cell_size - grid.width / 2.0,
+            -grid.height / 2.0,
+            (key.z as f32 + 0.5) * cell_size - grid.depth / 2.0,
+        )
+    }
+
+    /// Compte une nourriture mangée ; les compteurs repartent de zéro à chaque époque
+    pub fn record_consumption(
+        &mut self,
+        grid: &GridParameters,
+        epoch: usize,
+        simulation_id: usize,
+        position: Vec3,
+    ) {
+        if epoch != self.epoch {
+            self.consumption.clear();
+            self.epoch = epoch;
+        }
+
+        *self
+            .consumption
+            .entry(simulation_id)
+            .or_default()
+            .entry(Self::cell_key(grid, position))
+            .or_default() += 1;
+    }
+}
@@ -1,5 +1,5 @@
 use crate::globals::*;
-use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::boundary::{AxisBoundary, BoundaryMode};
 use bevy::prelude::*;
 
 #[derive(Resource)]
@@ -9,6 +9,25 @@ pub struct GridParameters {
     pub depth: f32,
 }
 
+/// Affichage des limites de la grille dans les viewports
+#[derive(Resource)]
+pub struct GridBoundsDisplay {
+    pub visible: bool,
+}
+
+impl Default for GridBoundsDisplay {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+/// Redimensionnement de la grille en cours de run : étirer les positions avec la grille
+/// plutôt que seulement ramener au bord celles qui en sortent
+#[derive(Resource, Default)]
+pub struct GridResizeMode {
+    pub rescale: bool,
+}
+
 impl Default for GridParameters {
     fn default() -> Self {
         Self {
@@ -20,75 +39,42 @@ impl Default for GridParameters {
 }
 
 impl GridParameters {
-    /// Vérifie si une position est dans les limites de la grille
-    pub fn is_in_bounds(&self, position: Vec3) -> bool {
-        let half_width = self.width / 2.0;
-        let half_height = self.height / 2.0;
-        let half_depth = self.depth / 2.0;
-
-        position.x.abs() <= half_width
-            && position.y.abs() <= half_height
-            && position.z.abs() <= half_depth
-    }
-
-    /// Applique les bords selon le mode (rebond ou téléportation)
-    pub fn apply_bounds(&self, position: &mut Vec3, velocity: &mut Vec3, mode: BoundaryMode) {
-        match mode {
-            BoundaryMode::Bounce => self.apply_bounce_bounds(position, velocity),
-            BoundaryMode::Teleport => self.apply_teleport_bounds(position),
+    /// Applique les bords axe par axe selon le mode (rebond ou téléportation)
+    pub fn apply_bounds(
+        &self,
+        position: &mut Vec3,
+        velocity: &mut Vec3,
+        mode: BoundaryMode,
+        collision_damping: f32,
+    ) {
+        let half_extents = [self.width / 2.0, self.height / 2.0, self.depth / 2.0];
+        for (axis, boundary) in mode.axes.into_iter().enumerate() {
+            match boundary {
+                AxisBoundary::Bounce => Self::bounce_axis(
+                    &mut position[axis],
+                    &mut velocity[axis],
+                    half_extents[axis],
+                    collision_damping,
+                ),
+                AxisBoundary::Teleport => Self::teleport_axis(&mut position[axis], half_extents[axis]),
+            }
         }
     }
 
-    /// Applique les rebonds sur les murs
-    fn apply_bounce_bounds(&self, position: &mut Vec3, velocity: &mut Vec3) {
-        let half_width = self.width / 2.0;
-        let half_height = self.height / 2.0;
-        let half_depth = self.depth / 2.0;
-
-        // Rebond sur les murs X
-        if position.x.abs() > half_width - PARTICLE_RADIUS {
-            position.x = position.x.signum() * (half_width - PARTICLE_RADIUS);
-            velocity.x *= -COLLISION_DAMPING;
-        }
-
-        // Rebond sur les murs Y
-        if position.y.abs() > half_height - PARTICLE_RADIUS {
-            position.y = position.y.signum() * (half_height - PARTICLE_RADIUS);
-            velocity.y *= -COLLISION_DAMPING;
-        }
-
-        // Rebond sur les murs Z
-        if position.z.abs() > half_depth - PARTICLE_RADIUS {
-            position.z = position.z.signum() * (half_depth - PARTICLE_RADIUS);
-            velocity.z *= -COLLISION_DAMPING;
+    /// Rebond sur les deux murs d'un axe
+    fn bounce_axis(position: &mut f32, velocity: &mut f32, half_extent: f32, collision_damping: f32) {
+        if position.abs() > half_extent - PARTICLE_RADIUS {
+            *position = position.signum() * (half_extent - PARTICLE_RADIUS);
+            *velocity *= -collision_damping;
         }
     }
 
-    /// Téléporte les particules de l'autre côté
-    fn apply_teleport_bounds(&self, position: &mut Vec3) {
-        let half_width = self.width / 2.0;
-        let half_height = self.height / 2.0;
-        let half_depth = self.depth / 2.0;
-
-        // Téléportation X
-        if position.x > half_width {
-            position.x = -half_width + (position.x - half_width);
-        } else if position.x < -half_width {
-            position.x = half_width + (position.x + half_width);
-        }
-
-        // Téléportation Y
-        if position.y > half_height {
-            position.y = -half_height + (position.y - half_height);
-        } else if position.y < -half_height {
-            position.y = half_height + (position.y + half_height);
-        }
-
-        // Téléportation Z
-        if position.z > half_depth {
-            position.z = -half_depth + (position.z - half_depth);
-        } else if position.z < -half_depth {
-            position.z = half_depth + (position.z + half_depth);
+    /// Téléporte la particule de l'autre côté d'un axe
+    fn teleport_axis(position: &mut f32, half_extent: f32) {
+        if *position > half_extent {
+            *position = -half_extent + (*position - half_extent);
+        } else if *position < -half_extent {
+            *position = half_extent + (*position + half_extent);
         }
     }
 }
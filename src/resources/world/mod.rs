@@ -1,3 +1,5 @@
 pub mod boundary;
 pub mod camera;
-pub mod grid;
\ No newline at end of file
+pub mod food_heatmap;
+pub mod grid;
+pub mod mouse_interaction;
\ No newline at end of file
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+/// Outil d'attraction/répulsion piloté par la souris
+#[derive(Resource)]
+pub struct MouseInteraction {
+    pub strength: f32,
+    pub radius: f32,
+    pub target: Option<MouseForceTarget>,
+}
+
+/// Point d'application courant de la force souris
+#[derive(Clone, Copy)]
+pub struct MouseForceTarget {
+    pub simulation_id: usize,
+    pub point: Vec3,
+    /// 1.0 pour attirer, -1.0 pour repousser
+    pub sign: f32,
+}
+
+impl Default for MouseInteraction {
+    fn default() -> Self {
+        Self {
+            strength: 50.0,
+            radius: 150.0,
+            target: None,
+        }
+    }
+}
+
+impl MouseInteraction {
+    /// Force en 1/d² exercée sur une particule de la simulation ciblée
+    pub fn force_at(&self, simulation_id: usize, position: Vec3) -> Vec3 {
+        let Some(target) = self.target else {
+            return Vec3::ZERO;
+        };
+        if target.simulation_id != simulation_id {
+            return Vec3::ZERO;
+        }
+
+        let offset = target.point - position;
+        let distance = offset.length();
+        if distance > self.radius || distance < 0.001 {
+            return Vec3::ZERO;
+        }
+
+        // Distance plancher pour éviter l'explosion au voisinage du curseur
+        let effective_distance = distance.max(self.radius * 0.1);
+        let falloff = (self.radius / effective_distance).powi(2);
+        offset / distance * self.strength * falloff * target.sign
+    }
+}
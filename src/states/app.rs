@@ -8,4 +8,6 @@ pub enum AppState {
     Simulation,
     Visualizer,
     Visualization,  
+    /// Rapport de fin de run, atteint quand `max_epochs` est joué
+    Finished,
 }
\ No newline at end of file
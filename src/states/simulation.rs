@@ -6,5 +6,4 @@ pub enum SimulationState {
     Starting,
     Running,
     Paused,
-    GeneticSelection,
 }
\ No newline at end of file
@@ -1,27 +1,99 @@
 use bevy::prelude::*;
-use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::config::simulation::{
+    AutoPauseOnEpochEnd, EpochEndPending, FastForward, SimulationParameters, StepRequested,
+};
 use crate::states::simulation::SimulationState;
+use crate::systems::persistence::run_report::ExportStatsEvent;
 
 pub fn check_epoch_end(
     mut sim_params: ResMut<SimulationParameters>,
     mut next_state: ResMut<NextState<SimulationState>>,
+    auto_pause: Res<AutoPauseOnEpochEnd>,
+    mut epoch_end_pending: ResMut<EpochEndPending>,
     time: Res<Time>,
 ) {
     sim_params.tick(time.delta());
 
-    if sim_params.is_epoch_finished() {
-        info!("Époque {} terminée!", sim_params.current_epoch);
-        sim_params.start_new_epoch();
+    if !sim_params.is_epoch_finished() {
+        return;
+    }
+
+    // Les scores finaux restent affichés jusqu'à confirmation ; le timer ne tourne
+    // pas en pause, l'attente ne compte donc pas dans la durée de l'époque
+    if auto_pause.0 {
+        info!(
+            "Époque {} terminée, en attente de confirmation (Entrée pour évoluer)",
+            sim_params.current_epoch
+        );
+        epoch_end_pending.0 = true;
+        next_state.set(SimulationState::Paused);
+        return;
+    }
+
+    info!("Époque {} terminée!", sim_params.current_epoch);
+    advance_epoch(&mut sim_params, &mut next_state);
+}
+
+/// Passe à l'époque suivante, sauf après la dernière : la simulation reste alors en pause
+/// avec les scores finaux, pour le bilan de fin de run
+pub fn advance_epoch(
+    sim_params: &mut SimulationParameters,
+    next_state: &mut NextState<SimulationState>,
+) {
+    sim_params.start_new_epoch();
+    if sim_params.all_epochs_done() {
+        info!("Dernière époque terminée ({} / {})", sim_params.max_epochs, sim_params.max_epochs);
+        next_state.set(SimulationState::Paused);
+    } else {
         next_state.set(SimulationState::Starting);
     }
 }
 
+/// Lance la sélection génétique d'une époque mise en attente par `AutoPauseOnEpochEnd`
+pub fn confirm_epoch_end(
+    sim_params: &mut SimulationParameters,
+    epoch_end_pending: &mut EpochEndPending,
+    next_state: &mut NextState<SimulationState>,
+) {
+    if !epoch_end_pending.0 {
+        return;
+    }
+
+    info!("Époque {} confirmée, évolution", sim_params.current_epoch);
+    epoch_end_pending.0 = false;
+    advance_epoch(sim_params, next_state);
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_pause_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     state: Res<State<SimulationState>>,
     mut next_state: ResMut<NextState<SimulationState>>,
+    mut step_requested: ResMut<StepRequested>,
+    fast_forward: Res<FastForward>,
+    mut sim_params: ResMut<SimulationParameters>,
+    mut epoch_end_pending: ResMut<EpochEndPending>,
+    mut export_events: EventWriter<ExportStatsEvent>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
+    if keyboard.just_pressed(KeyCode::KeyE) {
+        export_events.write(ExportStatsEvent);
+    }
+
+    // Fin d'époque en attente : seule la confirmation fait avancer la simulation
+    if epoch_end_pending.0 {
+        if keyboard.just_pressed(KeyCode::Enter) {
+            confirm_epoch_end(&mut sim_params, &mut epoch_end_pending, &mut next_state);
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Period) && *state.get() == SimulationState::Paused {
+        info!("Avance d'un pas de simulation");
+        step_requested.0 = true;
+    }
+
+    // La pause interromprait l'avance rapide au milieu d'une époque
+    if keyboard.just_pressed(KeyCode::Space) && !fast_forward.is_active() {
         match state.get() {
             SimulationState::Running => {
                 info!("Simulation en pause");
@@ -34,4 +106,18 @@ pub fn handle_pause_input(
             _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+/// Vrai si la simulation tourne ou si un pas unique a été demandé pendant la pause
+pub fn running_or_step_requested(
+    state: Res<State<SimulationState>>,
+    step_requested: Res<StepRequested>,
+) -> bool {
+    *state.get() == SimulationState::Running
+        || (*state.get() == SimulationState::Paused && step_requested.0)
+}
+
+/// Consomme la demande de pas unique une fois la physique exécutée
+pub fn clear_step_request(mut step_requested: ResMut<StepRequested>) {
+    step_requested.0 = false;
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::components::genetics::genotype::Genotype;
+
+/// Contenu JSON d'un export de génome, lisible sans le reste de l'application
+#[derive(Serialize)]
+struct GenotypeMatrixExport<'a> {
+    type_count: usize,
+    genome_hash: String,
+    /// force_matrix[i][j] : attraction du type i vers le type j
+    force_matrix: Vec<Vec<f32>>,
+    food_forces: &'a [f32],
+    fitness_history: &'a [f32],
+}
+
+/// Dossier par défaut des exports de matrices
+pub fn exports_dir() -> PathBuf {
+    PathBuf::from("exports")
+}
+
+/// Écrit la matrice complète et les forces de nourriture en CSV (`path` avec extension .csv)
+/// et en JSON (même chemin, extension .json). Retourne les deux chemins écrits.
+pub fn export_genotype_matrix(
+    genotype: &Genotype,
+    path: &Path,
+) -> Result<(PathBuf, PathBuf), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let type_count = genotype.type_count;
+    let rows = genotype.get_force_matrix();
+
+    // CSV : une ligne par type, une colonne par type cible, puis la force de nourriture
+    let mut csv = String::from("type");
+    for j in 0..type_count {
+        write!(csv, ",type_{}", j)?;
+    }
+    csv.push_str(",food\n");
+    for (i, row) in rows.iter().enumerate() {
+        write!(csv, "type_{}", i)?;
+        for force in row.iter() {
+            write!(csv, ",{}", force)?;
+        }
+        writeln!(csv, ",{}", genotype.food_forces.get(i).copied().unwrap_or(0.0))?;
+    }
+
+    let csv_path = path.with_extension("csv");
+    fs::write(&csv_path, csv)?;
+
+    let export = GenotypeMatrixExport {
+        type_count,
+        genome_hash: format!("{:016x}", genotype.genome_hash()),
+        force_matrix: rows,
+        food_forces: &genotype.food_forces,
+        fitness_history: &genotype.fitness_history,
+    };
+
+    let json_path = path.with_extension("json");
+    fs::write(&json_path, serde_json::to_string_pretty(&export)?)?;
+
+    Ok((csv_path, json_path))
+}
+
+/// Format JSON accepté à l'import (celui produit par l'export)
+#[derive(Deserialize)]
+struct GenotypeMatrixImport {
+    force_matrix: Vec<Vec<f32>>,
+    #[serde(default)]
+    food_forces: Vec<f32>,
+    #[serde(default)]
+    fitness_history: Vec<f32>,
+}
+
+/// Fichiers CSV/JSON disponibles dans le dossier d'export
+pub fn list_exported_matrices() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(exports_dir()) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("csv") | Some("json")
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Charge une matrice CSV ou JSON éditée à la main. Les dimensions doivent
+/// correspondre à `type_count` ; les valeurs sont ramenées dans -2.0..=2.0.
+pub fn import_genotype_matrix(path: &Path, type_count: usize) -> Result<Genotype, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible: {}", path.display(), e))?;
+
+    let (rows, food_forces, fitness_history) =
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => {
+                let import: GenotypeMatrixImport =
+                    serde_json::from_str(&content).map_err(|e| format!("JSON invalide: {}", e))?;
+                (import.force_matrix, import.food_forces, import.fitness_history)
+            }
+            Some("csv") => {
+                let (rows, food_forces) = parse_matrix_csv(&content, type_count)?;
+                (rows, food_forces, Vec::new())
+            }
+            _ => return Err("Format non reconnu (attendu: .csv ou .json)".to_string()),
+        };
+
+    if rows.len() != type_count || rows.iter().any(|row| row.len() != type_count) {
+        return Err(format!(
+            "La matrice doit faire {}×{} (simulation à {} types)",
+            type_count, type_count, type_count
+        ));
+    }
+
+    let food_forces = if food_forces.is_empty() {
+        vec![0.0; type_count]
+    } else {
+        food_forces
+    };
+
+    let mut genotype = Genotype {
+        force_matrix: rows.into_iter().flatten().collect(),
+        food_forces,
+        type_count,
+        fitness_history,
+        locked_mask: Vec::new(),
+    };
+    genotype.validate()?;
+
+    for value in genotype
+        .force_matrix
+        .iter_mut()
+        .chain(genotype.food_forces.iter_mut())
+    {
+        *value = value.clamp(-2.0, 2.0);
+    }
+
+    Ok(genotype)
+}
+
+/// Lit le CSV produit par l'export : en-tête puis une ligne par type,
+/// la colonne `food` étant optionnelle
+fn parse_matrix_csv(content: &str, type_count: usize) -> Result<(Vec<Vec<f32>>, Vec<f32>), String> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or("Fichier CSV vide")?;
+    let has_food = header.split(',').any(|cell| cell.trim() == "food");
+
+    let mut rows = Vec::new();
+    let mut food_forces = Vec::new();
+    for (line_index, line) in lines.enumerate() {
+        let values = line
+            .split(',')
+            .skip(1)
+            .map(|cell| {
+                cell.trim()
+                    .parse::<f32>()
+                    .map_err(|_| format!("Ligne {}: valeur invalide '{}'", line_index + 2, cell))
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+
+        let expected = type_count + usize::from(has_food);
+        if values.len() != expected {
+            return Err(format!(
+                "Ligne {}: {} valeurs, {} attendues",
+                line_index + 2,
+                values.len(),
+                expected
+            ));
+        }
+
+        if has_food {
+            food_forces.push(values[type_count]);
+        }
+        rows.push(values[..type_count].to_vec());
+    }
+
+    Ok((rows, food_forces))
+}
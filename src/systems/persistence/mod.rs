@@ -1 +1,4 @@
-pub mod population_save;
\ No newline at end of file
+pub mod genome_export;
+pub mod population_save;
+pub mod run_config;
+pub mod run_report;
@@ -1,25 +1,36 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::components::{
     entities::simulation::*,
-    entities::particle::*,
-    entities::food::*,
     genetics::genotype::*,
     genetics::score::*,
 };
 
-use crate::resources::config::food::FoodParameters;
+use crate::globals::*;
+use crate::resources::config::food::{FoodDistribution, FoodParameters, FoodRespawnMode};
 use crate::resources::config::particle_types::ParticleTypesConfig;
-use crate::resources::config::simulation::{SimulationParameters, SimulationSpeed};
-use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::config::seed::SimulationSeed;
+use crate::resources::config::simulation::{
+    CrossoverStrategy, Elitism, ForceCurve, Integrator, MutationKind, SimulationParameters, SpawnMode,
+    SimulationSpeed,
+};
+use crate::resources::world::boundary::{AxisBoundary, BoundaryMode};
 use crate::resources::world::grid::GridParameters;
+use crate::systems::persistence::genome_export::exports_dir;
+
+/// Version du format écrit par `save_population_to_file`, à incrémenter avec une étape de
+/// `migrate_population` à chaque changement qui casserait la lecture des anciens fichiers
+pub const POPULATION_SCHEMA_VERSION: u32 = 1;
 
 /// Structure pour sauvegarder une population complète avec ses paramètres
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SavedPopulation {
+    /// Version du format, 0 pour les fichiers antérieurs au versionnage
+    #[serde(default)]
+    pub version: u32,
     pub name: String,
     pub timestamp: String,
     pub genotype: SavedGenotype,
@@ -30,6 +41,9 @@ pub struct SavedPopulation {
     pub particle_types_config: SavedParticleTypesConfig,
     pub boundary_mode: SavedBoundaryMode,
     pub description: Option<String>,
+    /// Étiquettes libres pour classer les populations (ajoutées aux fichiers v0 par
+    /// `migrate_population`)
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -37,6 +51,9 @@ pub struct SavedGenotype {
     pub force_matrix: Vec<f32>,
     pub food_forces: Vec<f32>,
     pub type_count: usize,
+    /// Vide si rien n'est figé ; ajouté ou remis à la taille de la matrice par
+    /// `migrate_population` pour les fichiers v0
+    pub locked_mask: Vec<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -46,6 +63,110 @@ pub struct SavedSimulationParams {
     pub max_force_range: f32,
     pub velocity_half_life: f32,
     pub epoch_duration: f32,
+    #[serde(default = "default_force_scale_factor")]
+    pub force_scale_factor: f32,
+    #[serde(default = "default_max_velocity")]
+    pub max_velocity: f32,
+    #[serde(default = "default_collision_damping")]
+    pub collision_damping: f32,
+    #[serde(default = "default_physics_timestep")]
+    pub physics_timestep: f32,
+    /// Graine du run d'origine (absente des anciennes sauvegardes)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Absent des anciennes sauvegardes : Euler
+    #[serde(default)]
+    pub integrator: SavedIntegrator,
+    /// Absente des anciennes sauvegardes : courbe en triangle
+    #[serde(default)]
+    pub force_curve: SavedForceCurve,
+    /// Absente des anciennes sauvegardes : nombre de types × rayon
+    #[serde(default)]
+    pub min_distance: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum SavedIntegrator {
+    #[default]
+    Euler,
+    Verlet,
+}
+
+impl From<Integrator> for SavedIntegrator {
+    fn from(integrator: Integrator) -> Self {
+        match integrator {
+            Integrator::Euler => SavedIntegrator::Euler,
+            Integrator::Verlet => SavedIntegrator::Verlet,
+        }
+    }
+}
+
+impl From<SavedIntegrator> for Integrator {
+    fn from(integrator: SavedIntegrator) -> Self {
+        match integrator {
+            SavedIntegrator::Euler => Integrator::Euler,
+            SavedIntegrator::Verlet => Integrator::Verlet,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum SavedForceCurve {
+    #[default]
+    Tent,
+    Linear,
+    LennardJones,
+    Smooth,
+}
+
+impl From<ForceCurve> for SavedForceCurve {
+    fn from(curve: ForceCurve) -> Self {
+        match curve {
+            ForceCurve::Tent => SavedForceCurve::Tent,
+            ForceCurve::Linear => SavedForceCurve::Linear,
+            ForceCurve::LennardJones => SavedForceCurve::LennardJones,
+            ForceCurve::Smooth => SavedForceCurve::Smooth,
+        }
+    }
+}
+
+impl From<SavedForceCurve> for ForceCurve {
+    fn from(curve: SavedForceCurve) -> Self {
+        match curve {
+            SavedForceCurve::Tent => ForceCurve::Tent,
+            SavedForceCurve::Linear => ForceCurve::Linear,
+            SavedForceCurve::LennardJones => ForceCurve::LennardJones,
+            SavedForceCurve::Smooth => ForceCurve::Smooth,
+        }
+    }
+}
+
+impl SavedGenotype {
+    pub fn to_genotype(&self) -> Genotype {
+        Genotype {
+            force_matrix: self.force_matrix.clone(),
+            food_forces: self.food_forces.clone(),
+            type_count: self.type_count,
+            fitness_history: Vec::new(),
+            locked_mask: self.locked_mask.clone(),
+        }
+    }
+}
+
+fn default_force_scale_factor() -> f32 {
+    FORCE_SCALE_FACTOR
+}
+
+fn default_max_velocity() -> f32 {
+    DEFAULT_MAX_VELOCITY
+}
+
+fn default_collision_damping() -> f32 {
+    DEFAULT_COLLISION_DAMPING
+}
+
+fn default_physics_timestep() -> f32 {
+    PHYSICS_TIMESTEP
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -60,31 +181,144 @@ pub struct SavedFoodParams {
     pub food_count: usize,
     pub respawn_enabled: bool,
     pub respawn_cooldown: f32,
+    /// Absent des anciennes sauvegardes : délai identique pour toutes les nourritures
+    #[serde(default)]
+    pub respawn_jitter: f32,
     pub food_value: f32,
+    /// Absents des anciennes sauvegardes : valeur fixe et répartition uniforme
+    #[serde(default)]
+    pub max_food_value: Option<f32>,
+    #[serde(default)]
+    pub distribution: SavedFoodDistribution,
+    /// Absent des anciennes sauvegardes : réapparition sur place
+    #[serde(default)]
+    pub respawn_mode: SavedFoodRespawnMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum SavedFoodRespawnMode {
+    #[default]
+    SamePosition,
+    RandomPosition,
+}
+
+impl From<FoodRespawnMode> for SavedFoodRespawnMode {
+    fn from(mode: FoodRespawnMode) -> Self {
+        match mode {
+            FoodRespawnMode::SamePosition => SavedFoodRespawnMode::SamePosition,
+            FoodRespawnMode::RandomPosition => SavedFoodRespawnMode::RandomPosition,
+        }
+    }
+}
+
+impl From<SavedFoodRespawnMode> for FoodRespawnMode {
+    fn from(mode: SavedFoodRespawnMode) -> Self {
+        match mode {
+            SavedFoodRespawnMode::SamePosition => FoodRespawnMode::SamePosition,
+            SavedFoodRespawnMode::RandomPosition => FoodRespawnMode::RandomPosition,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum SavedFoodDistribution {
+    #[default]
+    Uniform,
+    Clustered { clusters: usize, spread: f32 },
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SavedParticleTypesConfig {
     pub type_count: usize,
     pub colors: Vec<(f32, f32, f32, f32)>, // RGBA values
+    /// Répartition des particules par type (vide dans les anciennes sauvegardes)
+    #[serde(default)]
+    pub counts_per_type: Vec<usize>,
+    /// Rayon et masse par type (vides dans les anciennes sauvegardes : valeurs globales)
+    #[serde(default)]
+    pub radii: Vec<f32>,
+    #[serde(default)]
+    pub masses: Vec<f32>,
 }
 
+/// Les anciennes sauvegardes ne connaissent que `Bounce` et `Teleport` (mêmes bords sur les
+/// trois axes) ; un mélange est enregistré axe par axe sous `Mixed`
 #[derive(Serialize, Deserialize, Clone, Copy)]
 pub enum SavedBoundaryMode {
     Bounce,
     Teleport,
+    Mixed([SavedAxisBoundary; 3]),
 }
 
-#[derive(Resource, Default)]
-pub struct PopulationSaveEvents {
-    pub save_requests: Vec<PopulationSaveRequest>,
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SavedAxisBoundary {
+    Bounce,
+    Teleport,
+}
+
+impl From<BoundaryMode> for SavedBoundaryMode {
+    fn from(mode: BoundaryMode) -> Self {
+        match mode.uniform_axis() {
+            Some(AxisBoundary::Bounce) => SavedBoundaryMode::Bounce,
+            Some(AxisBoundary::Teleport) => SavedBoundaryMode::Teleport,
+            None => SavedBoundaryMode::Mixed(mode.axes.map(|axis| match axis {
+                AxisBoundary::Bounce => SavedAxisBoundary::Bounce,
+                AxisBoundary::Teleport => SavedAxisBoundary::Teleport,
+            })),
+        }
+    }
+}
+
+impl From<SavedBoundaryMode> for BoundaryMode {
+    fn from(mode: SavedBoundaryMode) -> Self {
+        match mode {
+            SavedBoundaryMode::Bounce => BoundaryMode::BOUNCE,
+            SavedBoundaryMode::Teleport => BoundaryMode::TELEPORT,
+            SavedBoundaryMode::Mixed(axes) => BoundaryMode {
+                axes: axes.map(|axis| match axis {
+                    SavedAxisBoundary::Bounce => AxisBoundary::Bounce,
+                    SavedAxisBoundary::Teleport => AxisBoundary::Teleport,
+                }),
+            },
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct PopulationSaveRequest {
+/// Demande de sauvegarde d'un génome, émise par l'interface ou la fin de run et traitée
+/// par `process_save_requests`
+#[derive(Event, Clone)]
+pub struct SavePopulationEvent {
     pub simulation_id: usize,
     pub name: String,
     pub description: Option<String>,
+    pub tags: Vec<String>,
+    /// Génome et score à sauvegarder à la place de ceux de la simulation (hall of fame,
+    /// classement)
+    pub genome: Option<(Genotype, f32)>,
+}
+
+/// Sauvegarde groupée du classement : les `count` meilleures simulations de l'époque en
+/// cours, hormis celles sous `min_score`
+#[derive(Event, Clone, Copy)]
+pub struct SaveTopGenomesEvent {
+    pub count: usize,
+    pub min_score: f32,
+}
+
+/// Réglages de la sauvegarde groupée, conservés entre deux clics
+#[derive(Resource, Clone, Copy)]
+pub struct BulkSaveSettings {
+    pub count: usize,
+    pub min_score: f32,
+}
+
+impl Default for BulkSaveSettings {
+    fn default() -> Self {
+        Self {
+            count: 5,
+            min_score: 0.0,
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -94,10 +328,11 @@ pub struct AvailablePopulations {
 }
 
 impl SavedPopulation {
+    #[allow(clippy::too_many_arguments)]
     pub fn from_current_state(
-        simulation_id: usize,
         name: String,
         description: Option<String>,
+        tags: Vec<String>,
         genotype: &Genotype,
         score: f32,
         sim_params: &SimulationParameters,
@@ -105,16 +340,19 @@ impl SavedPopulation {
         food_params: &FoodParameters,
         particle_config: &ParticleTypesConfig,
         boundary_mode: &BoundaryMode,
+        seed: Option<u64>,
     ) -> Self {
         let timestamp = chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string();
 
         Self {
+            version: POPULATION_SCHEMA_VERSION,
             name,
             timestamp,
             genotype: SavedGenotype {
                 force_matrix: genotype.force_matrix.clone(),
                 food_forces: genotype.food_forces.clone(),
                 type_count: genotype.type_count,
+                locked_mask: genotype.locked_mask.clone(),
             },
             score,
             simulation_params: SavedSimulationParams {
@@ -122,7 +360,15 @@ impl SavedPopulation {
                 particle_types: sim_params.particle_types,
                 max_force_range: sim_params.max_force_range,
                 velocity_half_life: sim_params.velocity_half_life,
+                max_velocity: sim_params.max_velocity,
+                collision_damping: sim_params.collision_damping,
+                physics_timestep: sim_params.physics_timestep,
                 epoch_duration: sim_params.epoch_duration,
+                force_scale_factor: sim_params.force_scale_factor,
+                seed,
+                integrator: sim_params.integrator.into(),
+                force_curve: sim_params.force_curve.into(),
+                min_distance: sim_params.min_distance,
             },
             grid_params: SavedGridParams {
                 width: grid_params.width,
@@ -133,7 +379,16 @@ impl SavedPopulation {
                 food_count: food_params.food_count,
                 respawn_enabled: food_params.respawn_enabled,
                 respawn_cooldown: food_params.respawn_cooldown,
+                respawn_jitter: food_params.respawn_jitter,
                 food_value: food_params.food_value,
+                max_food_value: food_params.max_food_value,
+                distribution: match food_params.distribution {
+                    FoodDistribution::Uniform => SavedFoodDistribution::Uniform,
+                    FoodDistribution::Clustered { clusters, spread } => {
+                        SavedFoodDistribution::Clustered { clusters, spread }
+                    }
+                },
+                respawn_mode: food_params.respawn_mode.into(),
             },
             particle_types_config: SavedParticleTypesConfig {
                 type_count: particle_config.type_count,
@@ -145,12 +400,13 @@ impl SavedPopulation {
                         (srgba.red, srgba.green, srgba.blue, srgba.alpha)
                     })
                     .collect(),
+                counts_per_type: particle_config.counts_per_type.clone(),
+                radii: particle_config.radii.clone(),
+                masses: particle_config.masses.clone(),
             },
-            boundary_mode: match boundary_mode {
-                BoundaryMode::Bounce => SavedBoundaryMode::Bounce,
-                BoundaryMode::Teleport => SavedBoundaryMode::Teleport,
-            },
+            boundary_mode: (*boundary_mode).into(),
             description,
+            tags,
         }
     }
 
@@ -164,10 +420,16 @@ impl SavedPopulation {
         ParticleTypesConfig,
         BoundaryMode,
     ) {
-        let genotype = Genotype {
-            force_matrix: self.genotype.force_matrix.clone(),
-            food_forces: self.genotype.food_forces.clone(),
-            type_count: self.genotype.type_count,
+        let genotype = self.genotype.to_genotype();
+
+        let type_count = self.particle_types_config.type_count;
+        let counts_per_type = if self.particle_types_config.counts_per_type.is_empty() {
+            ParticleTypesConfig::even_split(self.simulation_params.particle_count, type_count)
+        } else {
+            ParticleTypesConfig::normalize_counts(
+                self.particle_types_config.counts_per_type.clone(),
+                type_count,
+            )
         };
 
         let sim_params = SimulationParameters {
@@ -179,14 +441,32 @@ impl SavedPopulation {
                 TimerMode::Once,
             ),
             simulation_count: 1,
-            particle_count: self.simulation_params.particle_count,
+            particle_count: counts_per_type.iter().sum(),
             particle_types: self.simulation_params.particle_types,
             simulation_speed: SimulationSpeed::Normal,
+            physics_timestep: self.simulation_params.physics_timestep,
             max_force_range: self.simulation_params.max_force_range,
             velocity_half_life: self.simulation_params.velocity_half_life,
+            max_velocity: self.simulation_params.max_velocity,
+            integrator: self.simulation_params.integrator.into(),
+            force_curve: self.simulation_params.force_curve.into(),
+            min_distance: self.simulation_params.min_distance,
+            collision_damping: self.simulation_params.collision_damping,
+            force_scale_factor: self.simulation_params.force_scale_factor,
+            max_interactions_per_particle: DEFAULT_MAX_INTERACTIONS,
+            elitism: Elitism::default(),
             elite_ratio: 0.1,
             mutation_rate: 0.1,
+            elite_mutation_rate: DEFAULT_ELITE_MUTATION_RATE,
             crossover_rate: 0.7,
+            tournament_size: DEFAULT_TOURNAMENT_SIZE,
+            mutation_kind: MutationKind::default(),
+            crossover_strategy: CrossoverStrategy::default(),
+            sharing_enabled: false,
+            sharing_radius: DEFAULT_SHARING_RADIUS,
+            allow_self_attraction: false,
+            inherit_fitness_history: false,
+            spawn_mode: SpawnMode::default(),
         };
 
         let grid_params = GridParameters {
@@ -199,7 +479,16 @@ impl SavedPopulation {
             food_count: self.food_params.food_count,
             respawn_enabled: self.food_params.respawn_enabled,
             respawn_cooldown: self.food_params.respawn_cooldown,
+            respawn_jitter: self.food_params.respawn_jitter,
+            respawn_mode: self.food_params.respawn_mode.into(),
             food_value: self.food_params.food_value,
+            max_food_value: self.food_params.max_food_value,
+            distribution: match self.food_params.distribution {
+                SavedFoodDistribution::Uniform => FoodDistribution::Uniform,
+                SavedFoodDistribution::Clustered { clusters, spread } => {
+                    FoodDistribution::Clustered { clusters, spread }
+                }
+            },
         };
 
         let colors = self
@@ -208,20 +497,23 @@ impl SavedPopulation {
             .iter()
             .map(|(r, g, b, a)| {
                 let base_color = Color::srgba(*r, *g, *b, *a);
-                let emissive = base_color.to_linear() * 0.5;
-                (base_color, emissive)
+                (base_color, ParticleTypesConfig::emissive_for(base_color))
             })
             .collect();
 
+        let mut radii = self.particle_types_config.radii.clone();
+        let mut masses = self.particle_types_config.masses.clone();
+        ParticleTypesConfig::resize_physical_properties(&mut radii, &mut masses, type_count);
+
         let particle_config = ParticleTypesConfig {
-            type_count: self.particle_types_config.type_count,
+            type_count,
             colors,
+            counts_per_type,
+            radii,
+            masses,
         };
 
-        let boundary_mode = match self.boundary_mode {
-            SavedBoundaryMode::Bounce => BoundaryMode::Bounce,
-            SavedBoundaryMode::Teleport => BoundaryMode::Teleport,
-        };
+        let boundary_mode = BoundaryMode::from(self.boundary_mode);
 
         (
             genotype,
@@ -234,31 +526,38 @@ impl SavedPopulation {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_save_requests(
-    mut save_events: ResMut<PopulationSaveEvents>,
+    mut save_events: EventReader<SavePopulationEvent>,
     simulations: Query<(&SimulationId, &Genotype, &Score), With<Simulation>>,
     sim_params: Res<SimulationParameters>,
     grid_params: Res<GridParameters>,
     food_params: Res<FoodParameters>,
     particle_config: Res<ParticleTypesConfig>,
     boundary_mode: Res<BoundaryMode>,
+    seed: Option<Res<SimulationSeed>>,
 ) {
-    for request in save_events.save_requests.drain(..) {
-        if let Some((_, genotype, score)) = simulations
-            .iter()
-            .find(|(sim_id, _, _)| sim_id.0 == request.simulation_id)
-        {
+    for request in save_events.read() {
+        let genome = request.genome.clone().or_else(|| {
+            simulations
+                .iter()
+                .find(|(sim_id, _, _)| sim_id.0 == request.simulation_id)
+                .map(|(_, genotype, score)| (genotype.clone(), score.get()))
+        });
+
+        if let Some((genotype, score)) = genome {
             let saved_population = SavedPopulation::from_current_state(
-                request.simulation_id,
                 request.name.clone(),
                 request.description.clone(),
-                genotype,
-                score.get(),
+                request.tags.clone(),
+                &genotype,
+                score,
                 &sim_params,
                 &grid_params,
                 &food_params,
                 &particle_config,
                 &boundary_mode,
+                seed.as_deref().map(|seed| seed.0),
             );
 
             if let Err(e) = save_population_to_file(&saved_population) {
@@ -270,14 +569,54 @@ pub fn process_save_requests(
     }
 }
 
-pub fn save_population_to_file(
-    population: &SavedPopulation,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let populations_dir = Path::new("populations");
-    if !populations_dir.exists() {
-        fs::create_dir_all(populations_dir)?;
+/// Traduit chaque demande de sauvegarde groupée en une sauvegarde par simulation retenue,
+/// nommée `run_{époque}_rank{rang}`. Génome et score sont figés ici : un changement
+/// d'époque avant l'écriture ne mélange pas deux classements
+pub fn process_bulk_save_requests(
+    mut bulk_events: EventReader<SaveTopGenomesEvent>,
+    simulations: Query<(&SimulationId, &Genotype, &Score), With<Simulation>>,
+    sim_params: Res<SimulationParameters>,
+    mut save_events: EventWriter<SavePopulationEvent>,
+) {
+    for request in bulk_events.read() {
+        let mut ranking: Vec<_> = simulations.iter().collect();
+        ranking.sort_by(|a, b| b.2.get().total_cmp(&a.2.get()).then(a.0.0.cmp(&b.0.0)));
+
+        let mut saved = 0;
+        let mut below_threshold = 0;
+        for (rank, (sim_id, genotype, score)) in
+            ranking.into_iter().take(request.count).enumerate()
+        {
+            if score.get() < request.min_score {
+                below_threshold += 1;
+                continue;
+            }
+
+            let rank = rank + 1;
+            save_events.write(SavePopulationEvent {
+                simulation_id: sim_id.0,
+                name: format!("run_{}_rank{}", sim_params.current_epoch, rank),
+                description: Some(format!(
+                    "Rang {} à l'époque {}, score {:.1}",
+                    rank,
+                    sim_params.current_epoch,
+                    score.get()
+                )),
+                tags: vec!["classement".to_string()],
+                genome: Some((genotype.clone(), score.get())),
+            });
+            saved += 1;
+        }
+
+        info!(
+            "Sauvegarde du top {} : {} génome(s) enregistré(s), {} sous le score minimum {:.1}",
+            request.count, saved, below_threshold, request.min_score
+        );
     }
+}
 
+/// Chemin du fichier JSON associé à une population sauvegardée
+pub fn population_file_path(population: &SavedPopulation) -> PathBuf {
     let safe_name = population
         .name
         .chars()
@@ -291,7 +630,18 @@ pub fn save_population_to_file(
         .collect::<String>();
 
     let filename = format!("{}_{}.json", safe_name, population.timestamp);
-    let file_path = populations_dir.join(filename);
+    Path::new("populations").join(filename)
+}
+
+pub fn save_population_to_file(
+    population: &SavedPopulation,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let populations_dir = Path::new("populations");
+    if !populations_dir.exists() {
+        fs::create_dir_all(populations_dir)?;
+    }
+
+    let file_path = population_file_path(population);
 
     let json = serde_json::to_string_pretty(population)?;
     fs::write(file_path, json)?;
@@ -299,6 +649,159 @@ pub fn save_population_to_file(
     Ok(())
 }
 
+/// Supprime le fichier d'une population. Retourne `false` si le fichier n'existait déjà plus
+pub fn delete_population_file(
+    population: &SavedPopulation,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let file_path = population_file_path(population);
+    if !file_path.exists() {
+        return Ok(false);
+    }
+
+    fs::remove_file(file_path)?;
+    Ok(true)
+}
+
+/// Renomme une population : réécrit le JSON sous le nouveau nom puis supprime l'ancien fichier
+pub fn rename_population(
+    population: &SavedPopulation,
+    new_name: &str,
+) -> Result<SavedPopulation, Box<dyn std::error::Error>> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("le nom ne peut pas être vide".into());
+    }
+
+    let mut renamed = population.clone();
+    renamed.name = new_name.to_string();
+
+    let old_path = population_file_path(population);
+    let new_path = population_file_path(&renamed);
+
+    save_population_to_file(&renamed)?;
+
+    if old_path != new_path && old_path.exists() {
+        fs::remove_file(old_path)?;
+    }
+
+    Ok(renamed)
+}
+
+/// Découpe une saisie « a, b, c » en étiquettes sans doublons ni vides
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Remplace les étiquettes d'une population et réécrit son JSON sur place
+pub fn update_population_tags(
+    population: &SavedPopulation,
+    tags: Vec<String>,
+) -> Result<SavedPopulation, Box<dyn std::error::Error>> {
+    let mut updated = population.clone();
+    updated.tags = tags;
+    save_population_to_file(&updated)?;
+    Ok(updated)
+}
+
+/// Exporte un résumé des populations en CSV dans `exports/`, une ligne par population
+pub fn export_population_statistics(
+    populations: &[SavedPopulation],
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = exports_dir();
+    fs::create_dir_all(&dir)?;
+
+    let timestamp = chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S");
+    let path = dir.join(format!("populations_{}.csv", timestamp));
+
+    let mut csv = String::from("name,timestamp,score,particle_types,particle_count,food_count,tags\n");
+    for population in populations {
+        csv.push_str(&format!(
+            "{},{},{:.3},{},{},{},{}\n",
+            csv_field(&population.name),
+            population.timestamp,
+            population.score,
+            population.simulation_params.particle_types,
+            population.simulation_params.particle_count,
+            population.food_params.food_count,
+            csv_field(&population.tags.join(";")),
+        ));
+    }
+
+    fs::write(&path, csv)?;
+    Ok(path)
+}
+
+/// Entoure le champ de guillemets s'il contient un séparateur
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Met un fichier au format courant, étape par étape depuis `version`, avant sa
+/// désérialisation typée
+fn migrate_population(value: &mut serde_json::Value, version: u32) {
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+
+    if version < 1 {
+        // v0 → v1 : étiquettes et masque de verrouillage deviennent obligatoires ; le
+        // masque est vidé s'il ne correspond pas à la matrice (certains fichiers en
+        // gardaient un d'une ancienne taille)
+        object
+            .entry("tags")
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let Some(genotype) = object.get_mut("genotype").and_then(|g| g.as_object_mut()) {
+            let matrix_len = genotype
+                .get("force_matrix")
+                .and_then(|matrix| matrix.as_array())
+                .map_or(0, Vec::len);
+            let mask_len = genotype
+                .get("locked_mask")
+                .and_then(|mask| mask.as_array())
+                .map(Vec::len);
+            if mask_len.is_none_or(|mask_len| mask_len != 0 && mask_len != matrix_len) {
+                genotype.insert("locked_mask".to_string(), serde_json::Value::Array(Vec::new()));
+            }
+        }
+    }
+
+    object.insert("version".to_string(), POPULATION_SCHEMA_VERSION.into());
+}
+
+/// Lit une sauvegarde de n'importe quelle version ; renvoie la population migrée, validée,
+/// et la version d'origine du fichier
+pub fn parse_saved_population(
+    content: &str,
+) -> Result<(SavedPopulation, u32), Box<dyn std::error::Error>> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .unwrap_or(0) as u32;
+    if version > POPULATION_SCHEMA_VERSION {
+        return Err(format!(
+            "format v{} plus récent que celui de cette version (v{})",
+            version, POPULATION_SCHEMA_VERSION
+        )
+        .into());
+    }
+
+    migrate_population(&mut value, version);
+    let population: SavedPopulation = serde_json::from_value(value)?;
+    population.genotype.to_genotype().validate()?;
+    Ok((population, version))
+}
+
 pub fn load_all_populations() -> Result<Vec<SavedPopulation>, Box<dyn std::error::Error>> {
     let populations_dir = Path::new("populations");
     if !populations_dir.exists() {
@@ -313,8 +816,18 @@ pub fn load_all_populations() -> Result<Vec<SavedPopulation>, Box<dyn std::error
 
         if path.extension().and_then(|s| s.to_str()) == Some("json") {
             match fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str::<SavedPopulation>(&content) {
-                    Ok(population) => populations.push(population),
+                Ok(content) => match parse_saved_population(&content) {
+                    Ok((population, version)) => {
+                        if version < POPULATION_SCHEMA_VERSION {
+                            info!(
+                                "{:?}: format v{} migré vers v{}",
+                                path, version, POPULATION_SCHEMA_VERSION
+                            );
+                        } else {
+                            debug!("{:?}: format v{}", path, version);
+                        }
+                        populations.push(population);
+                    }
                     Err(e) => warn!("Erreur lors du chargement de {:?}: {}", path, e),
                 },
                 Err(e) => warn!("Impossible de lire {:?}: {}", path, e),
@@ -327,6 +840,25 @@ pub fn load_all_populations() -> Result<Vec<SavedPopulation>, Box<dyn std::error
     Ok(populations)
 }
 
+/// Génomes des `count` meilleures populations sauvegardées compatibles avec `type_count`,
+/// par score décroissant
+pub fn top_compatible_genomes(
+    populations: &[SavedPopulation],
+    type_count: usize,
+    count: usize,
+) -> Vec<Genotype> {
+    let mut compatible: Vec<&SavedPopulation> = populations
+        .iter()
+        .filter(|population| population.genotype.type_count == type_count)
+        .collect();
+    compatible.sort_by(|a, b| b.score.total_cmp(&a.score));
+    compatible
+        .into_iter()
+        .take(count)
+        .map(|population| population.genotype.to_genotype())
+        .collect()
+}
+
 pub fn load_available_populations(mut available: ResMut<AvailablePopulations>) {
     if available.loaded {
         return;
@@ -346,3 +878,116 @@ pub fn load_available_populations(mut available: ResMut<AvailablePopulations>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    /// Sauvegarde puis relit un génome de `type_count` types par le même chemin que le jeu
+    fn round_trip(type_count: usize) -> Result<(Genotype, Genotype), Box<dyn std::error::Error>> {
+        let mut genotype = Genotype::random(type_count, true, &mut StdRng::seed_from_u64(90));
+        genotype.locked_mask = (0..type_count * type_count).map(|i| i % 3 == 0).collect();
+        let sim_params = SimulationParameters {
+            particle_types: type_count,
+            ..default()
+        };
+        let particle_config = ParticleTypesConfig::new(type_count, Vec::new());
+
+        let population = SavedPopulation::from_current_state(
+            "grande".to_string(),
+            None,
+            vec!["test".to_string()],
+            &genotype,
+            1.0,
+            &sim_params,
+            &GridParameters::default(),
+            &FoodParameters::default(),
+            &particle_config,
+            &BoundaryMode::default(),
+            Some(90),
+        );
+        let json = serde_json::to_string_pretty(&population)?;
+        let (loaded, _) = parse_saved_population(&json)?;
+        Ok((genotype, loaded.genotype.to_genotype()))
+    }
+
+    #[test]
+    fn max_type_count_survives_save_and_load() {
+        let (saved, loaded) =
+            round_trip(MAX_PARTICLE_TYPES).expect("12 types doivent se sauvegarder et se relire");
+
+        assert_eq!(loaded.type_count, saved.type_count);
+        assert_eq!(loaded.force_matrix, saved.force_matrix);
+        assert_eq!(loaded.food_forces, saved.food_forces);
+        assert_eq!(loaded.locked_mask, saved.locked_mask);
+        assert!(loaded.validate().is_ok());
+    }
+
+    #[test]
+    fn too_many_types_are_rejected_on_load() {
+        let error = round_trip(MAX_PARTICLE_TYPES + 1).expect_err("13 types doivent être refusés au chargement");
+        assert!(error.to_string().contains("au maximum"), "{error}");
+    }
+
+    /// Sauvegarde telle qu'écrite avant le versionnage : ni `version`, ni `tags`, et le
+    /// masque de verrouillage fourni par `locked_mask` (fragment JSON, éventuellement vide)
+    fn v0_json(locked_mask: &str) -> String {
+        format!(
+            r#"{{
+                "name": "ancienne",
+                "timestamp": "20240101_120000",
+                "genotype": {{
+                    "force_matrix": [0.1, -0.2, 0.3, -0.4],
+                    "food_forces": [0.5, -0.5],
+                    "type_count": 2
+                    {locked_mask}
+                }},
+                "score": 12.0,
+                "simulation_params": {{
+                    "particle_count": 100,
+                    "particle_types": 2,
+                    "max_force_range": 80.0,
+                    "velocity_half_life": 0.043,
+                    "epoch_duration": 60.0
+                }},
+                "grid_params": {{ "width": 400.0, "height": 400.0, "depth": 400.0 }},
+                "food_params": {{
+                    "food_count": 10,
+                    "respawn_enabled": true,
+                    "respawn_cooldown": 5.0,
+                    "food_value": 1.0
+                }},
+                "particle_types_config": {{
+                    "type_count": 2,
+                    "colors": [[1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]]
+                }},
+                "boundary_mode": "Bounce",
+                "description": null
+            }}"#
+        )
+    }
+
+    #[test]
+    fn v0_population_is_migrated_and_validated() {
+        for locked_mask in ["", r#", "locked_mask": [true, false, true]"#] {
+            let (population, version) = parse_saved_population(&v0_json(locked_mask))
+                .expect("une sauvegarde v0 doit rester lisible");
+
+            assert_eq!(version, 0);
+            assert_eq!(population.version, POPULATION_SCHEMA_VERSION);
+            assert!(population.tags.is_empty());
+            assert!(population.genotype.locked_mask.is_empty());
+            assert!(population.genotype.to_genotype().validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn v0_fields_are_required_once_versioned() {
+        // Sans l'étape v0 → v1, les champs qu'elle ajoute manquent à la désérialisation
+        let unversioned = v0_json("");
+        let v1 = unversioned.replacen('{', r#"{ "version": 1,"#, 1);
+        assert!(parse_saved_population(&v1).is_err());
+    }
+}
@@ -0,0 +1,570 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::globals::*;
+use crate::resources::config::food::FoodDistribution;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::{
+    CrossoverStrategy, Elitism, Integrator, MutationKind, SpawnMode,
+};
+use crate::resources::genetics::auto_adapt::AutoAdapt;
+use crate::resources::genetics::diversity_guard::{CollapseAction, DiversityGuard};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::systems::persistence::population_save::{
+    SavedBoundaryMode, SavedFoodDistribution, SavedFoodParams, SavedForceCurve, SavedGridParams,
+};
+use crate::ui::menus::main_menu::MenuConfig;
+
+/// Expérience complète (tout le menu principal et la graine) : la recharger rejoue
+/// exactement la même évolution, génomes initiaux compris puisqu'ils sortent de la graine
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunConfig {
+    pub seed: u64,
+    pub grid: SavedGridParams,
+
+    pub simulation_count: usize,
+    /// Absent des anciennes configs : positions communes à toutes les simulations
+    #[serde(default)]
+    pub spawn_mode: SavedSpawnMode,
+    pub particle_count: usize,
+    pub particle_types: usize,
+    pub counts_per_type: Vec<usize>,
+    pub type_colors: Vec<(f32, f32, f32, f32)>, // RGBA values
+    /// Vides dans les anciens fichiers : valeurs globales
+    #[serde(default)]
+    pub type_radii: Vec<f32>,
+    #[serde(default)]
+    pub type_masses: Vec<f32>,
+    pub epoch_duration: f32,
+    pub max_epochs: usize,
+    #[serde(default)]
+    pub auto_save_on_finish: bool,
+    pub max_force_range: f32,
+    pub velocity_half_life: f32,
+    pub max_velocity: f32,
+    pub integrator: SavedIntegrator,
+    /// Absente des anciennes configs : courbe en triangle
+    #[serde(default)]
+    pub force_curve: SavedForceCurve,
+    /// Absente des anciennes configs : nombre de types × rayon
+    #[serde(default)]
+    pub min_distance: Option<f32>,
+    pub collision_damping: f32,
+    pub physics_timestep: f32,
+    pub max_interactions_per_particle: usize,
+    pub force_scale_factor: f32,
+
+    pub food: SavedFoodParams,
+    pub boundary_mode: SavedBoundaryMode,
+    pub use_gpu: bool,
+
+    /// Nombre fixe d'élites, `None` pour le ratio (seul mode des anciennes configs)
+    #[serde(default)]
+    pub elite_count: Option<usize>,
+    pub elite_ratio: f32,
+    pub mutation_rate: f32,
+    /// Absent des anciennes configs, où les élites étaient recopiées à l'identique
+    #[serde(default)]
+    pub elite_mutation_rate: f32,
+    pub crossover_rate: f32,
+    /// Absente des anciennes configs, où le tournoi opposait 3 candidats
+    #[serde(default = "default_tournament_size")]
+    pub tournament_size: usize,
+    pub mutation_kind: SavedMutationKind,
+    pub crossover_strategy: SavedCrossoverStrategy,
+    pub sharing_enabled: bool,
+    pub sharing_radius: f32,
+    pub hall_of_fame_size: usize,
+    pub hall_of_fame_reinject: bool,
+    pub genome_archive_depth: usize,
+    pub seed_interesting_forces: bool,
+    #[serde(default)]
+    pub library_seed_count: usize,
+    #[serde(default)]
+    pub allow_self_attraction: bool,
+    #[serde(default)]
+    pub inherit_fitness_history: bool,
+    pub fitness_food_weight: f32,
+    pub fitness_exploration_weight: f32,
+    /// Absent des anciens fichiers : score à la nourriture mangée seulement
+    #[serde(default)]
+    pub fitness_proximity_enabled: bool,
+    #[serde(default = "default_proximity_weight")]
+    pub fitness_proximity_weight: f32,
+    /// Absente des anciens fichiers : adaptation désactivée
+    #[serde(default)]
+    pub auto_adapt: SavedAutoAdapt,
+    /// Absente des anciens fichiers : surveillance désactivée
+    #[serde(default)]
+    pub diversity_guard: SavedDiversityGuard,
+}
+
+fn default_tournament_size() -> usize {
+    DEFAULT_TOURNAMENT_SIZE
+}
+
+fn default_proximity_weight() -> f32 {
+    FitnessWeights::default().proximity
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct SavedAutoAdapt {
+    pub enabled: bool,
+    pub stagnation_threshold: usize,
+    pub mutation_step: f32,
+    pub elite_step: f32,
+}
+
+impl Default for SavedAutoAdapt {
+    fn default() -> Self {
+        Self::from(&AutoAdapt::default())
+    }
+}
+
+impl From<&AutoAdapt> for SavedAutoAdapt {
+    fn from(auto_adapt: &AutoAdapt) -> Self {
+        Self {
+            enabled: auto_adapt.enabled,
+            stagnation_threshold: auto_adapt.stagnation_threshold,
+            mutation_step: auto_adapt.mutation_step,
+            elite_step: auto_adapt.elite_step,
+        }
+    }
+}
+
+impl SavedAutoAdapt {
+    pub fn to_auto_adapt(self) -> AutoAdapt {
+        AutoAdapt {
+            enabled: self.enabled,
+            stagnation_threshold: self.stagnation_threshold,
+            mutation_step: self.mutation_step,
+            elite_step: self.elite_step,
+            ..default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct SavedDiversityGuard {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub patience: usize,
+    pub action: SavedCollapseAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SavedCollapseAction {
+    Inject,
+    Stop,
+}
+
+impl Default for SavedDiversityGuard {
+    fn default() -> Self {
+        Self::from(&DiversityGuard::default())
+    }
+}
+
+impl From<&DiversityGuard> for SavedDiversityGuard {
+    fn from(guard: &DiversityGuard) -> Self {
+        Self {
+            enabled: guard.enabled,
+            threshold: guard.threshold,
+            patience: guard.patience,
+            action: match guard.action {
+                CollapseAction::Inject => SavedCollapseAction::Inject,
+                CollapseAction::Stop => SavedCollapseAction::Stop,
+            },
+        }
+    }
+}
+
+impl SavedDiversityGuard {
+    pub fn to_diversity_guard(self) -> DiversityGuard {
+        DiversityGuard {
+            enabled: self.enabled,
+            threshold: self.threshold,
+            patience: self.patience,
+            action: match self.action {
+                SavedCollapseAction::Inject => CollapseAction::Inject,
+                SavedCollapseAction::Stop => CollapseAction::Stop,
+            },
+            ..default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SavedIntegrator {
+    Euler,
+    Verlet,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum SavedSpawnMode {
+    #[default]
+    SharedInitial,
+    IndependentRandom,
+}
+
+impl From<SpawnMode> for SavedSpawnMode {
+    fn from(mode: SpawnMode) -> Self {
+        match mode {
+            SpawnMode::SharedInitial => SavedSpawnMode::SharedInitial,
+            SpawnMode::IndependentRandom => SavedSpawnMode::IndependentRandom,
+        }
+    }
+}
+
+impl From<SavedSpawnMode> for SpawnMode {
+    fn from(mode: SavedSpawnMode) -> Self {
+        match mode {
+            SavedSpawnMode::SharedInitial => SpawnMode::SharedInitial,
+            SavedSpawnMode::IndependentRandom => SpawnMode::IndependentRandom,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SavedMutationKind {
+    Uniform,
+    Gaussian,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SavedCrossoverStrategy {
+    Uniform,
+    SymmetricRelations,
+    TypeBlocks,
+    Blend { alpha: f32, auto_weight: bool },
+    Auto,
+}
+
+impl RunConfig {
+    pub fn from_menu_config(config: &MenuConfig) -> Self {
+        Self {
+            seed: config.seed,
+            grid: SavedGridParams {
+                width: config.grid_width,
+                height: config.grid_height,
+                depth: config.grid_depth,
+            },
+
+            simulation_count: config.simulation_count,
+            spawn_mode: config.spawn_mode.into(),
+            particle_count: config.particle_count,
+            particle_types: config.particle_types,
+            counts_per_type: config.counts_per_type.clone(),
+            type_colors: config
+                .type_colors
+                .iter()
+                .map(|color| {
+                    let srgba = color.to_srgba();
+                    (srgba.red, srgba.green, srgba.blue, srgba.alpha)
+                })
+                .collect(),
+            type_radii: config.type_radii.clone(),
+            type_masses: config.type_masses.clone(),
+            epoch_duration: config.epoch_duration,
+            max_epochs: config.max_epochs,
+            auto_save_on_finish: config.auto_save_on_finish,
+            max_force_range: config.max_force_range,
+            velocity_half_life: config.velocity_half_life,
+            max_velocity: config.max_velocity,
+            integrator: match config.integrator {
+                Integrator::Euler => SavedIntegrator::Euler,
+                Integrator::Verlet => SavedIntegrator::Verlet,
+            },
+            force_curve: config.force_curve.into(),
+            min_distance: config.min_distance,
+            collision_damping: config.collision_damping,
+            physics_timestep: config.physics_timestep,
+            max_interactions_per_particle: config.max_interactions_per_particle,
+            force_scale_factor: config.force_scale_factor,
+
+            food: SavedFoodParams {
+                food_count: config.food_count,
+                respawn_enabled: config.food_respawn_enabled,
+                respawn_cooldown: config.food_respawn_time,
+                respawn_jitter: config.food_respawn_jitter,
+                food_value: config.food_value,
+                max_food_value: config.food_value_max,
+                distribution: match config.food_distribution {
+                    FoodDistribution::Uniform => SavedFoodDistribution::Uniform,
+                    FoodDistribution::Clustered { clusters, spread } => {
+                        SavedFoodDistribution::Clustered { clusters, spread }
+                    }
+                },
+                respawn_mode: config.food_respawn_mode.into(),
+            },
+            boundary_mode: config.boundary_mode.into(),
+            use_gpu: config.use_gpu,
+
+            elite_count: match config.elitism {
+                Elitism::Ratio => None,
+                Elitism::Count(count) => Some(count),
+            },
+            elite_ratio: config.elite_ratio,
+            mutation_rate: config.mutation_rate,
+            elite_mutation_rate: config.elite_mutation_rate,
+            crossover_rate: config.crossover_rate,
+            tournament_size: config.tournament_size,
+            mutation_kind: match config.mutation_kind {
+                MutationKind::Uniform => SavedMutationKind::Uniform,
+                MutationKind::Gaussian => SavedMutationKind::Gaussian,
+            },
+            crossover_strategy: match config.crossover_strategy {
+                CrossoverStrategy::Uniform => SavedCrossoverStrategy::Uniform,
+                CrossoverStrategy::SymmetricRelations => {
+                    SavedCrossoverStrategy::SymmetricRelations
+                }
+                CrossoverStrategy::TypeBlocks => SavedCrossoverStrategy::TypeBlocks,
+                CrossoverStrategy::Blend { alpha, auto_weight } => {
+                    SavedCrossoverStrategy::Blend { alpha, auto_weight }
+                }
+                CrossoverStrategy::Auto => SavedCrossoverStrategy::Auto,
+            },
+            sharing_enabled: config.sharing_enabled,
+            sharing_radius: config.sharing_radius,
+            hall_of_fame_size: config.hall_of_fame_size,
+            hall_of_fame_reinject: config.hall_of_fame_reinject,
+            genome_archive_depth: config.genome_archive_depth,
+            seed_interesting_forces: config.seed_interesting_forces,
+            library_seed_count: config.library_seed_count,
+            allow_self_attraction: config.allow_self_attraction,
+            inherit_fitness_history: config.inherit_fitness_history,
+            fitness_food_weight: config.fitness_weights.food,
+            fitness_exploration_weight: config.fitness_weights.exploration,
+            fitness_proximity_enabled: config.fitness_weights.proximity_enabled,
+            fitness_proximity_weight: config.fitness_weights.proximity,
+            auto_adapt: SavedAutoAdapt::from(&config.auto_adapt),
+            diversity_guard: SavedDiversityGuard::from(&config.diversity_guard),
+        }
+    }
+
+    /// Écarts avec les contraintes du menu actuel (vide = configuration utilisable)
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if !(2..=MAX_PARTICLE_TYPES).contains(&self.particle_types) {
+            problems.push(format!(
+                "{} types de particules (autorisé : 2 à {})",
+                self.particle_types, MAX_PARTICLE_TYPES
+            ));
+        }
+        if !(1..=MAX_PARTICLE_COUNT).contains(&self.particle_count) {
+            problems.push(format!(
+                "{} particules (autorisé : 1 à {})",
+                self.particle_count, MAX_PARTICLE_COUNT
+            ));
+        }
+        if self.counts_per_type.len() != self.particle_types
+            || self.counts_per_type.iter().sum::<usize>() != self.particle_count
+        {
+            problems.push(format!(
+                "répartition par type {:?} incohérente avec {} particules de {} types",
+                self.counts_per_type, self.particle_count, self.particle_types
+            ));
+        }
+        if self.type_colors.len() != self.particle_types {
+            problems.push(format!(
+                "{} couleurs pour {} types",
+                self.type_colors.len(),
+                self.particle_types
+            ));
+        }
+        if !(1..=MAX_SIMULATION_COUNT).contains(&self.simulation_count) {
+            problems.push(format!(
+                "{} simulations (autorisé : 1 à {})",
+                self.simulation_count, MAX_SIMULATION_COUNT
+            ));
+        }
+        if let Some(min_distance) = self.min_distance
+            && !(min_distance > 0.0 && min_distance < self.max_force_range)
+        {
+            problems.push(format!(
+                "distance de répulsion {} (autorisé : entre 0 et la portée {})",
+                min_distance, self.max_force_range
+            ));
+        }
+        if self.food.food_count > MAX_FOOD_COUNT {
+            problems.push(format!(
+                "{} nourritures (max {})",
+                self.food.food_count, MAX_FOOD_COUNT
+            ));
+        }
+        for (label, size) in [
+            ("largeur", self.grid.width),
+            ("hauteur", self.grid.height),
+            ("profondeur", self.grid.depth),
+        ] {
+            if !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&size) {
+                problems.push(format!(
+                    "{} de grille {} (autorisé : {} à {})",
+                    label, size, MIN_GRID_SIZE, MAX_GRID_SIZE
+                ));
+            }
+        }
+        if !(MIN_PHYSICS_TIMESTEP..=MAX_PHYSICS_TIMESTEP).contains(&self.physics_timestep) {
+            problems.push(format!(
+                "timestep {} s (autorisé : {} à {})",
+                self.physics_timestep, MIN_PHYSICS_TIMESTEP, MAX_PHYSICS_TIMESTEP
+            ));
+        }
+        if self.epoch_duration <= 0.0 || self.max_epochs == 0 {
+            problems.push("durée ou nombre d'époques nul".to_string());
+        }
+
+        problems
+    }
+
+    /// Remplace le contenu du menu ; refuse une configuration hors contraintes
+    pub fn apply_to_menu_config(&self, config: &mut MenuConfig) -> Result<(), Vec<String>> {
+        let problems = self.validate();
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        let mut type_radii = self.type_radii.clone();
+        let mut type_masses = self.type_masses.clone();
+        ParticleTypesConfig::resize_physical_properties(
+            &mut type_radii,
+            &mut type_masses,
+            self.particle_types,
+        );
+
+        *config = MenuConfig {
+            grid_width: self.grid.width,
+            grid_height: self.grid.height,
+            grid_depth: self.grid.depth,
+
+            simulation_count: self.simulation_count,
+            spawn_mode: self.spawn_mode.into(),
+            particle_count: self.particle_count,
+            particle_types: self.particle_types,
+            counts_per_type: self.counts_per_type.clone(),
+            type_colors: self
+                .type_colors
+                .iter()
+                .map(|&(r, g, b, a)| Color::srgba(r, g, b, a))
+                .collect(),
+            type_radii,
+            type_masses,
+            epoch_duration: self.epoch_duration,
+            max_epochs: self.max_epochs,
+            auto_save_on_finish: self.auto_save_on_finish,
+            max_force_range: self.max_force_range,
+            velocity_half_life: self.velocity_half_life,
+            max_velocity: self.max_velocity,
+            integrator: match self.integrator {
+                SavedIntegrator::Euler => Integrator::Euler,
+                SavedIntegrator::Verlet => Integrator::Verlet,
+            },
+            force_curve: self.force_curve.into(),
+            min_distance: self.min_distance,
+            collision_damping: self.collision_damping,
+            physics_timestep: self.physics_timestep,
+            max_interactions_per_particle: self.max_interactions_per_particle,
+            force_scale_factor: self.force_scale_factor,
+            seed: self.seed,
+
+            food_count: self.food.food_count,
+            food_respawn_enabled: self.food.respawn_enabled,
+            food_respawn_time: self.food.respawn_cooldown,
+            food_respawn_jitter: self.food.respawn_jitter,
+            food_respawn_mode: self.food.respawn_mode.into(),
+            food_value: self.food.food_value,
+            food_value_max: self.food.max_food_value,
+            food_distribution: match self.food.distribution {
+                SavedFoodDistribution::Uniform => FoodDistribution::Uniform,
+                SavedFoodDistribution::Clustered { clusters, spread } => {
+                    FoodDistribution::Clustered { clusters, spread }
+                }
+            },
+
+            boundary_mode: self.boundary_mode.into(),
+            use_gpu: self.use_gpu,
+            // Réglage d'affichage, propre à la machine et non au run
+            render_quality: config.render_quality,
+            render_style: config.render_style,
+
+            elitism: self.elite_count.map_or(Elitism::Ratio, Elitism::Count),
+            elite_ratio: self.elite_ratio,
+            mutation_rate: self.mutation_rate,
+            elite_mutation_rate: self.elite_mutation_rate,
+            crossover_rate: self.crossover_rate,
+            tournament_size: self.tournament_size,
+            mutation_kind: match self.mutation_kind {
+                SavedMutationKind::Uniform => MutationKind::Uniform,
+                SavedMutationKind::Gaussian => MutationKind::Gaussian,
+            },
+            crossover_strategy: match self.crossover_strategy {
+                SavedCrossoverStrategy::Uniform => CrossoverStrategy::Uniform,
+                SavedCrossoverStrategy::SymmetricRelations => {
+                    CrossoverStrategy::SymmetricRelations
+                }
+                SavedCrossoverStrategy::TypeBlocks => CrossoverStrategy::TypeBlocks,
+                SavedCrossoverStrategy::Blend { alpha, auto_weight } => {
+                    CrossoverStrategy::Blend { alpha, auto_weight }
+                }
+                SavedCrossoverStrategy::Auto => CrossoverStrategy::Auto,
+            },
+            sharing_enabled: self.sharing_enabled,
+            sharing_radius: self.sharing_radius,
+            hall_of_fame_size: self.hall_of_fame_size,
+            hall_of_fame_reinject: self.hall_of_fame_reinject,
+            genome_archive_depth: self.genome_archive_depth,
+            seed_interesting_forces: self.seed_interesting_forces,
+            library_seed_count: self.library_seed_count,
+            allow_self_attraction: self.allow_self_attraction,
+            inherit_fitness_history: self.inherit_fitness_history,
+            fitness_weights: FitnessWeights {
+                food: self.fitness_food_weight,
+                exploration: self.fitness_exploration_weight,
+                proximity_enabled: self.fitness_proximity_enabled,
+                proximity: self.fitness_proximity_weight,
+            },
+            auto_adapt: self.auto_adapt.to_auto_adapt(),
+            diversity_guard: self.diversity_guard.to_diversity_guard(),
+        };
+
+        Ok(())
+    }
+}
+
+/// Dossier par défaut des configurations de run
+pub fn run_configs_dir() -> PathBuf {
+    PathBuf::from("run_configs")
+}
+
+pub fn save_run_config(config: &RunConfig, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+pub fn load_run_config(path: &Path) -> Result<RunConfig, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Configurations disponibles dans le dossier par défaut, triées par nom
+pub fn list_run_configs() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(run_configs_dir()) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    files
+}
@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::exploration::Exploration;
+use crate::components::genetics::genotype::Genotype;
+use crate::components::genetics::score::Score;
+use crate::resources::config::food::FoodParameters;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::seed::SimulationSeed;
+use crate::resources::config::simulation::{AutoSaveOnFinish, SimulationParameters};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::genetics::hall_of_fame::HallOfFame;
+use crate::resources::genetics::history::EvolutionHistory;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::states::app::AppState;
+use crate::systems::persistence::genome_export::exports_dir;
+use crate::systems::persistence::population_save::{
+    SavePopulationEvent, SavedPopulation,
+};
+
+/// Meilleurs génomes de la dernière époque proposés à la sauvegarde dans le rapport
+const REPORT_TOP_GENOMES: usize = 5;
+
+/// Bilan d'un run arrivé à `max_epochs`, affiché par l'écran `AppState::Finished`
+#[derive(Resource, Clone)]
+pub struct RunSummary {
+    pub epochs: usize,
+    /// Meilleure fitness de la dernière époque
+    pub final_best_score: f32,
+    /// Meilleure fitness de la première époque
+    pub first_best_score: f32,
+    /// Meilleur génome du run (dernière époque ou hall of fame), prêt à visualiser
+    pub champion: SavedPopulation,
+    pub saved_genomes: usize,
+    pub statistics_path: Option<PathBuf>,
+    /// Meilleurs génomes de la dernière époque (simulation, génome, fitness), par fitness
+    /// décroissante
+    pub top_genomes: Vec<(usize, Genotype, f32)>,
+    /// Génomes de `top_genomes` déjà sauvegardés depuis le rapport
+    pub saved_top: Vec<bool>,
+}
+
+impl RunSummary {
+    pub fn total_improvement(&self) -> f32 {
+        self.final_best_score - self.first_best_score
+    }
+}
+
+/// Demande d'export de l'historique d'évolution (bouton ou touche E)
+#[derive(Event)]
+pub struct ExportStatsEvent;
+
+/// Exporte l'historique une fois par frame, quel que soit le nombre de demandes
+pub fn process_export_requests(
+    mut export_events: EventReader<ExportStatsEvent>,
+    history: Res<EvolutionHistory>,
+) {
+    if export_events.read().count() == 0 {
+        return;
+    }
+
+    match export_evolution_history(&history) {
+        Ok(path) => info!("Historique d'évolution exporté vers {}", path.display()),
+        Err(error) => error!("Échec de l'export de l'historique : {}", error),
+    }
+}
+
+/// Écrit une ligne par époque (meilleur, moyenne, diversité) en CSV dans `exports/`
+pub fn export_evolution_history(
+    history: &EvolutionHistory,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = exports_dir();
+    fs::create_dir_all(&dir)?;
+
+    let timestamp = chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S");
+    let path = dir.join(format!("evolution_{}.csv", timestamp));
+
+    let mut csv = String::from("epoch,best,average,diversity\n");
+    for epoch in 0..history.epoch_count() {
+        csv.push_str(&format!(
+            "{},{:.3},{:.3},{:.4}\n",
+            epoch + 1,
+            history.best_scores[epoch],
+            history.average_scores[epoch],
+            history.diversity[epoch],
+        ));
+    }
+
+    fs::write(&path, csv)?;
+    Ok(path)
+}
+
+/// Fin du run : sauvegarde éventuelle des génomes, export des statistiques puis passage à
+/// l'écran de rapport. La dernière époque n'est pas passée par `reset_for_new_epoch`, ses
+/// statistiques sont donc ajoutées ici
+#[allow(clippy::too_many_arguments)]
+pub fn finish_run(
+    mut commands: Commands,
+    sim_params: Res<SimulationParameters>,
+    auto_save: Res<AutoSaveOnFinish>,
+    simulations: Query<(&SimulationId, &Genotype, &Score, &Exploration), With<Simulation>>,
+    fitness_weights: Res<FitnessWeights>,
+    mut history: ResMut<EvolutionHistory>,
+    hall_of_fame: Res<HallOfFame>,
+    mut save_events: EventWriter<SavePopulationEvent>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    grid_params: Res<GridParameters>,
+    food_params: Res<FoodParameters>,
+    particle_config: Res<ParticleTypesConfig>,
+    boundary_mode: Res<BoundaryMode>,
+    seed: Option<Res<SimulationSeed>>,
+) {
+    if !sim_params.all_epochs_done() {
+        return;
+    }
+
+    let mut final_genomes: Vec<(usize, &Genotype, f32)> = simulations
+        .iter()
+        .map(|(sim_id, genotype, score, exploration)| {
+            (sim_id.0, genotype, fitness_weights.combine(score.get(), exploration.fraction()))
+        })
+        .collect();
+    final_genomes.sort_by_key(|(sim_id, ..)| *sim_id);
+    let Some(&(best_sim, best_genotype, final_best)) =
+        final_genomes.iter().max_by(|a, b| a.2.total_cmp(&b.2))
+    else {
+        next_app_state.set(AppState::MainMenu);
+        return;
+    };
+
+    let average = final_genomes.iter().map(|(_, _, fitness)| fitness).sum::<f32>()
+        / final_genomes.len() as f32;
+    let mut distance_sum = 0.0;
+    let mut pair_count = 0;
+    for (i, (_, a, _)) in final_genomes.iter().enumerate() {
+        for (_, b, _) in &final_genomes[i + 1..] {
+            distance_sum += a.genetic_distance(b);
+            pair_count += 1;
+        }
+    }
+    let diversity = if pair_count > 0 { distance_sum / pair_count as f32 } else { 0.0 };
+    history.record_epoch(final_best, average, diversity);
+
+    let saved_genomes = if auto_save.0 {
+        for (sim_id, _, fitness) in &final_genomes {
+            save_events.write(SavePopulationEvent {
+                simulation_id: *sim_id,
+                name: format!("fin_run_sim{}", sim_id + 1),
+                description: Some(format!(
+                    "Fin de run après {} époques, fitness {:.1}",
+                    sim_params.max_epochs, fitness
+                )),
+                tags: vec!["fin de run".to_string()],
+                genome: None,
+            });
+        }
+        final_genomes.len()
+    } else {
+        0
+    };
+
+    let statistics_path = match export_evolution_history(&history) {
+        Ok(path) => {
+            info!("Statistiques du run exportées vers {}", path.display());
+            Some(path)
+        }
+        Err(error) => {
+            error!("Échec de l'export des statistiques du run : {}", error);
+            None
+        }
+    };
+
+    // Champion : meilleur de la dernière époque, sauf si le hall of fame a mieux
+    let (champion_genotype, champion_score, champion_origin) = match hall_of_fame.entries.first() {
+        Some(entry) if entry.score > final_best => (
+            entry.genotype.clone(),
+            entry.score,
+            format!("simulation #{}, époque {}", entry.simulation_id + 1, entry.epoch),
+        ),
+        _ => (
+            best_genotype.clone(),
+            final_best,
+            format!("simulation #{}, dernière époque", best_sim + 1),
+        ),
+    };
+    let champion = SavedPopulation::from_current_state(
+        format!("champion_{}_epoques", sim_params.max_epochs),
+        Some(format!("Champion du run ({})", champion_origin)),
+        vec!["champion".to_string()],
+        &champion_genotype,
+        champion_score,
+        &sim_params,
+        &grid_params,
+        &food_params,
+        &particle_config,
+        &boundary_mode,
+        seed.as_deref().map(|seed| seed.0),
+    );
+
+    let mut top_genomes: Vec<(usize, Genotype, f32)> = final_genomes
+        .iter()
+        .map(|&(sim_id, genotype, fitness)| (sim_id, genotype.clone(), fitness))
+        .collect();
+    top_genomes.sort_by(|a, b| b.2.total_cmp(&a.2));
+    top_genomes.truncate(REPORT_TOP_GENOMES);
+
+    let first_best = history.best_scores.first().copied().unwrap_or(final_best);
+    info!(
+        "=== RUN TERMINÉ : {} époques, meilleure fitness {:.1} ({:+.1} depuis la première époque) ===",
+        sim_params.max_epochs,
+        final_best,
+        final_best - first_best
+    );
+
+    commands.insert_resource(RunSummary {
+        epochs: sim_params.max_epochs,
+        final_best_score: final_best,
+        first_best_score: first_best,
+        champion,
+        saved_genomes,
+        statistics_path,
+        saved_top: vec![auto_save.0; top_genomes.len()],
+        top_genomes,
+    });
+    next_app_state.set(AppState::Finished);
+}
+
+/// Le bilan ne survit pas à l'écran de rapport
+pub fn clear_run_summary(mut commands: Commands) {
+    commands.remove_resource::<RunSummary>();
+}
@@ -1,19 +1,66 @@
 use bevy::input::ButtonInput;
-use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
 use bevy::math::{EulerRot, Quat, Vec2, Vec3};
-use bevy::prelude::{Camera, MouseButton, Query, Res, Transform, With};
-use crate::resources::world::camera::CameraSettings;
+use bevy::prelude::{
+    Camera, ChildOf, Entity, Local, MouseButton, Query, Res, ResMut, Time, Transform, Window, With,
+    Without,
+};
+use bevy_egui::EguiContexts;
+use std::collections::HashMap;
+use crate::components::entities::particle::Particle;
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::plugins::core::camera::DefaultCamera;
+use crate::resources::world::camera::{CameraSettings, CameraTarget};
+use crate::resources::world::grid::GridParameters;
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+
+/// Distance d'orbite minimale autorisée par le zoom
+const MIN_ORBIT_DISTANCE: f32 = 50.0;
+
+/// Constante de temps (secondes) du lissage du point visé par les caméras
+const CAMERA_FOCUS_SMOOTHING: f32 = 0.5;
 
 pub fn orbit(
-    mut camera: Query<&mut Transform, With<Camera>>,
-    camera_settings: Res<CameraSettings>,
+    mut contexts: EguiContexts,
+    mut camera: Query<&mut Transform, With<DefaultCamera>>,
+    mut camera_settings: ResMut<CameraSettings>,
+    grid_params: Res<GridParameters>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
 ) {
+    if contexts.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+
     let delta = mouse_motion.delta;
+    let rotating = mouse_buttons.pressed(MouseButton::Left) && delta != Vec2::ZERO;
+
+    // Zoom : la molette ajuste la distance d'orbite
+    let scroll_lines = match mouse_scroll.unit {
+        MouseScrollUnit::Line => mouse_scroll.delta.y,
+        MouseScrollUnit::Pixel => mouse_scroll.delta.y / 100.0,
+    };
+    let zooming = scroll_lines != 0.0;
+
+    if zooming {
+        let diagonal = (grid_params.width.powi(2)
+            + grid_params.height.powi(2)
+            + grid_params.depth.powi(2))
+        .sqrt();
+        let max_distance = (diagonal * 2.0).max(MIN_ORBIT_DISTANCE);
+        let zoom_factor = (1.0 - scroll_lines * camera_settings.zoom_speed).max(0.1);
+
+        camera_settings.orbit_distance =
+            (camera_settings.orbit_distance * zoom_factor).clamp(MIN_ORBIT_DISTANCE, max_distance);
+    }
+
+    if !rotating && !zooming {
+        return;
+    }
 
-    if mouse_buttons.pressed(MouseButton::Left) && delta != Vec2::ZERO {
-        for mut transform in camera.iter_mut() {
+    for mut transform in camera.iter_mut() {
+        if rotating {
             let delta_pitch = delta.y * camera_settings.pitch_speed;
             let delta_yaw = delta.x * camera_settings.yaw_speed;
 
@@ -25,12 +72,143 @@ pub fn orbit(
             );
             let yaw = yaw + delta_yaw;
             transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
+        }
+
+        let target = camera_settings.focus;
 
-            let target = Vec3::ZERO;
+        let orbit_distance = camera_settings.orbit_distance;
 
-            let orbit_distance = camera_settings.orbit_distance;
+        transform.translation = target - transform.forward() * orbit_distance;
+    }
+}
 
-            transform.translation = target - transform.forward() * orbit_distance;
+/// Orbite la caméra du viewport sous le curseur (ou toutes si elles sont liées)
+pub fn orbit_viewport_cameras(
+    mut contexts: EguiContexts,
+    mut cameras: Query<(Entity, &Camera, &mut Transform, &mut ViewportCamera)>,
+    camera_settings: Res<CameraSettings>,
+    windows: Query<&Window>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mut dragged_camera: Local<Option<Entity>>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        *dragged_camera = None;
+        return;
+    }
+
+    // La caméra est choisie au clic, puis suivie jusqu'au relâchement
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        let over_ui = contexts.ctx_mut().is_pointer_over_area();
+        let cursor = windows
+            .single()
+            .ok()
+            .and_then(|window| window.physical_cursor_position());
+
+        *dragged_camera = match cursor {
+            Some(cursor) if !over_ui => cameras
+                .iter()
+                .find(|(_, camera, _, _)| {
+                    camera.is_active
+                        && camera.viewport.as_ref().is_some_and(|viewport| {
+                            let min = viewport.physical_position.as_vec2();
+                            let max = min + viewport.physical_size.as_vec2();
+                            cursor.cmpge(min).all() && cursor.cmplt(max).all()
+                        })
+                })
+                .map(|(entity, _, _, _)| entity),
+            _ => None,
+        };
+    }
+
+    let Some(dragged) = *dragged_camera else {
+        return;
+    };
+
+    let delta = mouse_motion.delta;
+    if delta == Vec2::ZERO {
+        return;
+    }
+
+    let Ok((_, _, _, dragged_viewport)) = cameras.get(dragged) else {
+        return;
+    };
+    let yaw = dragged_viewport.yaw - delta.x * camera_settings.yaw_speed;
+    let pitch = (dragged_viewport.pitch + delta.y * camera_settings.pitch_speed)
+        .clamp(camera_settings.pitch_range.start, camera_settings.pitch_range.end);
+
+    // Caméras liées : toutes reprennent l'orientation du viewport manipulé
+    for (entity, _, mut transform, mut viewport_camera) in cameras.iter_mut() {
+        if entity != dragged && !camera_settings.link_viewports {
+            continue;
+        }
+
+        viewport_camera.yaw = yaw;
+        viewport_camera.pitch = pitch;
+        *transform = viewport_camera.orbit_transform();
+    }
+}
+
+/// Fait glisser le point visé de chaque caméra vers le centre de masse de sa simulation
+/// (la première pour la caméra par défaut) en mode `CenterOfMass`, ou vers l'origine.
+/// Le lissage exponentiel évite de transmettre à la vue l'agitation des particules
+#[allow(clippy::type_complexity)]
+pub fn follow_camera_targets(
+    time: Res<Time>,
+    mut camera_settings: ResMut<CameraSettings>,
+    simulations: Query<(Entity, &SimulationId), With<Simulation>>,
+    particles: Query<(&Transform, &ChildOf), With<Particle>>,
+    mut viewport_cameras: Query<(&mut Transform, &mut ViewportCamera), Without<Particle>>,
+    mut default_camera: Query<
+        &mut Transform,
+        (With<DefaultCamera>, Without<ViewportCamera>, Without<Particle>),
+    >,
+) {
+    let mut centers: HashMap<usize, Vec3> = HashMap::new();
+    if camera_settings.target == CameraTarget::CenterOfMass {
+        let mut sums: HashMap<Entity, (Vec3, u32)> = HashMap::new();
+        for (transform, parent) in particles.iter() {
+            let sum = sums.entry(parent.parent()).or_default();
+            sum.0 += transform.translation;
+            sum.1 += 1;
         }
+        for (entity, sim_id) in simulations.iter() {
+            if let Some(&(sum, count)) = sums.get(&entity) {
+                centers.insert(sim_id.0, sum / count as f32);
+            }
+        }
+    }
+    let target_for = |sim_id: usize| centers.get(&sim_id).copied().unwrap_or(Vec3::ZERO);
+
+    // Indépendant du framerate
+    let blend = 1.0 - (-time.delta_secs() / CAMERA_FOCUS_SMOOTHING).exp();
+
+    for (mut transform, mut viewport_camera) in viewport_cameras.iter_mut() {
+        let target = target_for(viewport_camera.simulation_id);
+        if viewport_camera.focus != target {
+            viewport_camera.focus = smooth_focus(viewport_camera.focus, target, blend);
+            *transform = viewport_camera.orbit_transform();
+        }
+    }
+
+    let first_simulation = simulations.iter().map(|(_, sim_id)| sim_id.0).min();
+    let target = first_simulation.map_or(Vec3::ZERO, target_for);
+    if camera_settings.focus != target {
+        let focus = smooth_focus(camera_settings.focus, target, blend);
+        // Translation seule : l'orientation et la distance de l'utilisateur sont gardées
+        for mut transform in default_camera.iter_mut() {
+            transform.translation += focus - camera_settings.focus;
+        }
+        camera_settings.focus = focus;
+    }
+}
+
+/// Rapproche `focus` de `target`, et l'y fixe une fois assez près pour ne plus toucher
+/// aux caméras
+fn smooth_focus(focus: Vec3, target: Vec3, blend: f32) -> Vec3 {
+    if focus.distance_squared(target) < 1e-4 {
+        target
+    } else {
+        focus.lerp(target, blend)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use std::collections::HashMap;
+use std::f32::consts::FRAC_PI_2;
+use crate::components::entities::food::Food;
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::resources::world::food_heatmap::FoodHeatmap;
+use crate::resources::world::grid::GridParameters;
+
+/// Gizmo de la heatmap d'une simulation, sur le render layer de celle-ci
+#[derive(Component)]
+pub struct FoodHeatmapGizmo;
+
+/// Nombre max de contours concentriques pour une cellule très consommée
+const CONSUMPTION_RINGS: usize = 4;
+
+/// Dessine au sol la densité de nourriture visible (bleu → jaune) et, par simulation,
+/// les cellules où la nourriture a été mangée pendant l'époque (anneaux rouges)
+pub fn draw_food_heatmap(
+    mut commands: Commands,
+    heatmap: Res<FoodHeatmap>,
+    grid: Res<GridParameters>,
+    mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
+    food_query: Query<(&Transform, &Visibility), With<Food>>,
+    simulations: Query<(Entity, &SimulationId, Option<&Children>), With<Simulation>>,
+    heatmap_gizmos: Query<(Entity, &Gizmo), With<FoodHeatmapGizmo>>,
+) {
+    if !heatmap.enabled {
+        for (entity, _) in heatmap_gizmos.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let mut density: HashMap<IVec3, u32> = HashMap::new();
+    for (transform, visibility) in food_query.iter() {
+        if *visibility != Visibility::Hidden {
+            *density
+                .entry(FoodHeatmap::cell_key(&grid, transform.translation))
+                .or_default() += 1;
+        }
+    }
+    let max_density = density.values().copied().max().unwrap_or(1) as f32;
+
+    let cell_size = FoodHeatmap::cell_size(&grid);
+    let rotation = Quat::from_rotation_x(FRAC_PI_2);
+    let cell_rect = |gizmo: &mut GizmoAsset, key: IVec3, inset: f32, color: Color| {
+        let center = FoodHeatmap::cell_center(&grid, key);
+        gizmo.rect(
+            Isometry3d::new(center, rotation),
+            Vec2::splat(cell_size - inset),
+            color,
+        );
+    };
+
+    for (sim_entity, sim_id, children) in simulations.iter() {
+        let mut gizmo = GizmoAsset::new();
+
+        for (key, count) in &density {
+            let t = *count as f32 / max_density;
+            let color = Color::srgb(0.2, 0.4, 1.0)
+                .mix(&Color::srgb(1.0, 0.9, 0.2), t)
+                .with_alpha(0.15 + 0.45 * t);
+            cell_rect(&mut gizmo, *key, 0.0, color);
+        }
+
+        if let Some(consumption) = heatmap.consumption.get(&sim_id.0) {
+            let max_eaten = consumption.values().copied().max().unwrap_or(1) as f32;
+            for (key, eaten) in consumption {
+                let t = *eaten as f32 / max_eaten;
+                let rings = ((t * CONSUMPTION_RINGS as f32).ceil() as usize).max(1);
+                let color = Color::srgba(1.0, 0.25, 0.1, 0.3 + 0.6 * t);
+                for ring in 1..=rings {
+                    let inset = cell_size * ring as f32 / (CONSUMPTION_RINGS + 1) as f32;
+                    cell_rect(&mut gizmo, *key, inset, color);
+                }
+            }
+        }
+
+        let existing_handle = children.and_then(|children| {
+            children
+                .iter()
+                .find_map(|child| heatmap_gizmos.get(child).ok())
+                .map(|(_, gizmo)| gizmo.handle.clone())
+        });
+
+        match existing_handle.and_then(|handle| gizmo_assets.get_mut(&handle)) {
+            Some(asset) => *asset = gizmo,
+            None => {
+                let heatmap_entity = commands
+                    .spawn((
+                        Gizmo {
+                            handle: gizmo_assets.add(gizmo),
+                            ..default()
+                        },
+                        FoodHeatmapGizmo,
+                        RenderLayers::layer(sim_id.0 + 1),
+                    ))
+                    .id();
+                commands.entity(sim_entity).add_child(heatmap_entity);
+            }
+        }
+    }
+}
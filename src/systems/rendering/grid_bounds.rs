@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+use crate::resources::world::boundary::{AxisBoundary, BoundaryMode};
+use crate::resources::world::grid::{GridBoundsDisplay, GridParameters};
+
+/// Dessine les limites de la grille en fil de fer (couleur distincte en mode torique ou mixte)
+pub fn draw_grid_bounds(
+    mut gizmos: Gizmos,
+    grid_params: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    display: Res<GridBoundsDisplay>,
+) {
+    if !display.visible {
+        return;
+    }
+
+    let color = match boundary_mode.uniform_axis() {
+        Some(AxisBoundary::Bounce) => Color::srgba(0.6, 0.6, 0.6, 0.25),
+        Some(AxisBoundary::Teleport) => Color::srgba(0.2, 0.8, 1.0, 0.35),
+        None => Color::srgba(0.6, 0.5, 1.0, 0.3),
+    };
+
+    let size = Vec3::new(grid_params.width, grid_params.height, grid_params.depth);
+    gizmos.cuboid(Transform::from_scale(size), color);
+}
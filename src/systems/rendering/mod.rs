@@ -1,3 +1,12 @@
 pub mod camera;
+pub mod food_heatmap;
+pub mod grid_bounds;
+pub mod neighbor_coloring;
+pub mod particle_colors;
+pub mod render_quality;
+pub mod render_style;
+pub mod trails;
+pub mod video_recording;
+pub mod viewport_capture;
 pub mod viewport_overlay;
 pub mod viewport_manager;
\ No newline at end of file
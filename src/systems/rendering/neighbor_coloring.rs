@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use crate::components::entities::particle::Particle;
+use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::resources::config::render_style::RenderStyle;
+
+/// Nombre de teintes de la rampe : un matériau partagé par teinte garde le batching
+const NEIGHBOR_COLOR_STEPS: usize = 8;
+
+/// Mode debug : teinte les particules selon leur nombre de voisins à portée
+/// (bleu = isolée, rouge = entourée). Physique CPU uniquement.
+#[derive(Resource, Default)]
+pub struct NeighborColoring {
+    pub enabled: bool,
+    /// Voisins à portée de chaque particule au dernier pas de physique, triés par entité
+    pub counts: Vec<(Entity, u32)>,
+    palette: Vec<Handle<StandardMaterial>>,
+}
+
+impl NeighborColoring {
+    /// Reprend les comptes calculés avec les forces (entité, force, voisins)
+    pub fn record(&mut self, forces: &[(Entity, Vec3, u32)]) {
+        self.counts.clear();
+        self.counts
+            .extend(forces.iter().map(|(entity, _, neighbors)| (*entity, *neighbors)));
+    }
+
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().map(|(_, count)| *count).max().unwrap_or(0)
+    }
+
+    /// Matériaux de la rampe, à mettre au style de rendu comme ceux des types
+    pub fn palette(&self) -> impl Iterator<Item = (usize, &Handle<StandardMaterial>)> {
+        self.palette.iter().enumerate()
+    }
+}
+
+/// Matériau d'origine (celui du type) d'une particule teintée par le mode debug
+#[derive(Component)]
+pub struct TypeMaterial(pub Handle<StandardMaterial>);
+
+pub fn ramp_color(step: usize) -> Color {
+    let t = step as f32 / (NEIGHBOR_COLOR_STEPS - 1) as f32;
+    Color::hsl(240.0 * (1.0 - t), 0.9, 0.55)
+}
+
+/// Applique la rampe de couleurs par voisinage, ou restaure les matériaux des types
+#[allow(clippy::type_complexity)]
+pub fn apply_neighbor_coloring(
+    mut commands: Commands,
+    mut coloring: ResMut<NeighborColoring>,
+    compute_enabled: Res<ComputeEnabled>,
+    render_style: Res<RenderStyle>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut particles: Query<
+        (Entity, &mut MeshMaterial3d<StandardMaterial>, Option<&TypeMaterial>),
+        With<Particle>,
+    >,
+) {
+    if !coloring.enabled || compute_enabled.0 {
+        for (entity, mut material, type_material) in particles.iter_mut() {
+            if let Some(type_material) = type_material {
+                material.0 = type_material.0.clone();
+                commands.entity(entity).remove::<TypeMaterial>();
+            }
+        }
+        if !coloring.counts.is_empty() {
+            coloring.counts.clear();
+        }
+        return;
+    }
+
+    if coloring.palette.is_empty() {
+        coloring.palette = (0..NEIGHBOR_COLOR_STEPS)
+            .map(|step| {
+                let color = ramp_color(step);
+                materials.add(render_style.material(color, color.to_linear() * 0.5))
+            })
+            .collect();
+    }
+
+    // Échelle relative au plus entouré, pour rester lisible quelle que soit la densité
+    let max_count = coloring.max_count().max(1);
+    for (entity, mut material, type_material) in particles.iter_mut() {
+        let Ok(index) = coloring.counts.binary_search_by_key(&entity, |(e, _)| *e) else {
+            continue;
+        };
+
+        if type_material.is_none() {
+            commands.entity(entity).insert(TypeMaterial(material.0.clone()));
+        }
+
+        let count = coloring.counts[index].1;
+        let step = (count as usize * (NEIGHBOR_COLOR_STEPS - 1)) / max_count as usize;
+        let handle = &coloring.palette[step];
+        if material.0 != *handle {
+            material.0 = handle.clone();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::components::entities::particle::{Particle, ParticleType};
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::render_style::RenderStyle;
+use crate::systems::rendering::neighbor_coloring::TypeMaterial;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Répercute les couleurs de `ParticleTypesConfig` et le style de rendu sur les matériaux
+/// partagés des particules
+#[allow(clippy::type_complexity)]
+pub fn sync_particle_materials(
+    particle_config: Res<ParticleTypesConfig>,
+    render_style: Res<RenderStyle>,
+    particles: Query<
+        (&ParticleType, &MeshMaterial3d<StandardMaterial>, Option<&TypeMaterial>),
+        With<Particle>,
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Un matériau par type, partagé par toutes les simulations ; en mode voisinage,
+    // le matériau du type est mis de côté dans `TypeMaterial`
+    let mut updated = HashSet::new();
+    for (particle_type, material, type_material) in particles.iter() {
+        let material = type_material.map_or(&material.0, |type_material| &type_material.0);
+        if !updated.insert(material.id()) {
+            continue;
+        }
+
+        if let Some(material) = materials.get_mut(material) {
+            let (base_color, emissive) = particle_config.get_color_for_type(particle_type.0);
+            material.base_color = base_color;
+            render_style.apply(material, emissive);
+        }
+    }
+}
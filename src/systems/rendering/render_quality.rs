@@ -0,0 +1,66 @@
+use crate::components::entities::food::Food;
+use crate::components::entities::particle::Particle;
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::plugins::core::camera::DefaultCamera;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Remplace le contenu des meshes partagés : les entités gardent leur handle, rien n'est respawné
+pub fn apply_render_quality(
+    render_quality: Res<RenderQuality>,
+    particles: Query<&Mesh3d, With<Particle>>,
+    food: Query<&Mesh3d, (With<Food>, Without<Particle>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let particle_meshes: HashSet<AssetId<Mesh>> =
+        particles.iter().map(|mesh| mesh.0.id()).collect();
+    for id in particle_meshes {
+        meshes.insert(id, render_quality.particle_mesh());
+    }
+
+    let food_meshes: HashSet<AssetId<Mesh>> = food.iter().map(|mesh| mesh.0.id()).collect();
+    for id in food_meshes {
+        meshes.insert(id, render_quality.food_mesh());
+    }
+}
+
+/// En billboard, tourne les disques de chaque simulation vers la caméra de son viewport
+/// (la caméra par défaut quand il n'y a pas de viewports)
+pub fn orient_billboards(
+    render_quality: Res<RenderQuality>,
+    viewport_cameras: Query<(&GlobalTransform, &ViewportCamera)>,
+    default_camera: Query<&GlobalTransform, (With<DefaultCamera>, Without<ViewportCamera>)>,
+    simulations: Query<(Entity, &SimulationId), With<Simulation>>,
+    mut particles: Query<(&mut Transform, &ChildOf), With<Particle>>,
+) {
+    if *render_quality != RenderQuality::Billboard {
+        return;
+    }
+
+    let fallback = default_camera
+        .iter()
+        .next()
+        .map(|transform| transform.compute_transform().rotation);
+
+    let rotations: HashMap<Entity, Quat> = simulations
+        .iter()
+        .filter_map(|(entity, sim_id)| {
+            viewport_cameras
+                .iter()
+                .find(|(_, camera)| camera.simulation_id == sim_id.0)
+                .map(|(camera_transform, _)| camera_transform.compute_transform().rotation)
+                .or(fallback)
+                .map(|rotation| (entity, rotation))
+        })
+        .collect();
+
+    for (mut transform, parent) in particles.iter_mut() {
+        if let Some(&rotation) = rotations.get(&parent.parent())
+            && transform.rotation != rotation
+        {
+            transform.rotation = rotation;
+        }
+    }
+}
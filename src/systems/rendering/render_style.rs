@@ -0,0 +1,60 @@
+use crate::components::entities::food::Food;
+use crate::globals::MAX_SIMULATION_COUNT;
+use crate::resources::config::render_style::RenderStyle;
+use crate::systems::rendering::neighbor_coloring::{NeighborColoring, ramp_color};
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use std::collections::HashSet;
+
+/// Lumière directionnelle du mode éclairé
+#[derive(Component)]
+pub struct SceneLight;
+
+/// Met la nourriture et la rampe du mode voisinage au style choisi (les matériaux des types
+/// suivent dans `sync_particle_materials`) et allume ou éteint la lumière de la scène
+pub fn apply_render_style(
+    mut commands: Commands,
+    render_style: Res<RenderStyle>,
+    neighbor_coloring: Res<NeighborColoring>,
+    food: Query<&MeshMaterial3d<StandardMaterial>, With<Food>>,
+    lights: Query<Entity, With<SceneLight>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let food_materials: HashSet<AssetId<StandardMaterial>> =
+        food.iter().map(|material| material.0.id()).collect();
+    for id in food_materials {
+        if let Some(material) = materials.get_mut(id) {
+            render_style.apply(material, LinearRgba::WHITE);
+        }
+    }
+
+    for (step, handle) in neighbor_coloring.palette() {
+        if let Some(material) = materials.get_mut(handle) {
+            render_style.apply(material, ramp_color(step).to_linear() * 0.5);
+        }
+    }
+
+    match (render_style.is_lit(), lights.is_empty()) {
+        (true, true) => {
+            // Chaque viewport a sa couche (id + 1) et la nourriture est sur la couche 0 :
+            // la lumière doit les éclairer toutes
+            let layers: Vec<usize> = (0..=MAX_SIMULATION_COUNT).collect();
+            commands.spawn((
+                SceneLight,
+                DirectionalLight {
+                    illuminance: light_consts::lux::AMBIENT_DAYLIGHT,
+                    shadows_enabled: false,
+                    ..default()
+                },
+                Transform::from_xyz(1.0, 2.0, 1.5).looking_at(Vec3::ZERO, Vec3::Y),
+                RenderLayers::from_layers(&layers),
+            ));
+        }
+        (false, false) => {
+            for light in lights.iter() {
+                commands.entity(light).despawn();
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use crate::components::entities::particle::{Particle, ParticleTrail, ParticleType};
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::trail::TrailConfig;
+use crate::resources::world::grid::GridParameters;
+
+/// Gizmo des traînées d'une simulation, sur le render layer de celle-ci
+#[derive(Component)]
+pub struct TrailGizmo;
+
+/// Enregistre les dernières positions de chaque particule (physique CPU uniquement)
+pub fn record_particle_trails(
+    mut commands: Commands,
+    trail_config: Res<TrailConfig>,
+    compute_enabled: Res<ComputeEnabled>,
+    grid_params: Res<GridParameters>,
+    mut particles: Query<(&Transform, &mut ParticleTrail), With<Particle>>,
+    trail_gizmos: Query<Entity, With<TrailGizmo>>,
+) {
+    if !trail_config.enabled || compute_enabled.0 {
+        for entity in trail_gizmos.iter() {
+            commands.entity(entity).despawn();
+        }
+        for (_, mut trail) in particles.iter_mut() {
+            if !trail.0.is_empty() {
+                trail.0.clear();
+            }
+        }
+        return;
+    }
+
+    // Au-delà de ce saut, la particule a été téléportée : on coupe la traînée
+    let max_jump = grid_params.width.min(grid_params.height).min(grid_params.depth) / 2.0;
+
+    for (transform, mut trail) in particles.iter_mut() {
+        let position = transform.translation;
+
+        if trail
+            .0
+            .back()
+            .is_some_and(|last| last.distance(position) > max_jump)
+        {
+            trail.0.clear();
+        }
+
+        trail.0.push_back(position);
+        while trail.0.len() > trail_config.length {
+            trail.0.pop_front();
+        }
+    }
+}
+
+/// Dessine les traînées de chaque simulation dans son propre gizmo
+#[allow(clippy::too_many_arguments)]
+pub fn draw_particle_trails(
+    mut commands: Commands,
+    trail_config: Res<TrailConfig>,
+    compute_enabled: Res<ComputeEnabled>,
+    particle_config: Res<ParticleTypesConfig>,
+    mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
+    simulations: Query<(Entity, &SimulationId, Option<&Children>), With<Simulation>>,
+    particles: Query<(&ParticleType, &ParticleTrail), With<Particle>>,
+    trail_gizmos: Query<&Gizmo, With<TrailGizmo>>,
+) {
+    if !trail_config.enabled || compute_enabled.0 {
+        return;
+    }
+
+    for (sim_entity, sim_id, children) in simulations.iter() {
+        let Some(children) = children else {
+            continue;
+        };
+
+        let existing_handle = children
+            .iter()
+            .find_map(|child| trail_gizmos.get(child).ok())
+            .map(|gizmo| gizmo.handle.clone());
+
+        let mut gizmo = GizmoAsset::new();
+        let particle_count = children.iter().filter(|c| particles.contains(*c)).count();
+        let segments_per_particle = (trail_config.max_segments / particle_count.max(1))
+            .min(trail_config.length.saturating_sub(1));
+
+        for child in children.iter() {
+            let Ok((particle_type, trail)) = particles.get(child) else {
+                continue;
+            };
+            if trail.0.len() < 2 || segments_per_particle == 0 {
+                continue;
+            }
+
+            let base_color = particle_config.get_color_for_type(particle_type.0).0;
+            let start = trail.0.len().saturating_sub(segments_per_particle + 1);
+            let points: Vec<Vec3> = trail.0.iter().skip(start).copied().collect();
+            let last = (points.len() - 1) as f32;
+
+            for (i, segment) in points.windows(2).enumerate() {
+                let alpha_from = trail_config.fade * i as f32 / last;
+                let alpha_to = trail_config.fade * (i + 1) as f32 / last;
+                gizmo.line_gradient(
+                    segment[0],
+                    segment[1],
+                    base_color.with_alpha(alpha_from),
+                    base_color.with_alpha(alpha_to),
+                );
+            }
+        }
+
+        match existing_handle.and_then(|handle| gizmo_assets.get_mut(&handle)) {
+            Some(asset) => *asset = gizmo,
+            None => {
+                let trail_entity = commands
+                    .spawn((
+                        Gizmo {
+                            handle: gizmo_assets.add(gizmo),
+                            ..default()
+                        },
+                        TrailGizmo,
+                        RenderLayers::layer(sim_id.0 + 1),
+                    ))
+                    .id();
+                commands.entity(sim_entity).add_child(trail_entity);
+            }
+        }
+    }
+}
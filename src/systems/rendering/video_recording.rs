@@ -0,0 +1,258 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::AsyncComputeTaskPool;
+use bevy_egui::{EguiContexts, egui};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::score::Score;
+use crate::globals::{DEFAULT_VIDEO_FRAME_INTERVAL, VIDEO_MAX_PENDING_FRAMES};
+use crate::resources::config::simulation::SimulationParameters;
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+use crate::ui::panels::force_matrix::ForceMatrixUI;
+
+/// Résolution des images enregistrées, relative au viewport capturé
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecordingScale {
+    #[default]
+    Full,
+    Half,
+    Quarter,
+}
+
+impl RecordingScale {
+    pub const ALL: [RecordingScale; 3] = [
+        RecordingScale::Full,
+        RecordingScale::Half,
+        RecordingScale::Quarter,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecordingScale::Full => "Pleine",
+            RecordingScale::Half => "1/2",
+            RecordingScale::Quarter => "1/4",
+        }
+    }
+
+    fn divisor(&self) -> u32 {
+        match self {
+            RecordingScale::Full => 1,
+            RecordingScale::Half => 2,
+            RecordingScale::Quarter => 4,
+        }
+    }
+}
+
+/// Enregistrement du viewport en images PNG numérotées (`recordings/{session}/frame_000000.png`),
+/// à assembler en vidéo avec un outil externe
+#[derive(Resource)]
+pub struct VideoRecording {
+    pub active: bool,
+    /// Une image toutes les `interval` frames
+    pub interval: u32,
+    pub scale: RecordingScale,
+    /// Incruste l'id, l'époque et le score dans les images
+    pub include_hud: bool,
+    session: Option<PathBuf>,
+    frames_since_capture: u32,
+    /// Images demandées ; la numérotation reste continue malgré les images abandonnées
+    pub captured: usize,
+    /// Images abandonnées faute de pouvoir suivre le rythme
+    pub dropped: usize,
+    /// Captures en cours (lecture GPU ou écriture disque), partagées avec les tâches d'écriture
+    pending: Arc<AtomicUsize>,
+}
+
+impl Default for VideoRecording {
+    fn default() -> Self {
+        Self {
+            active: false,
+            interval: DEFAULT_VIDEO_FRAME_INTERVAL,
+            scale: RecordingScale::default(),
+            include_hud: true,
+            session: None,
+            frames_since_capture: 0,
+            captured: 0,
+            dropped: 0,
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl VideoRecording {
+    pub fn start(&mut self) {
+        let session = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        self.session = Some(recordings_dir().join(session));
+        self.frames_since_capture = 0;
+        self.captured = 0;
+        self.dropped = 0;
+        self.active = true;
+    }
+
+    pub fn stop(&mut self) {
+        self.active = false;
+        if let Some(session) = &self.session {
+            info!(
+                "⏹ Enregistrement terminé : {} image(s) dans {} ({} abandonnée(s))",
+                self.captured,
+                session.display(),
+                self.dropped
+            );
+        }
+    }
+
+    pub fn session(&self) -> Option<&PathBuf> {
+        self.session.as_ref()
+    }
+}
+
+/// Quitter la simulation ou la visualisation clôt la session en cours
+pub fn stop_video_recording(mut recording: ResMut<VideoRecording>) {
+    if recording.active {
+        recording.stop();
+    }
+}
+
+pub fn recordings_dir() -> PathBuf {
+    PathBuf::from("recordings")
+}
+
+/// Pendant l'enregistrement, capture le viewport de la simulation sélectionnée (la fenêtre
+/// entière à défaut) toutes les `interval` frames. La capture GPU est asynchrone et l'écriture
+/// PNG se fait hors du thread principal ; si trop de captures sont en cours, l'image est
+/// abandonnée plutôt que de ralentir la simulation
+#[allow(clippy::too_many_arguments)]
+pub fn record_video_frames(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut recording: ResMut<VideoRecording>,
+    ui_state: Res<ForceMatrixUI>,
+    sim_params: Res<SimulationParameters>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &ViewportCamera)>,
+    simulations: Query<(&SimulationId, &Score), With<Simulation>>,
+) {
+    if !recording.active {
+        return;
+    }
+    let Some(session) = recording.session.clone() else {
+        return;
+    };
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let visible = ui_state.visible_simulations();
+    let sim_id = ui_state
+        .selected_simulation
+        .filter(|sim_id| visible.contains(sim_id))
+        .or_else(|| visible.first().copied());
+    let viewport = sim_id.and_then(|sim_id| {
+        cameras
+            .iter()
+            .find(|(camera, viewport_camera)| {
+                camera.is_active && viewport_camera.simulation_id == sim_id
+            })
+            .and_then(|(camera, _)| camera.viewport.clone())
+    });
+
+    // L'incrustation est dessinée à chaque frame pour figurer dans toutes les images
+    if recording.include_hud
+        && let (Some(sim_id), Some(viewport)) = (sim_id, &viewport)
+    {
+        let scale_factor = window.resolution.scale_factor();
+        let score = simulations
+            .iter()
+            .find(|(id, _)| id.0 == sim_id)
+            .map_or(0.0, |(_, score)| score.get());
+        egui::Area::new(egui::Id::new("video_recording_hud"))
+            .fixed_pos(egui::pos2(
+                viewport.physical_position.x as f32 / scale_factor + 10.0,
+                (viewport.physical_position.y + viewport.physical_size.y) as f32 / scale_factor
+                    - 60.0,
+            ))
+            .order(egui::Order::Foreground)
+            .show(contexts.ctx_mut(), |ui| {
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_rgba_premultiplied(0, 0, 0, 200))
+                    .corner_radius(egui::CornerRadius::same(4))
+                    .inner_margin(egui::Margin::same(6))
+                    .show(ui, |ui| {
+                        let text = |value: String| {
+                            egui::RichText::new(value)
+                                .color(egui::Color32::WHITE)
+                                .monospace()
+                        };
+                        ui.label(text(format!("Simulation #{}", sim_id + 1)).strong());
+                        ui.label(text(format!(
+                            "Époque {}  Score {:.1}",
+                            sim_params.current_epoch, score
+                        )));
+                    });
+            });
+    }
+
+    recording.frames_since_capture += 1;
+    if recording.frames_since_capture < recording.interval.max(1) {
+        return;
+    }
+    recording.frames_since_capture = 0;
+
+    if recording.pending.load(Ordering::Relaxed) >= VIDEO_MAX_PENDING_FRAMES {
+        recording.dropped += 1;
+        return;
+    }
+
+    let frame_index = recording.captured;
+    recording.captured += 1;
+    let pending = recording.pending.clone();
+    pending.fetch_add(1, Ordering::Relaxed);
+
+    let crop = viewport.map(|viewport| (viewport.physical_position, viewport.physical_size));
+    let divisor = recording.scale.divisor();
+    let path = session.join(format!("frame_{:06}.png", frame_index));
+
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |trigger: Trigger<ScreenshotCaptured>| {
+            let image = trigger.event().0.clone();
+            let pending = pending.clone();
+            let path = path.clone();
+            let session = session.clone();
+
+            AsyncComputeTaskPool::get()
+                .spawn(async move {
+                    let result = image
+                        .try_into_dynamic()
+                        .map_err(|error| error.to_string())
+                        .and_then(|mut image| {
+                            if let Some((position, size)) = crop {
+                                // Le viewport peut déborder si la fenêtre a été redimensionnée
+                                let x = position.x.min(image.width());
+                                let y = position.y.min(image.height());
+                                let width = size.x.min(image.width() - x);
+                                let height = size.y.min(image.height() - y);
+                                if width > 0 && height > 0 {
+                                    image = image.crop_imm(x, y, width, height);
+                                }
+                            }
+                            if divisor > 1 {
+                                image = image.thumbnail_exact(
+                                    (image.width() / divisor).max(1),
+                                    (image.height() / divisor).max(1),
+                                );
+                            }
+                            fs::create_dir_all(&session).map_err(|error| error.to_string())?;
+                            image.to_rgb8().save(&path).map_err(|error| error.to_string())
+                        });
+                    if let Err(error) = result {
+                        error!("Échec de l'enregistrement de {} : {}", path.display(), error);
+                    }
+                    pending.fetch_sub(1, Ordering::Relaxed);
+                })
+                .detach();
+        },
+    );
+}
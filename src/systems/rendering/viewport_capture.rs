@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy_egui::{EguiContexts, egui};
+use std::fs;
+use std::path::PathBuf;
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::score::Score;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+use crate::ui::panels::force_matrix::ForceMatrixUI;
+
+/// Capture PNG du viewport d'une simulation, demandée depuis la barre de contrôle
+#[derive(Resource)]
+pub struct ViewportCapture {
+    /// Incruste l'id, l'époque et le score dans l'image
+    pub include_overlay: bool,
+    pub requested: bool,
+    /// Résultat de la dernière capture (message, succès)
+    pub status: Option<(String, bool)>,
+}
+
+impl Default for ViewportCapture {
+    fn default() -> Self {
+        Self {
+            include_overlay: true,
+            requested: false,
+            status: None,
+        }
+    }
+}
+
+pub fn captures_dir() -> PathBuf {
+    PathBuf::from("captures")
+}
+
+/// Traite une demande de capture : dessine l'incrustation éventuelle dans la frame
+/// courante puis capture la fenêtre et n'en garde que le rectangle du viewport
+#[allow(clippy::too_many_arguments)]
+pub fn capture_selected_viewport(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut capture: ResMut<ViewportCapture>,
+    ui_state: Res<ForceMatrixUI>,
+    sim_params: Res<SimulationParameters>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &ViewportCamera)>,
+    simulations: Query<(&SimulationId, &Score), With<Simulation>>,
+) {
+    if !capture.requested {
+        return;
+    }
+    capture.requested = false;
+
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let visible = ui_state.visible_simulations();
+    let Some(sim_id) = ui_state
+        .selected_simulation
+        .filter(|sim_id| visible.contains(sim_id))
+        .or_else(|| visible.first().copied())
+    else {
+        capture.status = Some(("Aucune simulation affichée".to_string(), false));
+        return;
+    };
+
+    let Some(viewport) = cameras
+        .iter()
+        .find(|(camera, viewport_camera)| {
+            camera.is_active && viewport_camera.simulation_id == sim_id
+        })
+        .and_then(|(camera, _)| camera.viewport.clone())
+    else {
+        capture.status = Some((format!("Pas de viewport pour la simulation #{}", sim_id + 1), false));
+        return;
+    };
+
+    let epoch = sim_params.current_epoch;
+    let score = simulations
+        .iter()
+        .find(|(id, _)| id.0 == sim_id)
+        .map_or(0.0, |(_, score)| score.get());
+
+    // Les coordonnées physiques du viewport partent du coin haut gauche, comme l'image capturée
+    let position = viewport.physical_position;
+    let size = viewport.physical_size;
+
+    if capture.include_overlay {
+        let scale_factor = window.resolution.scale_factor();
+        egui::Area::new(egui::Id::new("viewport_capture_overlay"))
+            .fixed_pos(egui::pos2(
+                position.x as f32 / scale_factor + 10.0,
+                (position.y + size.y) as f32 / scale_factor - 60.0,
+            ))
+            .order(egui::Order::Foreground)
+            .show(contexts.ctx_mut(), |ui| {
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_rgba_premultiplied(0, 0, 0, 200))
+                    .corner_radius(egui::CornerRadius::same(4))
+                    .inner_margin(egui::Margin::same(6))
+                    .show(ui, |ui| {
+                        let text = |value: String| {
+                            egui::RichText::new(value)
+                                .color(egui::Color32::WHITE)
+                                .monospace()
+                        };
+                        ui.label(text(format!("Simulation #{}", sim_id + 1)).strong());
+                        ui.label(text(format!("Époque {}  Score {:.1}", epoch, score)));
+                    });
+            });
+    }
+
+    let path = captures_dir().join(format!(
+        "sim{}_epoque{}_{}.png",
+        sim_id + 1,
+        epoch,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |trigger: Trigger<ScreenshotCaptured>, mut capture: ResMut<ViewportCapture>| {
+            let image = match trigger.event().0.clone().try_into_dynamic() {
+                Ok(image) => image,
+                Err(error) => {
+                    capture.status = Some((format!("Format de capture non supporté : {}", error), false));
+                    return;
+                }
+            };
+
+            // Le viewport peut déborder si la fenêtre a été redimensionnée entre-temps
+            let x = position.x.min(image.width());
+            let y = position.y.min(image.height());
+            let width = size.x.min(image.width() - x);
+            let height = size.y.min(image.height() - y);
+            if width == 0 || height == 0 {
+                capture.status = Some(("Viewport hors de la fenêtre".to_string(), false));
+                return;
+            }
+
+            let result = fs::create_dir_all(captures_dir())
+                .map_err(|error| error.to_string())
+                .and_then(|_| {
+                    image
+                        .crop_imm(x, y, width, height)
+                        .to_rgb8()
+                        .save(&path)
+                        .map_err(|error| error.to_string())
+                });
+
+            capture.status = Some(match result {
+                Ok(()) => {
+                    info!("Capture enregistrée : {}", path.display());
+                    (format!("Capture : {}", path.display()), true)
+                }
+                Err(error) => {
+                    error!("Échec de la capture {} : {}", path.display(), error);
+                    (format!("Échec de la capture : {}", error), false)
+                }
+            });
+        },
+    );
+}
@@ -7,10 +7,42 @@ use bevy::window::WindowResized;
 use crate::components::entities::particle::Particle;
 use crate::components::entities::simulation::{Simulation, SimulationId};
 
-/// Marqueur pour les caméras des viewports
+/// Marqueur pour les caméras des viewports, avec leur orbite propre
 #[derive(Component)]
 pub struct ViewportCamera {
     pub simulation_id: usize,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    /// Point visé, suit le centre de masse de la simulation selon `CameraTarget`
+    pub focus: Vec3,
+}
+
+/// Direction de vue initiale des caméras de viewport (depuis l'origine)
+const DEFAULT_VIEW_DIRECTION: Vec3 = Vec3::new(0.7, 0.8, 0.7);
+
+impl ViewportCamera {
+    fn new(simulation_id: usize, distance: f32) -> Self {
+        let direction = DEFAULT_VIEW_DIRECTION;
+        Self {
+            simulation_id,
+            yaw: direction.x.atan2(direction.z),
+            pitch: direction.y.atan2(direction.xz().length()),
+            distance: distance * direction.length(),
+            focus: Vec3::ZERO,
+        }
+    }
+
+    /// Transform de la caméra en orbite autour de `focus`
+    pub fn orbit_transform(&self) -> Transform {
+        let position = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        ) * self.distance;
+
+        Transform::from_translation(self.focus + position).looking_at(self.focus, Vec3::Y)
+    }
 }
 
 /// Ressource pour stocker les dimensions de l'UI
@@ -20,6 +52,42 @@ pub struct UISpace {
     pub top_panel_height: f32,
 }
 
+/// Disposition des viewports ; `Auto` choisit selon leur nombre
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ViewportLayout {
+    #[default]
+    Auto,
+    /// Nombre de lignes imposé, colonnes déduites
+    Rows(usize),
+    /// Nombre de colonnes imposé, lignes déduites
+    Cols(usize),
+    /// Colonnes × lignes
+    Grid(usize, usize),
+}
+
+impl ViewportLayout {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViewportLayout::Auto => "Auto",
+            ViewportLayout::Rows(_) => "Lignes",
+            ViewportLayout::Cols(_) => "Colonnes",
+            ViewportLayout::Grid(_, _) => "Grille",
+        }
+    }
+
+    /// (colonnes, lignes) pour `total` viewports ; `None` pour `Auto` ou si la disposition
+    /// ne peut pas les contenir
+    pub fn grid_dims(&self, total: usize) -> Option<(usize, usize)> {
+        match *self {
+            ViewportLayout::Auto => None,
+            ViewportLayout::Rows(rows) if rows > 0 => Some((total.div_ceil(rows), rows)),
+            ViewportLayout::Cols(cols) if cols > 0 => Some((cols, total.div_ceil(cols))),
+            ViewportLayout::Grid(cols, rows) if cols * rows >= total => Some((cols, rows)),
+            _ => None,
+        }
+    }
+}
+
 /// Ressource pour forcer la mise à jour des viewports
 #[derive(Resource)]
 pub struct ForceViewportUpdate;
@@ -64,15 +132,16 @@ fn calculate_adaptive_camera_distance(grid: &GridParameters, viewport_count: usi
         _ => 1.3,
     };
 
-    let final_distance = base_distance * viewport_factor;
-    final_distance
+    base_distance * viewport_factor
 }
 
 /// Gère les viewports et caméras pour les simulations sélectionnées
+#[allow(clippy::too_many_arguments)]
 pub fn update_viewports(
     mut commands: Commands,
     ui_state: Res<ForceMatrixUI>,
     ui_space: Res<UISpace>,
+    layout: Res<ViewportLayout>,
     grid_params: Res<GridParameters>,
     windows: Query<&Window>,
     mut existing_cameras: Query<(
@@ -91,6 +160,7 @@ pub fn update_viewports(
     let should_update = force_update.is_some()
         || ui_state.is_changed()
         || ui_space.is_changed()
+        || layout.is_changed()
         || grid_params.is_changed()
         || has_resize;
 
@@ -119,8 +189,7 @@ pub fn update_viewports(
         return;
     }
 
-    let mut selected_sims: Vec<usize> = ui_state.selected_simulations.iter().cloned().collect();
-    selected_sims.sort();
+    let selected_sims = ui_state.visible_simulations();
 
     let mut cameras_to_reuse: Vec<Entity> =
         existing_cameras.iter().map(|(e, _, _, _, _)| e).collect();
@@ -135,10 +204,19 @@ pub fn update_viewports(
     let viewport_count = selected_sims.len();
     let camera_distance = calculate_adaptive_camera_distance(&grid_params, viewport_count);
 
+    let forced_dims = layout.grid_dims(viewport_count);
+    if *layout != ViewportLayout::Auto && forced_dims.is_none() {
+        warn!(
+            "Disposition {:?} trop petite pour {} viewports, retour en automatique",
+            *layout, viewport_count
+        );
+    }
+
     for (idx, &sim_id) in selected_sims.iter().enumerate() {
         let (x, y, w, h) = calculate_viewport_rect(
             idx,
             viewport_count,
+            forced_dims,
             available_width,
             available_height,
             ui_top_physical,
@@ -179,10 +257,12 @@ pub fn update_viewports(
     }
 }
 
-/// Calcule la position et taille d'un viewport
+/// Calcule la position et taille d'un viewport ; `forced_dims` (colonnes, lignes) remplace
+/// la disposition automatique
 fn calculate_viewport_rect(
     idx: usize,
     total: usize,
+    forced_dims: Option<(usize, usize)>,
     available_width: f32,
     available_height: f32,
     ui_top: f32,
@@ -190,14 +270,28 @@ fn calculate_viewport_rect(
 ) -> (u32, u32, u32, u32) {
     let margin = 8.0; // Marge plus grande pour éviter les chevauchements
 
-    let (x, y_from_top, w, h) = match total {
-        1 => (
+    let grid_cell = |cols: usize, rows: usize| {
+        let width = (available_width - (cols + 1) as f32 * margin) / cols as f32;
+        let height = (available_height - (rows + 1) as f32 * margin) / rows as f32;
+        let col = idx % cols;
+        let row = idx / cols;
+        (
+            margin + (col as f32 * (width + margin)),
+            margin + (row as f32 * (height + margin)),
+            width,
+            height,
+        )
+    };
+
+    let (x, y_from_top, w, h) = match (total, forced_dims) {
+        (_, Some((cols, rows))) => grid_cell(cols, rows),
+        (1, None) => (
             margin,
             margin,
             available_width - 2.0 * margin,
             available_height - 2.0 * margin,
         ),
-        2 => {
+        (2, None) => {
             let width = (available_width - 3.0 * margin) / 2.0;
             (
                 margin + (idx as f32 * (width + margin)),
@@ -206,7 +300,7 @@ fn calculate_viewport_rect(
                 available_height - 2.0 * margin,
             )
         }
-        3 => {
+        (3, None) => {
             let width = (available_width - 4.0 * margin) / 3.0;
             (
                 margin + (idx as f32 * (width + margin)),
@@ -215,7 +309,7 @@ fn calculate_viewport_rect(
                 available_height - 2.0 * margin,
             )
         }
-        4 => {
+        (4, None) => {
             let width = (available_width - 3.0 * margin) / 2.0;
             let height = (available_height - 3.0 * margin) / 2.0;
             let col = idx % 2;
@@ -230,16 +324,7 @@ fn calculate_viewport_rect(
         _ => {
             let cols = (total as f32).sqrt().ceil() as usize;
             let rows = ((total as f32) / (cols as f32)).ceil() as usize;
-            let width = (available_width - (cols + 1) as f32 * margin) / cols as f32;
-            let height = (available_height - (rows + 1) as f32 * margin) / rows as f32;
-            let col = idx % cols;
-            let row = idx / cols;
-            (
-                margin + (col as f32 * (width + margin)),
-                margin + (row as f32 * (height + margin)),
-                width,
-                height,
-            )
+            grid_cell(cols, rows)
         }
     };
 
@@ -248,6 +333,7 @@ fn calculate_viewport_rect(
 }
 
 /// Met à jour une caméra existante
+#[allow(clippy::too_many_arguments)]
 fn update_camera_viewport(
     camera: &mut Camera,
     transform: &mut Transform,
@@ -270,15 +356,16 @@ fn update_camera_viewport(
     camera.order = order as isize;
     camera.clear_color = ClearColorConfig::Custom(Color::srgb(0.02, 0.02, 0.02));
 
-    let camera_pos = Vec3::new(distance * 0.7, distance * 0.8, distance * 0.7);
-
-    *transform = Transform::from_translation(camera_pos).looking_at(Vec3::ZERO, Vec3::Y);
+    // L'orientation choisie par l'utilisateur est conservée, seule la distance s'adapte
+    viewport_camera.simulation_id = sim_id;
+    viewport_camera.distance = ViewportCamera::new(sim_id, distance).distance;
+    *transform = viewport_camera.orbit_transform();
 
     *render_layers = RenderLayers::from_layers(&[0, sim_id + 1]);
-    viewport_camera.simulation_id = sim_id;
 }
 
 /// Crée une nouvelle caméra de viewport
+#[allow(clippy::too_many_arguments)]
 fn spawn_viewport_camera(
     commands: &mut Commands,
     x: u32,
@@ -289,7 +376,7 @@ fn spawn_viewport_camera(
     sim_id: usize,
     distance: f32,
 ) {
-    let camera_pos = Vec3::new(distance * 0.7, distance * 0.8, distance * 0.7);
+    let viewport_camera = ViewportCamera::new(sim_id, distance);
 
     commands.spawn((
         Camera {
@@ -304,15 +391,14 @@ fn spawn_viewport_camera(
             ..default()
         },
         Camera3d::default(),
-        Transform::from_translation(camera_pos).looking_at(Vec3::ZERO, Vec3::Y),
-        ViewportCamera {
-            simulation_id: sim_id,
-        },
+        viewport_camera.orbit_transform(),
+        viewport_camera,
         RenderLayers::from_layers(&[0, sim_id + 1]),
     ));
 }
 
 /// Assigne les RenderLayers aux simulations et particules
+#[allow(clippy::type_complexity)]
 pub fn assign_render_layers(
     mut commands: Commands,
     simulations: Query<
@@ -334,10 +420,10 @@ pub fn assign_render_layers(
         }
 
         for child in children.iter() {
-            if particles.get(child).is_ok() {
-                if let Ok(mut entity_commands) = commands.get_entity(child) {
-                    entity_commands.insert(RenderLayers::layer(sim_id.0 + 1));
-                }
+            if particles.get(child).is_ok()
+                && let Ok(mut entity_commands) = commands.get_entity(child)
+            {
+                entity_commands.insert(RenderLayers::layer(sim_id.0 + 1));
             }
         }
     }
@@ -1,15 +1,17 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::systems::rendering::viewport_manager::{ViewportCamera, UISpace};
 use crate::ui::panels::force_matrix::ForceMatrixUI;
 
-/// Système pour dessiner les overlays des numéros de simulation sur chaque viewport
+/// Système pour dessiner les overlays des numéros de simulation (et de leur portée) sur chaque viewport
 pub fn draw_viewport_overlays(
     mut contexts: EguiContexts,
     ui_state: Res<ForceMatrixUI>,
     ui_space: Res<UISpace>,
     windows: Query<&Window>,
     cameras: Query<(&Camera, &ViewportCamera)>,
+    simulations: Query<(&SimulationId, &ForceRange), With<Simulation>>,
 ) {
     let Ok(window) = windows.single() else {
         return;
@@ -33,9 +35,7 @@ pub fn draw_viewport_overlays(
         return;
     }
 
-    let selected_sims: Vec<usize> = ui_state.selected_simulations.iter().cloned().collect();
-
-    if selected_sims.is_empty() {
+    if ui_state.visible_simulations().is_empty() {
         return;
     }
 
@@ -47,11 +47,14 @@ pub fn draw_viewport_overlays(
 
         if let Some(viewport) = &camera.viewport {
             let sim_id = viewport_camera.simulation_id;
+            let force_range = simulations
+                .iter()
+                .find(|(id, _)| id.0 == sim_id)
+                .map(|(_, range)| range.0);
 
             // Convertir les coordonnées physiques en coordonnées logiques pour egui
             let logical_x = viewport.physical_position.x as f32 / scale_factor;
             let logical_y = viewport.physical_position.y as f32 / scale_factor;
-            let logical_width = viewport.physical_size.x as f32 / scale_factor;
             let logical_height = viewport.physical_size.y as f32 / scale_factor;
 
             // Convertir en coordonnées egui (Y=0 en haut)
@@ -64,7 +67,7 @@ pub fn draw_viewport_overlays(
                 .movable(false)
                 .collapsible(false)
                 .fixed_pos(egui::pos2(logical_x + 10.0, egui_y + 10.0))
-                .fixed_size(egui::vec2(100.0, 40.0))
+                .fixed_size(egui::vec2(100.0, 55.0))
                 .frame(egui::Frame::NONE)
                 .show(ctx, |ui| {
                     // Style du texte avec fond semi-transparent
@@ -84,6 +87,13 @@ pub fn draw_viewport_overlays(
                                 .size(14.0)
                                 .strong()
                         );
+                        if let Some(force_range) = force_range {
+                            ui.label(
+                                egui::RichText::new(format!("Portée {:.0}", force_range))
+                                    .color(text_color)
+                                    .size(11.0),
+                            );
+                        }
                     });
                 });
         }
@@ -1,73 +1,152 @@
 use bevy::prelude::*;
+use rand::Rng;
 use crate::components::entities::food::{Food, FoodRespawnTimer, FoodValue};
-use crate::components::entities::particle::Particle;
+use crate::components::entities::particle::{Particle, ParticleType};
 use crate::components::entities::simulation::Simulation;
 use crate::components::genetics::score::Score;
+use crate::components::entities::simulation::SimulationId;
 use crate::globals::*;
+use crate::resources::config::food::FoodParameters;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::seed::SimulationRng;
+use crate::resources::config::simulation::{ScoringEnabled, SimulationParameters};
+use crate::resources::world::food_heatmap::FoodHeatmap;
+use crate::resources::world::grid::GridParameters;
+
+/// Fait avancer le timer de respawn ; renvoie `false` si la nourriture est encore cachée.
+/// À la réapparition, la nourriture est déplacée si `food_params` le demande
+#[allow(clippy::too_many_arguments)]
+fn update_food_respawn(
+    commands: &mut Commands,
+    food_entity: Entity,
+    respawn_timer: &mut FoodRespawnTimer,
+    visibility: &Visibility,
+    transform: &mut Transform,
+    food_params: &FoodParameters,
+    grid: &GridParameters,
+    rng: &mut impl Rng,
+    delta: std::time::Duration,
+) -> bool {
+    if let Some(ref mut timer) = respawn_timer.0 {
+        if timer.finished() {
+            // La nourriture réapparaît
+            timer.reset();
+            if let Some(position) = food_params.respawn_position(grid, rng) {
+                transform.translation = position;
+            }
+            commands.entity(food_entity).insert(Visibility::Visible);
+        } else if *visibility == Visibility::Hidden {
+            // Timer en cours et nourriture cachée
+            timer.tick(delta);
+            return false;
+        }
+    }
+    true
+}
+
+/// Cache la nourriture mangée si le respawn est activé, sinon la détruit
+pub fn consume_food(
+    commands: &mut Commands,
+    food_entity: Entity,
+    respawn_timer: &mut FoodRespawnTimer,
+) {
+    if let Some(ref mut timer) = respawn_timer.0 {
+        commands.entity(food_entity).insert(Visibility::Hidden);
+        timer.reset();
+    } else {
+        commands.entity(food_entity).despawn();
+    }
+}
 
 /// Détecte les collisions entre particules et nourriture
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn detect_food_collision(
     mut commands: Commands,
     time: Res<Time>,
-    particles: Query<(&Transform, &ChildOf), With<Particle>>,
+    particles: Query<(&Transform, &ParticleType, &ChildOf), With<Particle>>,
     mut food_query: Query<
         (
             Entity,
-            &Transform,
+            &mut Transform,
             &FoodValue,
             &mut FoodRespawnTimer,
-            &ViewVisibility,
+            &Visibility,
         ),
-        With<Food>,
+        (With<Food>, Without<Particle>),
     >,
-    mut simulations: Query<&mut Score, With<Simulation>>,
+    mut simulations: Query<(&SimulationId, &mut Score), With<Simulation>>,
+    mut heatmap: ResMut<FoodHeatmap>,
+    grid: Res<GridParameters>,
+    sim_params: Res<SimulationParameters>,
+    scoring_enabled: Res<ScoringEnabled>,
+    particle_config: Res<ParticleTypesConfig>,
+    food_params: Res<FoodParameters>,
+    mut sim_rng: ResMut<SimulationRng>,
 ) {
     // Pour chaque nourriture
-    for (food_entity, food_transform, food_value, mut respawn_timer, visibility) in
+    for (food_entity, mut food_transform, food_value, mut respawn_timer, visibility) in
         food_query.iter_mut()
     {
-        // Si la nourriture a un timer de respawn actif
-        if let Some(ref mut timer) = respawn_timer.0 {
-            if timer.finished() {
-                // La nourriture réapparaît
-                timer.reset();
-                commands.entity(food_entity).insert(Visibility::Visible);
-            } else if !visibility.get() {
-                // Timer en cours et nourriture cachée, passer à la suivante
-                timer.tick(time.delta());
-                continue;
-            }
+        if !update_food_respawn(
+            &mut commands,
+            food_entity,
+            &mut respawn_timer,
+            visibility,
+            &mut food_transform,
+            &food_params,
+            &grid,
+            &mut sim_rng.0,
+            time.delta(),
+        ) {
+            continue;
         }
 
         let food_pos = food_transform.translation;
-        let collision_distance = PARTICLE_RADIUS + FOOD_RADIUS;
 
-        // Vérifier collision avec chaque particule
-        for (particle_transform, parent) in particles.iter() {
+        // Vérifier collision avec chaque particule, à la distance propre à son type
+        for (particle_transform, particle_type, parent) in particles.iter() {
             let distance = (particle_transform.translation - food_pos).length();
+            let collision_distance = particle_config.radius_for(particle_type.0) + FOOD_RADIUS;
 
             if distance < collision_distance {
                 // Collision détectée !
                 // Augmenter le score de la simulation parente
-                if let Ok(mut score) = simulations.get_mut(parent.parent()) {
-                    score.add(food_value.0);
-                }
-
-                // Gérer la nourriture
-                if respawn_timer.0.is_some() {
-                    // Si respawn activé, cacher la nourriture
-                    commands.entity(food_entity).insert(Visibility::Hidden);
-                    if let Some(ref mut timer) = respawn_timer.0 {
-                        timer.reset();
+                if let Ok((sim_id, mut score)) = simulations.get_mut(parent.parent()) {
+                    if scoring_enabled.0 {
+                        score.add(food_value.0);
                     }
-                } else {
-                    // Sinon, détruire la nourriture
-                    commands.entity(food_entity).despawn();
+                    heatmap.record_consumption(&grid, sim_params.current_epoch, sim_id.0, food_pos);
                 }
 
+                consume_food(&mut commands, food_entity, &mut respawn_timer);
+
                 // Une seule particule peut manger cette nourriture
                 break;
             }
         }
     }
 }
+
+/// Respawn seul : avec le compute GPU, les collisions sont détectées par le shader
+pub fn tick_food_respawn(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut food_query: Query<(Entity, &mut FoodRespawnTimer, &Visibility, &mut Transform), With<Food>>,
+    food_params: Res<FoodParameters>,
+    grid: Res<GridParameters>,
+    mut sim_rng: ResMut<SimulationRng>,
+) {
+    for (food_entity, mut respawn_timer, visibility, mut transform) in food_query.iter_mut() {
+        update_food_respawn(
+            &mut commands,
+            food_entity,
+            &mut respawn_timer,
+            visibility,
+            &mut transform,
+            &food_params,
+            &grid,
+            &mut sim_rng.0,
+            time.delta(),
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::components::entities::particle::Particle;
+use crate::components::entities::simulation::Simulation;
+use crate::components::genetics::exploration::Exploration;
+use crate::globals::*;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::systems::simulation::spatial::SpatialGrid;
+use bevy::prelude::*;
+
+/// Marque les cellules occupées par les particules de chaque simulation
+pub fn track_exploration(
+    grid: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    particles: Query<(&Transform, &ChildOf), With<Particle>>,
+    mut simulations: Query<&mut Exploration, With<Simulation>>,
+) {
+    let cells = SpatialGrid::new(&grid, EXPLORATION_CELL_SIZE, *boundary_mode);
+    let cell_count = cells.cell_count();
+
+    for (transform, parent) in particles.iter() {
+        if let Ok(mut exploration) = simulations.get_mut(parent.parent()) {
+            exploration.visit(cells.cell_of(transform.translation), cell_count);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::globals::*;
+use crate::resources::config::simulation::{FastForward, SimulationParameters, SimulationSpeed};
+use crate::states::simulation::SimulationState;
+use crate::systems::lifecycle::advance_epoch;
+use crate::systems::simulation::collision::detect_food_collision;
+use crate::systems::simulation::exploration::track_exploration;
+use crate::systems::simulation::food_proximity::accumulate_food_proximity;
+use crate::systems::simulation::physics::physics_simulation_system;
+use bevy::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Vrai hors avance rapide : les systèmes pilotés par l'horloge réelle reprennent la main
+pub fn fast_forward_inactive(fast_forward: Res<FastForward>) -> bool {
+    !fast_forward.is_active()
+}
+
+/// Enchaîne des frames simulées de `PHYSICS_TIMESTEP` dans un budget de temps par frame,
+/// jusqu'à la fin de l'époque. La transition d'époque passe par `SimulationState::Starting`,
+/// donc par le même pipeline génétique que le mode interactif.
+pub fn run_fast_forward(world: &mut World) {
+    if !world.resource::<FastForward>().is_active() {
+        return;
+    }
+
+    world.resource_mut::<SimulationParameters>().simulation_speed = SimulationSpeed::Normal;
+
+    let step = Duration::from_secs_f32(PHYSICS_TIMESTEP);
+    let budget = Duration::from_millis(FAST_FORWARD_FRAME_BUDGET_MS);
+    let saved_time = *world.resource::<Time>();
+    let frame_start = Instant::now();
+    let mut epoch_finished = false;
+
+    while frame_start.elapsed() < budget {
+        // Horloge simulée : les timers de nourriture avancent d'un pas à chaque itération
+        world.resource_mut::<Time>().advance_by(step);
+
+        let _ = world.run_system_cached(physics_simulation_system);
+        let _ = world.run_system_cached(detect_food_collision);
+        let _ = world.run_system_cached(track_exploration);
+        let _ = world.run_system_cached(accumulate_food_proximity);
+
+        let mut sim_params = world.resource_mut::<SimulationParameters>();
+        sim_params.tick(step);
+        if sim_params.is_epoch_finished() {
+            epoch_finished = true;
+            break;
+        }
+    }
+
+    *world.resource_mut::<Time>() = saved_time;
+
+    if !epoch_finished {
+        return;
+    }
+
+    world.resource_scope(|world, mut sim_params: Mut<SimulationParameters>| {
+        info!("Époque {} terminée (avance rapide)", sim_params.current_epoch);
+        advance_epoch(&mut sim_params, &mut world.resource_mut::<NextState<SimulationState>>());
+    });
+    let run_done = world.resource::<SimulationParameters>().all_epochs_done();
+
+    let mut fast_forward = world.resource_mut::<FastForward>();
+    fast_forward.remaining_epochs -= 1;
+    // Le run est terminé : plus d'époque à enchaîner
+    if run_done {
+        fast_forward.remaining_epochs = 0;
+    }
+    if fast_forward.remaining_epochs == 0 {
+        let previous_speed = fast_forward.previous_speed.clone();
+        let total = fast_forward.total_epochs;
+        world.resource_mut::<SimulationParameters>().simulation_speed = previous_speed;
+        info!("Avance rapide terminée ({} époques)", total);
+    }
+}
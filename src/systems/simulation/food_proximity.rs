@@ -0,0 +1,83 @@
+use crate::components::entities::food::Food;
+use crate::components::entities::particle::Particle;
+use crate::components::entities::simulation::{ForceRange, Simulation};
+use crate::components::genetics::score::Score;
+use crate::resources::config::simulation::{ScoringEnabled, SimulationParameters};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::systems::simulation::physics::torus_direction_vector;
+use crate::systems::simulation::spatial::SpatialGrid;
+use bevy::prelude::*;
+
+/// Bonus de proximité à la nourriture : chaque frame, somme de `max(0, 1 - d / portée)` sur
+/// les paires particule-nourriture à portée, pondérée par la part de l'époque écoulée. Sur
+/// une époque complète, le bonus vaut `poids × proximité moyenne`, quelle que soit la durée
+/// de l'époque ou le nombre de frames
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn accumulate_food_proximity(
+    time: Res<Time>,
+    fitness_weights: Res<FitnessWeights>,
+    scoring_enabled: Res<ScoringEnabled>,
+    sim_params: Res<SimulationParameters>,
+    grid: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    particles: Query<(&Transform, &ChildOf), With<Particle>>,
+    food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    mut simulations: Query<(&ForceRange, &mut Score), With<Simulation>>,
+) {
+    if !fitness_weights.proximity_enabled
+        || !scoring_enabled.0
+        || sim_params.epoch_duration <= 0.0
+    {
+        return;
+    }
+
+    // Temps simulé, comme le timer d'époque : rien pendant la pause
+    let epoch_fraction = time.delta_secs() * sim_params.simulation_speed.multiplier()
+        / sim_params.epoch_duration;
+    if epoch_fraction <= 0.0 {
+        return;
+    }
+
+    let max_force_range = simulations
+        .iter()
+        .map(|(force_range, _)| force_range.0)
+        .fold(0.0f32, f32::max);
+    if max_force_range <= 0.0 {
+        return;
+    }
+
+    let food_positions: Vec<Vec3> = food_query
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(transform, _)| transform.translation)
+        .collect();
+    let mut food_grid = SpatialGrid::new(&grid, max_force_range, *boundary_mode);
+    for (index, food_pos) in food_positions.iter().enumerate() {
+        food_grid.insert(index, *food_pos);
+    }
+
+    let wraps = boundary_mode.wraps();
+    for (transform, parent) in particles.iter() {
+        let Ok((force_range, mut score)) = simulations.get_mut(parent.parent()) else {
+            continue;
+        };
+        let range = force_range.0;
+        if range <= 0.0 {
+            continue;
+        }
+
+        let position = transform.translation;
+        let mut proximity = 0.0;
+        food_grid.for_each_neighbor(position, |index| {
+            let distance =
+                torus_direction_vector(position, food_positions[index], &grid, wraps).length();
+            proximity += (1.0 - distance / range).max(0.0);
+        });
+
+        if proximity > 0.0 {
+            score.add(fitness_weights.proximity * proximity * epoch_fraction);
+        }
+    }
+}
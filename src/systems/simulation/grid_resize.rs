@@ -0,0 +1,73 @@
+use crate::components::entities::food::Food;
+use crate::components::entities::particle::{Particle, Velocity};
+use crate::globals::*;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::systems::simulation::spawning::FoodPositions;
+use bevy::prelude::*;
+
+/// Nouvelle taille de grille demandée depuis la barre de contrôle, en cours de run
+#[derive(Event, Clone, Copy)]
+pub struct ResizeGridEvent {
+    pub size: Vec3,
+    /// Étire les positions avec la grille au lieu de seulement ramener les sortantes au bord
+    pub rescale: bool,
+}
+
+/// Applique la dernière taille demandée et replace particules et nourriture. Tourne en
+/// `PreUpdate` : la physique CPU comme les buffers GPU voient la nouvelle grille dès cette
+/// frame, et caméras et viewports se recadrent sur `GridParameters::is_changed`
+pub fn apply_grid_resize(
+    mut resize_events: EventReader<ResizeGridEvent>,
+    mut grid_params: ResMut<GridParameters>,
+    sim_params: Res<SimulationParameters>,
+    mut particles: Query<(&mut Transform, &mut Velocity), With<Particle>>,
+    mut food_query: Query<&mut Transform, (With<Food>, Without<Particle>)>,
+    food_positions: Option<ResMut<FoodPositions>>,
+) {
+    let Some(&ResizeGridEvent { size, rescale }) = resize_events.read().last() else {
+        return;
+    };
+    let size = size.clamp(Vec3::splat(MIN_GRID_SIZE), Vec3::splat(MAX_GRID_SIZE));
+    let previous = Vec3::new(grid_params.width, grid_params.height, grid_params.depth);
+    if size == previous {
+        return;
+    }
+
+    grid_params.width = size.x;
+    grid_params.height = size.y;
+    grid_params.depth = size.z;
+    let scale = if rescale { size / previous } else { Vec3::ONE };
+
+    // Toujours ramenées vers l'intérieur, même en mode tore : une téléportation après un
+    // rétrécissement enverrait les particules à un endroit arbitraire
+    for (mut transform, mut velocity) in particles.iter_mut() {
+        transform.translation *= scale;
+        grid_params.apply_bounds(
+            &mut transform.translation,
+            &mut velocity.0,
+            BoundaryMode::BOUNCE,
+            sim_params.collision_damping,
+        );
+    }
+
+    let food_limit = size / 2.0 - Vec3::splat(FOOD_RADIUS);
+    let place_food = |position: Vec3| (position * scale).clamp(-food_limit, food_limit);
+    for mut transform in food_query.iter_mut() {
+        transform.translation = place_food(transform.translation);
+    }
+    if let Some(mut food_positions) = food_positions {
+        for position in food_positions.0.iter_mut() {
+            *position = place_food(*position);
+        }
+    }
+
+    info!(
+        "📐 Grille redimensionnée: {:.0} × {:.0} × {:.0} ({})",
+        size.x,
+        size.y,
+        size.z,
+        if rescale { "positions étirées" } else { "positions bornées" }
+    );
+}
@@ -0,0 +1,167 @@
+use crate::components::entities::food::Food;
+use crate::components::entities::particle::{Particle, ParticleType};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
+use crate::components::genetics::genotype::Genotype;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+use crate::systems::simulation::physics::force_breakdown;
+use crate::ui::panels::particle_inspector::ParticleInspectorUI;
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy_egui::EguiContexts;
+
+/// Tolérance du pointage, en rayons de particule autour du rayon de la souris
+const PICK_TOLERANCE: f32 = 2.0;
+
+/// Gizmos de l'inspecteur, affichés sur le seul render layer de la simulation inspectée
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct InspectorGizmos;
+
+/// Alt + clic gauche : sélectionne la particule la plus proche du rayon lancé depuis la
+/// caméra du viewport survolé, parmi celles de la simulation de ce viewport
+#[allow(clippy::too_many_arguments)]
+pub fn pick_inspected_particle(
+    mut contexts: EguiContexts,
+    mut inspector: ResMut<ParticleInspectorUI>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform, &ViewportCamera)>,
+    simulations: Query<&SimulationId, With<Simulation>>,
+    particles: Query<(Entity, &Transform, &ParticleType, &ChildOf), With<Particle>>,
+    particle_config: Res<ParticleTypesConfig>,
+) {
+    if !inspector.show_window
+        || !mouse_buttons.just_pressed(MouseButton::Left)
+        || !keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+        || contexts.ctx_mut().is_pointer_over_area()
+    {
+        return;
+    }
+
+    let Some(cursor) = windows.single().ok().and_then(|window| window.cursor_position()) else {
+        return;
+    };
+    let Some((camera, camera_transform, viewport_camera)) =
+        cameras.iter().find(|(camera, _, _)| {
+            camera.is_active
+                && camera
+                    .logical_viewport_rect()
+                    .is_some_and(|rect| rect.contains(cursor))
+        })
+    else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+
+    // La plus proche de la caméra parmi celles que le rayon touche
+    let picked = particles
+        .iter()
+        .filter(|(_, _, _, parent)| {
+            simulations
+                .get(parent.parent())
+                .is_ok_and(|sim_id| sim_id.0 == viewport_camera.simulation_id)
+        })
+        .filter_map(|(entity, transform, particle_type, _)| {
+            let offset = transform.translation - ray.origin;
+            let along = offset.dot(*ray.direction);
+            let miss = (offset - *ray.direction * along).length();
+            let tolerance = particle_config.radius_for(particle_type.0) * PICK_TOLERANCE;
+            (along > 0.0 && miss <= tolerance).then_some((entity, along))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+
+    if let Some(entity) = picked {
+        inspector.selected = Some(entity);
+    }
+}
+
+/// Recalcule chaque frame la décomposition des forces de la particule inspectée
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_force_breakdown(
+    mut inspector: ResMut<ParticleInspectorUI>,
+    mut gizmo_config: ResMut<GizmoConfigStore>,
+    sim_params: Res<SimulationParameters>,
+    grid: Res<GridParameters>,
+    boundary_mode: Res<BoundaryMode>,
+    simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    particles: Query<(Entity, &Transform, &ParticleType, &ChildOf), With<Particle>>,
+    food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    particle_config: Res<ParticleTypesConfig>,
+) {
+    let Some(entity) = inspector.selected.filter(|_| inspector.show_window) else {
+        inspector.breakdown = None;
+        return;
+    };
+
+    let breakdown = force_breakdown(
+        entity,
+        &sim_params,
+        &grid,
+        &boundary_mode,
+        &simulations,
+        &particles,
+        &food_query,
+        &particle_config,
+    );
+
+    match breakdown {
+        Some(breakdown) => {
+            let (config, _) = gizmo_config.config_mut::<InspectorGizmos>();
+            config.render_layers = RenderLayers::layer(breakdown.sim_id + 1);
+            inspector.breakdown = Some(breakdown);
+        }
+        // Particule disparue (nouveau run, retour au menu)
+        None => {
+            inspector.selected = None;
+            inspector.breakdown = None;
+        }
+    }
+}
+
+/// Flèches vers les voisines les plus influentes (vert : attirée, rouge : repoussée)
+/// et force résultante en blanc
+pub fn draw_inspector_gizmos(
+    mut gizmos: Gizmos<InspectorGizmos>,
+    inspector: Res<ParticleInspectorUI>,
+    particle_config: Res<ParticleTypesConfig>,
+) {
+    let Some(breakdown) = &inspector.breakdown else {
+        return;
+    };
+
+    let radius = particle_config.radius_for(breakdown.particle_type);
+    gizmos.sphere(
+        Isometry3d::from_translation(breakdown.position),
+        radius * 1.8,
+        Color::srgb(1.0, 1.0, 0.3),
+    );
+
+    for neighbor in breakdown
+        .neighbors
+        .iter()
+        .filter(|neighbor| !neighbor.ignored)
+        .take(inspector.top_count)
+    {
+        let color = if neighbor.force.dot(neighbor.direction) >= 0.0 {
+            Color::srgb(0.3, 0.9, 0.4)
+        } else {
+            Color::srgb(0.95, 0.3, 0.3)
+        };
+        gizmos.arrow(breakdown.position, breakdown.position + neighbor.direction, color);
+    }
+
+    if breakdown.total.length() > 0.001 {
+        gizmos.arrow(
+            breakdown.position,
+            breakdown.position + breakdown.total.normalize() * radius * 6.0,
+            Color::WHITE,
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::resources::world::mouse_interaction::{MouseForceTarget, MouseInteraction};
+use crate::systems::rendering::viewport_manager::ViewportCamera;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+/// Projette le curseur dans le viewport survolé et met à jour la cible de la force souris.
+/// Clic droit + Maj : attraction, clic droit + Ctrl : répulsion
+pub fn update_mouse_interaction(
+    mut contexts: EguiContexts,
+    mut mouse_interaction: ResMut<MouseInteraction>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform, &ViewportCamera)>,
+) {
+    let sign = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        1.0
+    } else if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        -1.0
+    } else {
+        0.0
+    };
+
+    let pointer_over_ui = contexts.ctx_mut().is_pointer_over_area();
+    let target = if mouse_buttons.pressed(MouseButton::Right) && sign != 0.0 && !pointer_over_ui {
+        windows
+            .single()
+            .ok()
+            .and_then(|window| window.cursor_position())
+            .and_then(|cursor| cursor_target(cursor, &cameras, sign))
+    } else {
+        None
+    };
+
+    // Évite de marquer la ressource modifiée quand rien ne change
+    if target.is_some() || mouse_interaction.target.is_some() {
+        mouse_interaction.target = target;
+    }
+}
+
+/// Cible sous le curseur, via la caméra dont le viewport contient le curseur
+fn cursor_target(
+    cursor: Vec2,
+    cameras: &Query<(&Camera, &GlobalTransform, &ViewportCamera)>,
+    sign: f32,
+) -> Option<MouseForceTarget> {
+    let (camera, camera_transform, viewport_camera) = cameras.iter().find(|(camera, _, _)| {
+        camera.is_active
+            && camera
+                .logical_viewport_rect()
+                .is_some_and(|rect| rect.contains(cursor))
+    })?;
+
+    let ray = camera.viewport_to_world(camera_transform, cursor).ok()?;
+
+    // Plan passant par le centre de la grille, face à la caméra
+    let plane_normal = camera_transform.forward();
+    let distance = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(plane_normal))?;
+
+    Some(MouseForceTarget {
+        simulation_id: viewport_camera.simulation_id,
+        point: ray.get_point(distance),
+        sign,
+    })
+}
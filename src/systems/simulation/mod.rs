@@ -1,5 +1,13 @@
 pub mod collision;
+pub mod exploration;
+pub mod fast_forward;
+pub mod food_proximity;
+pub mod grid_resize;
+pub mod inspector;
+pub mod interaction;
+pub mod particle_count;
 pub mod physics;
 pub mod reset;
+pub mod spatial;
 pub mod spawning;
 pub mod visualizer_spawning;
\ No newline at end of file
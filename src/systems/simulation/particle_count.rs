@@ -0,0 +1,146 @@
+use crate::components::entities::particle::{Particle, ParticleType};
+use crate::components::entities::simulation::Simulation;
+use crate::globals::*;
+use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::seed::SimulationRng;
+use crate::resources::config::simulation::{SimulationParameters, SpawnMode};
+use crate::resources::world::grid::GridParameters;
+use crate::systems::rendering::neighbor_coloring::TypeMaterial;
+use crate::systems::simulation::spawning::random_position_in_grid;
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+
+/// Ajoute (positif) ou retire (négatif) autant de particules de chaque type dans chaque
+/// simulation, depuis la barre de contrôle
+#[derive(Event, Clone, Copy)]
+pub struct AdjustParticleCountEvent {
+    pub per_type: isize,
+}
+
+/// Applique les demandes de la frame : les nouvelles particules sont placées selon
+/// `SpawnMode` (mêmes positions partout ou tirage par simulation), les retirées sont les
+/// dernières de leur type. Les types à 0 le restent. `ParticleTypesConfig` suit, pour que la
+/// prochaine époque replace tout le monde
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn apply_particle_count_change(
+    mut commands: Commands,
+    mut adjust_events: EventReader<AdjustParticleCountEvent>,
+    mut particle_config: ResMut<ParticleTypesConfig>,
+    mut sim_params: ResMut<SimulationParameters>,
+    grid: Res<GridParameters>,
+    compute_enabled: Res<ComputeEnabled>,
+    render_quality: Res<RenderQuality>,
+    render_style: Res<RenderStyle>,
+    mut sim_rng: ResMut<SimulationRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    simulations: Query<(Entity, &RenderLayers, &Children), With<Simulation>>,
+    particles: Query<
+        (&ParticleType, &Mesh3d, &MeshMaterial3d<StandardMaterial>, Option<&TypeMaterial>),
+        With<Particle>,
+    >,
+) {
+    let per_type: isize = adjust_events.read().map(|event| event.per_type).sum();
+    if per_type == 0 {
+        return;
+    }
+
+    let old_counts = particle_config.counts_per_type.clone();
+    let mut new_counts: Vec<usize> = old_counts
+        .iter()
+        .map(|&count| if count == 0 { 0 } else { count.saturating_add_signed(per_type) })
+        .collect();
+    // Les buffers GPU sont partagés par toutes les simulations : au-delà, les particules
+    // en trop ne seraient plus simulées
+    let max_per_simulation = if compute_enabled.0 {
+        MAX_PARTICLE_COUNT.min(GPU_MAX_PARTICLES / simulations.iter().count().max(1))
+    } else {
+        MAX_PARTICLE_COUNT
+    };
+    let new_total = new_counts.iter().sum::<usize>();
+    if new_total > max_per_simulation && new_total > old_counts.iter().sum() {
+        warn!(
+            "Ajout refusé : plus de {} particules par simulation",
+            max_per_simulation
+        );
+        return;
+    }
+    if new_counts.iter().all(|&count| count == 0) {
+        // Au moins une particule par simulation pour garder un sens aux scores
+        new_counts = old_counts.iter().map(|&count| count.min(1)).collect();
+    }
+
+    // Mesh partagé et matériau de chaque type, repris des particules existantes
+    let mut particle_mesh = None;
+    let mut type_materials: Vec<Option<Handle<StandardMaterial>>> =
+        vec![None; particle_config.type_count];
+    for (particle_type, mesh, material, type_material) in particles.iter() {
+        particle_mesh.get_or_insert_with(|| mesh.0.clone());
+        if let Some(slot) = type_materials.get_mut(particle_type.0) {
+            slot.get_or_insert_with(|| {
+                type_material.map_or(material.0.clone(), |type_material| type_material.0.clone())
+            });
+        }
+    }
+    let particle_mesh = particle_mesh.unwrap_or_else(|| meshes.add(render_quality.particle_mesh()));
+
+    let rng = &mut sim_rng.0;
+    for (particle_type, (&old_count, &new_count)) in
+        old_counts.iter().zip(&new_counts).enumerate()
+    {
+        if new_count > old_count {
+            let material = type_materials[particle_type].clone().unwrap_or_else(|| {
+                let (base_color, emissive) = particle_config.get_color_for_type(particle_type);
+                materials.add(render_style.material(base_color, emissive))
+            });
+            let draw_positions = |rng: &mut _| -> Vec<Vec3> {
+                (old_count..new_count)
+                    .map(|_| random_position_in_grid(&grid, rng))
+                    .collect()
+            };
+            let shared_positions = (sim_params.spawn_mode == SpawnMode::SharedInitial)
+                .then(|| draw_positions(&mut *rng));
+
+            for (simulation, render_layers, _) in simulations.iter() {
+                let positions = shared_positions.clone().unwrap_or_else(|| draw_positions(&mut *rng));
+                commands.entity(simulation).with_children(|parent| {
+                    for position in &positions {
+                        parent.spawn((
+                            Particle,
+                            ParticleType(particle_type),
+                            Transform::from_translation(*position)
+                                .with_scale(particle_config.mesh_scale_for(particle_type)),
+                            Mesh3d(particle_mesh.clone()),
+                            MeshMaterial3d(material.clone()),
+                            render_layers.clone(),
+                        ));
+                    }
+                });
+            }
+        } else if new_count < old_count {
+            for (_, _, children) in simulations.iter() {
+                let of_type: Vec<Entity> = children
+                    .iter()
+                    .filter(|child| {
+                        particles
+                            .get(*child)
+                            .is_ok_and(|(child_type, ..)| child_type.0 == particle_type)
+                    })
+                    .collect();
+                for child in of_type.iter().skip(new_count) {
+                    commands.entity(*child).despawn();
+                }
+            }
+        }
+    }
+
+    particle_config.counts_per_type = new_counts;
+    sim_params.particle_count = particle_config.total_particles();
+    info!(
+        "🔢 {} particules par simulation ({:?} par type)",
+        sim_params.particle_count, particle_config.counts_per_type
+    );
+}
@@ -1,39 +1,56 @@
 use crate::components::entities::food::Food;
-use crate::components::entities::particle::{Particle, ParticleType, Velocity};
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::particle::{
+    Particle, ParticleType, PreviousAcceleration, Velocity,
+};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
 use crate::globals::*;
-use crate::resources::config::simulation::{SimulationParameters, SimulationSpeed};
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::simulation::{
+    ForceCurve, Integrator, NeighborBackend, SimulationParameters, StepRequested,
+};
 use crate::resources::world::boundary::BoundaryMode;
 use crate::resources::world::grid::GridParameters;
+use crate::resources::world::mouse_interaction::MouseInteraction;
+use crate::systems::rendering::neighbor_coloring::NeighborColoring;
+use crate::systems::simulation::spatial::SpatialGrid;
 use bevy::prelude::*;
+use kd_tree::KdIndexTree3;
+use rayon::prelude::*;
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn physics_simulation_system(
     sim_params: Res<SimulationParameters>,
+    step_requested: Res<StepRequested>,
     grid: Res<GridParameters>,
     boundary_mode: Res<BoundaryMode>,
-    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
+    mouse_interaction: Option<Res<MouseInteraction>>,
+    simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
     mut particles: Query<
         (
             Entity,
             &mut Transform,
             &mut Velocity,
+            &mut PreviousAcceleration,
             &ParticleType,
             &ChildOf,
         ),
         With<Particle>,
     >,
-    food_query: Query<(&Transform, &ViewVisibility), (With<Food>, Without<Particle>)>,
+    food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    particle_config: Res<ParticleTypesConfig>,
+    mut neighbor_coloring: Option<ResMut<NeighborColoring>>,
+    neighbor_backend: Res<NeighborBackend>,
+    mut step_accumulator: Local<f32>,
 ) {
-    if sim_params.simulation_speed == SimulationSpeed::Paused {
-        return;
-    }
-
-    let iterations = match sim_params.simulation_speed {
-        SimulationSpeed::Paused => 0,
-        SimulationSpeed::Normal => 1,
-        SimulationSpeed::Fast => 2,
-        SimulationSpeed::VeryFast => 4,
+    // Pas unique demandé pendant la pause : une seule itération, quelle que soit la vitesse
+    let iterations = if step_requested.0 {
+        1
+    } else {
+        *step_accumulator += sim_params.physics_steps_per_frame();
+        let steps = step_accumulator.floor();
+        *step_accumulator -= steps;
+        steps as usize
     };
 
     for _iteration in 0..iterations {
@@ -44,158 +61,620 @@ pub fn physics_simulation_system(
             &simulations,
             &particles,
             &food_query,
+            &particle_config,
+            *neighbor_backend,
         );
 
         apply_physics_step(
             &grid,
             &boundary_mode,
+            &simulations,
             &mut particles,
             &particle_forces,
+            mouse_interaction.as_deref(),
             &sim_params,
+            &particle_config,
         );
+
+        if let Some(coloring) = neighbor_coloring
+            .as_deref_mut()
+            .filter(|coloring| coloring.enabled)
+        {
+            coloring.record(&particle_forces);
+        }
     }
 }
 
+/// Copie en lecture seule d'une particule, partagée entre les threads de calcul
+struct ParticleSnapshot {
+    entity: Entity,
+    position: Vec3,
+    particle_type: usize,
+    sim_id: usize,
+    /// Axes sur lesquels la particule est proche d'un bord : seul cas où le repliement
+    /// torique compte
+    near_axes: BVec3,
+}
+
+/// Structure de recherche des voisines construite à chaque pas (voir `NeighborBackend`)
+enum NeighborIndex<'a> {
+    Linear,
+    Grid(SpatialGrid),
+    KdTree(KdIndexTree3<'a, [f32; 3]>),
+}
+
+impl NeighborIndex<'_> {
+    /// Appelle `f` pour chaque candidate (index dans le snapshot) pouvant être à moins de
+    /// `range` de `particle`, images toriques comprises ; le filtrage exact reste à l'appelant
+    fn for_each_candidate(
+        &self,
+        particle: &ParticleSnapshot,
+        snapshot_len: usize,
+        range: f32,
+        grid: &GridParameters,
+        mut f: impl FnMut(usize),
+    ) {
+        match self {
+            NeighborIndex::Linear => (0..snapshot_len).for_each(f),
+            NeighborIndex::Grid(spatial_grid) => spatial_grid.for_each_neighbor(particle.position, f),
+            NeighborIndex::KdTree(tree) => {
+                let query = |position: Vec3| tree.within_radius(&position.to_array(), range);
+                if !particle.near_axes.any() {
+                    query(particle.position).into_iter().for_each(|index| f(*index));
+                    return;
+                }
+
+                // Près d'un bord du tore : la requête est répétée sur chaque image de la
+                // particule de l'autre côté, les doublons écartés
+                let size = Vec3::new(grid.width, grid.height, grid.depth);
+                let shift = -particle.position.signum() * size;
+                let mut candidates = Vec::new();
+                for mask in 0..8u32 {
+                    let axes = BVec3::new(mask & 1 != 0, mask & 2 != 0, mask & 4 != 0);
+                    if (axes & !particle.near_axes).any() {
+                        continue;
+                    }
+                    let image = particle.position + Vec3::select(axes, shift, Vec3::ZERO);
+                    candidates.extend(query(image).into_iter().copied());
+                }
+                candidates.sort_unstable();
+                candidates.dedup();
+                candidates.into_iter().for_each(f);
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn calculate_forces(
     sim_params: &SimulationParameters,
     grid: &GridParameters,
     boundary_mode: &BoundaryMode,
-    simulations: &Query<(&SimulationId, &Genotype), With<Simulation>>,
+    simulations: &Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
     particles: &Query<
         (
             Entity,
             &mut Transform,
             &mut Velocity,
+            &mut PreviousAcceleration,
             &ParticleType,
             &ChildOf,
         ),
         With<Particle>,
     >,
-    food_query: &Query<(&Transform, &ViewVisibility), (With<Food>, Without<Particle>)>,
-) -> std::collections::HashMap<Entity, Vec3> {
-    let mut genotypes_cache = std::collections::HashMap::new();
-    for (sim_id, genotype) in simulations.iter() {
-        genotypes_cache.insert(sim_id.0, genotype);
+    food_query: &Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    particle_config: &ParticleTypesConfig,
+    neighbor_backend: NeighborBackend,
+) -> Vec<(Entity, Vec3, u32)> {
+    // Conteneur ordonné : aucun parcours ne dépend d'un ordre de hachage
+    let mut genotypes_cache = std::collections::BTreeMap::new();
+    for (sim_id, genotype, force_range) in simulations.iter() {
+        genotypes_cache.insert(sim_id.0, (genotype, force_range.0));
     }
+    // Les cellules de la grille doivent couvrir la plus grande portée
+    let max_force_range = genotypes_cache
+        .values()
+        .map(|(_, range)| *range)
+        .fold(0.0f32, f32::max);
 
     let food_positions: Vec<Vec3> = food_query
         .iter()
-        .filter(|(_, visibility)| visibility.get())
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
         .map(|(transform, _)| transform.translation)
         .collect();
 
-    let mut forces = std::collections::HashMap::new();
+    // Grille de la nourriture : chaque particule ne voit que la nourriture à portée
+    let food_grid = (max_force_range > 0.0).then(|| {
+        let mut food_grid = SpatialGrid::new(grid, max_force_range, *boundary_mode);
+        for (index, food_pos) in food_positions.iter().enumerate() {
+            food_grid.insert(index, *food_pos);
+        }
+        food_grid
+    });
 
-    for (entity_a, transform, _, particle_type, parent) in particles.iter() {
-        let Ok((sim_id, _)) = simulations.get(parent.parent()) else {
-            continue;
-        };
+    // Snapshot des particules pour éviter d'accéder à la query depuis plusieurs threads
+    let mut torus_images = 0usize;
+    let snapshot: Vec<ParticleSnapshot> = particles
+        .iter()
+        .filter_map(|(entity, transform, _, _, particle_type, parent)| {
+            let (sim_id, _, force_range) = simulations.get(parent.parent()).ok()?;
+            let near_axes = near_wall_axes(transform.translation, grid, force_range.0)
+                & boundary_mode.wraps();
+            torus_images += 1 << near_axes.bitmask().count_ones();
+
+            Some(ParticleSnapshot {
+                entity,
+                position: transform.translation,
+                particle_type: particle_type.0,
+                sim_id: sim_id.0,
+                near_axes,
+            })
+        })
+        .collect();
 
-        let mut total_force = Vec3::ZERO;
-        let position = transform.translation;
+    // Sans axe en téléportation il n'y a aucune image torique : rien à mesurer
+    if boundary_mode.any_wrap() && !snapshot.is_empty() {
+        debug!(
+            "Images toriques par particule: {:.2} en moyenne",
+            torus_images as f32 / snapshot.len() as f32
+        );
+    }
 
-        if let Some(genotype) = genotypes_cache.get(&sim_id.0) {
-            // Forces avec autres particules
-            let mut interaction_count = 0;
-            for (entity_b, other_transform, _, other_type, other_parent) in particles.iter() {
-                if entity_a == entity_b || interaction_count >= 100 {
-                    continue;
-                }
+    let positions: Vec<[f32; 3]> = snapshot
+        .iter()
+        .map(|particle| particle.position.to_array())
+        .collect();
+    let neighbor_index = match neighbor_backend {
+        NeighborBackend::UniformGrid if max_force_range > 0.0 => {
+            let mut spatial_grid = SpatialGrid::new(grid, max_force_range, *boundary_mode);
+            for (index, particle) in snapshot.iter().enumerate() {
+                spatial_grid.insert(index, particle.position);
+            }
+            NeighborIndex::Grid(spatial_grid)
+        }
+        NeighborBackend::KdTree => {
+            NeighborIndex::KdTree(KdIndexTree3::build_by_ordered_float(&positions))
+        }
+        _ => NeighborIndex::Linear,
+    };
 
-                let Ok((other_sim_id, _)) = simulations.get(other_parent.parent()) else {
-                    continue;
-                };
-                if other_sim_id.0 != sim_id.0 {
-                    continue;
-                }
+    let mut forces: Vec<(Entity, Vec3, u32)> = snapshot
+        .par_iter()
+        .map(|particle| {
+            let (total_force, neighbor_count) = genotypes_cache
+                .get(&particle.sim_id)
+                .map_or((Vec3::ZERO, 0), |(genotype, force_range)| {
+                    particle_total_force(
+                        particle,
+                        genotype,
+                        *force_range,
+                        &snapshot,
+                        &neighbor_index,
+                        &food_positions,
+                        food_grid.as_ref(),
+                        sim_params,
+                        grid,
+                        particle_config,
+                    )
+                });
+            (particle.entity, total_force, neighbor_count)
+        })
+        .collect();
 
-                let distance_vec = match *boundary_mode {
-                    BoundaryMode::Teleport => {
-                        torus_direction_vector(position, other_transform.translation, grid)
-                    }
-                    BoundaryMode::Bounce => other_transform.translation - position,
-                };
+    // Ordre stable indépendant de l'ordonnancement des threads
+    forces.sort_unstable_by_key(|(entity, ..)| *entity);
+    forces
+}
 
-                let distance_squared = distance_vec.dot(distance_vec);
-                if distance_squared > sim_params.max_force_range * sim_params.max_force_range
-                    || distance_squared < 0.001
-                {
-                    continue;
-                }
+#[allow(clippy::too_many_arguments)]
+fn particle_total_force(
+    particle: &ParticleSnapshot,
+    genotype: &Genotype,
+    force_range: f32,
+    snapshot: &[ParticleSnapshot],
+    neighbor_index: &NeighborIndex,
+    food_positions: &[Vec3],
+    food_grid: Option<&SpatialGrid>,
+    sim_params: &SimulationParameters,
+    grid: &GridParameters,
+    particle_config: &ParticleTypesConfig,
+) -> (Vec3, u32) {
+    let mut total_force = Vec3::ZERO;
+    let position = particle.position;
+
+    // Voisins dans la portée des forces, même filtrage quelle que soit la structure
+    let mut neighbors = Vec::new();
+    neighbor_index.for_each_candidate(particle, snapshot.len(), force_range, grid, |index| {
+        let other = &snapshot[index];
+        if other.entity == particle.entity || other.sim_id != particle.sim_id {
+            return;
+        }
 
-                interaction_count += 1;
+        let distance_vec = if particle.near_axes.any() {
+            torus_direction_vector(position, other.position, grid, particle.near_axes)
+        } else {
+            other.position - position
+        };
 
-                let min_r = sim_params.particle_types as f32 * PARTICLE_RADIUS;
-                let attraction =
-                    genotype.get_force(particle_type.0, other_type.0) * FORCE_SCALE_FACTOR;
-                let acceleration = calculate_acceleration(
-                    min_r,
-                    distance_vec,
-                    attraction,
-                    sim_params.max_force_range,
-                );
+        let distance_squared = distance_vec.dot(distance_vec);
+        if distance_squared > force_range * force_range
+            || distance_squared < 0.001
+        {
+            return;
+        }
 
-                total_force += acceleration * sim_params.max_force_range;
-            }
+        neighbors.push((distance_squared, distance_vec, other.particle_type));
+    });
 
-            // Forces avec nourriture
-            let food_force = genotype.get_food_force(particle_type.0) * FORCE_SCALE_FACTOR;
-            if food_force.abs() > 0.001 {
-                for food_pos in &food_positions {
-                    let distance_vec = match *boundary_mode {
-                        BoundaryMode::Teleport => torus_direction_vector(position, *food_pos, grid),
-                        BoundaryMode::Bounce => *food_pos - position,
-                    };
-
-                    let distance = distance_vec.length();
-                    if distance > 0.001 && distance < sim_params.max_force_range {
-                        let force_direction = distance_vec.normalize();
-                        let distance_factor = ((FOOD_RADIUS * 2.0) / distance).min(1.0).powf(0.5);
-                        let force_magnitude = food_force * distance_factor;
-                        total_force += force_direction * force_magnitude;
-                    }
-                }
+    // Voisins à portée, avant la limite d'interactions
+    let neighbor_count = neighbors.len() as u32;
+
+    // Limite d'interactions : on garde les N plus proches pour éviter un biais directionnel
+    let max_interactions = sim_params.max_interactions_per_particle;
+    if max_interactions > 0 && neighbors.len() > max_interactions {
+        neighbors.select_nth_unstable_by(max_interactions, |a, b| a.0.total_cmp(&b.0));
+        neighbors.truncate(max_interactions);
+    }
+
+    // Forces avec autres particules
+    for (_, distance_vec, other_type) in neighbors {
+        total_force += pair_force(
+            particle.particle_type,
+            other_type,
+            distance_vec,
+            genotype,
+            force_range,
+            sim_params,
+            particle_config,
+        );
+    }
+
+    // Forces avec nourriture
+    let food_force = genotype.get_food_force(particle.particle_type) * sim_params.force_scale_factor;
+    if food_force.abs() > 0.001 {
+        let mut apply_food_force = |food_pos: Vec3| {
+            let distance_vec = if particle.near_axes.any() {
+                torus_direction_vector(position, food_pos, grid, particle.near_axes)
+            } else {
+                food_pos - position
+            };
+
+            total_force += food_pull(food_force, distance_vec, force_range);
+        };
+
+        match food_grid {
+            Some(food_grid) => {
+                food_grid.for_each_neighbor(position, |index| apply_food_force(food_positions[index]))
             }
+            // Pas de grille : parcours complet pour ne manquer aucune nourriture
+            None => food_positions.iter().for_each(|food_pos| apply_food_force(*food_pos)),
         }
+    }
+
+    (total_force, neighbor_count)
+}
 
-        forces.insert(entity_a, total_force);
+/// Force exercée par une voisine de type `other_type` située à `distance_vec` ; la distance
+/// de répulsion suit le rayon moyen du couple
+fn pair_force(
+    particle_type: usize,
+    other_type: usize,
+    distance_vec: Vec3,
+    genotype: &Genotype,
+    force_range: f32,
+    sim_params: &SimulationParameters,
+    particle_config: &ParticleTypesConfig,
+) -> Vec3 {
+    let pair_radius =
+        (particle_config.radius_for(particle_type) + particle_config.radius_for(other_type)) * 0.5;
+    let min_r = sim_params.pair_min_distance(pair_radius);
+    let attraction = genotype.get_force(particle_type, other_type) * sim_params.force_scale_factor;
+    calculate_acceleration(sim_params.force_curve, min_r, distance_vec, attraction, force_range)
+        * force_range
+}
+
+/// Force d'une nourriture située à `distance_vec`, nulle hors de portée
+fn food_pull(food_force: f32, distance_vec: Vec3, force_range: f32) -> Vec3 {
+    let distance = distance_vec.length();
+    if distance <= 0.001 || distance >= force_range {
+        return Vec3::ZERO;
     }
+    let distance_factor = ((FOOD_RADIUS * 2.0) / distance).min(1.0).powf(0.5);
+    distance_vec / distance * food_force * distance_factor
+}
 
-    forces
+/// Contribution d'une voisine à la force d'une particule inspectée
+pub struct NeighborContribution {
+    pub entity: Entity,
+    pub particle_type: usize,
+    pub distance: f32,
+    /// Vers la voisine, à travers le tore le cas échéant
+    pub direction: Vec3,
+    pub force: Vec3,
+    /// Écartée par la limite d'interactions : non comptée dans le total
+    pub ignored: bool,
+}
+
+/// Décomposition de la force d'une particule, calculée comme dans `particle_total_force`
+/// (hors outil souris)
+pub struct ForceBreakdown {
+    pub position: Vec3,
+    pub particle_type: usize,
+    pub sim_id: usize,
+    /// Triées par intensité décroissante
+    pub neighbors: Vec<NeighborContribution>,
+    pub food_force: Vec3,
+    pub total: Vec3,
+}
+
+/// Recalcule la force de `entity` voisine par voisine, par un parcours complet : une seule
+/// particule est inspectée, la structure de recherche n'apporterait rien
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn force_breakdown(
+    entity: Entity,
+    sim_params: &SimulationParameters,
+    grid: &GridParameters,
+    boundary_mode: &BoundaryMode,
+    simulations: &Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+    particles: &Query<(Entity, &Transform, &ParticleType, &ChildOf), With<Particle>>,
+    food_query: &Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    particle_config: &ParticleTypesConfig,
+) -> Option<ForceBreakdown> {
+    let (_, transform, particle_type, parent) = particles.get(entity).ok()?;
+    let (sim_id, genotype, force_range) = simulations.get(parent.parent()).ok()?;
+    let (position, particle_type, force_range) = (transform.translation, particle_type.0, force_range.0);
+    let separation = |to: Vec3| torus_direction_vector(position, to, grid, boundary_mode.wraps());
+
+    let mut neighbors: Vec<NeighborContribution> = particles
+        .iter()
+        .filter(|(other, _, _, other_parent)| {
+            *other != entity && other_parent.parent() == parent.parent()
+        })
+        .filter_map(|(other, other_transform, other_type, _)| {
+            let distance_vec = separation(other_transform.translation);
+            let distance_squared = distance_vec.dot(distance_vec);
+            if distance_squared > force_range * force_range || distance_squared < 0.001 {
+                return None;
+            }
+            Some(NeighborContribution {
+                entity: other,
+                particle_type: other_type.0,
+                distance: distance_squared.sqrt(),
+                direction: distance_vec,
+                force: pair_force(
+                    particle_type,
+                    other_type.0,
+                    distance_vec,
+                    genotype,
+                    force_range,
+                    sim_params,
+                    particle_config,
+                ),
+                ignored: false,
+            })
+        })
+        .collect();
+
+    // Même limite que la physique : seules les N plus proches comptent
+    let max_interactions = sim_params.max_interactions_per_particle;
+    if max_interactions > 0 && neighbors.len() > max_interactions {
+        neighbors.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        for neighbor in &mut neighbors[max_interactions..] {
+            neighbor.ignored = true;
+        }
+    }
+
+    let food_strength = genotype.get_food_force(particle_type) * sim_params.force_scale_factor;
+    let food_force = if food_strength.abs() > 0.001 {
+        food_query
+            .iter()
+            .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+            .map(|(food_transform, _)| {
+                food_pull(food_strength, separation(food_transform.translation), force_range)
+            })
+            .sum()
+    } else {
+        Vec3::ZERO
+    };
+
+    let total = neighbors
+        .iter()
+        .filter(|neighbor| !neighbor.ignored)
+        .map(|neighbor| neighbor.force)
+        .sum::<Vec3>()
+        + food_force;
+    neighbors.sort_by(|a, b| b.force.length().total_cmp(&a.force.length()));
+
+    Some(ForceBreakdown {
+        position,
+        particle_type,
+        sim_id: sim_id.0,
+        neighbors,
+        food_force,
+        total,
+    })
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn apply_physics_step(
     grid: &GridParameters,
     boundary_mode: &BoundaryMode,
+    simulations: &Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
     particles: &mut Query<
         (
             Entity,
             &mut Transform,
             &mut Velocity,
+            &mut PreviousAcceleration,
             &ParticleType,
             &ChildOf,
         ),
         With<Particle>,
     >,
-    forces: &std::collections::HashMap<Entity, Vec3>,
+    forces: &[(Entity, Vec3, u32)],
+    mouse_interaction: Option<&MouseInteraction>,
     sim_params: &SimulationParameters,
+    particle_config: &ParticleTypesConfig,
 ) {
-    for (entity, mut transform, mut velocity, _, _) in particles.iter_mut() {
-        if let Some(force) = forces.get(&entity) {
-            velocity.0 += *force * PHYSICS_TIMESTEP;
-            velocity.0 *= (0.5_f32).powf(PHYSICS_TIMESTEP / sim_params.velocity_half_life);
+    for (entity, mut transform, mut velocity, mut previous_acceleration, particle_type, parent) in
+        particles.iter_mut()
+    {
+        let force = forces
+            .binary_search_by_key(&entity, |(e, ..)| *e)
+            .ok()
+            .map(|index| {
+                let mut force = forces[index].1;
+
+                // Force de l'outil souris sur la simulation survolée
+                if let Some(mouse_interaction) = mouse_interaction
+                    && let Ok((sim_id, _, _)) = simulations.get(parent.parent())
+                {
+                    force += mouse_interaction.force_at(sim_id.0, transform.translation);
+                }
+                force
+            });
+
+        integrate(
+            &mut transform.translation,
+            &mut velocity.0,
+            &mut previous_acceleration.0,
+            force,
+            particle_config.mass_for(particle_type.0),
+            sim_params,
+            grid,
+            boundary_mode,
+        );
+    }
+}
 
-            if velocity.0.length() > MAX_VELOCITY {
-                velocity.0 = velocity.0.normalize() * MAX_VELOCITY;
+/// Un pas d'intégration pour une particule ; sans force calculée, l'accélération est nulle
+/// et la vitesse n'est ni amortie ni bornée
+#[allow(clippy::too_many_arguments)]
+fn integrate(
+    position: &mut Vec3,
+    velocity: &mut Vec3,
+    previous_acceleration: &mut Option<Vec3>,
+    force: Option<Vec3>,
+    mass: f32,
+    sim_params: &SimulationParameters,
+    grid: &GridParameters,
+    boundary_mode: &BoundaryMode,
+) {
+    let dt = sim_params.physics_timestep();
+
+    let mut acceleration = Vec3::ZERO;
+    if let Some(force) = force {
+        acceleration = force / mass;
+
+        match sim_params.integrator {
+            Integrator::Euler => *velocity += acceleration * dt,
+            // Termine le demi-pas de vitesse du pas précédent avec l'accélération actuelle
+            Integrator::Verlet => {
+                let previous = previous_acceleration.unwrap_or(acceleration);
+                *velocity += 0.5 * (previous + acceleration) * dt;
             }
         }
+        *velocity *= (0.5_f32).powf(dt / sim_params.velocity_half_life);
+
+        if velocity.length() > sim_params.max_velocity {
+            *velocity = velocity.normalize() * sim_params.max_velocity;
+        }
+    }
 
-        transform.translation += velocity.0 * PHYSICS_TIMESTEP;
-        grid.apply_bounds(&mut transform.translation, &mut velocity.0, *boundary_mode);
+    *position += match sim_params.integrator {
+        Integrator::Euler => *velocity * dt,
+        Integrator::Verlet => *velocity * dt + 0.5 * acceleration * dt * dt,
+    };
+    *previous_acceleration = Some(acceleration);
+
+    grid.apply_bounds(position, velocity, *boundary_mode, sim_params.collision_damping);
+}
+
+/// Particule copiée hors de l'ECS, avancée par `simulate_detached`
+#[derive(Clone)]
+pub struct DetachedParticle {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub particle_type: usize,
+    pub sim_id: usize,
+}
+
+/// Avance des copies de particules de `steps` pas avec la physique CPU, sans toucher aux
+/// entités : parcours complet des voisines, ni outil souris ni consommation de nourriture
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_detached(
+    particles: &mut [DetachedParticle],
+    steps: usize,
+    genotypes: &std::collections::BTreeMap<usize, (Genotype, f32)>,
+    food_positions: &[Vec3],
+    sim_params: &SimulationParameters,
+    grid: &GridParameters,
+    boundary_mode: &BoundaryMode,
+    particle_config: &ParticleTypesConfig,
+) {
+    let mut previous_accelerations = vec![None; particles.len()];
+
+    for _ in 0..steps {
+        let snapshot: Vec<ParticleSnapshot> = particles
+            .iter()
+            .map(|particle| ParticleSnapshot {
+                entity: particle.entity,
+                position: particle.position,
+                particle_type: particle.particle_type,
+                sim_id: particle.sim_id,
+                near_axes: match genotypes.get(&particle.sim_id) {
+                    Some((_, force_range)) => {
+                        near_wall_axes(particle.position, grid, *force_range)
+                            & boundary_mode.wraps()
+                    }
+                    None => BVec3::FALSE,
+                },
+            })
+            .collect();
+
+        let forces: Vec<Option<Vec3>> = snapshot
+            .par_iter()
+            .map(|particle| {
+                genotypes.get(&particle.sim_id).map(|(genotype, force_range)| {
+                    particle_total_force(
+                        particle,
+                        genotype,
+                        *force_range,
+                        &snapshot,
+                        &NeighborIndex::Linear,
+                        food_positions,
+                        None,
+                        sim_params,
+                        grid,
+                        particle_config,
+                    )
+                    .0
+                })
+            })
+            .collect();
+
+        for ((particle, force), previous_acceleration) in particles
+            .iter_mut()
+            .zip(forces)
+            .zip(&mut previous_accelerations)
+        {
+            integrate(
+                &mut particle.position,
+                &mut particle.velocity,
+                previous_acceleration,
+                force,
+                particle_config.mass_for(particle.particle_type),
+                sim_params,
+                grid,
+                boundary_mode,
+            );
+        }
     }
 }
 
 fn calculate_acceleration(
+    curve: ForceCurve,
     min_r: f32,
     relative_pos: Vec3,
     attraction: f32,
@@ -208,54 +687,263 @@ fn calculate_acceleration(
 
     let normalized_pos = relative_pos / max_force_range;
     let normalized_dist = dist / max_force_range;
-    let min_r_normalized = min_r / max_force_range;
-
-    let force = if normalized_dist < min_r_normalized {
-        normalized_dist / min_r_normalized - 1.0
-    } else {
-        attraction
-            * (1.0
-                - (1.0 + min_r_normalized - 2.0 * normalized_dist).abs() / (1.0 - min_r_normalized))
-    };
+    let force = curve.force(normalized_dist, min_r / max_force_range, attraction);
 
     normalized_pos * force / normalized_dist
 }
 
-fn torus_direction_vector(from: Vec3, to: Vec3, grid: &GridParameters) -> Vec3 {
-    let mut direction = Vec3::ZERO;
+/// Axes sur lesquels la particule est à portée d'un bord (loin des bords, aucun voisin
+/// ne peut être plus proche à travers le tore)
+fn near_wall_axes(position: Vec3, grid: &GridParameters, range: f32) -> BVec3 {
+    let half_extents = Vec3::new(grid.width, grid.height, grid.depth) / 2.0;
+    (half_extents - position.abs()).cmplt(Vec3::splat(range))
+}
+
+/// Plus court vecteur de `from` vers `to`, en passant par les bords sur les axes `wraps`
+/// seulement (les axes en rebond gardent la différence directe)
+pub fn torus_direction_vector(from: Vec3, to: Vec3, grid: &GridParameters, wraps: BVec3) -> Vec3 {
+    let size = Vec3::new(grid.width, grid.height, grid.depth);
+    let delta = to - from;
+    let wrapped = delta - delta.signum() * size;
+    let use_wrapped = wraps & delta.abs().cmpgt(size / 2.0);
+    Vec3::select(use_wrapped, wrapped, delta)
+}
 
-    let dx = to.x - from.x;
-    if dx.abs() <= grid.width / 2.0 {
-        direction.x = dx;
-    } else {
-        direction.x = if dx > 0.0 {
-            dx - grid.width
-        } else {
-            dx + grid.width
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::time::Instant;
+
+    /// Monde contenant une simulation au génome aléatoire et une particule par position,
+    /// dans la grille par défaut en téléportation
+    fn world_with_particles(positions: &[Vec3], seed: u64) -> World {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut world = World::new();
+        world.insert_resource(SimulationParameters::default());
+        world.insert_resource(GridParameters::default());
+        world.insert_resource(BoundaryMode::TELEPORT);
+        world.insert_resource(ParticleTypesConfig::default());
+
+        let genotype = Genotype::random(DEFAULT_PARTICLE_TYPES, true, &mut rng);
+        let simulation = world.spawn((Simulation, SimulationId(0), genotype)).id();
+        for (index, position) in positions.iter().enumerate() {
+            world.spawn((
+                Particle,
+                ParticleType(index % DEFAULT_PARTICLE_TYPES),
+                Transform::from_translation(*position),
+                ChildOf(simulation),
+            ));
+        }
+        world
     }
 
-    let dy = to.y - from.y;
-    if dy.abs() <= grid.height / 2.0 {
-        direction.y = dy;
-    } else {
-        direction.y = if dy > 0.0 {
-            dy - grid.height
-        } else {
-            dy + grid.height
-        };
+    /// `count` particules réparties uniformément dans la grille par défaut
+    fn populated_world(count: usize, seed: u64) -> World {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let half_extents = half_extents(&GridParameters::default());
+        let positions: Vec<Vec3> = (0..count)
+            .map(|_| {
+                Vec3::new(
+                    rng.random_range(-half_extents.x..half_extents.x),
+                    rng.random_range(-half_extents.y..half_extents.y),
+                    rng.random_range(-half_extents.z..half_extents.z),
+                )
+            })
+            .collect();
+        world_with_particles(&positions, seed)
     }
 
-    let dz = to.z - from.z;
-    if dz.abs() <= grid.depth / 2.0 {
-        direction.z = dz;
-    } else {
-        direction.z = if dz > 0.0 {
-            dz - grid.depth
-        } else {
-            dz + grid.depth
+    fn half_extents(grid: &GridParameters) -> Vec3 {
+        Vec3::new(grid.width, grid.height, grid.depth) / 2.0
+    }
+
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn forces_system(
+        In(neighbor_backend): In<NeighborBackend>,
+        sim_params: Res<SimulationParameters>,
+        grid: Res<GridParameters>,
+        boundary_mode: Res<BoundaryMode>,
+        particle_config: Res<ParticleTypesConfig>,
+        simulations: Query<(&SimulationId, &Genotype, &ForceRange), With<Simulation>>,
+        particles: Query<
+            (
+                Entity,
+                &mut Transform,
+                &mut Velocity,
+                &mut PreviousAcceleration,
+                &ParticleType,
+                &ChildOf,
+            ),
+            With<Particle>,
+        >,
+        food_query: Query<(&Transform, &Visibility), (With<Food>, Without<Particle>)>,
+    ) -> Vec<(Entity, Vec3, u32)> {
+        calculate_forces(
+            &sim_params,
+            &grid,
+            &boundary_mode,
+            &simulations,
+            &particles,
+            &food_query,
+            &particle_config,
+            neighbor_backend,
+        )
+    }
+
+    fn forces_once(world: &mut World, neighbor_backend: NeighborBackend) -> Vec<(Entity, Vec3, u32)> {
+        world.run_system_once_with(forces_system, neighbor_backend).unwrap()
+    }
+
+    /// Chronométrage du calcul des forces de 2000 particules, sur un puis quatre threads
+    /// (`cargo test --release -- --ignored --nocapture`)
+    #[test]
+    #[ignore]
+    fn parallel_forces_timing() {
+        let mut world = populated_world(2000, 7);
+        let mut time_with = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let start = Instant::now();
+            let mut forces = Vec::new();
+            for _ in 0..10 {
+                forces = pool.install(|| forces_once(&mut world, NeighborBackend::UniformGrid));
+            }
+            (start.elapsed() / 10, forces)
         };
+
+        let (sequential, sequential_forces) = time_with(1);
+        let (parallel, parallel_forces) = time_with(4);
+        println!("2000 particules : 1 thread {sequential:?}, 4 threads {parallel:?}");
+
+        assert_eq!(sequential_forces, parallel_forces);
+        // Sans quatre cœurs, les threads se partagent le même processeur
+        let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+        if cores >= 4 {
+            assert!(parallel < sequential);
+        }
     }
 
-    direction
+    /// Deux particules de part et d'autre du bord téléporté, sur un axe puis sur les trois :
+    /// le parcours des particules comme la grille de la nourriture doivent les trouver
+    /// voisines à travers le tore
+    #[test]
+    fn neighbors_found_across_teleport_seam() {
+        let grid = GridParameters::default();
+        let half_extents = half_extents(&grid);
+        let layouts = [
+            (Vec3::new(half_extents.x - 2.0, 0.0, 0.0), Vec3::new(-half_extents.x + 2.0, 0.0, 0.0)),
+            (half_extents - 2.0, -half_extents + 2.0),
+        ];
+        for (a, b) in layouts {
+            for neighbor_backend in NeighborBackend::ALL {
+                let mut world = world_with_particles(&[a, b], 13);
+                let forces = forces_once(&mut world, neighbor_backend);
+                assert_eq!(forces.len(), 2);
+                for (_, _, neighbor_count) in forces {
+                    assert_eq!(
+                        neighbor_count,
+                        1,
+                        "{} : voisine manquée entre {a} et {b}",
+                        neighbor_backend.label()
+                    );
+                }
+            }
+
+            let mut food_grid = SpatialGrid::new(
+                &grid,
+                SimulationParameters::default().max_force_range,
+                BoundaryMode::TELEPORT,
+            );
+            food_grid.insert(0, b);
+            let mut found = Vec::new();
+            food_grid.for_each_neighbor(a, |index| found.push(index));
+            assert_eq!(found, [0], "nourriture manquée entre {a} et {b}");
+        }
+    }
+
+    /// En rebond sur tous les axes, la force d'une particule près des bords est la somme
+    /// euclidienne directe des forces de couple : aucune image torique ne s'en mêle
+    #[test]
+    fn bounce_forces_match_euclidean_sum() {
+        let half_extents = half_extents(&GridParameters::default());
+        let positions = [
+            half_extents - 10.0,
+            half_extents - Vec3::new(60.0, 10.0, 40.0),
+            half_extents - Vec3::new(10.0, 120.0, 10.0),
+            // De l'autre côté des bords : voisines seulement à travers le tore
+            -half_extents + 10.0,
+            Vec3::new(-half_extents.x + 5.0, half_extents.y - 10.0, half_extents.z - 10.0),
+        ];
+        let mut world = world_with_particles(&positions, 35);
+        world.insert_resource(BoundaryMode::BOUNCE);
+        let forces = forces_once(&mut world, NeighborBackend::Linear);
+
+        let sim_params = SimulationParameters::default();
+        let genotype = world.query::<&Genotype>().single(&world).unwrap().clone();
+        let min_r = sim_params.particle_types as f32 * PARTICLE_RADIUS;
+        let mut particles = world.query::<(Entity, &Transform, &ParticleType)>();
+        for (entity, force, _) in forces {
+            let (_, transform, particle_type) = particles.get(&world, entity).unwrap();
+            let mut expected = Vec3::ZERO;
+            for (other, other_transform, other_type) in particles.iter(&world) {
+                let distance_vec = other_transform.translation - transform.translation;
+                if other == entity || distance_vec.length() > sim_params.max_force_range {
+                    continue;
+                }
+                let attraction = genotype.get_force(particle_type.0, other_type.0)
+                    * sim_params.force_scale_factor;
+                expected += calculate_acceleration(
+                    sim_params.force_curve,
+                    min_r,
+                    distance_vec,
+                    attraction,
+                    sim_params.max_force_range,
+                ) * sim_params.max_force_range;
+            }
+            assert!(
+                force.distance(expected) <= 1e-4 * expected.length().max(1.0),
+                "{force} au lieu de {expected}"
+            );
+        }
+    }
+
+    /// Image la plus proche axe par axe sur une grille non cubique : seuls les axes en
+    /// téléportation dont l'écart dépasse leur demi-taille passent par le bord, chacun
+    /// replié sur sa propre taille, quel que soit le mélange d'axes
+    #[test]
+    fn torus_direction_minimal_image_per_axis() {
+        let grid = GridParameters {
+            width: 800.0,
+            height: 400.0,
+            depth: 600.0,
+        };
+        let half_extents = half_extents(&grid);
+        // X et Y de part et d'autre d'un bord, Z proche sans le traverser
+        let from = Vec3::new(half_extents.x - 5.0, -half_extents.y + 10.0, 0.0);
+        let to = Vec3::new(-half_extents.x + 5.0, half_extents.y - 10.0, 20.0);
+        let direct = to - from;
+
+        for mask in 0..8u32 {
+            let wraps = BVec3::new(mask & 1 != 0, mask & 2 != 0, mask & 4 != 0);
+            let expected = Vec3::new(
+                if wraps.x { 10.0 } else { direct.x },
+                if wraps.y { -20.0 } else { direct.y },
+                20.0,
+            );
+            let vector = torus_direction_vector(from, to, &grid, wraps);
+            assert!(vector.abs_diff_eq(expected, 1e-3), "{wraps:?} : {vector} au lieu de {expected}");
+            // Même image dans l'autre sens
+            let back = torus_direction_vector(to, from, &grid, wraps);
+            assert!(back.abs_diff_eq(-expected, 1e-3), "{wraps:?} : retour {back}");
+        }
+
+        // À exactement une demi-taille, l'écart direct est gardé
+        let across = Vec3::new(grid.width / 2.0, grid.height / 2.0, grid.depth / 2.0);
+        assert_eq!(torus_direction_vector(Vec3::ZERO, across, &grid, BVec3::TRUE), across);
+    }
 }
@@ -1,13 +1,27 @@
-use crate::components::entities::food::{Food, FoodRespawnTimer};
-use crate::components::entities::particle::{Particle, ParticleType, Velocity};
+use crate::components::entities::food::{Food, FoodRespawnTimer, FoodValue};
+use crate::components::entities::particle::{
+    Particle, ParticleType, PreviousAcceleration, Velocity,
+};
 use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::exploration::Exploration;
 use crate::components::genetics::genotype::Genotype;
 use crate::components::genetics::score::Score;
+use crate::plugins::simulation::compute::ComputeEnabled;
+use crate::globals::*;
 use crate::resources::config::food::FoodParameters;
 use crate::resources::config::particle_types::ParticleTypesConfig;
-use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::config::seed::SimulationRng;
+use crate::resources::config::simulation::{
+    CrossoverStrategy, Elitism, Integrator, ScoringEnabled, SimulationParameters, SpawnMode,
+};
+use crate::resources::genetics::auto_adapt::AutoAdapt;
+use crate::resources::genetics::diversity_guard::{CollapseAction, DiversityGuard};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::genetics::genome_archive::{ArchivedGenome, GenomeArchive};
+use crate::resources::genetics::hall_of_fame::{HallOfFame, HallOfFameEntry};
+use crate::resources::genetics::history::EvolutionHistory;
 use crate::resources::world::grid::GridParameters;
-use crate::systems::simulation::spawning::FoodPositions;
+use crate::systems::simulation::spawning::{FoodPositions, random_positions_by_type};
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -15,7 +29,6 @@ use rand::Rng;
 struct ScoredGenome {
     genotype: Genotype,
     score: f32,
-    generation: usize,
 }
 
 #[derive(Default)]
@@ -26,62 +39,227 @@ struct EpochStats {
     median_score: f32,
     std_deviation: f32,
     improvement: f32,
+    diversity: f32,
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn reset_for_new_epoch(
     mut commands: Commands,
     grid: Res<GridParameters>,
-    sim_params: Res<SimulationParameters>,
+    mut sim_params: ResMut<SimulationParameters>,
     particle_config: Res<ParticleTypesConfig>,
     food_params: Res<FoodParameters>,
-    mut simulations: Query<(&SimulationId, &mut Genotype, &mut Score, &Children), With<Simulation>>,
-    mut particles: Query<(&mut Transform, &mut Velocity, &ParticleType), With<Particle>>,
+    mut simulations: Query<
+        (&SimulationId, &mut Genotype, &mut Score, &mut Exploration, &Children),
+        With<Simulation>,
+    >,
+    mut particles: Query<
+        (&mut Transform, &mut Velocity, &mut PreviousAcceleration, &ParticleType),
+        With<Particle>,
+    >,
     mut food_query: Query<
-        (&mut Transform, &mut FoodRespawnTimer, &mut Visibility),
+        (&mut Transform, &mut FoodValue, &mut FoodRespawnTimer, &mut Visibility),
         (With<Food>, Without<Particle>),
     >,
+    mut history: ResMut<EvolutionHistory>,
+    mut hall_of_fame: ResMut<HallOfFame>,
+    mut genome_archive: ResMut<GenomeArchive>,
+    (fitness_weights, compute_enabled): (Res<FitnessWeights>, Res<ComputeEnabled>),
+    (mut auto_adapt, mut diversity_guard): (ResMut<AutoAdapt>, ResMut<DiversityGuard>),
+    scoring_enabled: Res<ScoringEnabled>,
+    mut sim_rng: ResMut<SimulationRng>,
     mut previous_best_score: Local<f32>,
 ) {
     if sim_params.current_epoch == 0 {
         return;
     }
 
-    let mut rng = rand::rng();
+    let rng = &mut sim_rng.0;
 
-    let mut scored_genomes: Vec<ScoredGenome> = simulations
+    // Mode observation : pas de sélection ni de mutation, seules les positions sont relancées
+    if !scoring_enabled.0 {
+        info!(
+            "=== ÉPOQUE {} - MODE OBSERVATION : scores gelés, génomes inchangés ===",
+            sim_params.current_epoch
+        );
+        let mut unchanged_genomes: Vec<(usize, Genotype)> = simulations
+            .iter()
+            .map(|(sim_id, genotype, ..)| (sim_id.0, genotype.clone()))
+            .collect();
+        unchanged_genomes.sort_by_key(|(sim_id, _)| *sim_id);
+        let unchanged_genomes = unchanged_genomes
+            .into_iter()
+            .map(|(_, genotype)| genotype)
+            .collect();
+        reset_simulations_with_new_genomes(
+            &mut commands,
+            &grid,
+            &sim_params,
+            &particle_config,
+            &food_params,
+            unchanged_genomes,
+            &mut simulations,
+            &mut particles,
+            &mut food_query,
+            rng,
+        );
+        return;
+    }
+
+    // Fitness combinée : nourriture et exploration pondérées. L'ordre des ids de simulation
+    // (et non celui de la query) fixe le départ des égalités et du tirage des parents
+    let mut by_simulation: Vec<(usize, ScoredGenome)> = simulations
         .iter()
-        .map(|(_, genotype, score, _)| ScoredGenome {
-            genotype: genotype.clone(),
-            score: score.get(),
-            generation: sim_params.current_epoch,
+        .map(|(sim_id, genotype, score, exploration, _)| {
+            let fitness = fitness_weights.combine(score.get(), exploration.fraction());
+            let mut genotype = genotype.clone();
+            genotype.update_fitness_history(fitness);
+            let scored = ScoredGenome {
+                genotype,
+                score: fitness,
+            };
+            (sim_id.0, scored)
         })
         .collect();
+    by_simulation.sort_by_key(|(sim_id, _)| *sim_id);
+    let mut scored_genomes: Vec<ScoredGenome> =
+        by_simulation.into_iter().map(|(_, scored)| scored).collect();
 
     let stats = calculate_epoch_stats(&scored_genomes, *previous_best_score);
-    scored_genomes.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    let collapse = diversity_guard.observe_epoch(stats.diversity);
+
+    // Convergence : les génomes et scores de l'époque restent en place pour `finish_run`,
+    // qui enregistre lui-même ses statistiques et ouvre le rapport
+    if collapse == Some(CollapseAction::Stop) {
+        info!(
+            "🛑 CONVERGENCE : diversité {:.3} sous le seuil {:.3} depuis {} époques, arrêt du run après l'époque {}",
+            stats.diversity,
+            diversity_guard.threshold,
+            diversity_guard.patience.max(1),
+            sim_params.current_epoch
+        );
+        diversity_guard.converged_at = Some(sim_params.current_epoch);
+        sim_params.max_epochs = sim_params.current_epoch;
+        return;
+    }
+
+    history.record_epoch(stats.best_score, stats.average_score, stats.diversity);
+    auto_adapt.observe_epoch(stats.improvement > 0.0, &mut sim_params);
+
+    // Le hall of fame et l'archive retiennent les fitness non partagées de l'époque qui vient
+    // de se terminer
+    let mut hall_of_fame_entries = 0;
+    let mut archived = Vec::new();
+    for (sim_id, genotype, score, exploration, _) in simulations.iter() {
+        let fitness = fitness_weights.combine(score.get(), exploration.fraction());
+        let entered = hall_of_fame.consider(HallOfFameEntry {
+            genotype: genotype.clone(),
+            score: fitness,
+            epoch: sim_params.current_epoch,
+            simulation_id: sim_id.0,
+        });
+        hall_of_fame_entries += usize::from(entered);
+        archived.push(ArchivedGenome {
+            genotype: genotype.clone(),
+            score: fitness,
+            simulation_id: sim_id.0,
+        });
+    }
+    if hall_of_fame_entries > 0 {
+        info!("🏆 {} nouveau(x) génome(s) au hall of fame", hall_of_fame_entries);
+    }
+    genome_archive.record_epoch(sim_params.current_epoch, archived);
+
+    // Les statistiques restent sur les scores bruts, seule la sélection voit le partage
+    if sim_params.sharing_enabled {
+        apply_fitness_sharing(&mut scored_genomes, sim_params.sharing_radius);
+    }
+    // Tri stable : à score égal, l'ordre des ids est conservé
+    scored_genomes.sort_by(|a, b| b.score.total_cmp(&a.score));
     *previous_best_score = stats.best_score;
 
-    log_genetic_algorithm_stats(&stats, &sim_params, &scored_genomes);
+    log_genetic_algorithm_stats(&stats, &sim_params, &scored_genomes, compute_enabled.0);
+    if diversity_guard.low_epochs > 0 {
+        info!(
+            "🧬 Diversité sous le seuil {:.3} : {} / {} époques avant « {} »",
+            diversity_guard.threshold,
+            diversity_guard.low_epochs,
+            diversity_guard.patience.max(1),
+            diversity_guard.action.label().to_lowercase()
+        );
+    }
 
-    let elite_count =
-        ((sim_params.simulation_count as f32 * sim_params.elite_ratio).ceil() as usize).max(1);
+    let elite_count = sim_params.elite_count();
     let mut new_genomes = Vec::with_capacity(sim_params.simulation_count);
 
-    // Conservation des élites
-    for i in 0..elite_count {
-        new_genomes.push(scored_genomes[i].genotype.clone());
+    // Conservation des élites, légèrement perturbées si `elite_mutation_rate` > 0
+    let mut perturbed_elites = 0;
+    let mut elite_drift = 0.0;
+    for elite in scored_genomes.iter().take(elite_count) {
+        let mut genotype = elite.genotype.clone();
+        if sim_params.elite_mutation_rate > 0.0 {
+            genotype.mutate(sim_params.elite_mutation_rate, sim_params.mutation_kind, rng);
+            let drift = genotype.genetic_distance(&elite.genotype);
+            if drift > 0.0 {
+                perturbed_elites += 1;
+                elite_drift += drift;
+            }
+        }
+        new_genomes.push(genotype);
+    }
+    let kept_elites = new_genomes.len();
+    if sim_params.elite_mutation_rate <= 0.0 {
+        info!("🏆 Élites recopiées à l'identique (élitisme pur)");
+    } else if perturbed_elites > 0 {
+        info!(
+            "🏆 Élites perturbées: {} / {} (taux {:.1}%, distance moyenne {:.4})",
+            perturbed_elites,
+            kept_elites,
+            sim_params.elite_mutation_rate * 100.0,
+            elite_drift / perturbed_elites as f32
+        );
+    } else {
+        info!(
+            "🏆 Aucune élite perturbée (taux {:.1}%)",
+            sim_params.elite_mutation_rate * 100.0
+        );
+    }
+
+    // Réinjection d'un membre du hall of fame
+    if hall_of_fame.reinject
+        && !hall_of_fame.entries.is_empty()
+        && new_genomes.len() < sim_params.simulation_count
+    {
+        let index = rng.random_range(0..hall_of_fame.entries.len());
+        let entry = &hall_of_fame.entries[index];
+        info!(
+            "🏆 Réinjection du génome de l'époque {} (score {:.1})",
+            entry.epoch, entry.score
+        );
+        new_genomes.push(entry.genotype.clone());
     }
 
     // Génération de nouveaux individus
+    let tournament_size = sim_params.tournament_size();
+    let mut strategy_counts = [0usize; CrossoverStrategy::CONCRETE.len()];
     while new_genomes.len() < sim_params.simulation_count {
         let mut new_genotype;
 
         if rng.random::<f32>() < sim_params.crossover_rate && scored_genomes.len() >= 2 {
-            let parent1 = &weighted_tournament_selection(&scored_genomes, &mut rng);
-            let parent2 = &weighted_tournament_selection(&scored_genomes, &mut rng);
-            new_genotype = improved_crossover(parent1, parent2, &mut rng);
+            let parent1 = &weighted_tournament_selection(&scored_genomes, tournament_size, rng);
+            let parent2 = &weighted_tournament_selection(&scored_genomes, tournament_size, rng);
+            let strategy = sim_params
+                .crossover_strategy
+                .resolve(parent1.genetic_distance(parent2));
+            if let Some(index) = CrossoverStrategy::CONCRETE.iter().position(|s| s.same_kind(&strategy)) {
+                strategy_counts[index] += 1;
+            }
+            new_genotype = parent1.crossover(parent2, strategy, rng);
+            if sim_params.inherit_fitness_history {
+                new_genotype.inherit_fitness_history(parent1, parent2);
+            }
         } else {
-            let parent = weighted_tournament_selection(&scored_genomes, &mut rng);
+            let parent = weighted_tournament_selection(&scored_genomes, tournament_size, rng);
             new_genotype = parent;
         }
 
@@ -89,12 +267,47 @@ pub fn reset_for_new_epoch(
             &stats,
             sim_params.mutation_rate,
             sim_params.current_epoch,
+            auto_adapt.is_adapting(),
         );
 
-        new_genotype.mutate(adaptive_mutation_rate, &mut rng);
+        new_genotype.mutate(adaptive_mutation_rate, sim_params.mutation_kind, rng);
         new_genomes.push(new_genotype);
     }
 
+    // Diversité effondrée : une large part des non-élites repart de génomes aléatoires
+    if collapse == Some(CollapseAction::Inject) {
+        let replaceable = new_genomes.len().saturating_sub(kept_elites);
+        let injected = (replaceable as f32 * DIVERSITY_INJECTION_RATIO).ceil() as usize;
+        for genotype in new_genomes.iter_mut().rev().take(injected) {
+            *genotype = Genotype::random(
+                particle_config.type_count,
+                sim_params.allow_self_attraction,
+                rng,
+            );
+        }
+        info!(
+            "🌱 DIVERSITÉ EFFONDRÉE : {:.3} sous le seuil {:.3} depuis {} époques, {} génome(s) aléatoire(s) injecté(s)",
+            stats.diversity,
+            diversity_guard.threshold,
+            diversity_guard.patience.max(1),
+            injected
+        );
+    }
+
+    if strategy_counts.iter().any(|&count| count > 0) {
+        let summary: Vec<String> = CrossoverStrategy::CONCRETE
+            .iter()
+            .zip(strategy_counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(strategy, count)| format!("{} ×{}", strategy.label(), count))
+            .collect();
+        info!(
+            "🧬 Crossover ({}): {}",
+            sim_params.crossover_strategy.label(),
+            summary.join(", ")
+        );
+    }
+
     reset_simulations_with_new_genomes(
         &mut commands,
         &grid,
@@ -105,7 +318,7 @@ pub fn reset_for_new_epoch(
         &mut simulations,
         &mut particles,
         &mut food_query,
-        &mut rng,
+        rng,
     );
 }
 
@@ -130,7 +343,7 @@ fn calculate_epoch_stats(scored_genomes: &[ScoredGenome], previous_best: f32) ->
 
     let mut sorted_scores = scores.clone();
     sorted_scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let median = if sorted_scores.len() % 2 == 0 {
+    let median = if sorted_scores.len().is_multiple_of(2) {
         (sorted_scores[sorted_scores.len() / 2 - 1] + sorted_scores[sorted_scores.len() / 2]) / 2.0
     } else {
         sorted_scores[sorted_scores.len() / 2]
@@ -141,6 +354,21 @@ fn calculate_epoch_stats(scored_genomes: &[ScoredGenome], previous_best: f32) ->
 
     let improvement = best - previous_best;
 
+    // Diversité : distance génétique moyenne entre toutes les paires
+    let mut distance_sum = 0.0;
+    let mut pair_count = 0;
+    for (i, a) in scored_genomes.iter().enumerate() {
+        for b in &scored_genomes[i + 1..] {
+            distance_sum += a.genotype.genetic_distance(&b.genotype);
+            pair_count += 1;
+        }
+    }
+    let diversity = if pair_count > 0 {
+        distance_sum / pair_count as f32
+    } else {
+        0.0
+    };
+
     EpochStats {
         best_score: best,
         worst_score: worst,
@@ -148,6 +376,7 @@ fn calculate_epoch_stats(scored_genomes: &[ScoredGenome], previous_best: f32) ->
         median_score: median,
         std_deviation,
         improvement,
+        diversity,
     }
 }
 
@@ -155,17 +384,35 @@ fn log_genetic_algorithm_stats(
     stats: &EpochStats,
     sim_params: &SimulationParameters,
     genomes: &[ScoredGenome],
+    gpu_enabled: bool,
 ) {
     info!(
         "=== ALGORITHME GÉNÉTIQUE - ÉPOQUE {} ===",
         sim_params.current_epoch
     );
+    // Le compute shader n'implémente que l'intégration d'Euler
+    if gpu_enabled && sim_params.integrator != Integrator::Euler {
+        info!(
+            "⚙️ Intégration: Euler (GPU, {} indisponible)",
+            sim_params.integrator.label()
+        );
+    } else {
+        info!("⚙️ Intégration: {}", sim_params.integrator.label());
+    }
+    info!("⚙️ Courbe de force: {}", sim_params.force_curve.label());
+    info!(
+        "⚙️ Distance de répulsion: {:.1}{}",
+        sim_params.min_distance(),
+        if sim_params.min_distance.is_none() { " (auto)" } else { "" }
+    );
+    info!("⚙️ Placement initial: {}", sim_params.spawn_mode.label());
     info!("📊 Statistiques des scores:");
     info!("   • Meilleur: {:.2}", stats.best_score);
     info!("   • Pire: {:.2}", stats.worst_score);
     info!("   • Moyenne: {:.2}", stats.average_score);
     info!("   • Médiane: {:.2}", stats.median_score);
     info!("   • Écart-type: {:.2}", stats.std_deviation);
+    info!("🧬 Diversité génétique: {:.3}", stats.diversity);
 
     if stats.improvement > 0.0 {
         info!(
@@ -179,11 +426,19 @@ fn log_genetic_algorithm_stats(
         info!("➡️ Stagnation (pas d'amélioration)");
     }
 
-    let elite_count =
-        ((sim_params.simulation_count as f32 * sim_params.elite_ratio).ceil() as usize).max(1);
     info!(
-        "🏆 Élites conservées: {} / {}",
-        elite_count, sim_params.simulation_count
+        "🏆 Élites conservées: {} / {} ({})",
+        sim_params.elite_count(),
+        sim_params.simulation_count,
+        match sim_params.elitism {
+            Elitism::Ratio => format!("ratio {:.0}%", sim_params.elite_ratio * 100.0),
+            Elitism::Count(count) => format!("nombre fixe {}", count),
+        }
+    );
+    info!(
+        "🎯 Tournoi de sélection: {} candidat(s) sur {}",
+        sim_params.tournament_size(),
+        sim_params.simulation_count
     );
 
     let mut sorted_scores: Vec<f32> = genomes.iter().map(|g| g.score).collect();
@@ -200,9 +455,32 @@ fn log_genetic_algorithm_stats(
     }
 }
 
-fn weighted_tournament_selection(population: &[ScoredGenome], rng: &mut impl Rng) -> Genotype {
-    const TOURNAMENT_SIZE: usize = 3;
+/// Divise chaque score par la taille de sa niche (génomes à moins de `radius`, lui compris).
+/// Le dénominateur est plafonné pour qu'une population convergée garde des scores exploitables.
+fn apply_fitness_sharing(genomes: &mut [ScoredGenome], radius: f32) {
+    let niche_sizes: Vec<f32> = genomes
+        .iter()
+        .map(|genome| {
+            genomes
+                .iter()
+                .filter(|other| genome.genotype.genetic_distance(&other.genotype) < radius)
+                .count() as f32
+        })
+        .collect();
+
+    for (genome, niche_size) in genomes.iter_mut().zip(niche_sizes) {
+        genome.score /= niche_size.clamp(1.0, MAX_SHARING_DENOMINATOR);
+    }
+}
 
+/// Tire `tournament_size` candidats (avec remise, pondérés par le rang) et garde le meilleur.
+/// La taille est bornée à la population : au-delà, les tirages supplémentaires ne feraient
+/// que répéter des candidats
+fn weighted_tournament_selection(
+    population: &[ScoredGenome],
+    tournament_size: usize,
+    rng: &mut impl Rng,
+) -> Genotype {
     let weights: Vec<f32> = population
         .iter()
         .enumerate()
@@ -210,17 +488,19 @@ fn weighted_tournament_selection(population: &[ScoredGenome], rng: &mut impl Rng
         .collect();
 
     let mut tournament_indices = Vec::new();
-    for _ in 0..TOURNAMENT_SIZE.min(population.len()) {
-        let total_weight: f32 = weights.iter().sum();
+    let total_weight: f32 = weights.iter().sum();
+    for _ in 0..tournament_size.clamp(1, population.len().max(1)) {
         let mut random = rng.random::<f32>() * total_weight;
 
-        for (i, &weight) in weights.iter().enumerate() {
-            random -= weight;
-            if random <= 0.0 {
-                tournament_indices.push(i);
-                break;
-            }
-        }
+        // Les arrondis peuvent laisser un reste positif : le dernier rang est alors tiré
+        let picked = weights
+            .iter()
+            .position(|&weight| {
+                random -= weight;
+                random <= 0.0
+            })
+            .unwrap_or(weights.len().saturating_sub(1));
+        tournament_indices.push(picked);
     }
 
     tournament_indices
@@ -231,31 +511,14 @@ fn weighted_tournament_selection(population: &[ScoredGenome], rng: &mut impl Rng
         .unwrap_or(population[0].genotype.clone())
 }
 
-fn improved_crossover(parent1: &Genotype, parent2: &Genotype, rng: &mut impl Rng) -> Genotype {
-    let mut new_genotype = Genotype::new(parent1.type_count);
-
-    // Crossover des forces particule-particule
-    for i in 0..parent1.force_matrix.len() {
-        if rng.random_bool(0.5) {
-            new_genotype.force_matrix[i] = parent1.force_matrix[i];
-        } else {
-            new_genotype.force_matrix[i] = parent2.force_matrix[i];
-        }
-    }
-
-    // Crossover des forces de nourriture
-    for i in 0..parent1.food_forces.len() {
-        if rng.random_bool(0.5) {
-            new_genotype.food_forces[i] = parent1.food_forces[i];
-        } else {
-            new_genotype.food_forces[i] = parent2.food_forces[i];
-        }
-    }
-
-    new_genotype
-}
-
-fn calculate_adaptive_mutation_rate(stats: &EpochStats, base_rate: f32, epoch: usize) -> f32 {
+/// `stagnation_handled` : le taux de base a déjà été relevé par `AutoAdapt`, la stagnation
+/// n'est pas comptée une seconde fois
+fn calculate_adaptive_mutation_rate(
+    stats: &EpochStats,
+    base_rate: f32,
+    epoch: usize,
+    stagnation_handled: bool,
+) -> f32 {
     let diversity_factor = if stats.std_deviation < 5.0 {
         2.0
     } else if stats.std_deviation > 20.0 {
@@ -264,13 +527,18 @@ fn calculate_adaptive_mutation_rate(stats: &EpochStats, base_rate: f32, epoch: u
         1.0
     };
 
-    let stagnation_factor = if stats.improvement <= 0.0 { 1.5 } else { 1.0 };
+    let stagnation_factor = if stats.improvement <= 0.0 && !stagnation_handled {
+        1.5
+    } else {
+        1.0
+    };
 
     let early_exploration = if epoch < 10 { 1.5 } else { 1.0 };
 
     (base_rate * diversity_factor * stagnation_factor * early_exploration).min(0.5)
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn reset_simulations_with_new_genomes(
     commands: &mut Commands,
     grid: &GridParameters,
@@ -279,61 +547,72 @@ fn reset_simulations_with_new_genomes(
     food_params: &FoodParameters,
     new_genomes: Vec<Genotype>,
     simulations: &mut Query<
-        (&SimulationId, &mut Genotype, &mut Score, &Children),
+        (&SimulationId, &mut Genotype, &mut Score, &mut Exploration, &Children),
         With<Simulation>,
     >,
-    particles: &mut Query<(&mut Transform, &mut Velocity, &ParticleType), With<Particle>>,
+    particles: &mut Query<
+        (&mut Transform, &mut Velocity, &mut PreviousAcceleration, &ParticleType),
+        With<Particle>,
+    >,
     food_query: &mut Query<
-        (&mut Transform, &mut FoodRespawnTimer, &mut Visibility),
+        (&mut Transform, &mut FoodValue, &mut FoodRespawnTimer, &mut Visibility),
         (With<Food>, Without<Particle>),
     >,
     rng: &mut impl Rng,
 ) {
-    let particles_per_type =
-        (sim_params.particle_count + particle_config.type_count - 1) / particle_config.type_count;
-    let mut particle_positions = Vec::new();
-
-    for particle_type in 0..particle_config.type_count {
-        for _ in 0..particles_per_type {
-            particle_positions.push((particle_type, random_position_in_grid(grid, rng)));
-        }
-    }
-
-    let mut sim_index = 0;
-    for (_, mut genotype, mut score, children) in simulations.iter_mut() {
-        if sim_index < new_genomes.len() {
-            *genotype = new_genomes[sim_index].clone();
+    // Positions tirées par type : l'ordre des enfants ne suit plus `type_sequence` quand des
+    // particules ont été ajoutées en cours de run. Communes à toutes les simulations en
+    // `SharedInitial`, tirées pour chacune sinon
+    let shared_positions = (sim_params.spawn_mode == SpawnMode::SharedInitial)
+        .then(|| random_positions_by_type(particle_config, grid, rng));
+
+    // `new_genomes` est indexé par id de simulation
+    for (sim_id, mut genotype, mut score, mut exploration, children) in simulations.iter_mut() {
+        if let Some(new_genotype) = new_genomes.get(sim_id.0) {
+            *genotype = new_genotype.clone();
         }
 
         *score = Score::default();
-
-        let mut particle_index = 0;
+        exploration.reset();
+
+        let independent_positions;
+        let particle_positions = match &shared_positions {
+            Some(positions) => positions,
+            None => {
+                independent_positions = random_positions_by_type(particle_config, grid, rng);
+                &independent_positions
+            }
+        };
+        let mut next_of_type = vec![0; particle_positions.len()];
         for child in children.iter() {
-            if let Ok((mut transform, mut velocity, particle_type)) = particles.get_mut(child) {
-                if particle_index < particle_positions.len() {
-                    let (expected_type, position) = &particle_positions[particle_index];
-                    if particle_type.0 == *expected_type {
-                        transform.translation = *position;
-                        velocity.0 = Vec3::ZERO;
-                    }
+            if let Ok((mut transform, mut velocity, mut previous_acceleration, particle_type)) =
+                particles.get_mut(child)
+                && let Some(index) = next_of_type.get_mut(particle_type.0)
+            {
+                if let Some(position) = particle_positions[particle_type.0].get(*index) {
+                    transform.translation = *position;
+                    velocity.0 = Vec3::ZERO;
+                    previous_acceleration.0 = None;
                 }
-                particle_index += 1;
+                *index += 1;
             }
         }
-        sim_index += 1;
     }
 
-    let new_food_positions: Vec<Vec3> = (0..food_params.food_count)
-        .map(|_| random_position_in_grid(grid, rng))
-        .collect();
+    let new_food_positions = food_params.generate_positions(grid, rng);
 
     commands.insert_resource(FoodPositions(new_food_positions.clone()));
 
-    for (i, (mut transform, mut respawn_timer, mut visibility)) in food_query.iter_mut().enumerate()
+    for (i, (mut transform, mut food_value, mut respawn_timer, mut visibility)) in
+        food_query.iter_mut().enumerate()
     {
         if i < new_food_positions.len() {
             transform.translation = new_food_positions[i];
+            food_value.0 = food_params.random_value(rng);
             if let Some(ref mut timer) = respawn_timer.0 {
+                timer.set_duration(std::time::Duration::from_secs_f32(
+                    food_params.jittered_cooldown(rng),
+                ));
                 timer.reset();
             }
             *visibility = Visibility::Visible;
@@ -346,15 +625,3 @@ fn reset_simulations_with_new_genomes(
         new_genomes.len()
     );
 }
-
-fn random_position_in_grid(grid: &GridParameters, rng: &mut impl Rng) -> Vec3 {
-    let half_width = grid.width / 2.0;
-    let half_height = grid.height / 2.0;
-    let half_depth = grid.depth / 2.0;
-
-    Vec3::new(
-        rng.random_range(-half_width..half_width),
-        rng.random_range(-half_height..half_height),
-        rng.random_range(-half_depth..half_depth),
-    )
-}
@@ -0,0 +1,132 @@
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use bevy::prelude::*;
+
+/// Grille spatiale uniforme : limite la recherche de voisins aux cellules adjacentes
+pub struct SpatialGrid {
+    dims: [usize; 3],
+    cell_extent: Vec3,
+    half_extents: Vec3,
+    wrap: BVec3,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    /// Crée une grille dont les cellules font au moins `cell_size` de côté
+    pub fn new(grid: &GridParameters, cell_size: f32, boundary_mode: BoundaryMode) -> Self {
+        let size = Vec3::new(grid.width, grid.height, grid.depth);
+        let dims = [
+            ((size.x / cell_size).floor() as usize).max(1),
+            ((size.y / cell_size).floor() as usize).max(1),
+            ((size.z / cell_size).floor() as usize).max(1),
+        ];
+
+        Self {
+            dims,
+            cell_extent: size / Vec3::new(dims[0] as f32, dims[1] as f32, dims[2] as f32),
+            half_extents: size / 2.0,
+            wrap: boundary_mode.wraps(),
+            cells: vec![Vec::new(); dims[0] * dims[1] * dims[2]],
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, position: Vec3) {
+        let cell = self.cell_coords(position);
+        let cell_index = self.cell_index(cell);
+        self.cells[cell_index].push(index);
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Index de la cellule contenant `position` (clé stable pour une grille donnée)
+    pub fn cell_of(&self, position: Vec3) -> usize {
+        self.cell_index(self.cell_coords(position))
+    }
+
+    pub fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    pub fn cell_extent(&self) -> Vec3 {
+        self.cell_extent
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        self.half_extents
+    }
+
+    /// Format à plat pour le GPU : début de chaque cellule (somme préfixe, `cell_count() + 1`
+    /// entrées) suivi des index regroupés par cellule
+    pub fn to_compact(&self) -> Vec<u32> {
+        let item_count: usize = self.cells.iter().map(Vec::len).sum();
+        let mut compact = Vec::with_capacity(self.cells.len() + 1 + item_count);
+
+        let mut start = 0u32;
+        for cell in &self.cells {
+            compact.push(start);
+            start += cell.len() as u32;
+        }
+        compact.push(start);
+
+        for cell in &self.cells {
+            compact.extend(cell.iter().map(|index| *index as u32));
+        }
+        compact
+    }
+
+    /// Appelle `f` pour chaque élément des cellules voisines (bords toriques en mode téléportation)
+    pub fn for_each_neighbor(&self, position: Vec3, mut f: impl FnMut(usize)) {
+        let cell = self.cell_coords(position);
+        let axes = [
+            self.neighbor_cells(cell[0], 0),
+            self.neighbor_cells(cell[1], 1),
+            self.neighbor_cells(cell[2], 2),
+        ];
+
+        for &x in &axes[0] {
+            for &y in &axes[1] {
+                for &z in &axes[2] {
+                    for &index in &self.cells[self.cell_index([x, y, z])] {
+                        f(index);
+                    }
+                }
+            }
+        }
+    }
+
+    fn cell_coords(&self, position: Vec3) -> [usize; 3] {
+        let local = (position + self.half_extents) / self.cell_extent;
+        [
+            (local.x.floor().max(0.0) as usize).min(self.dims[0] - 1),
+            (local.y.floor().max(0.0) as usize).min(self.dims[1] - 1),
+            (local.z.floor().max(0.0) as usize).min(self.dims[2] - 1),
+        ]
+    }
+
+    fn cell_index(&self, cell: [usize; 3]) -> usize {
+        (cell[0] * self.dims[1] + cell[1]) * self.dims[2] + cell[2]
+    }
+
+    /// Cellules adjacentes sur un axe, sans doublon quand l'axe compte moins de 3 cellules
+    fn neighbor_cells(&self, cell: usize, axis: usize) -> Vec<usize> {
+        let dim = self.dims[axis];
+        let mut cells = Vec::with_capacity(3);
+        for offset in [-1isize, 0, 1] {
+            let neighbor = cell as isize + offset;
+            let neighbor = if self.wrap.test(axis) {
+                neighbor.rem_euclid(dim as isize) as usize
+            } else if neighbor < 0 || neighbor >= dim as isize {
+                continue;
+            } else {
+                neighbor as usize
+            };
+
+            if !cells.contains(&neighbor) {
+                cells.push(neighbor);
+            }
+        }
+        cells
+    }
+}
@@ -1,12 +1,14 @@
 use crate::components::entities::food::{Food, FoodRespawnTimer, FoodValue};
 use crate::components::entities::particle::{Particle, ParticleType};
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
 use crate::components::genetics::score::Score;
-use crate::globals::*;
 use crate::resources::config::food::FoodParameters;
 use crate::resources::config::particle_types::ParticleTypesConfig;
-use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::seed::SimulationRng;
+use crate::resources::config::simulation::{SimulationParameters, SpawnMode};
 use crate::resources::world::grid::GridParameters;
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
@@ -20,7 +22,12 @@ pub struct FoodPositions(pub Vec<Vec3>);
 #[derive(Resource, Default)]
 pub struct EntitiesSpawned(pub bool);
 
+/// Génomes de départ imposés pour le prochain run (consommés au spawn)
+#[derive(Resource, Default)]
+pub struct SeedGenomes(pub Vec<Genotype>);
+
 /// Spawn toutes les simulations avec leurs particules (première fois uniquement)
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_simulations_with_particles(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -28,7 +35,11 @@ pub fn spawn_simulations_with_particles(
     grid: Res<GridParameters>,
     particle_config: Res<ParticleTypesConfig>,
     simulation_params: Res<SimulationParameters>,
+    render_quality: Res<RenderQuality>,
+    render_style: Res<RenderStyle>,
+    mut sim_rng: ResMut<SimulationRng>,
     mut entities_spawned: ResMut<EntitiesSpawned>,
+    seed_genomes: Option<Res<SeedGenomes>>,
     existing_simulations: Query<Entity, With<Simulation>>,
 ) {
     // Si les entités ont déjà été créées, on ne fait rien
@@ -36,56 +47,47 @@ pub fn spawn_simulations_with_particles(
         return;
     }
 
-    let mut rng = rand::rng();
+    let rng = &mut sim_rng.0;
 
-    // Créer un mesh partagé pour toutes les particules
-    let particle_mesh = meshes.add(
-        Sphere::new(PARTICLE_RADIUS)
-            .mesh()
-            .ico(PARTICLE_SUBDIVISIONS)
-            .unwrap(),
-    );
+    // Créer un mesh partagé pour toutes les particules (mis à l'échelle du rayon de chaque type)
+    let particle_mesh = meshes.add(render_quality.particle_mesh());
 
     // Créer les matériaux pour chaque type avec émissive
     let particle_materials: Vec<_> = (0..particle_config.type_count)
         .map(|i| {
             let (base_color, emissive) = particle_config.get_color_for_type(i);
-            materials.add(StandardMaterial {
-                base_color,
-                emissive,
-                unlit: true,
-                ..default()
-            })
+            materials.add(render_style.material(base_color, emissive))
         })
         .collect();
 
-    // Calculer le nombre de particules par type (arrondi vers le haut)
-    let particles_per_type = (simulation_params.particle_count + particle_config.type_count - 1)
-        / particle_config.type_count;
-    let actual_particle_count = particles_per_type * particle_config.type_count;
-
-    // Ajuster le nombre total si nécessaire
-    if actual_particle_count != simulation_params.particle_count {
-        info!(
-            "Ajustement du nombre de particules de {} à {} pour une répartition équitable",
-            simulation_params.particle_count, actual_particle_count
-        );
-    }
-
-    // Générer les positions initiales pour toutes les particules
-    // Ces positions seront les mêmes pour toutes les simulations
-    let mut initial_positions = Vec::new();
-
-    for particle_type in 0..particle_config.type_count {
-        for _ in 0..particles_per_type {
-            initial_positions.push((particle_type, random_position_in_grid(&grid, &mut rng)));
-        }
-    }
+    // Positions initiales selon la répartition par type : tirées une fois pour toutes les
+    // simulations en `SharedInitial`, une fois par simulation sinon
+    let shared_positions = (simulation_params.spawn_mode == SpawnMode::SharedInitial)
+        .then(|| random_positions_by_type(&particle_config, &grid, rng));
 
     // Pour chaque simulation
     for sim_id in 0..simulation_params.simulation_count {
-        // Créer un génome avec le bon nombre de types
-        let genotype = Genotype::random(particle_config.type_count);
+        // Génome imposé s'il y en a un de la bonne taille, sinon aléatoire
+        let genotype = seed_genomes
+            .as_ref()
+            .and_then(|seeds| seeds.0.get(sim_id))
+            .filter(|genotype| genotype.type_count == particle_config.type_count)
+            .cloned()
+            .unwrap_or_else(|| {
+                Genotype::random(
+                    particle_config.type_count,
+                    simulation_params.allow_self_attraction,
+                    rng,
+                )
+            });
+        let independent_positions;
+        let positions = match &shared_positions {
+            Some(positions) => positions,
+            None => {
+                independent_positions = random_positions_by_type(&particle_config, &grid, rng);
+                &independent_positions
+            }
+        };
 
         // Spawn la simulation avec son RenderLayer
         commands
@@ -94,72 +96,76 @@ pub fn spawn_simulations_with_particles(
                 SimulationId(sim_id),
                 genotype,
                 Score::default(),
+                ForceRange(simulation_params.max_force_range),
                 // Assigner le RenderLayer à la simulation (layer sim_id + 1)
                 RenderLayers::layer(sim_id + 1),
             ))
             .with_children(|parent| {
-                // Spawn toutes les particules comme enfants avec les positions communes
-                for (particle_type, position) in &initial_positions {
-                    parent.spawn((
-                        Particle,
-                        ParticleType(*particle_type),
-                        Transform::from_translation(*position),
-                        Mesh3d(particle_mesh.clone()),
-                        MeshMaterial3d(particle_materials[*particle_type].clone()),
-                        // Les particules héritent automatiquement du RenderLayer du parent
-                        RenderLayers::layer(sim_id + 1),
-                    ));
+                // Spawn toutes les particules comme enfants, type par type
+                for (particle_type, type_positions) in positions.iter().enumerate() {
+                    for position in type_positions {
+                        parent.spawn((
+                            Particle,
+                            ParticleType(particle_type),
+                            Transform::from_translation(*position)
+                                .with_scale(particle_config.mesh_scale_for(particle_type)),
+                            Mesh3d(particle_mesh.clone()),
+                            MeshMaterial3d(particle_materials[particle_type].clone()),
+                            // Les particules héritent automatiquement du RenderLayer du parent
+                            RenderLayers::layer(sim_id + 1),
+                        ));
+                    }
                 }
             });
     }
 
+    if let Some(seeds) = seed_genomes {
+        info!("{} génome(s) de départ imposé(s)", seeds.0.len());
+        commands.remove_resource::<SeedGenomes>();
+    }
+
     // Marquer que les entités ont été créées
     entities_spawned.0 = true;
     info!(
-        "Création initiale des {} simulations avec {} particules chacune ({} par type)",
-        simulation_params.simulation_count, actual_particle_count, particles_per_type
+        "Création initiale des {} simulations avec {} particules chacune ({:?} par type, {})",
+        simulation_params.simulation_count,
+        particle_config.counts_per_type.iter().sum::<usize>(),
+        particle_config.counts_per_type,
+        simulation_params.spawn_mode.label().to_lowercase()
     );
 }
 
 /// Spawn la nourriture (première fois uniquement)
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_food(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     grid: Res<GridParameters>,
     food_params: Res<FoodParameters>,
+    render_quality: Res<RenderQuality>,
+    render_style: Res<RenderStyle>,
+    mut sim_rng: ResMut<SimulationRng>,
     existing_food: Query<Entity, With<Food>>,
 ) {
     if !existing_food.is_empty() {
         return;
     }
 
-    let mut rng = rand::rng();
+    let rng = &mut sim_rng.0;
 
-    let food_mesh = meshes.add(
-        Sphere::new(FOOD_RADIUS)
-            .mesh()
-            .ico(PARTICLE_SUBDIVISIONS)
-            .unwrap(),
-    );
+    let food_mesh = meshes.add(render_quality.food_mesh());
 
-    let food_material = materials.add(StandardMaterial {
-        base_color: Color::WHITE,
-        emissive: LinearRgba::WHITE,
-        unlit: true,
-        ..default()
-    });
+    let food_material = materials.add(render_style.material(Color::WHITE, LinearRgba::WHITE));
 
-    let food_positions: Vec<Vec3> = (0..food_params.food_count)
-        .map(|_| random_position_in_grid(&grid, &mut rng))
-        .collect();
+    let food_positions = food_params.generate_positions(&grid, rng);
 
     commands.insert_resource(FoodPositions(food_positions.clone()));
 
     for position in food_positions {
         let respawn_timer = if food_params.respawn_enabled {
             Some(Timer::from_seconds(
-                food_params.respawn_cooldown,
+                food_params.jittered_cooldown(rng),
                 TimerMode::Once,
             ))
         } else {
@@ -168,7 +174,7 @@ pub fn spawn_food(
 
         commands.spawn((
             Food,
-            FoodValue(food_params.food_value),
+            FoodValue(food_params.random_value(rng)),
             FoodRespawnTimer(respawn_timer),
             Transform::from_translation(position),
             Mesh3d(food_mesh.clone()),
@@ -184,7 +190,7 @@ pub fn spawn_food(
 }
 
 /// Génère une position aléatoire dans la grille
-fn random_position_in_grid(grid: &GridParameters, rng: &mut impl Rng) -> Vec3 {
+pub fn random_position_in_grid(grid: &GridParameters, rng: &mut impl Rng) -> Vec3 {
     let half_width = grid.width / 2.0;
     let half_height = grid.height / 2.0;
     let half_depth = grid.depth / 2.0;
@@ -195,3 +201,17 @@ fn random_position_in_grid(grid: &GridParameters, rng: &mut impl Rng) -> Vec3 {
         rng.random_range(-half_depth..half_depth),
     )
 }
+
+/// Positions aléatoires de toutes les particules, regroupées par type dans l'ordre de
+/// `type_sequence`
+pub fn random_positions_by_type(
+    particle_config: &ParticleTypesConfig,
+    grid: &GridParameters,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec3>> {
+    particle_config
+        .counts_per_type
+        .iter()
+        .map(|&count| (0..count).map(|_| random_position_in_grid(grid, rng)).collect())
+        .collect()
+}
@@ -2,61 +2,52 @@ use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use rand::Rng;
 use crate::components::entities::particle::{Particle, ParticleType};
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::score::Score;
-use crate::globals::*;
 use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::seed::SimulationRng;
 use crate::resources::config::simulation::SimulationParameters;
 use crate::resources::world::grid::GridParameters;
 use crate::ui::menus::visualizer_menu::VisualizerGenome;
 
 /// Spawn une seule simulation avec le génome spécifique du visualiseur
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_visualizer_simulation(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     grid: Res<GridParameters>,
     particle_config: Res<ParticleTypesConfig>,
-    simulation_params: Res<SimulationParameters>,
+    sim_params: Res<SimulationParameters>,
+    render_quality: Res<RenderQuality>,
+    render_style: Res<RenderStyle>,
     visualizer_genome: Res<VisualizerGenome>,
+    mut sim_rng: ResMut<SimulationRng>,
     existing_simulations: Query<Entity, With<Simulation>>,
 ) {
     if !existing_simulations.is_empty() {
         return;
     }
 
-    let mut rng = rand::rng();
+    let rng = &mut sim_rng.0;
 
     // Mesh et matériaux pour les particules
-    let particle_mesh = meshes.add(
-        Sphere::new(PARTICLE_RADIUS)
-            .mesh()
-            .ico(PARTICLE_SUBDIVISIONS)
-            .unwrap(),
-    );
+    let particle_mesh = meshes.add(render_quality.particle_mesh());
 
     let particle_materials: Vec<_> = (0..particle_config.type_count)
         .map(|i| {
             let (base_color, emissive) = particle_config.get_color_for_type(i);
-            materials.add(StandardMaterial {
-                base_color,
-                emissive,
-                unlit: true,
-                ..default()
-            })
+            materials.add(render_style.material(base_color, emissive))
         })
         .collect();
 
     // Calculer les positions initiales
-    let particles_per_type = (simulation_params.particle_count + particle_config.type_count - 1)
-        / particle_config.type_count;
-    let mut initial_positions = Vec::new();
-
-    for particle_type in 0..particle_config.type_count {
-        for _ in 0..particles_per_type {
-            initial_positions.push((particle_type, random_position_in_grid(&grid, &mut rng)));
-        }
-    }
+    let initial_positions: Vec<(usize, Vec3)> = particle_config
+        .type_sequence()
+        .map(|particle_type| (particle_type, random_position_in_grid(&grid, rng)))
+        .collect();
 
     // Spawn la simulation unique avec le génome du visualiseur
     commands
@@ -65,6 +56,7 @@ pub fn spawn_visualizer_simulation(
             SimulationId(0),             
             visualizer_genome.0.clone(), 
             Score::default(),
+            ForceRange(sim_params.max_force_range),
             RenderLayers::layer(1),
         ))
         .with_children(|parent| {
@@ -72,7 +64,8 @@ pub fn spawn_visualizer_simulation(
                 parent.spawn((
                     Particle,
                     ParticleType(*particle_type),
-                    Transform::from_translation(*position),
+                    Transform::from_translation(*position)
+                            .with_scale(particle_config.mesh_scale_for(*particle_type)),
                     Mesh3d(particle_mesh.clone()),
                     MeshMaterial3d(particle_materials[*particle_type].clone()),
                     RenderLayers::layer(1),
@@ -1,7 +1,9 @@
 use crate::components::entities::simulation::{Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
 use crate::components::genetics::score::Score;
-use crate::systems::persistence::population_save::{PopulationSaveEvents, PopulationSaveRequest};
+use crate::systems::persistence::population_save::{
+    SavePopulationEvent, parse_tags,
+};
 use crate::ui::panels::force_matrix::ForceMatrixUI;
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
@@ -12,13 +14,14 @@ pub struct SavePopulationUI {
     pub simulation_to_save: Option<usize>,
     pub save_name: String,
     pub save_description: String,
+    pub save_tags: String,
     pub save_in_progress: bool,
 }
 
 pub fn save_population_ui(
     mut contexts: EguiContexts,
     mut save_ui: ResMut<SavePopulationUI>,
-    mut save_events: ResMut<PopulationSaveEvents>,
+    mut save_events: EventWriter<SavePopulationEvent>,
     simulations: Query<(&SimulationId, &Score, &Genotype), With<Simulation>>,
 ) {
     let ctx = contexts.ctx_mut();
@@ -32,84 +35,92 @@ pub fn save_population_ui(
             .default_width(400.0)
             .open(&mut is_open)
             .show(ctx, |ui| {
-                if let Some(sim_id) = save_ui.simulation_to_save {
-                    if let Some((_, score, genotype)) = simulations
+                if let Some(sim_id) = save_ui.simulation_to_save
+                    && let Some((_, score, genotype)) = simulations
                         .iter()
                         .find(|(simulation_id, _, _)| simulation_id.0 == sim_id)
-                    {
-                        ui.group(|ui| {
-                            ui.label(
-                                egui::RichText::new(format!("Simulation #{}", sim_id + 1))
-                                    .size(16.0)
-                                    .strong(),
-                            );
-                            ui.label(format!("Score actuel: {:.1}", score.get()));
-                            ui.label(format!("Types de particules: {}", genotype.type_count));
-                            ui.label(format!(
-                                "Forces particule-particule: {}",
-                                genotype.force_matrix.len()
-                            ));
-                            ui.label(format!("Forces nourriture: {}", genotype.food_forces.len()));
-                        });
+                {
+                    ui.group(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("Simulation #{}", sim_id + 1))
+                                .size(16.0)
+                                .strong(),
+                        );
+                        ui.label(format!("Score actuel: {:.1}", score.get()));
+                        ui.label(format!("Types de particules: {}", genotype.type_count));
+                        ui.label(format!(
+                            "Forces particule-particule: {}",
+                            genotype.force_matrix.len()
+                        ));
+                        ui.label(format!("Forces nourriture: {}", genotype.food_forces.len()));
+                    });
 
-                        ui.separator();
+                    ui.separator();
 
-                        ui.label("Nom de la population *");
-                        ui.text_edit_singleline(&mut save_ui.save_name);
+                    ui.label("Nom de la population *");
+                    ui.text_edit_singleline(&mut save_ui.save_name);
 
-                        if save_ui.save_name.trim().is_empty() {
-                            ui.label(
-                                egui::RichText::new("Le nom est obligatoire")
-                                    .color(egui::Color32::RED)
-                                    .small(),
-                            );
-                        }
+                    if save_ui.save_name.trim().is_empty() {
+                        ui.label(
+                            egui::RichText::new("Le nom est obligatoire")
+                                .color(egui::Color32::RED)
+                                .small(),
+                        );
+                    }
 
-                        ui.add_space(10.0);
+                    ui.add_space(10.0);
 
-                        ui.label("Description (optionnelle)");
-                        ui.text_edit_multiline(&mut save_ui.save_description);
+                    ui.label("Description (optionnelle)");
+                    ui.text_edit_multiline(&mut save_ui.save_description);
 
-                        ui.add_space(10.0);
+                    ui.add_space(10.0);
 
-                        ui.horizontal(|ui| {
-                            let can_save =
-                                !save_ui.save_name.trim().is_empty() && !save_ui.save_in_progress;
+                    ui.label("Tags (séparés par des virgules)");
+                    ui.text_edit_singleline(&mut save_ui.save_tags);
 
-                            if ui
-                                .add_enabled(can_save, egui::Button::new("💾 Sauvegarder"))
-                                .clicked()
-                            {
-                                save_events.save_requests.push(PopulationSaveRequest {
-                                    simulation_id: sim_id,
-                                    name: save_ui.save_name.trim().to_string(),
-                                    description: if save_ui.save_description.trim().is_empty() {
-                                        None
-                                    } else {
-                                        Some(save_ui.save_description.trim().to_string())
-                                    },
-                                });
-
-                                save_ui.save_in_progress = true;
-                                save_ui.show_save_dialog = false;
-                                save_ui.simulation_to_save = None;
-                                save_ui.save_name.clear();
-                                save_ui.save_description.clear();
-                                save_ui.save_in_progress = false;
-                            }
-
-                            if ui.button("❌ Annuler").clicked() {
-                                save_ui.show_save_dialog = false;
-                                save_ui.simulation_to_save = None;
-                                save_ui.save_name.clear();
-                                save_ui.save_description.clear();
-                            }
-                        });
-
-                        if save_ui.save_in_progress {
-                            ui.add_space(5.0);
-                            ui.label("Sauvegarde en cours...");
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        let can_save =
+                            !save_ui.save_name.trim().is_empty() && !save_ui.save_in_progress;
+
+                        if ui
+                            .add_enabled(can_save, egui::Button::new("💾 Sauvegarder"))
+                            .clicked()
+                        {
+                            save_events.write(SavePopulationEvent {
+                                simulation_id: sim_id,
+                                name: save_ui.save_name.trim().to_string(),
+                                description: if save_ui.save_description.trim().is_empty() {
+                                    None
+                                } else {
+                                    Some(save_ui.save_description.trim().to_string())
+                                },
+                                tags: parse_tags(&save_ui.save_tags),
+                                genome: None,
+                            });
+
+                            save_ui.save_in_progress = true;
+                            save_ui.show_save_dialog = false;
+                            save_ui.simulation_to_save = None;
+                            save_ui.save_name.clear();
+                            save_ui.save_description.clear();
+                            save_ui.save_tags.clear();
+                            save_ui.save_in_progress = false;
+                        }
+
+                        if ui.button("❌ Annuler").clicked() {
+                            save_ui.show_save_dialog = false;
+                            save_ui.simulation_to_save = None;
+                            save_ui.save_name.clear();
+                            save_ui.save_description.clear();
+                            save_ui.save_tags.clear();
                         }
+                    });
+
+                    if save_ui.save_in_progress {
+                        ui.add_space(5.0);
+                        ui.label("Sauvegarde en cours...");
                     }
                 }
             });
@@ -119,10 +130,53 @@ pub fn save_population_ui(
             save_ui.simulation_to_save = None;
             save_ui.save_name.clear();
             save_ui.save_description.clear();
+            save_ui.save_tags.clear();
         }
     }
 }
 
+/// Mini-courbe (60×20 px) de l'historique de fitness d'un génome : verte s'il progresse,
+/// rouge sinon
+fn fitness_sparkline(ui: &mut egui::Ui, genotype: &Genotype) {
+    let size = egui::vec2(60.0, 20.0);
+    let history = &genotype.fitness_history;
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+    let Some(trend) = genotype.fitness_trend() else {
+        response.on_hover_text("Pas encore d'historique (moins de deux époques)");
+        return;
+    };
+    let color = if trend > 0.0 {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else {
+        egui::Color32::from_rgb(220, 100, 100)
+    };
+
+    let min = history.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = history.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let span = (max - min).max(f32::EPSILON);
+    let last = (history.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = history
+        .iter()
+        .enumerate()
+        .map(|(epoch, &score)| {
+            egui::pos2(
+                rect.left() + rect.width() * epoch as f32 / last,
+                rect.bottom() - rect.height() * (score - min) / span,
+            )
+        })
+        .collect();
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+
+    response.on_hover_text(format!(
+        "{} époques : {:.1} → {:.1} (tendance {:+.2} / époque)",
+        history.len(),
+        history[0],
+        history[history.len() - 1],
+        trend
+    ));
+}
+
 pub fn simulations_list_ui(
     mut contexts: EguiContexts,
     mut ui_state: ResMut<ForceMatrixUI>,
@@ -137,7 +191,7 @@ pub fn simulations_list_ui(
         return;
     }
 
-    let panel_width = 400.0;
+    let panel_width = 440.0;
 
     egui::SidePanel::right("simulations_panel")
         .exact_width(panel_width)
@@ -154,6 +208,9 @@ pub fn simulations_list_ui(
                 if ui.button("Tout désélectionner").clicked() {
                     ui_state.selected_simulations.clear();
                 }
+                if !ui_state.soloed.is_empty() && ui.button("Fin du solo").clicked() {
+                    ui_state.soloed.clear();
+                }
             });
 
             ui.separator();
@@ -163,7 +220,7 @@ pub fn simulations_list_ui(
 
             egui::ScrollArea::vertical().show(ui, |ui| {
                 egui::Grid::new("simulations_grid")
-                    .num_columns(5)
+                    .num_columns(6)
                     .spacing([15.0, 5.0])
                     .striped(true)
                     .min_col_width(40.0)
@@ -171,6 +228,7 @@ pub fn simulations_list_ui(
                         ui.label(egui::RichText::new("Vue").strong());
                         ui.label(egui::RichText::new("Simulation").strong());
                         ui.label(egui::RichText::new("Score").strong());
+                        ui.label(egui::RichText::new("Historique").strong());
                         ui.label(egui::RichText::new("Matrice").strong());
                         ui.label(egui::RichText::new("Sauvegarder").strong());
                         ui.end_row();
@@ -180,26 +238,50 @@ pub fn simulations_list_ui(
                         ui.separator();
                         ui.separator();
                         ui.separator();
+                        ui.separator();
                         ui.end_row();
 
-                        for (sim_id, score, _genotype) in sim_list {
+                        for (sim_id, score, genotype) in sim_list {
                             let is_selected_for_matrix =
                                 ui_state.selected_simulation == Some(sim_id.0);
 
-                            ui.with_layout(
-                                egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
-                                |ui| {
-                                    let mut is_selected_for_view =
-                                        ui_state.selected_simulations.contains(&sim_id.0);
-                                    if ui.checkbox(&mut is_selected_for_view, "").changed() {
-                                        if is_selected_for_view {
-                                            ui_state.selected_simulations.insert(sim_id.0);
-                                        } else {
-                                            ui_state.selected_simulations.remove(&sim_id.0);
-                                        }
+                            ui.horizontal(|ui| {
+                                let mut is_selected_for_view =
+                                    ui_state.selected_simulations.contains(&sim_id.0);
+                                if ui.checkbox(&mut is_selected_for_view, "").changed() {
+                                    if is_selected_for_view {
+                                        ui_state.selected_simulations.insert(sim_id.0);
+                                    } else {
+                                        ui_state.selected_simulations.remove(&sim_id.0);
                                     }
-                                },
-                            );
+                                }
+
+                                let soloed = ui_state.soloed.contains(&sim_id.0);
+                                if ui
+                                    .selectable_label(soloed, "S")
+                                    .on_hover_text("Solo : n'afficher que les simulations en solo")
+                                    .clicked()
+                                {
+                                    if soloed {
+                                        ui_state.soloed.remove(&sim_id.0);
+                                    } else {
+                                        ui_state.soloed.insert(sim_id.0);
+                                    }
+                                }
+
+                                let muted = ui_state.muted.contains(&sim_id.0);
+                                if ui
+                                    .selectable_label(muted, "M")
+                                    .on_hover_text("Masquer ce viewport sans le désélectionner")
+                                    .clicked()
+                                {
+                                    if muted {
+                                        ui_state.muted.remove(&sim_id.0);
+                                    } else {
+                                        ui_state.muted.insert(sim_id.0);
+                                    }
+                                }
+                            });
 
                             ui.with_layout(
                                 egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
@@ -240,6 +322,8 @@ pub fn simulations_list_ui(
                                 },
                             );
 
+                            fitness_sparkline(ui, genotype);
+
                             ui.with_layout(
                                 egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                                 |ui| {
@@ -262,6 +346,7 @@ pub fn simulations_list_ui(
                                         save_ui.simulation_to_save = Some(sim_id.0);
                                         save_ui.save_name = format!("Population_{}", sim_id.0 + 1);
                                         save_ui.save_description.clear();
+                                        save_ui.save_tags.clear();
                                     }
                                 },
                             );
@@ -272,10 +357,18 @@ pub fn simulations_list_ui(
             });
 
             ui.separator();
-            ui.label(format!(
-                "{} vue(s) active(s)",
-                ui_state.selected_simulations.len()
-            ));
+            let visible_count = ui_state.visible_simulations().len();
+            if ui_state.soloed.is_empty() && ui_state.muted.is_empty() {
+                ui.label(format!("{} vue(s) active(s)", visible_count));
+            } else {
+                ui.label(format!(
+                    "{} vue(s) active(s) sur {} sélectionnée(s) ({} solo, {} masquée(s))",
+                    visible_count,
+                    ui_state.selected_simulations.len(),
+                    ui_state.soloed.len(),
+                    ui_state.muted.len()
+                ));
+            }
         });
 
     ui_space.right_panel_width = panel_width;
@@ -1,14 +1,32 @@
+use crate::components::genetics::genotype::Genotype;
 use crate::globals::*;
 use crate::plugins::simulation::compute::ComputeEnabled;
-use crate::resources::config::food::FoodParameters;
+use crate::resources::config::food::{FoodDistribution, FoodParameters, FoodRespawnMode};
 use crate::resources::config::particle_types::ParticleTypesConfig;
-use crate::resources::config::simulation::{SimulationParameters, SimulationSpeed};
-use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::seed::{SimulationRng, SimulationSeed, random_seed};
+use crate::resources::config::simulation::{
+    AutoSaveOnFinish, CrossoverStrategy, Elitism, ForceCurve, Integrator, MutationKind, SimulationParameters, SimulationSpeed, SpawnMode,
+};
+use crate::resources::genetics::auto_adapt::AutoAdapt;
+use crate::resources::genetics::diversity_guard::{CollapseAction, DiversityGuard};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::genetics::genome_archive::GenomeArchive;
+use crate::resources::genetics::hall_of_fame::HallOfFame;
+use crate::resources::genetics::history::EvolutionHistory;
+use crate::resources::world::boundary::{AxisBoundary, BoundaryMode};
 use crate::resources::world::grid::GridParameters;
 use crate::states::app::AppState;
 use crate::systems::persistence::population_save::*;
+use crate::systems::persistence::run_config::{
+    RunConfig, list_run_configs, load_run_config, run_configs_dir, save_run_config,
+};
+use crate::systems::persistence::run_report::RunSummary;
+use crate::systems::simulation::spawning::SeedGenomes;
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
+use std::path::PathBuf;
 
 /// Configuration temporaire pour le menu
 #[derive(Resource)]
@@ -22,26 +40,72 @@ pub struct MenuConfig {
     pub simulation_count: usize,
     pub particle_count: usize,
     pub particle_types: usize,
+    /// Nombre de particules par type, la somme donne `particle_count`
+    pub counts_per_type: Vec<usize>,
+    /// Couleur de base de chaque type
+    pub type_colors: Vec<Color>,
+    /// Rayon et masse de chaque type
+    pub type_radii: Vec<f32>,
+    pub type_masses: Vec<f32>,
     pub epoch_duration: f32,
     pub max_epochs: usize,
+    pub auto_save_on_finish: bool,
     pub max_force_range: f32,
+    pub velocity_half_life: f32,
+    pub max_velocity: f32,
+    pub integrator: Integrator,
+    pub force_curve: ForceCurve,
+    /// Distance de répulsion imposée, `None` pour `particle_types × PARTICLE_RADIUS`
+    pub min_distance: Option<f32>,
+    pub collision_damping: f32,
+    pub physics_timestep: f32,
+    pub max_interactions_per_particle: usize,
+    pub force_scale_factor: f32,
+    pub seed: u64,
 
     // Paramètres de nourriture
     pub food_count: usize,
     pub food_respawn_enabled: bool,
     pub food_respawn_time: f32,
+    pub food_respawn_jitter: f32,
+    pub food_respawn_mode: FoodRespawnMode,
     pub food_value: f32,
+    pub food_value_max: Option<f32>,
+    pub food_distribution: FoodDistribution,
 
     // Mode de bords
     pub boundary_mode: BoundaryMode,
 
     // GPU compute
     pub use_gpu: bool,
+    pub render_quality: RenderQuality,
+    pub render_style: RenderStyle,
 
     // Paramètres génétiques
+    pub elitism: Elitism,
     pub elite_ratio: f32,
     pub mutation_rate: f32,
+    pub elite_mutation_rate: f32,
     pub crossover_rate: f32,
+    pub tournament_size: usize,
+    pub mutation_kind: MutationKind,
+    pub crossover_strategy: CrossoverStrategy,
+    pub sharing_enabled: bool,
+    pub sharing_radius: f32,
+    pub hall_of_fame_size: usize,
+    pub hall_of_fame_reinject: bool,
+    pub genome_archive_depth: usize,
+    /// Toutes les simulations partent de `Genotype::interesting` au lieu d'un génome aléatoire
+    pub seed_interesting_forces: bool,
+    /// Premières simulations partant des meilleurs génomes sauvegardés compatibles (0 = aucune)
+    pub library_seed_count: usize,
+    /// Forces d'un type sur lui-même tirées dans tout [-1, 1]
+    pub allow_self_attraction: bool,
+    pub inherit_fitness_history: bool,
+    pub spawn_mode: SpawnMode,
+    pub fitness_weights: FitnessWeights,
+    pub auto_adapt: AutoAdapt,
+    pub diversity_guard: DiversityGuard,
 }
 
 impl Default for MenuConfig {
@@ -54,21 +118,87 @@ impl Default for MenuConfig {
             simulation_count: DEFAULT_SIMULATION_COUNT,
             particle_count: DEFAULT_PARTICLE_COUNT,
             particle_types: DEFAULT_PARTICLE_TYPES,
+            counts_per_type: ParticleTypesConfig::even_split(
+                DEFAULT_PARTICLE_COUNT,
+                DEFAULT_PARTICLE_TYPES,
+            ),
+            type_colors: (0..DEFAULT_PARTICLE_TYPES)
+                .map(|index| ParticleTypesConfig::default_color(index, DEFAULT_PARTICLE_TYPES))
+                .collect(),
+            type_radii: vec![PARTICLE_RADIUS; DEFAULT_PARTICLE_TYPES],
+            type_masses: vec![PARTICLE_MASS; DEFAULT_PARTICLE_TYPES],
             epoch_duration: DEFAULT_EPOCH_DURATION,
             max_epochs: 100,
+            auto_save_on_finish: false,
             max_force_range: DEFAULT_MAX_FORCE_RANGE,
+            velocity_half_life: DEFAULT_VELOCITY_HALF_LIFE,
+            max_velocity: DEFAULT_MAX_VELOCITY,
+            integrator: Integrator::default(),
+            force_curve: ForceCurve::default(),
+            min_distance: None,
+            collision_damping: DEFAULT_COLLISION_DAMPING,
+            physics_timestep: PHYSICS_TIMESTEP,
+            max_interactions_per_particle: DEFAULT_MAX_INTERACTIONS,
+            force_scale_factor: FORCE_SCALE_FACTOR,
+            seed: random_seed(),
 
             food_count: DEFAULT_FOOD_COUNT,
             food_respawn_enabled: true,
             food_respawn_time: DEFAULT_FOOD_RESPAWN_TIME,
+            food_respawn_jitter: 0.0,
+            food_respawn_mode: FoodRespawnMode::default(),
             food_value: DEFAULT_FOOD_VALUE,
+            food_value_max: None,
+            food_distribution: FoodDistribution::default(),
 
             boundary_mode: BoundaryMode::default(),
             use_gpu: false,
+            render_quality: RenderQuality::default(),
+            render_style: RenderStyle::default(),
 
+            elitism: Elitism::default(),
             elite_ratio: DEFAULT_ELITE_RATIO,
             mutation_rate: DEFAULT_MUTATION_RATE,
+            elite_mutation_rate: DEFAULT_ELITE_MUTATION_RATE,
             crossover_rate: DEFAULT_CROSSOVER_RATE,
+            tournament_size: DEFAULT_TOURNAMENT_SIZE,
+            mutation_kind: MutationKind::default(),
+            crossover_strategy: CrossoverStrategy::default(),
+            sharing_enabled: false,
+            sharing_radius: DEFAULT_SHARING_RADIUS,
+            hall_of_fame_size: DEFAULT_HALL_OF_FAME_SIZE,
+            hall_of_fame_reinject: false,
+            genome_archive_depth: DEFAULT_GENOME_ARCHIVE_DEPTH,
+            seed_interesting_forces: false,
+            library_seed_count: 0,
+            allow_self_attraction: false,
+            inherit_fitness_history: false,
+            spawn_mode: SpawnMode::default(),
+            fitness_weights: FitnessWeights::default(),
+            auto_adapt: AutoAdapt::default(),
+            diversity_guard: DiversityGuard::default(),
+        }
+    }
+}
+
+/// État du panneau d'export/import de configuration de run
+#[derive(Resource)]
+pub struct RunConfigUI {
+    pub file_name: String,
+    /// Fichiers proposés au chargement, rafraîchis à la demande
+    pub files: Vec<PathBuf>,
+    pub selection: Option<PathBuf>,
+    /// Résultat de la dernière opération (message, succès)
+    pub status: Option<(String, bool)>,
+}
+
+impl Default for RunConfigUI {
+    fn default() -> Self {
+        Self {
+            file_name: "experience".to_string(),
+            files: list_run_configs(),
+            selection: None,
+            status: None,
         }
     }
 }
@@ -79,6 +209,7 @@ pub fn main_menu_ui(
     mut next_state: ResMut<NextState<AppState>>,
     mut commands: Commands,
     mut available_populations: ResMut<AvailablePopulations>,
+    mut run_config_ui: ResMut<RunConfigUI>,
 ) {
     let ctx = contexts.ctx_mut();
 
@@ -121,7 +252,7 @@ pub fn main_menu_ui(
                         ui.label("Largeur:");
                         ui.add(
                             egui::DragValue::new(&mut menu_config.grid_width)
-                                .range(100.0..=2000.0)
+                                .range(MIN_GRID_SIZE..=MAX_GRID_SIZE)
                                 .suffix(" unités"),
                         );
                         ui.end_row();
@@ -129,7 +260,7 @@ pub fn main_menu_ui(
                         ui.label("Hauteur:");
                         ui.add(
                             egui::DragValue::new(&mut menu_config.grid_height)
-                                .range(100.0..=2000.0)
+                                .range(MIN_GRID_SIZE..=MAX_GRID_SIZE)
                                 .suffix(" unités"),
                         );
                         ui.end_row();
@@ -137,7 +268,7 @@ pub fn main_menu_ui(
                         ui.label("Profondeur:");
                         ui.add(
                             egui::DragValue::new(&mut menu_config.grid_depth)
-                                .range(100.0..=2000.0)
+                                .range(MIN_GRID_SIZE..=MAX_GRID_SIZE)
                                 .suffix(" unités"),
                         );
                         ui.end_row();
@@ -171,43 +302,179 @@ pub fn main_menu_ui(
                     .show(ui, |ui| {
                         ui.label("Nombre de simulations:");
                         ui.add(
-                            egui::DragValue::new(&mut menu_config.simulation_count).range(1..=20),
+                            egui::DragValue::new(&mut menu_config.simulation_count).range(1..=MAX_SIMULATION_COUNT),
                         );
                         ui.end_row();
 
-                        ui.label("Nombre de particules:");
-                        ui.add(
-                            egui::DragValue::new(&mut menu_config.particle_count).range(10..=2000),
+                        ui.label("Placement initial:");
+                        ui.horizontal(|ui| {
+                            for mode in SpawnMode::ALL {
+                                ui.radio_value(&mut menu_config.spawn_mode, mode, mode.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Communes : toutes les simulations partent de la même disposition, seuls les génomes les départagent. Indépendantes : une disposition par simulation, à chaque époque",
                         );
                         ui.end_row();
 
+                        ui.label("Nombre de particules:");
+                        let count_changed = ui
+                            .add(
+                                egui::DragValue::new(&mut menu_config.particle_count)
+                                    .range(1..=MAX_PARTICLE_COUNT),
+                            )
+                            .changed();
+                        ui.end_row();
+
                         ui.label("Types de particules:");
+                        let mut types_changed = false;
                         ui.horizontal(|ui| {
-                            ui.add(
-                                egui::DragValue::new(&mut menu_config.particle_types).range(2..=5),
-                            );
-
-                            // Indicateur de diversité
+                            types_changed = ui
+                                .add(
+                                    egui::DragValue::new(&mut menu_config.particle_types)
+                                        .range(2..=MAX_PARTICLE_TYPES),
+                                )
+                                .changed();
+
+                            // Taille de la matrice : coût quadratique en nombre de types
                             let interactions =
                                 menu_config.particle_types * menu_config.particle_types;
-                            let bits_per_interaction = (64 / interactions.max(1)).max(2).min(8);
-                            let diversity_levels = 1 << bits_per_interaction;
-
-                            let diversity_color = match diversity_levels {
-                                256.. => egui::Color32::GREEN,
-                                64..=255 => egui::Color32::YELLOW,
-                                16..=63 => egui::Color32::from_rgb(255, 165, 0), // Orange
-                                _ => egui::Color32::RED,
+                            let cost_color = match interactions {
+                                0..=25 => egui::Color32::GREEN,
+                                26..=64 => egui::Color32::YELLOW,
+                                _ => egui::Color32::from_rgb(255, 165, 0), // Orange
                             };
 
                             ui.label(
-                                egui::RichText::new(format!("({} niveaux)", diversity_levels))
+                                egui::RichText::new(format!("({} forces)", interactions))
                                     .small()
-                                    .color(diversity_color),
+                                    .color(cost_color),
+                            )
+                            .on_hover_text(
+                                "La matrice a types² valeurs : au-delà d'une huitaine de types, \
+                                 l'évolution converge plus lentement et la fenêtre de la matrice défile",
+                            );
+                        });
+                        ui.end_row();
+
+                        // Un changement du total ou du nombre de types refait une répartition équitable
+                        if count_changed
+                            || types_changed
+                            || menu_config.counts_per_type.len() != menu_config.particle_types
+                        {
+                            menu_config.counts_per_type = ParticleTypesConfig::even_split(
+                                menu_config.particle_count,
+                                menu_config.particle_types,
                             );
+                        }
+
+                        ui.label("Particules par type:");
+                        ui.horizontal(|ui| {
+                            let mut edited = None;
+                            for (particle_type, count) in
+                                menu_config.counts_per_type.iter_mut().enumerate()
+                            {
+                                if ui
+                                    .add(
+                                        egui::DragValue::new(count)
+                                            .range(0..=2000)
+                                            .prefix(format!("T{}: ", particle_type)),
+                                    )
+                                    .changed()
+                                {
+                                    edited = Some(particle_type);
+                                }
+                            }
+
+                            if let Some(particle_type) = edited {
+                                // Total maintenu entre 1 et 2000 particules
+                                let total: usize = menu_config.counts_per_type.iter().sum();
+                                if total == 0 {
+                                    menu_config.counts_per_type[particle_type] = 1;
+                                } else if total > 2000 {
+                                    menu_config.counts_per_type[particle_type] -= total - 2000;
+                                }
+                                menu_config.particle_count =
+                                    menu_config.counts_per_type.iter().sum();
+                            }
+                        })
+                        .response
+                        .on_hover_text("Un type peut être vide (0 particule)");
+                        ui.end_row();
+
+                        let particle_types = menu_config.particle_types;
+                        ParticleTypesConfig::resize_colors(
+                            &mut menu_config.type_colors,
+                            particle_types,
+                        );
+
+                        ui.label("Couleurs:");
+                        ui.horizontal(|ui| {
+                            for (particle_type, color) in
+                                menu_config.type_colors.iter_mut().enumerate()
+                            {
+                                ui.label(format!("T{}", particle_type));
+                                edit_type_color(ui, color);
+                            }
+                            if ui
+                                .small_button("↺")
+                                .on_hover_text("Teintes par défaut")
+                                .clicked()
+                            {
+                                menu_config.type_colors.clear();
+                                ParticleTypesConfig::resize_colors(
+                                    &mut menu_config.type_colors,
+                                    particle_types,
+                                );
+                            }
                         });
                         ui.end_row();
 
+                        let MenuConfig {
+                            type_radii,
+                            type_masses,
+                            ..
+                        } = &mut *menu_config;
+                        ParticleTypesConfig::resize_physical_properties(
+                            type_radii,
+                            type_masses,
+                            particle_types,
+                        );
+
+                        ui.label("Rayon / masse:");
+                        ui.horizontal(|ui| {
+                            for (particle_type, (radius, mass)) in
+                                type_radii.iter_mut().zip(type_masses.iter_mut()).enumerate()
+                            {
+                                ui.add(
+                                    egui::DragValue::new(radius)
+                                        .range(1.0..=20.0)
+                                        .speed(0.1)
+                                        .prefix(format!("T{}: r ", particle_type)),
+                                );
+                                ui.add(
+                                    egui::DragValue::new(mass)
+                                        .range(0.1..=10.0)
+                                        .speed(0.05)
+                                        .prefix("m "),
+                                );
+                            }
+                            if ui
+                                .small_button("↺")
+                                .on_hover_text("Valeurs globales")
+                                .clicked()
+                            {
+                                type_radii.fill(PARTICLE_RADIUS);
+                                type_masses.fill(PARTICLE_MASS);
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Le rayon règle collisions, répulsion et taille ; la masse divise l'accélération",
+                        );
+                        ui.end_row();
+
                         ui.label("Durée d'une époque:");
                         ui.add(
                             egui::DragValue::new(&mut menu_config.epoch_duration)
@@ -217,7 +484,11 @@ pub fn main_menu_ui(
                         ui.end_row();
 
                         ui.label("Nombre max d'époques:");
-                        ui.add(egui::DragValue::new(&mut menu_config.max_epochs).range(1..=1000));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut menu_config.max_epochs).range(1..=1000));
+                            ui.checkbox(&mut menu_config.auto_save_on_finish, "Sauvegarder à la fin")
+                                .on_hover_text("Sauvegarde le génome de chaque simulation quand la dernière époque se termine");
+                        });
                         ui.end_row();
 
                         ui.label("Portée max des forces:");
@@ -227,31 +498,184 @@ pub fn main_menu_ui(
                                 .suffix(" unités"),
                         );
                         ui.end_row();
+
+                        ui.label("Demi-vie de la vitesse:");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Slider::new(&mut menu_config.velocity_half_life, 0.005..=0.5)
+                                    .logarithmic(true)
+                                    .suffix(" s"),
+                            );
+                            ui.label(velocity_half_life_feel(menu_config.velocity_half_life))
+                                .on_hover_text(
+                                    "Temps pour que la vitesse d'une particule soit divisée par deux : plus il est court, plus le milieu freine",
+                                );
+                        });
+                        ui.end_row();
+
+                        ui.label("Vitesse max:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.max_velocity)
+                                .range(10.0..=2000.0)
+                                .speed(1.0)
+                                .suffix(" unités/s"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Intégration:");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut menu_config.integrator,
+                                Integrator::Euler,
+                                Integrator::Euler.label(),
+                            );
+                            ui.radio_value(
+                                &mut menu_config.integrator,
+                                Integrator::Verlet,
+                                Integrator::Verlet.label(),
+                            )
+                            .on_hover_text(
+                                "Plus stable avec des forces fortes (physique CPU, le GPU reste en Euler)",
+                            );
+                        });
+                        ui.end_row();
+
+                        ui.label("Courbe de force:");
+                        egui::ComboBox::from_id_salt("force_curve")
+                            .selected_text(menu_config.force_curve.label())
+                            .show_ui(ui, |ui| {
+                                for curve in ForceCurve::ALL {
+                                    ui.selectable_value(
+                                        &mut menu_config.force_curve,
+                                        curve,
+                                        curve.label(),
+                                    )
+                                    .on_hover_text(force_curve_help(curve));
+                                }
+                            })
+                            .response
+                            .on_hover_text(force_curve_help(menu_config.force_curve));
+                        ui.end_row();
+
+                        ui.label("Distance de répulsion:");
+                        ui.horizontal(|ui| {
+                            let automatic_distance = menu_config.particle_types as f32 * PARTICLE_RADIUS;
+                            let mut automatic = menu_config.min_distance.is_none();
+                            if ui
+                                .checkbox(&mut automatic, "Auto")
+                                .on_hover_text("Nombre de types × rayon des particules, comme avant")
+                                .changed()
+                            {
+                                menu_config.min_distance = (!automatic).then_some(automatic_distance);
+                            }
+                            let max_distance = menu_config.max_force_range;
+                            match &mut menu_config.min_distance {
+                                Some(min_distance) => {
+                                    ui.add(
+                                        egui::DragValue::new(min_distance)
+                                            .range(1.0..=max_distance)
+                                            .speed(0.5)
+                                            .suffix(" unités"),
+                                    )
+                                    .on_hover_text(
+                                        "Plus grande : particules espacées, amas lâches. Plus petite : amas denses. À garder sous la portée des forces",
+                                    );
+                                }
+                                None => {
+                                    ui.label(format!("{:.0} unités", automatic_distance));
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Pas de physique:");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut menu_config.physics_timestep,
+                                    MIN_PHYSICS_TIMESTEP..=MAX_PHYSICS_TIMESTEP,
+                                )
+                                .logarithmic(true)
+                                .suffix(" s"),
+                            )
+                            .on_hover_text(
+                                "Petit pas = plus précis mais plus coûteux ; le temps simulé par frame ne change pas",
+                            );
+                            if menu_config.physics_timestep > UNSTABLE_PHYSICS_TIMESTEP {
+                                ui.colored_label(
+                                    egui::Color32::YELLOW,
+                                    "⚠ Un grand pas peut rendre la physique instable",
+                                );
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Rebond sur les murs:");
+                        ui.add(egui::Slider::new(&mut menu_config.collision_damping, 0.0..=1.0))
+                            .on_hover_text(
+                                "Fraction de la vitesse conservée au rebond : 0 = les particules collent au mur, 1 = rebond parfait",
+                            );
+                        ui.end_row();
+
+                        ui.label("Facteur d'échelle des forces:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.force_scale_factor)
+                                .range(1.0..=500.0)
+                                .speed(1.0)
+                                .fixed_decimals(1),
+                        );
+                        ui.end_row();
+
+                        ui.label("Interactions max par particule:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.max_interactions_per_particle)
+                                .range(0..=2000),
+                        )
+                        .on_hover_text(format!(
+                            "Seuls les N voisins les plus proches sont pris en compte (0 = illimité), au plus {} sur GPU",
+                            GPU_MAX_INTERACTIONS
+                        ));
+                        ui.end_row();
+
+                        ui.label("Graine aléatoire:");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut menu_config.seed).speed(1.0))
+                                .on_hover_text(
+                                    "Même graine et même configuration = mêmes génomes et positions initiales",
+                                );
+                            if ui.button("🎲 Aléatoire").clicked() {
+                                menu_config.seed = random_seed();
+                            }
+                        });
+                        ui.end_row();
                     });
 
                 ui.add_space(5.0);
 
-                // Informations de diversité détaillées
-                ui.collapsing("ℹ Diversité génétique", |ui| {
+                // Taille du génome : la matrice croît au carré du nombre de types
+                ui.collapsing("ℹ Taille du génome", |ui| {
                     let interactions = menu_config.particle_types * menu_config.particle_types;
-                    let bits_per_interaction = (64 / interactions.max(1)).max(2).min(8);
-                    let diversity_levels = 1 << bits_per_interaction;
-                    let resolution = 2.0 / (diversity_levels - 1) as f32;
 
                     ui.label(format!(
                         "• {} interactions possibles ({}×{})",
                         interactions, menu_config.particle_types, menu_config.particle_types
                     ));
-                    ui.label(format!("• {} bits par interaction", bits_per_interaction));
-                    ui.label(format!("• {} niveaux de force distincts", diversity_levels));
-                    ui.label(format!("• Résolution: {:.4} par step", resolution));
+                    ui.label(format!(
+                        "• {} forces de nourriture",
+                        menu_config.particle_types
+                    ));
+                    ui.label(format!(
+                        "• {} valeurs par génome, {} pour toutes les simulations",
+                        interactions + menu_config.particle_types,
+                        (interactions + menu_config.particle_types) * menu_config.simulation_count
+                    ));
 
                     match menu_config.particle_types {
-                        2 => ui.label("Excellent: très fine granularité"),
-                        3 => ui.label("Recommandé: bon équilibre diversité/granularité"),
-                        4 => ui.label("Acceptable: granularité moyenne"),
-                        5 => ui.label("Limité: seulement 4 niveaux par interaction"),
-                        _ => ui.label("Non recommandé"),
+                        2..=3 => ui.label("Recommandé: l'évolution explore vite la matrice"),
+                        4..=6 => ui.label("Acceptable: comportements plus riches, convergence plus lente"),
+                        _ => ui.label(
+                            "Coûteux: prévoir plus d'époques et de simulations, la matrice défile",
+                        ),
                     };
                 });
             });
@@ -271,17 +695,43 @@ pub fn main_menu_ui(
                     .num_columns(3)
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Ratio d'élites:");
-                        ui.add(
-                            egui::DragValue::new(&mut menu_config.elite_ratio)
-                                .range(0.01..=0.5)
-                                .speed(0.01)
-                                .fixed_decimals(2),
-                        );
-                        ui.label(format!(
-                            "({:.0}% conservés)",
-                            menu_config.elite_ratio * 100.0
+                        ui.label("Élites:");
+                        ui.horizontal(|ui| {
+                            let mut by_count = matches!(menu_config.elitism, Elitism::Count(_));
+                            ui.radio_value(&mut by_count, false, "Ratio");
+                            ui.radio_value(&mut by_count, true, "Nombre");
+                            match (by_count, &mut menu_config.elitism) {
+                                (false, Elitism::Ratio) => {
+                                    ui.add(
+                                        egui::DragValue::new(&mut menu_config.elite_ratio)
+                                            .range(0.01..=0.5)
+                                            .speed(0.01)
+                                            .fixed_decimals(2),
+                                    );
+                                }
+                                (true, Elitism::Count(count)) => {
+                                    ui.add(egui::DragValue::new(count).range(1..=MAX_SIMULATION_COUNT));
+                                }
+                                (false, _) => menu_config.elitism = Elitism::Ratio,
+                                (true, _) => menu_config.elitism = Elitism::Count(1),
+                            }
+                        });
+                        let requested = menu_config
+                            .elitism
+                            .requested(menu_config.elite_ratio, menu_config.simulation_count);
+                        let resolved = menu_config
+                            .elitism
+                            .resolve(menu_config.elite_ratio, menu_config.simulation_count);
+                        let label = ui.label(format!(
+                            "({} / {} conservées)",
+                            resolved, menu_config.simulation_count
                         ));
+                        if resolved != requested {
+                            label.on_hover_text(format!(
+                                "Ramené à {} : au moins une simulation doit rester pour les descendants",
+                                resolved
+                            ));
+                        }
                         ui.end_row();
 
                         ui.label("Taux de mutation:");
@@ -297,6 +747,38 @@ pub fn main_menu_ui(
                         ));
                         ui.end_row();
 
+                        ui.label("Mutation des élites:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.elite_mutation_rate)
+                                .range(0.0..=1.0)
+                                .speed(0.005)
+                                .fixed_decimals(3),
+                        )
+                        .on_hover_text("0 : les élites sont recopiées à l'identique");
+                        ui.label(if menu_config.elite_mutation_rate > 0.0 {
+                            format!("({:.1}% de chance)", menu_config.elite_mutation_rate * 100.0)
+                        } else {
+                            "(élitisme pur)".to_string()
+                        });
+                        ui.end_row();
+
+                        ui.label("Type de mutation:");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut menu_config.mutation_kind,
+                                MutationKind::Uniform,
+                                "Uniforme",
+                            );
+                            ui.radio_value(
+                                &mut menu_config.mutation_kind,
+                                MutationKind::Gaussian,
+                                "Gaussienne",
+                            )
+                            .on_hover_text("Petits pas fréquents, grands pas rares");
+                        });
+                        ui.label("");
+                        ui.end_row();
+
                         ui.label("Taux de crossover:");
                         ui.add(
                             egui::DragValue::new(&mut menu_config.crossover_rate)
@@ -309,6 +791,240 @@ pub fn main_menu_ui(
                             menu_config.crossover_rate * 100.0
                         ));
                         ui.end_row();
+
+                        ui.label("Taille de tournoi:");
+                        let max_tournament = menu_config.simulation_count.max(1);
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.tournament_size)
+                                .range(1..=max_tournament),
+                        )
+                        .on_hover_text(
+                            "Candidats comparés pour choisir chaque parent : plus grand favorise les meilleurs, plus petit préserve la diversité",
+                        );
+                        ui.label(if menu_config.tournament_size <= 1 {
+                            "(sélection aléatoire pondérée)".to_string()
+                        } else {
+                            format!("({} candidats)", menu_config.tournament_size)
+                        });
+                        ui.end_row();
+
+                        ui.label("Stratégie de crossover:");
+                        egui::ComboBox::from_id_salt("crossover_strategy")
+                            .selected_text(menu_config.crossover_strategy.label())
+                            .show_ui(ui, |ui| {
+                                for strategy in CrossoverStrategy::CONCRETE
+                                    .into_iter()
+                                    .chain([CrossoverStrategy::Auto])
+                                {
+                                    let selected =
+                                        menu_config.crossover_strategy.same_kind(&strategy);
+                                    if ui.selectable_label(selected, strategy.label()).clicked()
+                                        && !selected
+                                    {
+                                        menu_config.crossover_strategy = strategy;
+                                    }
+                                }
+                            });
+                        if let CrossoverStrategy::Blend { alpha, auto_weight } =
+                            &mut menu_config.crossover_strategy
+                        {
+                            ui.horizontal(|ui| {
+                                ui.add_enabled(
+                                    !*auto_weight,
+                                    egui::DragValue::new(alpha)
+                                        .range(0.0..=1.0)
+                                        .speed(0.01)
+                                        .fixed_decimals(2)
+                                        .prefix("α "),
+                                );
+                                ui.checkbox(auto_weight, "Pondéré par fitness")
+                                    .on_hover_text("α = part de la fitness du premier parent dans le couple");
+                            });
+                        } else {
+                            ui.label("");
+                        }
+                        ui.end_row();
+
+                        ui.label("Historique des enfants:");
+                        ui.checkbox(
+                            &mut menu_config.inherit_fitness_history,
+                            "Hériter de la fitness des parents",
+                        )
+                        .on_hover_text(
+                            "Un enfant de crossover reçoit la moyenne des historiques de ses parents au lieu d'un historique vide : le mélange pondéré par fitness dispose ainsi d'une valeur dès l'époque suivante",
+                        );
+                        ui.label("");
+                        ui.end_row();
+
+                        ui.checkbox(&mut menu_config.sharing_enabled, "Partage de fitness")
+                            .on_hover_text(
+                                "Divise le score par le nombre de génomes proches pour préserver plusieurs espèces",
+                            );
+                        ui.add_enabled(
+                            menu_config.sharing_enabled,
+                            egui::DragValue::new(&mut menu_config.sharing_radius)
+                                .range(0.05..=2.0)
+                                .speed(0.01)
+                                .fixed_decimals(2)
+                                .prefix("rayon "),
+                        );
+                        ui.label("(distance génétique)");
+                        ui.end_row();
+
+                        ui.label("Hall of fame:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.hall_of_fame_size)
+                                .range(0..=100)
+                                .suffix(" génomes"),
+                        );
+                        ui.checkbox(&mut menu_config.hall_of_fame_reinject, "Réinjecter")
+                            .on_hover_text(
+                                "Ajoute un membre du hall of fame à chaque nouvelle population",
+                            );
+                        ui.end_row();
+
+                        ui.label("Archive par époque:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.genome_archive_depth)
+                                .range(0..=10)
+                                .suffix(" génomes"),
+                        )
+                        .on_hover_text("Meilleurs génomes gardés à chaque époque pour les revoir (0 = désactivée)");
+                        ui.end_row();
+
+                        ui.label("Génomes initiaux:");
+                        ui.checkbox(
+                            &mut menu_config.seed_interesting_forces,
+                            "Partir des forces intéressantes",
+                        )
+                        .on_hover_text(
+                            "Préréglage connu pour 3 ou 4 types, aléatoire validé pour les autres",
+                        );
+                        ui.end_row();
+
+                        ui.label("Depuis la bibliothèque:");
+                        let max_library_seeds = menu_config.simulation_count;
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.library_seed_count)
+                                .range(0..=max_library_seeds)
+                                .suffix(" génomes"),
+                        )
+                        .on_hover_text(
+                            "Les premières simulations partent des meilleures populations sauvegardées ayant le même nombre de types (0 = désactivé)",
+                        );
+                        ui.end_row();
+
+                        ui.label("Auto-attraction:");
+                        ui.checkbox(
+                            &mut menu_config.allow_self_attraction,
+                            "Autoriser un type à s'attirer lui-même",
+                        )
+                        .on_hover_text(
+                            "Les génomes aléatoires tirent aussi la diagonale dans [-1, 1] : des amas cohésifs deviennent possibles, au risque de particules agglutinées",
+                        );
+                        ui.end_row();
+
+                        ui.label("Poids de la fitness:");
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.fitness_weights.food)
+                                .range(0.0..=10.0)
+                                .speed(0.05)
+                                .fixed_decimals(2)
+                                .prefix("nourriture "),
+                        );
+                        ui.add(
+                            egui::DragValue::new(&mut menu_config.fitness_weights.exploration)
+                                .range(0.0..=10.0)
+                                .speed(0.05)
+                                .fixed_decimals(2)
+                                .prefix("exploration "),
+                        )
+                        .on_hover_text("Multiplie le pourcentage de cellules visitées pendant l'époque");
+                        ui.end_row();
+
+                        ui.label("Bonus de proximité:");
+                        ui.checkbox(&mut menu_config.fitness_weights.proximity_enabled, "Activé")
+                            .on_hover_text(
+                                "Récompense aussi les particules proches de la nourriture sans la manger : signal plus dense pour les premières époques",
+                            );
+                        ui.add_enabled(
+                            menu_config.fitness_weights.proximity_enabled,
+                            egui::DragValue::new(&mut menu_config.fitness_weights.proximity)
+                                .range(0.0..=10.0)
+                                .speed(0.01)
+                                .fixed_decimals(2)
+                                .prefix("poids "),
+                        )
+                        .on_hover_text(
+                            "Points par époque = poids × proximité moyenne (somme de 1 - distance/portée sur les nourritures à portée)",
+                        );
+                        ui.end_row();
+
+                        ui.label("Adaptation auto:");
+                        ui.checkbox(&mut menu_config.auto_adapt.enabled, "En cas de stagnation")
+                            .on_hover_text(
+                                "Relève la mutation et baisse les élites quand le meilleur score stagne, restaure dès qu'il progresse",
+                            );
+                        ui.add_enabled_ui(menu_config.auto_adapt.enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut menu_config.auto_adapt.stagnation_threshold)
+                                        .range(1..=50)
+                                        .prefix("après ")
+                                        .suffix(" époques"),
+                                );
+                                ui.add(
+                                    egui::DragValue::new(&mut menu_config.auto_adapt.mutation_step)
+                                        .range(0.0..=0.5)
+                                        .speed(0.005)
+                                        .fixed_decimals(3)
+                                        .prefix("mutation +"),
+                                );
+                                ui.add(
+                                    egui::DragValue::new(&mut menu_config.auto_adapt.elite_step)
+                                        .range(0.0..=0.5)
+                                        .speed(0.005)
+                                        .fixed_decimals(3)
+                                        .prefix("élites -"),
+                                );
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label("Convergence:");
+                        ui.checkbox(&mut menu_config.diversity_guard.enabled, "Surveiller la diversité")
+                            .on_hover_text(
+                                "Réagit quand la diversité génétique reste sous le seuil trop longtemps : la population a convergé prématurément",
+                            );
+                        ui.add_enabled_ui(menu_config.diversity_guard.enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut menu_config.diversity_guard.threshold)
+                                        .range(0.0..=1.0)
+                                        .speed(0.005)
+                                        .fixed_decimals(3)
+                                        .prefix("sous "),
+                                );
+                                ui.add(
+                                    egui::DragValue::new(&mut menu_config.diversity_guard.patience)
+                                        .range(1..=50)
+                                        .prefix("pendant ")
+                                        .suffix(" époques"),
+                                );
+                                egui::ComboBox::from_id_salt("collapse_action")
+                                    .selected_text(menu_config.diversity_guard.action.label())
+                                    .show_ui(ui, |ui| {
+                                        for action in CollapseAction::ALL {
+                                            ui.selectable_value(
+                                                &mut menu_config.diversity_guard.action,
+                                                action,
+                                                action.label(),
+                                            );
+                                        }
+                                    });
+                            });
+                        });
+                        ui.end_row();
                     });
 
                 ui.add_space(5.0);
@@ -335,7 +1051,7 @@ pub fn main_menu_ui(
                     .spacing([10.0, 8.0])
                     .show(ui, |ui| {
                         ui.label("Nombre de nourritures:");
-                        ui.add(egui::DragValue::new(&mut menu_config.food_count).range(0..=200));
+                        ui.add(egui::DragValue::new(&mut menu_config.food_count).range(0..=MAX_FOOD_COUNT));
                         ui.end_row();
 
                         ui.label("Réapparition:");
@@ -350,6 +1066,29 @@ pub fn main_menu_ui(
                                     .suffix(" secondes"),
                             );
                             ui.end_row();
+
+                            ui.label("Variation du délai:");
+                            let max_jitter = menu_config.food_respawn_time;
+                            ui.add(
+                                egui::DragValue::new(&mut menu_config.food_respawn_jitter)
+                                    .range(0.0..=max_jitter)
+                                    .speed(0.1)
+                                    .fixed_decimals(1)
+                                    .prefix("± ")
+                                    .suffix(" secondes"),
+                            )
+                            .on_hover_text(
+                                "Chaque nourriture tire son propre délai autour du temps de réapparition (jamais négatif) : sans variation, les nourritures mangées ensemble réapparaissent ensemble, en vagues synchronisées",
+                            );
+                            ui.end_row();
+
+                            ui.label("Lieu de réapparition:");
+                            ui.horizontal(|ui| {
+                                for mode in [FoodRespawnMode::SamePosition, FoodRespawnMode::RandomPosition] {
+                                    ui.radio_value(&mut menu_config.food_respawn_mode, mode, mode.label());
+                                }
+                            });
+                            ui.end_row();
                         }
 
                         ui.label("Valeur nutritive:");
@@ -359,6 +1098,71 @@ pub fn main_menu_ui(
                                 .fixed_decimals(1),
                         );
                         ui.end_row();
+
+                        ui.label("Valeur variable:");
+                        let mut variable = menu_config.food_value_max.is_some();
+                        if ui
+                            .checkbox(&mut variable, "Activée")
+                            .on_hover_text("Valeur tirée entre la valeur nutritive et le max, les fortes valeurs sont rares")
+                            .changed()
+                        {
+                            menu_config.food_value_max =
+                                variable.then_some(menu_config.food_value * 3.0);
+                        }
+                        ui.end_row();
+
+                        let min_value = menu_config.food_value;
+                        if let Some(max_value) = menu_config.food_value_max.as_mut() {
+                            ui.label("Valeur max:");
+                            ui.add(
+                                egui::DragValue::new(max_value)
+                                    .range(min_value..=50.0)
+                                    .fixed_decimals(1),
+                            );
+                            ui.end_row();
+                        }
+
+                        ui.label("Répartition:");
+                        ui.horizontal(|ui| {
+                            let clustered =
+                                matches!(menu_config.food_distribution, FoodDistribution::Clustered { .. });
+                            if ui.radio(!clustered, FoodDistribution::Uniform.label()).clicked() {
+                                menu_config.food_distribution = FoodDistribution::Uniform;
+                            }
+                            if ui
+                                .radio(clustered, "Amas")
+                                .on_hover_text("Nourriture regroupée autour de quelques centres")
+                                .clicked()
+                                && !clustered
+                            {
+                                menu_config.food_distribution = FoodDistribution::Clustered {
+                                    clusters: DEFAULT_FOOD_CLUSTERS,
+                                    spread: DEFAULT_FOOD_CLUSTER_SPREAD,
+                                };
+                            }
+                        });
+                        ui.end_row();
+
+                        let max_spread = menu_config
+                            .grid_width
+                            .max(menu_config.grid_height)
+                            .max(menu_config.grid_depth)
+                            / 2.0;
+                        if let FoodDistribution::Clustered { clusters, spread } =
+                            &mut menu_config.food_distribution
+                        {
+                            ui.label("Nombre d'amas:");
+                            ui.add(egui::DragValue::new(clusters).range(1..=20));
+                            ui.end_row();
+
+                            ui.label("Étalement:");
+                            ui.add(
+                                egui::DragValue::new(spread)
+                                    .range(5.0..=max_spread)
+                                    .suffix(" unités"),
+                            );
+                            ui.end_row();
+                        }
                     });
 
                 ui.add_space(5.0);
@@ -385,25 +1189,47 @@ pub fn main_menu_ui(
                 ui.horizontal(|ui| {
                     ui.radio_value(
                         &mut menu_config.boundary_mode,
-                        BoundaryMode::Bounce,
+                        BoundaryMode::BOUNCE,
                         "🏀 Rebond",
                     );
                     ui.radio_value(
                         &mut menu_config.boundary_mode,
-                        BoundaryMode::Teleport,
+                        BoundaryMode::TELEPORT,
                         "🌀 Téléportation",
                     );
                 });
 
                 ui.add_space(5.0);
-                match menu_config.boundary_mode {
-                    BoundaryMode::Bounce => {
+                match menu_config.boundary_mode.uniform_axis() {
+                    Some(AxisBoundary::Bounce) => {
                         ui.label("Les particules rebondissent sur les murs avec amortissement");
                     }
-                    BoundaryMode::Teleport => {
+                    Some(AxisBoundary::Teleport) => {
                         ui.label("Les particules réapparaissent de l'autre côté (tore 3D)");
                     }
+                    None => {
+                        ui.label(format!(
+                            "{} : rebond sur certains axes, tore sur les autres",
+                            menu_config.boundary_mode.label()
+                        ));
+                    }
                 }
+
+                ui.collapsing("Avancé : bords par axe", |ui| {
+                    egui::Grid::new("boundary_axes").num_columns(3).show(ui, |ui| {
+                        for (axis, name) in ["X", "Y", "Z"].into_iter().enumerate() {
+                            ui.label(format!("Axe {}:", name));
+                            for boundary in [AxisBoundary::Bounce, AxisBoundary::Teleport] {
+                                ui.radio_value(
+                                    &mut menu_config.boundary_mode.axes[axis],
+                                    boundary,
+                                    boundary.label(),
+                                );
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
             });
 
             ui.add_space(10.0);
@@ -431,6 +1257,25 @@ pub fn main_menu_ui(
                     ui.label("Les calculs seront effectués sur le CPU");
                     ui.label("Plus flexible mais plus lent avec beaucoup de particules");
                 }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label("Qualité de rendu:");
+                    render_quality_combo(ui, "menu_render_quality", &mut menu_config.render_quality);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Style de rendu:");
+                    render_style_combo(ui, "menu_render_style", &mut menu_config.render_style);
+                });
+            });
+
+            ui.add_space(10.0);
+
+            // === Configuration de run ===
+            ui.group(|ui| {
+                ui.label(egui::RichText::new("Configuration du run").size(16.0).strong());
+                ui.separator();
+                run_config_panel(ui, &mut menu_config, &mut run_config_ui);
             });
 
             ui.add_space(20.0);
@@ -512,7 +1357,7 @@ pub fn main_menu_ui(
                 );
                 ui.label(
                     egui::RichText::new(
-                        "Échap: Quitter • Espace: Pause simulation • Sauvegarde: bouton 💾",
+                        "Échap: Quitter • Espace: Pause simulation • E: Export de l'historique • Sauvegarde: bouton 💾",
                     )
                     .small()
                     .color(egui::Color32::GRAY),
@@ -523,6 +1368,155 @@ pub fn main_menu_ui(
     });
 }
 
+/// Sélecteur de couleur egui branché sur une couleur Bevy (sRGB)
+pub fn edit_type_color(ui: &mut egui::Ui, color: &mut Color) -> bool {
+    let [red, green, blue, _] = color.to_srgba().to_u8_array();
+    let mut srgb = [red, green, blue];
+    let changed = ui.color_edit_button_srgb(&mut srgb).changed();
+    if changed {
+        *color = Color::srgb_u8(srgb[0], srgb[1], srgb[2]);
+    }
+    changed
+}
+
+/// Choix de la qualité de rendu, partagé par le menu et la barre de contrôle
+pub fn render_quality_combo(ui: &mut egui::Ui, id: &str, quality: &mut RenderQuality) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(quality.label())
+        .show_ui(ui, |ui| {
+            for option in RenderQuality::ALL {
+                ui.selectable_value(quality, option, option.label());
+            }
+        })
+        .response
+        .on_hover_text("Billboard : disques face caméra, le plus rapide avec beaucoup de particules");
+}
+
+/// Choix entre couleurs plates et éclairage, partagé par le menu et la barre de contrôle
+pub fn render_style_combo(ui: &mut egui::Ui, id: &str, style: &mut RenderStyle) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(style.label())
+        .show_ui(ui, |ui| {
+            for option in RenderStyle::ALL {
+                ui.selectable_value(style, option, option.label());
+            }
+        })
+        .response
+        .on_hover_text("Éclairé : lumière directionnelle et ombrage, pour mieux percevoir la profondeur");
+}
+
+/// Ressenti approximatif du frottement associé à une demi-vie de vitesse
+fn velocity_half_life_feel(half_life: f32) -> &'static str {
+    match half_life {
+        h if h < 0.02 => "très visqueux",
+        h if h < 0.06 => "visqueux",
+        h if h < 0.15 => "fluide",
+        _ => "quasi sans frottement",
+    }
+}
+
+/// Lecture de la distance de répulsion (`min_r`) et de la portée par chaque courbe
+fn force_curve_help(curve: ForceCurve) -> &'static str {
+    match curve {
+        ForceCurve::Tent => {
+            "Répulsion sous la distance minimale, puis force du génome maximale à mi-chemin entre cette distance et la portée, nulle aux deux bouts"
+        }
+        ForceCurve::Linear => {
+            "Répulsion sous la distance minimale, puis force du génome maximale dès cette distance, décroissant jusqu'à zéro à la portée"
+        }
+        ForceCurve::LennardJones => {
+            "Répulsion très raide sous la distance minimale, puis puits de force du génome le plus profond vers 1,12 × cette distance, coupé à la portée"
+        }
+        ForceCurve::Smooth => {
+            "Répulsion sous la distance minimale, puis arche sinusoïdale de force du génome, maximale à mi-chemin et sans angle"
+        }
+    }
+}
+
+/// Export de tout le menu (graine comprise) et rechargement d'une expérience partagée
+fn run_config_panel(ui: &mut egui::Ui, menu_config: &mut MenuConfig, state: &mut RunConfigUI) {
+    ui.horizontal(|ui| {
+        ui.label("Nom:");
+        ui.text_edit_singleline(&mut state.file_name);
+        if ui
+            .button("💾 Sauvegarder la config")
+            .on_hover_text("Grille, nourriture, paramètres génétiques et graine")
+            .clicked()
+        {
+            let name = state.file_name.trim();
+            let path = run_configs_dir().join(format!("{}.json", name));
+            state.status = Some(if name.is_empty() {
+                ("Nom de fichier vide".to_string(), false)
+            } else {
+                match save_run_config(&RunConfig::from_menu_config(menu_config), &path) {
+                    Ok(()) => {
+                        state.files = list_run_configs();
+                        (format!("Configuration écrite dans {}", path.display()), true)
+                    }
+                    Err(e) => (format!("Erreur d'écriture: {}", e), false),
+                }
+            });
+        }
+    });
+
+    ui.horizontal(|ui| {
+        let selected_text = state
+            .selection
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Choisir un fichier".to_string());
+        egui::ComboBox::from_id_salt("run_config_files")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for path in &state.files {
+                    let label = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    ui.selectable_value(&mut state.selection, Some(path.clone()), label);
+                }
+            });
+
+        if ui.button("🔄").on_hover_text("Rafraîchir la liste").clicked() {
+            state.files = list_run_configs();
+        }
+
+        if ui
+            .add_enabled(state.selection.is_some(), egui::Button::new("📂 Charger la config"))
+            .on_hover_text("Remplace tous les paramètres du menu et la graine")
+            .clicked()
+            && let Some(path) = state.selection.clone()
+        {
+            state.status = Some(match load_run_config(&path) {
+                Ok(run_config) => match run_config.apply_to_menu_config(menu_config) {
+                    Ok(()) => {
+                        info!("Configuration de run chargée depuis {}", path.display());
+                        (
+                            format!("Configuration chargée (graine {})", menu_config.seed),
+                            true,
+                        )
+                    }
+                    Err(problems) => (
+                        format!("Configuration refusée : {}", problems.join(" ; ")),
+                        false,
+                    ),
+                },
+                Err(e) => (format!("Lecture impossible: {}", e), false),
+            });
+        }
+    });
+
+    if let Some((message, success)) = &state.status {
+        let color = if *success {
+            egui::Color32::from_rgb(100, 200, 100)
+        } else {
+            egui::Color32::from_rgb(230, 100, 100)
+        };
+        ui.label(egui::RichText::new(message).small().color(color));
+    }
+}
+
 fn apply_configuration(commands: &mut Commands, config: &MenuConfig) {
     // Insérer les ressources configurées
     commands.insert_resource(GridParameters {
@@ -531,34 +1525,122 @@ fn apply_configuration(commands: &mut Commands, config: &MenuConfig) {
         depth: config.grid_depth,
     });
 
-    commands.insert_resource(SimulationParameters {
+    let sim_params = SimulationParameters {
         current_epoch: 0,
         max_epochs: config.max_epochs,
         epoch_duration: config.epoch_duration,
         epoch_timer: Timer::from_seconds(config.epoch_duration, TimerMode::Once),
         simulation_count: config.simulation_count,
-        particle_count: config.particle_count,
+        particle_count: config.counts_per_type.iter().sum(),
         particle_types: config.particle_types,
         simulation_speed: SimulationSpeed::Normal,
+        physics_timestep: config.physics_timestep,
         max_force_range: config.max_force_range,
-        velocity_half_life: 0.043,
+        velocity_half_life: config.velocity_half_life,
+        max_velocity: config.max_velocity,
+        integrator: config.integrator,
+        force_curve: config.force_curve,
+        min_distance: config.min_distance,
+        collision_damping: config.collision_damping,
+        force_scale_factor: config.force_scale_factor,
+        max_interactions_per_particle: config.max_interactions_per_particle,
+        elitism: config.elitism,
         elite_ratio: config.elite_ratio,
         mutation_rate: config.mutation_rate,
+        elite_mutation_rate: config.elite_mutation_rate,
         crossover_rate: config.crossover_rate,
-    });
-
-    commands.insert_resource(ParticleTypesConfig::new(config.particle_types));
+        tournament_size: config.tournament_size,
+        mutation_kind: config.mutation_kind,
+        crossover_strategy: config.crossover_strategy,
+        sharing_enabled: config.sharing_enabled,
+        sharing_radius: config.sharing_radius,
+        allow_self_attraction: config.allow_self_attraction,
+        inherit_fitness_history: config.inherit_fitness_history,
+        spawn_mode: config.spawn_mode,
+    };
+    let elite_count = sim_params.elite_count();
+    let tournament_size = sim_params.tournament_size();
+    commands.insert_resource(sim_params);
+
+    commands.insert_resource(
+        ParticleTypesConfig::new(config.particle_types, config.counts_per_type.clone())
+            .with_colors(&config.type_colors)
+            .with_physical_properties(&config.type_radii, &config.type_masses),
+    );
 
     commands.insert_resource(FoodParameters {
         food_count: config.food_count,
         respawn_enabled: config.food_respawn_enabled,
         respawn_cooldown: config.food_respawn_time,
+        respawn_jitter: config.food_respawn_jitter,
+        respawn_mode: config.food_respawn_mode,
         food_value: config.food_value,
+        max_food_value: config.food_value_max,
+        distribution: config.food_distribution,
     });
 
     commands.insert_resource(config.boundary_mode);
 
     commands.insert_resource(ComputeEnabled(config.use_gpu));
+    commands.insert_resource(config.render_quality);
+    commands.insert_resource(config.render_style);
+
+    commands.insert_resource(SimulationSeed(config.seed));
+    // Les génomes de départ sont tirés du même générateur que le spawn
+    let mut sim_rng = SimulationRng::from_seed(config.seed);
+
+    commands.insert_resource(config.fitness_weights);
+    commands.insert_resource(AutoSaveOnFinish(config.auto_save_on_finish));
+    commands.remove_resource::<RunSummary>();
+    commands.insert_resource(AutoAdapt {
+        stagnation_epochs: 0,
+        baseline: None,
+        ..config.auto_adapt.clone()
+    });
+    commands.insert_resource(DiversityGuard {
+        low_epochs: 0,
+        converged_at: None,
+        ..config.diversity_guard.clone()
+    });
+
+    // Génomes de la bibliothèque pour les premières simulations, puis forces intéressantes
+    // ou aléatoires (à défaut de graine, le spawn tire un génome aléatoire)
+    let mut seeds = Vec::new();
+    let library_seed_count = config.library_seed_count.min(config.simulation_count);
+    if library_seed_count > 0 {
+        let populations = load_all_populations().unwrap_or_else(|error| {
+            warn!("Bibliothèque illisible : {}", error);
+            Vec::new()
+        });
+        seeds = top_compatible_genomes(&populations, config.particle_types, library_seed_count);
+        if seeds.len() < library_seed_count {
+            warn!(
+                "Seulement {} génome(s) à {} types dans la bibliothèque sur {} demandés, le reste part sans graine",
+                seeds.len(),
+                config.particle_types,
+                library_seed_count
+            );
+        } else {
+            info!("🌱 {} génome(s) de départ repris de la bibliothèque", seeds.len());
+        }
+    }
+    if config.seed_interesting_forces {
+        seeds.extend((seeds.len()..config.simulation_count).map(|_| {
+            Genotype::interesting(config.particle_types, config.allow_self_attraction, &mut sim_rng.0)
+        }));
+    }
+    if !seeds.is_empty() {
+        commands.insert_resource(SeedGenomes(seeds));
+    }
+    commands.insert_resource(sim_rng);
+
+    // Nouveau run : historique, hall of fame et archive vierges
+    commands.insert_resource(EvolutionHistory::default());
+    commands.insert_resource(HallOfFame::new(
+        config.hall_of_fame_size,
+        config.hall_of_fame_reinject,
+    ));
+    commands.insert_resource(GenomeArchive::new(config.genome_archive_depth));
 
     info!("Configuration appliquée:");
     info!(
@@ -570,17 +1652,21 @@ fn apply_configuration(commands: &mut Commands, config: &MenuConfig) {
         config.simulation_count, config.particle_count
     );
     info!(
-        "  • Types: {} (diversité: {} niveaux)",
+        "  • Types: {} ({} forces par matrice)",
         config.particle_types,
-        1 << ((64 / (config.particle_types * config.particle_types).max(1))
-            .max(2)
-            .min(8))
+        config.particle_types * config.particle_types
     );
     info!(
-        "  • Algorithme génétique: {:.0}% élites, {:.0}% mutation, {:.0}% crossover",
-        config.elite_ratio * 100.0,
+        "  • Algorithme génétique: {} élite(s) (mutation {:.1}%), {:.0}% mutation, {:.0}% crossover, tournoi de {}",
+        elite_count,
+        config.elite_mutation_rate * 100.0,
         config.mutation_rate * 100.0,
-        config.crossover_rate * 100.0
+        config.crossover_rate * 100.0,
+        tournament_size
+    );
+    info!(
+        "  • Graine: {}",
+        config.seed
     );
     info!(
         "  • GPU Compute: {}",
@@ -1,23 +1,218 @@
+use crate::components::genetics::behavior::{
+    PopulationAnalysis, analyze_behavior, predict_emergent_behaviors,
+};
+use crate::components::genetics::clustering::{
+    GenomeCluster, cluster_genomes, diversity_contributions,
+};
 use crate::components::genetics::genotype::Genotype;
+use crate::globals::{DEFAULT_HALL_OF_FAME_SIZE, DEFAULT_MUTATION_RATE, DEFAULT_SIMULATION_COUNT};
+use crate::resources::config::seed::{SimulationRng, SimulationSeed, random_seed};
+use crate::resources::genetics::fitness::FitnessWeights;
+use crate::resources::genetics::genome_archive::GenomeArchive;
+use crate::resources::genetics::hall_of_fame::HallOfFame;
+use crate::resources::genetics::history::EvolutionHistory;
+use crate::resources::world::boundary::BoundaryMode;
 use crate::states::app::AppState;
 use crate::systems::persistence::population_save::*;
+use crate::systems::simulation::spawning::SeedGenomes;
+use crate::ui::panels::interaction_graph::behavior_confidence_bars;
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 
 #[derive(Resource, Default)]
 pub struct VisualizerSelection {
     pub selected_population: Option<SavedPopulation>,
     pub search_filter: String,
+    /// Comportements prédits exigés (tous doivent être présents)
+    pub behavior_filter: BTreeSet<String>,
+    pub filter_combination: FilterCombination,
+    /// Analyse comportementale par population, clé = horodatage
+    pub analysis_cache: HashMap<String, PopulationAnalysis>,
+    pub comparison: GenomeComparison,
+    pub clustering: GenomeClustering,
+    pub duplicate: DuplicateSettings,
+    /// Étiquette exigée (None = toutes les populations)
+    pub tag_filter: Option<String>,
+    pub group_by_tag: bool,
+    /// Saisie en cours dans l'éditeur d'étiquettes de la fenêtre de détails
+    pub tag_buffer: String,
+    pub export_status: Option<String>,
     pub sort_by: PopulationSortBy,
+    /// Score minimum exigé (None = pas de seuil)
+    pub min_score: Option<f32>,
+    /// Cohérence minimum exigée, entre 0 et 1 (None = pas de seuil)
+    pub min_coherence: Option<f32>,
+    /// Apport de diversité de chaque population à la bibliothèque, clé = fichier de la
+    /// population (voir `refresh_diversity_cache`)
+    pub diversity_cache: HashMap<PathBuf, f32>,
+    pub pending_deletion: Option<SavedPopulation>,
+    pub rename_target: Option<SavedPopulation>,
+    pub rename_buffer: String,
+    pub rename_error: Option<String>,
 }
 
-#[derive(Default, PartialEq)]
+/// Paramètres du « dupliquer et muter » : nouveau run issu d'un génome sauvegardé
+pub struct DuplicateSettings {
+    pub copies: usize,
+    pub mutation_rate: f32,
+}
+
+impl Default for DuplicateSettings {
+    fn default() -> Self {
+        Self {
+            copies: DEFAULT_SIMULATION_COUNT,
+            mutation_rate: DEFAULT_MUTATION_RATE,
+        }
+    }
+}
+
+/// Deux populations comparées côte à côte
+pub struct GenomeComparison {
+    pub populations: Vec<SavedPopulation>,
+    /// Écart minimal pour qu'une cellule soit marquée dans la matrice des différences
+    pub threshold: f32,
+}
+
+impl Default for GenomeComparison {
+    fn default() -> Self {
+        Self {
+            populations: Vec::new(),
+            threshold: 0.1,
+        }
+    }
+}
+
+impl GenomeComparison {
+    pub fn contains(&self, population: &SavedPopulation) -> bool {
+        self.populations
+            .iter()
+            .any(|p| p.name == population.name && p.timestamp == population.timestamp)
+    }
+
+    /// Ajoute ou retire la population ; au-delà de deux, la plus ancienne sélection sort
+    pub fn toggle(&mut self, population: &SavedPopulation) {
+        if self.contains(population) {
+            self.populations
+                .retain(|p| p.name != population.name || p.timestamp != population.timestamp);
+        } else {
+            if self.populations.len() == 2 {
+                self.populations.remove(0);
+            }
+            self.populations.push(population.clone());
+        }
+    }
+}
+
+/// Regroupement des populations par similarité génétique
+pub struct GenomeClustering {
+    pub show_window: bool,
+    /// Nombre de groupes visé pour chaque nombre de types
+    pub cluster_count: usize,
+    pub clusters: Vec<GenomeCluster>,
+    /// Groupe de chaque population, clé = horodatage
+    pub membership: HashMap<String, usize>,
+    /// Horodatages et nombre de groupes du dernier calcul
+    computed_for: Option<(Vec<String>, usize)>,
+}
+
+impl Default for GenomeClustering {
+    fn default() -> Self {
+        Self {
+            show_window: false,
+            cluster_count: 4,
+            clusters: Vec::new(),
+            membership: HashMap::new(),
+            computed_for: None,
+        }
+    }
+}
+
+impl GenomeClustering {
+    /// Recalcule les groupes si les populations ou le nombre de groupes ont changé
+    fn refresh(&mut self, populations: &[SavedPopulation]) {
+        let timestamps: Vec<String> = populations.iter().map(|p| p.timestamp.clone()).collect();
+        if self
+            .computed_for
+            .as_ref()
+            .is_some_and(|(known, count)| *known == timestamps && *count == self.cluster_count)
+        {
+            return;
+        }
+
+        let genotypes: Vec<Genotype> =
+            populations.iter().map(|p| p.genotype.to_genotype()).collect();
+        self.clusters = cluster_genomes(&genotypes, self.cluster_count);
+        self.membership = self
+            .clusters
+            .iter()
+            .enumerate()
+            .flat_map(|(cluster, group)| {
+                group.members.iter().map(move |&member| (member, cluster))
+            })
+            .map(|(member, cluster)| (timestamps[member].clone(), cluster))
+            .collect();
+        self.computed_for = Some((timestamps, self.cluster_count));
+    }
+}
+
+/// Couleur distincte par groupe, répétée au-delà de la palette
+fn cluster_color(cluster: usize) -> egui::Color32 {
+    const PALETTE: [egui::Color32; 8] = [
+        egui::Color32::from_rgb(230, 110, 90),
+        egui::Color32::from_rgb(90, 170, 230),
+        egui::Color32::from_rgb(120, 200, 110),
+        egui::Color32::from_rgb(230, 190, 80),
+        egui::Color32::from_rgb(180, 120, 220),
+        egui::Color32::from_rgb(80, 200, 190),
+        egui::Color32::from_rgb(230, 130, 180),
+        egui::Color32::from_rgb(170, 170, 170),
+    ];
+    PALETTE[cluster % PALETTE.len()]
+}
+
+/// Combinaison du filtre texte et du filtre de comportements
+#[derive(Default, PartialEq, Clone, Copy)]
+pub enum FilterCombination {
+    #[default]
+    And,
+    Or,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
 pub enum PopulationSortBy {
     #[default]
     Date,
     Name,
     Score,
     ParticleCount,
+    /// Réciprocité des forces (`coherence_score`), décroissante
+    Coherence,
+    /// Distance moyenne aux autres génomes de la bibliothèque, décroissante
+    DiversityContribution,
+}
+
+impl PopulationSortBy {
+    pub const ALL: [PopulationSortBy; 6] = [
+        PopulationSortBy::Date,
+        PopulationSortBy::Name,
+        PopulationSortBy::Score,
+        PopulationSortBy::ParticleCount,
+        PopulationSortBy::Coherence,
+        PopulationSortBy::DiversityContribution,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PopulationSortBy::Date => "Date",
+            PopulationSortBy::Name => "Nom",
+            PopulationSortBy::Score => "Score",
+            PopulationSortBy::ParticleCount => "Nb. Particules",
+            PopulationSortBy::Coherence => "Cohérence",
+            PopulationSortBy::DiversityContribution => "Apport de diversité",
+        }
+    }
 }
 
 /// Ressource pour stocker le génome à visualiser
@@ -50,6 +245,16 @@ pub fn visualizer_ui(
         }
     }
 
+    // Analyse calculée une seule fois par population, pas à chaque frame
+    for population in &available.populations {
+        if !visualizer.analysis_cache.contains_key(&population.timestamp) {
+            let analysis = analyze_behavior(&population.genotype.to_genotype());
+            visualizer
+                .analysis_cache
+                .insert(population.timestamp.clone(), analysis);
+        }
+    }
+
     egui::CentralPanel::default().show(ctx, |ui| {
         ui.vertical_centered(|ui| {
             ui.heading("Visualiseur de Populations Sauvegardées");
@@ -64,23 +269,62 @@ pub fn visualizer_ui(
 
             ui.label("Trier par:");
             egui::ComboBox::from_label("")
-                .selected_text(match visualizer.sort_by {
-                    PopulationSortBy::Date => "Date",
-                    PopulationSortBy::Name => "Nom",
-                    PopulationSortBy::Score => "Score",
-                    PopulationSortBy::ParticleCount => "Nb. Particules",
-                })
+                .selected_text(visualizer.sort_by.label())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut visualizer.sort_by, PopulationSortBy::Date, "Date");
-                    ui.selectable_value(&mut visualizer.sort_by, PopulationSortBy::Name, "Nom");
-                    ui.selectable_value(&mut visualizer.sort_by, PopulationSortBy::Score, "Score");
-                    ui.selectable_value(
-                        &mut visualizer.sort_by,
-                        PopulationSortBy::ParticleCount,
-                        "Nb. Particules",
-                    );
+                    for option in PopulationSortBy::ALL {
+                        ui.selectable_value(&mut visualizer.sort_by, option, option.label());
+                    }
                 });
 
+            let known_tags: BTreeSet<String> = available
+                .populations
+                .iter()
+                .flat_map(|population| population.tags.iter().cloned())
+                .collect();
+            if !known_tags.is_empty() {
+                ui.separator();
+
+                ui.label("Tag:");
+                egui::ComboBox::from_id_salt("tag_filter")
+                    .selected_text(visualizer.tag_filter.as_deref().unwrap_or("Tous"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut visualizer.tag_filter, None, "Tous");
+                        for tag in &known_tags {
+                            ui.selectable_value(
+                                &mut visualizer.tag_filter,
+                                Some(tag.clone()),
+                                tag.as_str(),
+                            );
+                        }
+                    });
+                ui.checkbox(&mut visualizer.group_by_tag, "Grouper par tag");
+            }
+
+            ui.separator();
+
+            if ui
+                .button("📄 Exporter CSV")
+                .on_hover_text("Résumé de toutes les populations dans exports/")
+                .clicked()
+            {
+                visualizer.export_status = Some(
+                    match export_population_statistics(&available.populations) {
+                        Ok(path) => format!("Exporté: {}", path.display()),
+                        Err(e) => format!("Export impossible: {}", e),
+                    },
+                );
+            }
+
+            ui.separator();
+
+            if ui
+                .selectable_label(visualizer.clustering.show_window, "🧩 Regroupement")
+                .on_hover_text("Regroupe les génomes sauvegardés par distance génétique")
+                .clicked()
+            {
+                visualizer.clustering.show_window = !visualizer.clustering.show_window;
+            }
+
             ui.separator();
 
             if ui
@@ -107,6 +351,90 @@ pub fn visualizer_ui(
             }
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Dupliquer et muter:");
+            ui.add(
+                egui::DragValue::new(&mut visualizer.duplicate.copies)
+                    .range(1..=32)
+                    .suffix(" copies"),
+            );
+            ui.add(
+                egui::DragValue::new(&mut visualizer.duplicate.mutation_rate)
+                    .range(0.0..=1.0)
+                    .speed(0.01)
+                    .fixed_decimals(2)
+                    .prefix("taux: "),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Seuils:");
+            let mut score_threshold = visualizer.min_score.is_some();
+            if ui.checkbox(&mut score_threshold, "Score min").changed() {
+                visualizer.min_score = score_threshold.then_some(0.0);
+            }
+            if let Some(min_score) = &mut visualizer.min_score {
+                ui.add(egui::DragValue::new(min_score).speed(1.0));
+            }
+
+            ui.separator();
+
+            let mut coherence_threshold = visualizer.min_coherence.is_some();
+            if ui
+                .checkbox(&mut coherence_threshold, "Cohérence min")
+                .on_hover_text("Réciprocité des forces entre types : 1 pour une matrice symétrique")
+                .changed()
+            {
+                visualizer.min_coherence = coherence_threshold.then_some(0.5);
+            }
+            if let Some(min_coherence) = &mut visualizer.min_coherence {
+                ui.add(
+                    egui::DragValue::new(min_coherence)
+                        .range(0.0..=1.0)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+            }
+        });
+
+        if let Some(status) = &visualizer.export_status {
+            ui.label(egui::RichText::new(status).small().color(egui::Color32::GRAY));
+        }
+
+        // Tags disponibles : union des comportements prédits de toutes les populations
+        let known_behaviors: BTreeSet<String> = available
+            .populations
+            .iter()
+            .filter_map(|population| visualizer.analysis_cache.get(&population.timestamp))
+            .flat_map(|analysis| analysis.labels().cloned())
+            .collect();
+
+        if !known_behaviors.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Comportements:");
+                for behavior in &known_behaviors {
+                    let selected = visualizer.behavior_filter.contains(behavior);
+                    if ui.selectable_label(selected, behavior.as_str()).clicked() {
+                        if selected {
+                            visualizer.behavior_filter.remove(behavior);
+                        } else {
+                            visualizer.behavior_filter.insert(behavior.clone());
+                        }
+                    }
+                }
+
+                if !visualizer.behavior_filter.is_empty() {
+                    ui.separator();
+                    ui.label("Avec la recherche:");
+                    ui.radio_value(&mut visualizer.filter_combination, FilterCombination::And, "ET");
+                    ui.radio_value(&mut visualizer.filter_combination, FilterCombination::Or, "OU");
+                    if ui.small_button("✖").on_hover_text("Effacer le filtre").clicked() {
+                        visualizer.behavior_filter.clear();
+                    }
+                }
+            });
+        }
+
         ui.separator();
 
         if available.populations.is_empty() {
@@ -123,15 +451,49 @@ pub fn visualizer_ui(
             .populations
             .iter()
             .filter(|pop| {
-                if visualizer.search_filter.is_empty() {
-                    true
-                } else {
+                visualizer
+                    .tag_filter
+                    .as_ref()
+                    .is_none_or(|tag| pop.tags.contains(tag))
+            })
+            .filter(|pop| visualizer.min_score.is_none_or(|min| pop.score >= min))
+            .filter(|pop| {
+                visualizer.min_coherence.is_none_or(|min| {
+                    visualizer
+                        .analysis_cache
+                        .get(&pop.timestamp)
+                        .is_some_and(|analysis| analysis.coherence >= min)
+                })
+            })
+            .filter(|pop| {
+                let text_match = (!visualizer.search_filter.is_empty()).then(|| {
                     let filter = visualizer.search_filter.to_lowercase();
                     pop.name.to_lowercase().contains(&filter)
                         || pop
                             .description
                             .as_ref()
-                            .map_or(false, |d| d.to_lowercase().contains(&filter))
+                            .is_some_and(|d| d.to_lowercase().contains(&filter))
+                });
+                let behavior_match = (!visualizer.behavior_filter.is_empty()).then(|| {
+                    visualizer
+                        .analysis_cache
+                        .get(&pop.timestamp)
+                        .is_some_and(|analysis| {
+                            visualizer
+                                .behavior_filter
+                                .iter()
+                                .all(|behavior| analysis.contains(behavior))
+                        })
+                });
+
+                // Un filtre vide ne restreint rien
+                match (text_match, behavior_match) {
+                    (None, None) => true,
+                    (Some(matched), None) | (None, Some(matched)) => matched,
+                    (Some(text), Some(behavior)) => match visualizer.filter_combination {
+                        FilterCombination::And => text && behavior,
+                        FilterCombination::Or => text || behavior,
+                    },
                 }
             })
             .collect();
@@ -157,6 +519,35 @@ pub fn visualizer_ui(
                         .cmp(&a.simulation_params.particle_count)
                 });
             }
+            PopulationSortBy::Coherence => {
+                let coherence = |pop: &SavedPopulation| {
+                    visualizer
+                        .analysis_cache
+                        .get(&pop.timestamp)
+                        .map_or(0.0, |analysis| analysis.coherence)
+                };
+                filtered_populations.sort_by(|a, b| coherence(b).total_cmp(&coherence(a)));
+            }
+            PopulationSortBy::DiversityContribution => {
+                let diversity = |pop: &SavedPopulation| {
+                    visualizer
+                        .diversity_cache
+                        .get(&population_file_path(pop))
+                        .copied()
+                        .unwrap_or(0.0)
+                };
+                filtered_populations.sort_by(|a, b| diversity(b).total_cmp(&diversity(a)));
+            }
+        }
+
+        // Tri stable : l'ordre choisi est conservé à l'intérieur de chaque groupe
+        if visualizer.group_by_tag {
+            filtered_populations.sort_by(|a, b| match (a.tags.first(), b.tags.first()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            });
         }
 
         ui.label(format!(
@@ -165,11 +556,59 @@ pub fn visualizer_ui(
             available.populations.len()
         ));
 
+        let mut rename_confirmed = false;
+
         egui::ScrollArea::vertical().show(ui, |ui| {
+            let mut current_group: Option<Option<&String>> = None;
             for population in filtered_populations {
+                if visualizer.group_by_tag && current_group != Some(population.tags.first()) {
+                    current_group = Some(population.tags.first());
+                    ui.add_space(4.0);
+                    ui.label(
+                        egui::RichText::new(
+                            population.tags.first().map_or("Sans tag", String::as_str),
+                        )
+                        .size(15.0)
+                        .strong(),
+                    );
+                }
+
+                let is_renaming = visualizer.rename_target.as_ref().is_some_and(|p| {
+                    p.name == population.name && p.timestamp == population.timestamp
+                });
+
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new(&population.name).size(16.0).strong());
+                        if is_renaming {
+                            let response = ui.text_edit_singleline(&mut visualizer.rename_buffer);
+                            if ui.button("✔").on_hover_text("Valider le nouveau nom").clicked()
+                                || (response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                            {
+                                rename_confirmed = true;
+                            }
+                            if ui.button("✖").on_hover_text("Annuler").clicked() {
+                                visualizer.rename_target = None;
+                                visualizer.rename_error = None;
+                            }
+                        } else {
+                            ui.label(egui::RichText::new(&population.name).size(16.0).strong());
+                            if ui.small_button("✏").on_hover_text("Renommer").clicked() {
+                                visualizer.rename_target = Some(population.clone());
+                                visualizer.rename_buffer = population.name.clone();
+                                visualizer.rename_error = None;
+                            }
+                        }
+
+                        if visualizer.clustering.show_window
+                            && let Some(&cluster) =
+                                visualizer.clustering.membership.get(&population.timestamp)
+                        {
+                            ui.label(
+                                egui::RichText::new(format!("● groupe {}", cluster + 1))
+                                    .color(cluster_color(cluster)),
+                            );
+                        }
 
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.label(
@@ -180,6 +619,14 @@ pub fn visualizer_ui(
                         });
                     });
 
+                    if is_renaming && let Some(rename_error) = &visualizer.rename_error {
+                        ui.label(
+                            egui::RichText::new(rename_error)
+                                .small()
+                                .color(egui::Color32::from_rgb(255, 100, 100)),
+                        );
+                    }
+
                     if let Some(desc) = &population.description {
                         ui.label(
                             egui::RichText::new(desc)
@@ -188,6 +635,43 @@ pub fn visualizer_ui(
                         );
                     }
 
+                    if !population.tags.is_empty() {
+                        ui.horizontal_wrapped(|ui| {
+                            for tag in &population.tags {
+                                ui.label(
+                                    egui::RichText::new(format!("#{}", tag))
+                                        .small()
+                                        .color(egui::Color32::from_rgb(130, 180, 255)),
+                                );
+                            }
+                        });
+                    }
+
+                    if let Some(analysis) = visualizer.analysis_cache.get(&population.timestamp)
+                        && !analysis.predicted_behaviors.is_empty()
+                    {
+                        ui.horizontal_wrapped(|ui| {
+                            for (behavior, confidence) in &analysis.predicted_behaviors {
+                                // Les comportements du filtre actif ressortent
+                                let background = if visualizer.behavior_filter.contains(behavior) {
+                                    egui::Color32::from_rgb(0, 110, 60)
+                                } else {
+                                    egui::Color32::from_rgb(50, 50, 60)
+                                };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} {:.0}%",
+                                        behavior,
+                                        confidence * 100.0
+                                    ))
+                                    .small()
+                                    .color(egui::Color32::WHITE)
+                                    .background_color(background),
+                                );
+                            }
+                        });
+                    }
+
                     ui.separator();
 
                     egui::Grid::new(format!("pop_info_{}", population.timestamp))
@@ -214,10 +698,22 @@ pub fn visualizer_ui(
                                 population.grid_params.depth
                             ));
                             ui.label("Bords:");
-                            ui.label(match population.boundary_mode {
-                                SavedBoundaryMode::Bounce => "Rebond",
-                                SavedBoundaryMode::Teleport => "Téléport",
-                            });
+                            ui.label(BoundaryMode::from(population.boundary_mode).label());
+                            ui.end_row();
+
+                            ui.label("Cohérence:");
+                            ui.label(visualizer.analysis_cache.get(&population.timestamp).map_or(
+                                "-".to_string(),
+                                |analysis| format!("{:.2}", analysis.coherence),
+                            ));
+                            ui.label("Diversité:");
+                            ui.label(
+                                visualizer
+                                    .diversity_cache
+                                    .get(&population_file_path(population))
+                                    .map_or("-".to_string(), |diversity| format!("{:.3}", diversity)),
+                            )
+                            .on_hover_text("Distance génétique moyenne aux populations de même nombre de types");
                             ui.end_row();
                         });
 
@@ -249,7 +745,62 @@ pub fn visualizer_ui(
                             .clicked()
                         {
                             visualizer.selected_population = Some(population.clone());
+                            visualizer.tag_buffer = population.tags.join(", ");
+                        }
+
+                        ui.add_space(10.0);
+
+                        if ui
+                            .add_sized(
+                                [160.0, 40.0],
+                                egui::Button::new(
+                                    egui::RichText::new("🧬 Dupliquer et muter").size(14.0),
+                                ),
+                            )
+                            .on_hover_text(format!(
+                                "Nouveau run de {} simulations partant de copies mutées de ce génome",
+                                visualizer.duplicate.copies
+                            ))
+                            .clicked()
+                        {
+                            start_evolution_from_population(
+                                &mut commands,
+                                population,
+                                &visualizer.duplicate,
+                            );
+                            next_state.set(AppState::Simulation);
                         }
+
+                        ui.add_space(10.0);
+
+                        let compared = visualizer.comparison.contains(population);
+                        if ui
+                            .add_sized(
+                                [120.0, 40.0],
+                                egui::SelectableLabel::new(
+                                    compared,
+                                    egui::RichText::new("⇄ Comparer").size(14.0),
+                                ),
+                            )
+                            .on_hover_text("Sélectionner deux populations pour comparer leurs génomes")
+                            .clicked()
+                        {
+                            visualizer.comparison.toggle(population);
+                        }
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .add_sized(
+                                    [40.0, 40.0],
+                                    egui::Button::new(egui::RichText::new("🗑").size(16.0))
+                                        .fill(egui::Color32::from_rgb(120, 30, 30)),
+                                )
+                                .on_hover_text("Supprimer cette population")
+                                .clicked()
+                            {
+                                visualizer.pending_deletion = Some(population.clone());
+                            }
+                        });
                     });
                 });
 
@@ -257,18 +808,182 @@ pub fn visualizer_ui(
             }
         });
 
+        if rename_confirmed && let Some(target) = visualizer.rename_target.clone() {
+            match rename_population(&target, &visualizer.rename_buffer) {
+                Ok(renamed) => {
+                    info!("Population '{}' renommée en '{}'", target.name, renamed.name);
+                    visualizer.rename_target = None;
+                    visualizer.rename_error = None;
+
+                    match load_all_populations() {
+                        Ok(populations) => available.populations = populations,
+                        Err(e) => error!("Erreur lors du rechargement: {}", e),
+                    }
+                }
+                Err(e) => {
+                    warn!("Renommage impossible: {}", e);
+                    visualizer.rename_error = Some(format!("Renommage impossible: {}", e));
+                }
+            }
+        }
+
         if let Some(ref selected) = visualizer.selected_population.clone() {
-            show_population_details(ctx, &mut visualizer.selected_population, selected);
+            let visualizer = &mut *visualizer;
+            let edited_tags = show_population_details(
+                ctx,
+                &mut visualizer.selected_population,
+                &mut visualizer.tag_buffer,
+                selected,
+            );
+
+            if let Some(tags) = edited_tags {
+                match update_population_tags(selected, tags) {
+                    Ok(updated) => {
+                        info!("Tags de '{}' mis à jour: {:?}", updated.name, updated.tags);
+                        if let Some(stored) = available.populations.iter_mut().find(|p| {
+                            p.name == updated.name && p.timestamp == updated.timestamp
+                        }) {
+                            *stored = updated.clone();
+                        }
+                        if visualizer.selected_population.is_some() {
+                            visualizer.selected_population = Some(updated);
+                        }
+                    }
+                    Err(e) => error!("Erreur lors de la mise à jour des tags: {}", e),
+                }
+            }
+        }
+
+        if visualizer.comparison.populations.len() == 2 {
+            let visualizer = &mut *visualizer;
+            show_genome_comparison(ctx, &mut visualizer.comparison, &visualizer.analysis_cache);
+        }
+
+        if visualizer.clustering.show_window {
+            let visualizer = &mut *visualizer;
+            visualizer.clustering.refresh(&available.populations);
+            if let Some(details) = show_genome_clusters(
+                ctx,
+                &mut visualizer.clustering,
+                &available.populations,
+            ) {
+                visualizer.selected_population = Some(details);
+            }
         }
     });
+
+    if let Some(pending) = visualizer.pending_deletion.clone() {
+        show_delete_confirmation(ctx, &mut visualizer, &mut available, &pending);
+    }
+}
+
+/// L'apport de diversité dépend de toute la bibliothèque : recalculé seulement quand elle
+/// change. Tourne après `visualizer_ui` pour voir les rechargements et suppressions faits
+/// dans l'interface
+pub fn refresh_diversity_cache(
+    available: Res<AvailablePopulations>,
+    mut visualizer: ResMut<VisualizerSelection>,
+) {
+    if !available.is_changed() {
+        return;
+    }
+
+    let genotypes: Vec<Genotype> = available
+        .populations
+        .iter()
+        .map(|population| population.genotype.to_genotype())
+        .collect();
+    visualizer.diversity_cache = available
+        .populations
+        .iter()
+        .map(population_file_path)
+        .zip(diversity_contributions(&genotypes))
+        .collect();
+}
+
+fn show_delete_confirmation(
+    ctx: &egui::Context,
+    visualizer: &mut VisualizerSelection,
+    available: &mut AvailablePopulations,
+    population: &SavedPopulation,
+) {
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Confirmer la suppression")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Supprimer définitivement la population '{}' ({}) ?",
+                population.name, population.timestamp
+            ));
+            ui.label(
+                egui::RichText::new("Cette action est irréversible.")
+                    .color(egui::Color32::from_rgb(255, 150, 0)),
+            );
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add(
+                        egui::Button::new("🗑 Supprimer")
+                            .fill(egui::Color32::from_rgb(150, 30, 30)),
+                    )
+                    .clicked()
+                {
+                    confirmed = true;
+                }
+
+                if ui.button("Annuler").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed {
+        match delete_population_file(population) {
+            Ok(true) => info!("Population '{}' supprimée", population.name),
+            Ok(false) => warn!(
+                "Fichier de la population '{}' déjà absent, retrait de la liste uniquement",
+                population.name
+            ),
+            Err(e) => {
+                error!("Erreur lors de la suppression: {}", e);
+                visualizer.pending_deletion = None;
+                return;
+            }
+        }
+
+        available
+            .populations
+            .retain(|p| !(p.name == population.name && p.timestamp == population.timestamp));
+
+        if visualizer
+            .selected_population
+            .as_ref()
+            .is_some_and(|p| p.name == population.name && p.timestamp == population.timestamp)
+        {
+            visualizer.selected_population = None;
+        }
+    }
+
+    if confirmed || cancelled {
+        visualizer.pending_deletion = None;
+    }
 }
 
+/// Retourne les nouvelles étiquettes quand l'utilisateur valide l'éditeur
 fn show_population_details(
     ctx: &egui::Context,
     selected_ref: &mut Option<SavedPopulation>,
+    tag_buffer: &mut String,
     population: &SavedPopulation,
-) {
+) -> Option<Vec<String>> {
     let mut is_open = true;
+    let mut edited_tags = None;
 
     egui::Window::new(format!("Détails: {}", population.name))
         .resizable(true)
@@ -305,6 +1020,22 @@ fn show_population_details(
                         ui.label("Description:");
                         ui.label(desc);
                     }
+
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Tags:");
+                        let response = ui.add(
+                            egui::TextEdit::singleline(tag_buffer)
+                                .hint_text("essaim, stable, ...")
+                                .desired_width(250.0),
+                        );
+                        if ui.button("💾").on_hover_text("Enregistrer les tags").clicked()
+                            || (response.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                        {
+                            edited_tags = Some(parse_tags(tag_buffer));
+                        }
+                    });
                 });
 
                 ui.add_space(10.0);
@@ -342,6 +1073,20 @@ fn show_population_details(
                                 population.simulation_params.velocity_half_life
                             ));
                             ui.end_row();
+
+                            ui.label("Facteur de force:");
+                            ui.label(format!(
+                                "{:.1}",
+                                population.simulation_params.force_scale_factor
+                            ));
+                            ui.end_row();
+
+                            ui.label("Pas de physique:");
+                            ui.label(format!(
+                                "{:.3}s",
+                                population.simulation_params.physics_timestep
+                            ));
+                            ui.end_row();
                         });
                 });
 
@@ -351,19 +1096,63 @@ fn show_population_details(
                     ui.label(egui::RichText::new("Génome").size(14.0).strong());
                     ui.separator();
 
-                    ui.label(format!(
-                        "Forces particule-particule: {} valeurs",
-                        population.genotype.force_matrix.len()
-                    ));
-                    ui.label(format!(
-                        "Forces nourriture: {} valeurs",
-                        population.genotype.food_forces.len()
-                    ));
-                    ui.label(format!("Types gérés: {}", population.genotype.type_count));
+                    let genotype = population.genotype.to_genotype();
+                    let forces = &genotype.force_matrix;
+
+                    egui::Grid::new("genome_info")
+                        .num_columns(2)
+                        .spacing([20.0, 5.0])
+                        .show(ui, |ui| {
+                            ui.label("Empreinte:");
+                            ui.monospace(format!("{:016x}", genotype.genome_hash()));
+                            ui.end_row();
+
+                            ui.label("Types gérés:");
+                            ui.label(format!("{}", genotype.type_count));
+                            ui.end_row();
+
+                            ui.label("Interactions:");
+                            ui.label(format!("{}", forces.len()));
+                            ui.end_row();
+
+                            if !forces.is_empty() {
+                                let min = forces.iter().copied().fold(f32::INFINITY, f32::min);
+                                let max = forces.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                                let mean = forces.iter().sum::<f32>() / forces.len() as f32;
+                                let attractive = forces.iter().filter(|f| **f > 0.0).count();
+                                let repulsive = forces.iter().filter(|f| **f < 0.0).count();
+
+                                ui.label("Forces min / max:");
+                                ui.label(format!("{:.3} / {:.3}", min, max));
+                                ui.end_row();
+
+                                ui.label("Force moyenne:");
+                                ui.label(format!("{:.3}", mean));
+                                ui.end_row();
 
-                    let interactions =
-                        population.genotype.type_count * population.genotype.type_count;
-                    ui.label(format!("Interactions possibles: {}", interactions));
+                                ui.label("Attractions / répulsions:");
+                                ui.label(format!("{} / {}", attractive, repulsive));
+                                ui.end_row();
+                            }
+
+                            ui.label("Forces nourriture:");
+                            ui.label(
+                                genotype
+                                    .food_forces
+                                    .iter()
+                                    .map(|f| format!("{:.2}", f))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            );
+                            ui.end_row();
+                        });
+
+                    let behaviors = predict_emergent_behaviors(&genotype);
+                    if !behaviors.is_empty() {
+                        ui.separator();
+                        ui.label("Comportements prédits:");
+                        behavior_confidence_bars(ui, behaviors);
+                    }
                 });
 
                 ui.add_space(10.0);
@@ -386,10 +1175,7 @@ fn show_population_details(
                             ui.end_row();
 
                             ui.label("Mode bords:");
-                            ui.label(match population.boundary_mode {
-                                SavedBoundaryMode::Bounce => "Rebond",
-                                SavedBoundaryMode::Teleport => "Téléportation",
-                            });
+                            ui.label(BoundaryMode::from(population.boundary_mode).label());
                             ui.end_row();
 
                             ui.label("Nourritures:");
@@ -406,10 +1192,12 @@ fn show_population_details(
 
                             if population.food_params.respawn_enabled {
                                 ui.label("Temps respawn:");
-                                ui.label(format!(
-                                    "{:.1}s",
-                                    population.food_params.respawn_cooldown
-                                ));
+                                let food = &population.food_params;
+                                ui.label(if food.respawn_jitter > 0.0 {
+                                    format!("{:.1}s ± {:.1}s", food.respawn_cooldown, food.respawn_jitter)
+                                } else {
+                                    format!("{:.1}s", food.respawn_cooldown)
+                                });
                                 ui.end_row();
                             }
                         });
@@ -420,9 +1208,319 @@ fn show_population_details(
     if !is_open {
         *selected_ref = None;
     }
+
+    edited_tags
+}
+
+/// Groupes de génomes proches, par nombre de types, avec leur représentant (médoïde)
+/// et la distance moyenne entre membres. Retourne la population dont on demande les détails
+fn show_genome_clusters(
+    ctx: &egui::Context,
+    clustering: &mut GenomeClustering,
+    populations: &[SavedPopulation],
+) -> Option<SavedPopulation> {
+    let mut is_open = true;
+    let mut details = None;
+
+    egui::Window::new("🧩 Regroupement des génomes")
+        .resizable(true)
+        .default_width(520.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Groupes par nombre de types:");
+                ui.add(egui::Slider::new(&mut clustering.cluster_count, 1..=12));
+            });
+            ui.label(
+                egui::RichText::new(
+                    "Classification hiérarchique (lien moyen) sur la distance génétique",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                let mut current_type_count = None;
+                for (index, cluster) in clustering.clusters.iter().enumerate() {
+                    if current_type_count != Some(cluster.type_count) {
+                        current_type_count = Some(cluster.type_count);
+                        ui.add_space(4.0);
+                        ui.label(
+                            egui::RichText::new(format!("{} types", cluster.type_count)).strong(),
+                        );
+                    }
+
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(format!("● Groupe {}", index + 1))
+                                    .color(cluster_color(index))
+                                    .strong(),
+                            );
+                            ui.label(format!("{} génome(s)", cluster.members.len()));
+                            ui.label(format!("distance moyenne {:.3}", cluster.mean_distance));
+                        });
+
+                        let representative = &populations[cluster.representative];
+                        ui.horizontal(|ui| {
+                            ui.label("Représentant:");
+                            if ui
+                                .link(format!(
+                                    "{} ({:.1})",
+                                    representative.name, representative.score
+                                ))
+                                .on_hover_text("Afficher les détails")
+                                .clicked()
+                            {
+                                details = Some(representative.clone());
+                            }
+                        });
+
+                        let others: Vec<&str> = cluster
+                            .members
+                            .iter()
+                            .filter(|&&member| member != cluster.representative)
+                            .map(|&member| populations[member].name.as_str())
+                            .collect();
+                        if !others.is_empty() {
+                            ui.label(
+                                egui::RichText::new(others.join(", "))
+                                    .small()
+                                    .color(egui::Color32::LIGHT_GRAY),
+                            );
+                        }
+                    });
+                }
+            });
+        });
+
+    if !is_open {
+        clustering.show_window = false;
+    }
+    details
 }
 
-fn load_population_for_visualization(commands: &mut Commands, population: SavedPopulation) {
+fn show_genome_comparison(
+    ctx: &egui::Context,
+    comparison: &mut GenomeComparison,
+    analysis_cache: &HashMap<String, PopulationAnalysis>,
+) {
+    let mut is_open = true;
+    let first = comparison.populations[0].clone();
+    let second = comparison.populations[1].clone();
+    let genotype_a = first.genotype.to_genotype();
+    let genotype_b = second.genotype.to_genotype();
+    let same_types = genotype_a.type_count == genotype_b.type_count;
+
+    egui::Window::new("Comparaison de génomes")
+        .resizable(true)
+        .default_width(900.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            egui::Grid::new("comparison_summary")
+                .num_columns(3)
+                .spacing([20.0, 5.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label(egui::RichText::new(format!("A: {}", first.name)).strong());
+                    ui.label(egui::RichText::new(format!("B: {}", second.name)).strong());
+                    ui.end_row();
+
+                    ui.label("Score:");
+                    ui.label(format!("{:.2}", first.score));
+                    ui.label(format!("{:.2}", second.score));
+                    ui.end_row();
+
+                    ui.label("Types:");
+                    ui.label(format!("{}", genotype_a.type_count));
+                    ui.label(format!("{}", genotype_b.type_count));
+                    ui.end_row();
+
+                    ui.label("Empreinte:");
+                    ui.monospace(format!("{:016x}", genotype_a.genome_hash()));
+                    ui.monospace(format!("{:016x}", genotype_b.genome_hash()));
+                    ui.end_row();
+
+                    ui.label("Comportements prédits:");
+                    for population in [&first, &second] {
+                        let behaviors = analysis_cache
+                            .get(&population.timestamp)
+                            .map(|analysis| {
+                                analysis
+                                    .predicted_behaviors
+                                    .iter()
+                                    .map(|(behavior, confidence)| {
+                                        format!("{} ({:.0}%)", behavior, confidence * 100.0)
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            })
+                            .filter(|behaviors| !behaviors.is_empty())
+                            .unwrap_or_else(|| "—".to_string());
+                        ui.label(behaviors);
+                    }
+                    ui.end_row();
+                });
+
+            ui.separator();
+
+            if same_types {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Distance génétique: {:.3}",
+                        genotype_a.genetic_distance(&genotype_b)
+                    ));
+                    ui.separator();
+                    ui.label("Seuil des différences:");
+                    ui.add(
+                        egui::DragValue::new(&mut comparison.threshold)
+                            .range(0.0..=2.0)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                });
+            } else {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Comparaison impossible : {} types contre {}, les matrices ne se correspondent pas",
+                        genotype_a.type_count, genotype_b.type_count
+                    ))
+                    .color(egui::Color32::from_rgb(255, 100, 100)),
+                );
+            }
+
+            ui.add_space(5.0);
+
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("A").strong());
+                    comparison_matrix_grid(ui, "comparison_a", genotype_a.type_count, |i, j| {
+                        force_cell(genotype_a.get_force(i, j))
+                    });
+                });
+
+                ui.separator();
+
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("B").strong());
+                    comparison_matrix_grid(ui, "comparison_b", genotype_b.type_count, |i, j| {
+                        force_cell(genotype_b.get_force(i, j))
+                    });
+                });
+
+                ui.separator();
+
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("B − A").strong());
+                    if same_types {
+                        let threshold = comparison.threshold;
+                        comparison_matrix_grid(ui, "comparison_delta", genotype_a.type_count, |i, j| {
+                            let delta = genotype_b.get_force(i, j) - genotype_a.get_force(i, j);
+                            let text = egui::RichText::new(format!("{:+.2}", delta)).monospace();
+                            if delta > threshold {
+                                text.background_color(egui::Color32::from_rgb(0, 110, 40))
+                            } else if delta < -threshold {
+                                text.background_color(egui::Color32::from_rgb(130, 30, 30))
+                            } else {
+                                text.color(egui::Color32::GRAY)
+                            }
+                        });
+                    } else {
+                        ui.label(egui::RichText::new("Indisponible").color(egui::Color32::GRAY));
+                    }
+                });
+            });
+        });
+
+    if !is_open {
+        comparison.populations.clear();
+    }
+}
+
+fn comparison_matrix_grid(
+    ui: &mut egui::Ui,
+    id: &str,
+    type_count: usize,
+    cell: impl Fn(usize, usize) -> egui::RichText,
+) {
+    egui::Grid::new(id)
+        .num_columns(type_count + 1)
+        .spacing([6.0, 3.0])
+        .show(ui, |ui| {
+            ui.label("");
+            for j in 0..type_count {
+                ui.label(egui::RichText::new(format!("T{}", j)).small());
+            }
+            ui.end_row();
+
+            for i in 0..type_count {
+                ui.label(egui::RichText::new(format!("T{}", i)).small());
+                for j in 0..type_count {
+                    ui.label(cell(i, j));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn force_cell(force: f32) -> egui::RichText {
+    let color = if force > 0.0 {
+        egui::Color32::from_rgb(120, 220, 120)
+    } else if force < 0.0 {
+        egui::Color32::from_rgb(230, 120, 120)
+    } else {
+        egui::Color32::GRAY
+    };
+    egui::RichText::new(format!("{:+.2}", force)).monospace().color(color)
+}
+
+/// Prépare un run d'évolution dont toutes les simulations partent d'une copie mutée du génome
+fn start_evolution_from_population(
+    commands: &mut Commands,
+    population: &SavedPopulation,
+    settings: &DuplicateSettings,
+) {
+    let (genotype, mut sim_params, grid_params, food_params, particle_config, boundary_mode) =
+        population.to_bevy_resources();
+    sim_params.simulation_count = settings.copies;
+
+    let seed = random_seed();
+    let mut sim_rng = SimulationRng::from_seed(seed);
+    let seed_genomes = (0..settings.copies)
+        .map(|_| {
+            let mut copy = genotype.clone();
+            copy.mutate(settings.mutation_rate, sim_params.mutation_kind, &mut sim_rng.0);
+            copy
+        })
+        .collect();
+
+    commands.insert_resource(sim_params);
+    commands.insert_resource(grid_params);
+    commands.insert_resource(food_params);
+    commands.insert_resource(particle_config);
+    commands.insert_resource(boundary_mode);
+    commands.insert_resource(SeedGenomes(seed_genomes));
+    commands.insert_resource(SimulationSeed(seed));
+    commands.insert_resource(sim_rng);
+    commands.insert_resource(FitnessWeights::default());
+
+    // Nouveau run : historique, hall of fame et archive vierges
+    commands.insert_resource(EvolutionHistory::default());
+    commands.insert_resource(HallOfFame::new(DEFAULT_HALL_OF_FAME_SIZE, false));
+    commands.insert_resource(GenomeArchive::default());
+
+    info!(
+        "Run d'évolution lancé depuis '{}': {} copies mutées (taux {:.0}%, graine {})",
+        population.name,
+        settings.copies,
+        settings.mutation_rate * 100.0,
+        seed
+    );
+}
+
+pub fn load_population_for_visualization(commands: &mut Commands, population: SavedPopulation) {
     let (genotype, sim_params, grid_params, food_params, particle_config, boundary_mode) =
         population.to_bevy_resources();
 
@@ -433,6 +1531,12 @@ fn load_population_for_visualization(commands: &mut Commands, population: SavedP
     commands.insert_resource(boundary_mode);
     commands.insert_resource(VisualizerGenome(genotype));
 
+    // Même graine que le run d'origine : mêmes positions initiales des particules
+    if let Some(seed) = population.simulation_params.seed {
+        commands.insert_resource(SimulationSeed(seed));
+        commands.insert_resource(SimulationRng::from_seed(seed));
+    }
+
     info!(
         "Population '{}' chargée pour visualisation",
         population.name
@@ -0,0 +1,59 @@
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::score::Score;
+use crate::resources::config::simulation::{EpochEndPending, SimulationParameters};
+use crate::states::simulation::SimulationState;
+use crate::systems::lifecycle::confirm_epoch_end;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+/// Invite affichée quand `AutoPauseOnEpochEnd` retient une époque terminée :
+/// classement final et confirmation avant la sélection génétique
+pub fn epoch_end_prompt(
+    mut contexts: EguiContexts,
+    mut sim_params: ResMut<SimulationParameters>,
+    mut epoch_end_pending: ResMut<EpochEndPending>,
+    mut next_state: ResMut<NextState<SimulationState>>,
+    simulations: Query<(&SimulationId, &Score), With<Simulation>>,
+) {
+    if !epoch_end_pending.0 {
+        return;
+    }
+
+    let mut ranking: Vec<(usize, f32)> = simulations
+        .iter()
+        .map(|(sim_id, score)| (sim_id.0, score.get()))
+        .collect();
+    ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut confirmed = false;
+    egui::Window::new(format!("Époque {} terminée", sim_params.current_epoch + 1))
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label("Scores finaux :");
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("epoch_end_ranking")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (rank, (sim_id, score)) in ranking.iter().enumerate() {
+                                ui.label(format!("{}.", rank + 1));
+                                ui.label(format!("Simulation #{}", sim_id + 1));
+                                ui.label(format!("{:.1}", score));
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.separator();
+            ui.label("Appuyez sur Entrée pour évoluer");
+            confirmed = ui.button("🧬 Évoluer").clicked();
+        });
+
+    if confirmed {
+        confirm_epoch_end(&mut sim_params, &mut epoch_end_pending, &mut next_state);
+    }
+}
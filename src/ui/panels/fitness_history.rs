@@ -0,0 +1,150 @@
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::genotype::Genotype;
+use crate::resources::genetics::diversity_guard::DiversityGuard;
+use crate::resources::genetics::history::EvolutionHistory;
+use crate::systems::persistence::run_report::ExportStatsEvent;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use egui_plot::{HLine, Legend, Line, Plot, PlotPoints};
+
+#[derive(Resource, Default)]
+pub struct FitnessHistoryUI {
+    pub show_window: bool,
+    pub overlay_simulation: Option<usize>,
+}
+
+/// Points alignés à droite : la dernière valeur tombe sur la dernière époque
+fn right_aligned_points(values: &[f32], last_epoch: usize) -> PlotPoints<'static> {
+    let first_epoch = (last_epoch + 1).saturating_sub(values.len());
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| [(first_epoch + i) as f64, *value as f64])
+        .collect()
+}
+
+pub fn fitness_history_window(
+    mut contexts: EguiContexts,
+    mut history_ui: ResMut<FitnessHistoryUI>,
+    history: Res<EvolutionHistory>,
+    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
+    mut export_events: EventWriter<ExportStatsEvent>,
+    diversity_guard: Res<DiversityGuard>,
+) {
+    if !history_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+    let last_epoch = history.epoch_count();
+
+    let mut sim_ids: Vec<usize> = simulations.iter().map(|(sim_id, _)| sim_id.0).collect();
+    sim_ids.sort();
+
+    egui::Window::new("Historique de l'évolution")
+        .resizable(true)
+        .default_width(500.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            if last_epoch == 0 {
+                ui.label("Aucune époque terminée pour le moment.");
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Superposer la simulation:");
+                let selected_text = match history_ui.overlay_simulation {
+                    Some(sim_id) => format!("#{}", sim_id + 1),
+                    None => "Aucune".to_string(),
+                };
+                egui::ComboBox::from_id_salt("fitness_overlay")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut history_ui.overlay_simulation, None, "Aucune");
+                        for sim_id in &sim_ids {
+                            ui.selectable_value(
+                                &mut history_ui.overlay_simulation,
+                                Some(*sim_id),
+                                format!("#{}", sim_id + 1),
+                            );
+                        }
+                    });
+
+                if ui
+                    .button("📄 Exporter CSV")
+                    .on_hover_text("Historique par époque dans exports/ (touche E)")
+                    .clicked()
+                {
+                    export_events.write(ExportStatsEvent);
+                }
+            });
+
+            ui.separator();
+            ui.label(egui::RichText::new("Scores par époque").strong());
+
+            Plot::new("fitness_scores_plot")
+                .height(200.0)
+                .legend(Legend::default())
+                .x_axis_label("Époque")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(right_aligned_points(&history.best_scores, last_epoch))
+                            .name("Meilleur")
+                            .color(egui::Color32::from_rgb(0, 200, 0)),
+                    );
+                    plot_ui.line(
+                        Line::new(right_aligned_points(&history.average_scores, last_epoch))
+                            .name("Moyenne")
+                            .color(egui::Color32::from_rgb(100, 200, 255)),
+                    );
+
+                    if let Some(overlay_id) = history_ui.overlay_simulation
+                        && let Some((_, genotype)) =
+                            simulations.iter().find(|(sim_id, _)| sim_id.0 == overlay_id)
+                    {
+                        plot_ui.line(
+                            Line::new(right_aligned_points(&genotype.fitness_history, last_epoch))
+                                .name(format!("Génome #{}", overlay_id + 1))
+                                .color(egui::Color32::YELLOW),
+                        );
+                    }
+                });
+
+            ui.add_space(5.0);
+            ui.label(egui::RichText::new("Diversité génétique").strong());
+
+            Plot::new("fitness_diversity_plot")
+                .height(120.0)
+                .x_axis_label("Époque")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(right_aligned_points(&history.diversity, last_epoch))
+                            .name("Diversité")
+                            .color(egui::Color32::from_rgb(255, 150, 0)),
+                    );
+                    // Seuil de convergence surveillé
+                    if diversity_guard.enabled {
+                        plot_ui.hline(
+                            HLine::new(diversity_guard.threshold)
+                                .name("Seuil")
+                                .color(egui::Color32::from_rgb(220, 100, 100)),
+                        );
+                    }
+                });
+            if diversity_guard.enabled && diversity_guard.low_epochs > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 100, 100),
+                    format!(
+                        "Sous le seuil depuis {} / {} époques",
+                        diversity_guard.low_epochs,
+                        diversity_guard.patience.max(1)
+                    ),
+                );
+            }
+        });
+
+    if !is_open {
+        history_ui.show_window = false;
+    }
+}
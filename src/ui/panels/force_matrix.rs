@@ -1,12 +1,45 @@
-use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::entities::particle::Particle;
+use crate::components::entities::simulation::{ForceRange, Simulation, SimulationId};
 use crate::components::genetics::genotype::Genotype;
+use crate::components::genetics::score::Score;
+use crate::globals::{MAX_GRID_SIZE, MAX_SIMULATION_COUNT, MIN_GRID_SIZE, PARTICLE_COUNT_STEP};
 use crate::plugins::simulation::compute::ComputeEnabled;
 use crate::resources::config::particle_types::ParticleTypesConfig;
-use crate::resources::config::simulation::{SimulationParameters, SimulationSpeed};
-use crate::systems::rendering::viewport_manager::UISpace;
+use crate::resources::config::render_quality::RenderQuality;
+use crate::resources::config::render_style::RenderStyle;
+use crate::resources::config::simulation::{
+    AutoPauseOnEpochEnd, FastForward, ScoringEnabled, SimulationParameters, SimulationSpeed,
+};
+use crate::resources::config::trail::TrailConfig;
+use crate::resources::world::camera::{CameraSettings, CameraTarget};
+use crate::resources::world::food_heatmap::FoodHeatmap;
+use crate::resources::world::grid::{GridBoundsDisplay, GridParameters, GridResizeMode};
+use crate::resources::world::mouse_interaction::MouseInteraction;
+use crate::states::app::AppState;
+use crate::states::simulation::SimulationState;
+use crate::systems::persistence::genome_export::{
+    export_genotype_matrix, exports_dir, import_genotype_matrix, list_exported_matrices,
+};
+use crate::systems::persistence::population_save::{BulkSaveSettings, SaveTopGenomesEvent};
+use crate::ui::panels::fitness_history::FitnessHistoryUI;
+use crate::ui::panels::genome_archive::GenomeArchiveUI;
+use crate::ui::panels::hall_of_fame::HallOfFameUI;
+use crate::ui::panels::interaction_graph::InteractionGraphUI;
+use crate::ui::panels::particle_inspector::ParticleInspectorUI;
+use crate::ui::panels::performance::{PerformanceUI, smoothed_fps};
+use crate::ui::menus::main_menu::{render_quality_combo, render_style_combo};
+use crate::ui::panels::type_colors::TypeColorsUI;
+use crate::systems::rendering::neighbor_coloring::NeighborColoring;
+use crate::systems::simulation::grid_resize::ResizeGridEvent;
+use crate::systems::simulation::particle_count::AdjustParticleCountEvent;
+use crate::systems::rendering::video_recording::{RecordingScale, VideoRecording};
+use crate::systems::rendering::viewport_capture::ViewportCapture;
+use crate::systems::rendering::viewport_manager::{UISpace, ViewportLayout};
+use bevy::diagnostic::DiagnosticsStore;
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 #[derive(Resource)]
 pub struct ForceMatrixUI {
@@ -14,6 +47,18 @@ pub struct ForceMatrixUI {
     pub show_matrix_window: bool,
     pub show_simulations_list: bool,
     pub selected_simulations: HashSet<usize>,
+    /// Simulations en solo : si non vide, seules elles ont un viewport (sélection conservée)
+    pub soloed: HashSet<usize>,
+    /// Simulations masquées sans les retirer de la sélection
+    pub muted: HashSet<usize>,
+    /// Simulations dont la matrice a été modifiée à la main (époque courante)
+    pub edited_simulations: HashSet<usize>,
+    pub edited_epoch: usize,
+    /// Résultat du dernier export/import de matrice (message, succès)
+    pub file_status: Option<(String, bool)>,
+    /// Fichiers proposés à l'import, rafraîchis à la demande
+    pub import_files: Vec<PathBuf>,
+    pub import_selection: Option<PathBuf>,
 }
 
 impl Default for ForceMatrixUI {
@@ -26,16 +71,88 @@ impl Default for ForceMatrixUI {
             show_matrix_window: false,
             show_simulations_list: true,
             selected_simulations,
+            soloed: HashSet::new(),
+            muted: HashSet::new(),
+            edited_simulations: HashSet::new(),
+            edited_epoch: 0,
+            file_status: None,
+            import_files: Vec::new(),
+            import_selection: None,
         }
     }
 }
 
+impl ForceMatrixUI {
+    /// Simulations affichées, triées : le solo prime sur la sélection, le mute retire
+    pub fn visible_simulations(&self) -> Vec<usize> {
+        let base = if self.soloed.is_empty() {
+            &self.selected_simulations
+        } else {
+            &self.soloed
+        };
+        let mut visible: Vec<usize> = base
+            .iter()
+            .filter(|sim_id| !self.muted.contains(sim_id))
+            .copied()
+            .collect();
+        visible.sort();
+        visible
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn speed_control_ui(
     mut contexts: EguiContexts,
     mut sim_params: ResMut<SimulationParameters>,
-    mut ui_space: ResMut<UISpace>,
+    (mut ui_space, mut viewport_layout): (ResMut<UISpace>, ResMut<ViewportLayout>),
     mut compute_enabled: ResMut<ComputeEnabled>,
-    time: Res<Time>,
+    mut history_ui: ResMut<FitnessHistoryUI>,
+    (mut hall_of_fame_ui, mut archive_ui): (ResMut<HallOfFameUI>, ResMut<GenomeArchiveUI>),
+    (mut type_colors_ui, mut graph_ui, mut inspector_ui): (
+        ResMut<TypeColorsUI>,
+        ResMut<InteractionGraphUI>,
+        ResMut<ParticleInspectorUI>,
+    ),
+    mut performance_ui: ResMut<PerformanceUI>,
+    mut mouse_interaction: ResMut<MouseInteraction>,
+    mut camera_settings: ResMut<CameraSettings>,
+    (
+        mut grid_bounds,
+        mut food_heatmap,
+        mut render_quality,
+        mut render_style,
+        grid_params,
+        mut resize_mode,
+        mut resize_events,
+    ): (
+        ResMut<GridBoundsDisplay>,
+        ResMut<FoodHeatmap>,
+        ResMut<RenderQuality>,
+        ResMut<RenderStyle>,
+        Res<GridParameters>,
+        ResMut<GridResizeMode>,
+        EventWriter<ResizeGridEvent>,
+    ),
+    (mut trail_config, mut viewport_capture, mut neighbor_coloring, mut video_recording): (
+        ResMut<TrailConfig>,
+        ResMut<ViewportCapture>,
+        ResMut<NeighborColoring>,
+        ResMut<VideoRecording>,
+    ),
+    (mut fast_forward, mut scoring_enabled, mut auto_pause): (
+        ResMut<FastForward>,
+        ResMut<ScoringEnabled>,
+        ResMut<AutoPauseOnEpochEnd>,
+    ),
+    app_state: Res<State<AppState>>,
+    sim_state: Res<State<SimulationState>>,
+    (diagnostics, particles, mut particle_count_events, mut bulk_save, mut bulk_save_events): (
+        Res<DiagnosticsStore>,
+        Query<(), With<Particle>>,
+        EventWriter<AdjustParticleCountEvent>,
+        ResMut<BulkSaveSettings>,
+        EventWriter<SaveTopGenomesEvent>,
+    ),
 ) {
     let ctx = contexts.ctx_mut();
 
@@ -43,56 +160,450 @@ pub fn speed_control_ui(
         ui.horizontal(|ui| {
             ui.label("Vitesse:");
 
+            // La vitesse et le backend sont pilotés par l'avance rapide tant qu'elle tourne
+            ui.add_enabled_ui(!fast_forward.is_active(), |ui| {
+                if ui
+                    .selectable_label(
+                        sim_params.simulation_speed == SimulationSpeed::Paused,
+                        "⏸ Pause",
+                    )
+                    .clicked()
+                {
+                    sim_params.simulation_speed = SimulationSpeed::Paused;
+                }
+
+                if ui
+                    .selectable_label(
+                        sim_params.simulation_speed == SimulationSpeed::Normal,
+                        "▶ Normal",
+                    )
+                    .clicked()
+                {
+                    sim_params.simulation_speed = SimulationSpeed::Normal;
+                }
+
+                if ui
+                    .selectable_label(
+                        sim_params.simulation_speed == SimulationSpeed::Fast,
+                        "⏩ Rapide (2x)",
+                    )
+                    .clicked()
+                {
+                    sim_params.simulation_speed = SimulationSpeed::Fast;
+                }
+
+                if ui
+                    .selectable_label(
+                        sim_params.simulation_speed == SimulationSpeed::VeryFast,
+                        "⏭ Très rapide (4x)",
+                    )
+                    .clicked()
+                {
+                    sim_params.simulation_speed = SimulationSpeed::VeryFast;
+                }
+
+                ui.separator();
+
+                let gpu_text = if compute_enabled.0 {
+                    "🚀 GPU Activé"
+                } else {
+                    "💻 CPU Only"
+                };
+                if ui.selectable_label(compute_enabled.0, gpu_text).clicked() {
+                    compute_enabled.0 = !compute_enabled.0;
+                    info!("GPU Compute toggled to: {}", compute_enabled.0);
+                }
+            });
+
+            ui.separator();
+
+            // Passe par une copie : la ressource n'est marquée modifiée que sur un vrai changement
+            let mut quality = *render_quality;
+            render_quality_combo(ui, "bar_render_quality", &mut quality);
+            if quality != *render_quality {
+                *render_quality = quality;
+            }
+            let mut style = *render_style;
+            render_style_combo(ui, "bar_render_style", &mut style);
+            if style != *render_style {
+                *render_style = style;
+            }
+
+            let mut target = camera_settings.target;
+            egui::ComboBox::from_id_salt("camera_target")
+                .selected_text(format!("🎯 {}", target.label()))
+                .show_ui(ui, |ui| {
+                    for option in CameraTarget::ALL {
+                        ui.selectable_value(&mut target, option, option.label());
+                    }
+                })
+                .response
+                .on_hover_text("Point autour duquel tournent les caméras");
+            if target != camera_settings.target {
+                camera_settings.target = target;
+            }
+
             if ui
-                .selectable_label(
-                    sim_params.simulation_speed == SimulationSpeed::Paused,
-                    "⏸ Pause",
-                )
+                .selectable_label(grid_bounds.visible, "⬜ Bords")
+                .on_hover_text("Afficher les limites de la grille")
                 .clicked()
             {
-                sim_params.simulation_speed = SimulationSpeed::Paused;
+                grid_bounds.visible = !grid_bounds.visible;
             }
 
+            ui.menu_button("📐 Grille", |ui| {
+                let mut size = Vec3::new(grid_params.width, grid_params.height, grid_params.depth);
+                let mut changed = false;
+                for (label, value) in [
+                    ("Largeur", &mut size.x),
+                    ("Hauteur", &mut size.y),
+                    ("Profondeur", &mut size.z),
+                ] {
+                    changed |= ui
+                        .add(egui::Slider::new(value, MIN_GRID_SIZE..=MAX_GRID_SIZE).text(label))
+                        .changed();
+                }
+                ui.checkbox(&mut resize_mode.rescale, "Étirer les positions")
+                    .on_hover_text(
+                        "Déplace particules et nourriture proportionnellement à la grille ; sinon seules celles qui sortent sont ramenées au bord",
+                    );
+                if changed {
+                    resize_events.write(ResizeGridEvent {
+                        size,
+                        rescale: resize_mode.rescale,
+                    });
+                }
+            });
+
             if ui
-                .selectable_label(
-                    sim_params.simulation_speed == SimulationSpeed::Normal,
-                    "▶ Normal",
+                .selectable_label(food_heatmap.enabled, "🔥 Heatmap")
+                .on_hover_text(
+                    "Densité de nourriture au sol et zones où chaque simulation a mangé pendant l'époque",
                 )
                 .clicked()
             {
-                sim_params.simulation_speed = SimulationSpeed::Normal;
+                food_heatmap.enabled = !food_heatmap.enabled;
             }
 
             if ui
-                .selectable_label(
-                    sim_params.simulation_speed == SimulationSpeed::Fast,
-                    "⏩ Rapide (2x)",
+                .add_enabled(
+                    !compute_enabled.0,
+                    egui::SelectableLabel::new(trail_config.enabled, "〰 Traînées"),
                 )
+                .on_hover_text("Traînées des particules (physique CPU uniquement)")
+                .on_disabled_hover_text("Indisponible avec le calcul GPU")
                 .clicked()
             {
-                sim_params.simulation_speed = SimulationSpeed::Fast;
+                trail_config.enabled = !trail_config.enabled;
+            }
+
+            if trail_config.enabled {
+                let mut length = trail_config.length;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut length)
+                            .range(2..=200)
+                            .prefix("Longueur: "),
+                    )
+                    .changed()
+                {
+                    trail_config.length = length;
+                }
             }
 
             if ui
-                .selectable_label(
-                    sim_params.simulation_speed == SimulationSpeed::VeryFast,
-                    "⏭ Très rapide (4x)",
+                .add_enabled(
+                    !compute_enabled.0,
+                    egui::SelectableLabel::new(neighbor_coloring.enabled, "🔴 Voisins"),
+                )
+                .on_hover_text(
+                    "Teinte chaque particule selon son nombre de voisins à portée : bleu isolée, rouge entourée",
                 )
+                .on_disabled_hover_text("Indisponible avec le calcul GPU")
                 .clicked()
             {
-                sim_params.simulation_speed = SimulationSpeed::VeryFast;
+                neighbor_coloring.enabled = !neighbor_coloring.enabled;
             }
 
-            ui.separator();
+            if *app_state.get() == AppState::Simulation
+                && ui
+                    .selectable_label(history_ui.show_window, "📈 Historique")
+                    .clicked()
+            {
+                history_ui.show_window = !history_ui.show_window;
+            }
+
+            if *app_state.get() == AppState::Simulation
+                && ui
+                    .selectable_label(hall_of_fame_ui.show_window, "🏆 Hall of fame")
+                    .clicked()
+            {
+                hall_of_fame_ui.show_window = !hall_of_fame_ui.show_window;
+            }
+
+            if ui
+                .selectable_label(archive_ui.show_window, "🎞 Archive")
+                .on_hover_text("Revoir le champion d'une époque passée")
+                .clicked()
+            {
+                archive_ui.show_window = !archive_ui.show_window;
+            }
 
-            let gpu_text = if compute_enabled.0 {
-                "🚀 GPU Activé"
+            if ui
+                .selectable_label(type_colors_ui.show_window, "🎨 Couleurs")
+                .clicked()
+            {
+                type_colors_ui.show_window = !type_colors_ui.show_window;
+            }
+
+            if ui
+                .selectable_label(graph_ui.show_window, "🕸 Graphe")
+                .on_hover_text("Graphe orienté des forces entre types de la simulation sélectionnée")
+                .clicked()
+            {
+                graph_ui.show_window = !graph_ui.show_window;
+            }
+
+            let record_label = if video_recording.active {
+                format!("⏹ {} images", video_recording.captured)
             } else {
-                "💻 CPU Only"
+                "⏺ Enregistrer".to_string()
             };
-            if ui.selectable_label(compute_enabled.0, gpu_text).clicked() {
-                compute_enabled.0 = !compute_enabled.0;
-                info!("GPU Compute toggled to: {}", compute_enabled.0);
+            ui.menu_button(record_label, |ui| {
+                ui.add_enabled_ui(!video_recording.active, |ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut video_recording.interval)
+                            .range(1..=120)
+                            .prefix("Une image toutes les ")
+                            .suffix(" frames"),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label("Résolution:");
+                        for scale in RecordingScale::ALL {
+                            ui.radio_value(&mut video_recording.scale, scale, scale.label());
+                        }
+                    });
+                    ui.checkbox(&mut video_recording.include_hud, "Infos")
+                        .on_hover_text("Incruster l'id, l'époque et le score dans les images");
+                });
+
+                if video_recording.active {
+                    if let Some(session) = video_recording.session() {
+                        ui.label(session.display().to_string());
+                    }
+                    if video_recording.dropped > 0 {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            format!(
+                                "{} image(s) abandonnée(s) : augmenter l'intervalle ou réduire la résolution",
+                                video_recording.dropped
+                            ),
+                        );
+                    }
+                    if ui.button("⏹ Arrêter").clicked() {
+                        video_recording.stop();
+                        ui.close_menu();
+                    }
+                } else if ui
+                    .button("⏺ Démarrer")
+                    .on_hover_text(
+                        "Viewport de la simulation sélectionnée en PNG numérotés dans recordings/, à assembler en vidéo",
+                    )
+                    .clicked()
+                {
+                    video_recording.start();
+                    ui.close_menu();
+                }
+            });
+
+            if *app_state.get() == AppState::Simulation {
+                let mut capture_button = ui.button("📷 Capture").on_hover_text(
+                    "Enregistre le viewport de la simulation sélectionnée en PNG dans captures/",
+                );
+                if let Some((message, _)) = &viewport_capture.status {
+                    capture_button = capture_button.on_hover_text(message.as_str());
+                }
+                if capture_button.clicked() {
+                    viewport_capture.requested = true;
+                }
+                ui.checkbox(&mut viewport_capture.include_overlay, "Infos")
+                    .on_hover_text("Incruster l'id, l'époque et le score dans l'image");
+
+                if ui
+                    .selectable_label(inspector_ui.show_window, "🔍 Inspecteur")
+                    .on_hover_text("Alt + clic gauche sur une particule pour voir les forces qu'elle subit")
+                    .clicked()
+                {
+                    inspector_ui.show_window = !inspector_ui.show_window;
+                }
+
+                ui.menu_button(format!("💾 Top {}", bulk_save.count), |ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut bulk_save.count)
+                            .range(1..=MAX_SIMULATION_COUNT)
+                            .prefix("Meilleures simulations: "),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut bulk_save.min_score)
+                            .range(0.0..=f32::MAX)
+                            .speed(0.5)
+                            .prefix("Score minimum: "),
+                    )
+                    .on_hover_text("Les simulations classées sous ce score ne sont pas sauvegardées");
+                    if ui
+                        .button("Sauvegarder le classement")
+                        .on_hover_text("Fichiers run_<époque>_rank<rang> dans populations/")
+                        .clicked()
+                    {
+                        bulk_save_events.write(SaveTopGenomesEvent {
+                            count: bulk_save.count,
+                            min_score: bulk_save.min_score,
+                        });
+                        ui.close_menu();
+                    }
+                });
+            }
+
+            if *app_state.get() == AppState::Simulation {
+                ui.separator();
+
+                ui.label("🖱")
+                    .on_hover_text("Clic droit + Maj : attirer, clic droit + Ctrl : repousser");
+                let mut strength = mouse_interaction.strength;
+                let mut radius = mouse_interaction.radius;
+                ui.add(
+                    egui::DragValue::new(&mut strength)
+                        .speed(1.0)
+                        .range(0.0..=1000.0)
+                        .prefix("Force: "),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut radius)
+                        .speed(1.0)
+                        .range(10.0..=1000.0)
+                        .prefix("Rayon: "),
+                );
+                if strength != mouse_interaction.strength || radius != mouse_interaction.radius {
+                    mouse_interaction.strength = strength;
+                    mouse_interaction.radius = radius;
+                }
+
+                ui.separator();
+
+                if ui
+                    .selectable_label(camera_settings.link_viewports, "🔗 Caméras liées")
+                    .on_hover_text("Tourner un viewport fait tourner tous les autres")
+                    .clicked()
+                {
+                    camera_settings.link_viewports = !camera_settings.link_viewports;
+                }
+
+                let mut layout = *viewport_layout;
+                egui::ComboBox::from_id_salt("viewport_layout")
+                    .selected_text(format!("▦ {}", layout.label()))
+                    .width(90.0)
+                    .show_ui(ui, |ui| {
+                        for option in [
+                            ViewportLayout::Auto,
+                            ViewportLayout::Rows(1),
+                            ViewportLayout::Cols(1),
+                            ViewportLayout::Grid(2, 2),
+                        ] {
+                            let selected =
+                                std::mem::discriminant(&layout) == std::mem::discriminant(&option);
+                            if ui.selectable_label(selected, option.label()).clicked() && !selected {
+                                layout = option;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Disposition des viewports (retour en auto si elle ne suffit pas)");
+                match &mut layout {
+                    ViewportLayout::Auto => {}
+                    ViewportLayout::Rows(count) | ViewportLayout::Cols(count) => {
+                        ui.add(egui::DragValue::new(count).range(1..=MAX_SIMULATION_COUNT));
+                    }
+                    ViewportLayout::Grid(cols, rows) => {
+                        ui.add(egui::DragValue::new(cols).range(1..=MAX_SIMULATION_COUNT));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(rows).range(1..=MAX_SIMULATION_COUNT));
+                    }
+                }
+                if layout != *viewport_layout {
+                    *viewport_layout = layout;
+                }
+
+                if ui
+                    .selectable_label(!scoring_enabled.0, "👁 Mode observation")
+                    .on_hover_text(
+                        "La nourriture ne rapporte plus de points et les génomes restent inchangés d'une époque à l'autre",
+                    )
+                    .clicked()
+                {
+                    scoring_enabled.0 = !scoring_enabled.0;
+                    info!(
+                        "Mode observation {}",
+                        if scoring_enabled.0 { "désactivé" } else { "activé : scores gelés" }
+                    );
+                }
+
+                if ui
+                    .selectable_label(auto_pause.0, "⏸ Pause fin d'époque")
+                    .on_hover_text(
+                        "Met en pause à la fin de chaque époque ; Entrée lance la sélection génétique",
+                    )
+                    .clicked()
+                {
+                    auto_pause.0 = !auto_pause.0;
+                }
+
+                ui.separator();
+
+                if fast_forward.is_active() {
+                    ui.add(
+                        egui::ProgressBar::new(
+                            fast_forward.progress(sim_params.epoch_timer.fraction()),
+                        )
+                        .text(format!(
+                            "⏩ {}/{} époques",
+                            fast_forward.total_epochs - fast_forward.remaining_epochs,
+                            fast_forward.total_epochs
+                        ))
+                        .desired_width(150.0),
+                    );
+                    if ui.button("✖ Annuler").clicked() {
+                        fast_forward.remaining_epochs = 0;
+                        sim_params.simulation_speed = fast_forward.previous_speed.clone();
+                        info!("Avance rapide annulée");
+                    }
+                } else {
+                    ui.add(
+                        egui::DragValue::new(&mut fast_forward.requested_epochs)
+                            .range(1..=500)
+                            .suffix(" ép."),
+                    );
+                    let can_fast_forward =
+                        !compute_enabled.0 && *sim_state.get() == SimulationState::Running;
+                    if ui
+                        .add_enabled(
+                            can_fast_forward,
+                            egui::Button::new(format!(
+                                "⏩ Avancer {} époques",
+                                fast_forward.requested_epochs
+                            )),
+                        )
+                        .on_hover_text("Enchaîne les époques sans attendre l'horloge réelle")
+                        .on_disabled_hover_text(
+                            "Disponible en physique CPU, simulation en cours",
+                        )
+                        .clicked()
+                    {
+                        let epochs = fast_forward.requested_epochs;
+                        let current_speed = sim_params.simulation_speed.clone();
+                        fast_forward.start(epochs, current_speed);
+                        info!("Avance rapide de {} époques", epochs);
+                    }
+                }
             }
 
             ui.separator();
@@ -114,19 +625,130 @@ pub fn speed_control_ui(
 
             ui.separator();
 
-            let fps = 1.0 / time.delta_secs();
-            ui.label(format!("FPS: {:.0}", fps));
+            // Ajout et retrait en cours de run uniquement : le visualiseur garde son génome figé
+            let particle_total = particles.iter().count();
+            ui.add_enabled_ui(*app_state.get() == AppState::Simulation, |ui| {
+                if ui
+                    .small_button("➖")
+                    .on_hover_text(format!(
+                        "Retirer {} particules de chaque type dans chaque simulation",
+                        PARTICLE_COUNT_STEP
+                    ))
+                    .clicked()
+                {
+                    particle_count_events.write(AdjustParticleCountEvent {
+                        per_type: -(PARTICLE_COUNT_STEP as isize),
+                    });
+                }
+                ui.label(format!("Particules: {}", particle_total))
+                    .on_hover_text("Total de toutes les simulations");
+                if ui
+                    .small_button("➕")
+                    .on_hover_text(format!(
+                        "Ajouter {} particules de chaque type présent dans chaque simulation",
+                        PARTICLE_COUNT_STEP
+                    ))
+                    .clicked()
+                {
+                    particle_count_events.write(AdjustParticleCountEvent {
+                        per_type: PARTICLE_COUNT_STEP as isize,
+                    });
+                }
+            });
+
+            ui.separator();
+
+            let fps_text = match smoothed_fps(&diagnostics) {
+                Some(fps) => format!("FPS: {:.0}", fps),
+                None => "FPS: -".to_string(),
+            };
+            if ui
+                .selectable_label(performance_ui.show_window, fps_text)
+                .on_hover_text("Afficher les performances détaillées")
+                .clicked()
+            {
+                performance_ui.show_window = !performance_ui.show_window;
+            }
         });
     });
 
     ui_space.top_panel_height = top_panel_response.response.rect.height();
 }
 
+/// Touches 1-9 : simulation #1 à #9 ; ←/→ : simulation précédente/suivante au classement.
+/// Ouvre la matrice de la simulation choisie, sauf si egui attend une saisie clavier
+pub fn force_matrix_keyboard_selection(
+    mut contexts: EguiContexts,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut ui_state: ResMut<ForceMatrixUI>,
+    sim_params: Res<SimulationParameters>,
+    simulations: Query<(&SimulationId, &Score), With<Simulation>>,
+) {
+    if contexts.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+
+    const DIGITS: [KeyCode; 9] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+
+    let mut ranking: Vec<(usize, f32)> = simulations
+        .iter()
+        .map(|(sim_id, score)| (sim_id.0, score.get()))
+        .collect();
+    if ranking.is_empty() {
+        return;
+    }
+    ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut selection = None;
+
+    if let Some(digit) = DIGITS.iter().position(|key| keyboard.just_pressed(*key)) {
+        let sim_id = digit.min(sim_params.simulation_count.saturating_sub(1));
+        if ranking.iter().any(|(id, _)| *id == sim_id) {
+            selection = Some(sim_id);
+        }
+    }
+
+    let step: isize = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        1
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else {
+        0
+    };
+    if step != 0 {
+        let rank = match ui_state
+            .selected_simulation
+            .and_then(|current| ranking.iter().position(|(id, _)| *id == current))
+        {
+            Some(rank) => (rank as isize + step).rem_euclid(ranking.len() as isize) as usize,
+            None => 0,
+        };
+        selection = Some(ranking[rank].0);
+    }
+
+    if let Some(sim_id) = selection {
+        ui_state.selected_simulation = Some(sim_id);
+        ui_state.show_matrix_window = true;
+    }
+}
+
 pub fn force_matrix_window(
     mut contexts: EguiContexts,
     mut ui_state: ResMut<ForceMatrixUI>,
     particle_config: Res<ParticleTypesConfig>,
-    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
+    sim_params: Res<SimulationParameters>,
+    sim_state: Res<State<SimulationState>>,
+    mut simulations: Query<(&SimulationId, &mut Genotype, &mut ForceRange), With<Simulation>>,
 ) {
     if !ui_state.show_matrix_window || ui_state.selected_simulation.is_none() {
         return;
@@ -135,6 +757,21 @@ pub fn force_matrix_window(
     let ctx = contexts.ctx_mut();
     let selected_sim = ui_state.selected_simulation.unwrap();
 
+    // Les génomes sont remplacés à chaque époque
+    if ui_state.edited_epoch != sim_params.current_epoch {
+        ui_state.edited_simulations.clear();
+        ui_state.edited_epoch = sim_params.current_epoch;
+    }
+
+    // L'édition n'est permise qu'en pause pour ne pas lutter contre la physique
+    let editable = *sim_state.get() == SimulationState::Paused
+        || sim_params.simulation_speed == SimulationSpeed::Paused;
+    let mut show_window = ui_state.show_matrix_window;
+    let mut edited = ui_state.edited_simulations.contains(&selected_sim);
+    let mut file_status = None;
+    let mut import_selection = ui_state.import_selection.clone();
+    let mut refresh_imports = false;
+
     egui::Window::new(format!(
         "Matrice des Forces - Simulation #{}",
         selected_sim + 1
@@ -142,20 +779,79 @@ pub fn force_matrix_window(
     .resizable(true)
     .collapsible(true)
     .min_width(500.0)
-    .open(&mut ui_state.show_matrix_window)
+    .open(&mut show_window)
     .show(ctx, |ui| {
-        if let Some((_, genotype)) = simulations
-            .iter()
-            .find(|(sim_id, _)| sim_id.0 == selected_sim)
+        if let Some((_, mut genotype, mut force_range)) = simulations
+            .iter_mut()
+            .find(|(sim_id, _, _)| sim_id.0 == selected_sim)
         {
             let type_count = particle_config.type_count;
 
             ui.label(format!("Types de particules: {}", type_count));
+            ui.horizontal(|ui| {
+                ui.label("Portée des forces:");
+                let mut range = force_range.0;
+                ui.add_enabled(
+                    editable,
+                    egui::DragValue::new(&mut range)
+                        .range(10.0..=500.0)
+                        .suffix(" unités"),
+                )
+                .on_hover_text(format!(
+                    "Portée propre à cette simulation (globale: {:.0})",
+                    sim_params.max_force_range
+                ));
+                if range != force_range.0 {
+                    force_range.0 = range;
+                }
+            });
             ui.label(
                 egui::RichText::new("Forces normalisées entre -2.000 et +2.000")
                     .small()
                     .color(egui::Color32::from_rgb(150, 150, 150)),
             );
+            ui.horizontal(|ui| {
+                if editable {
+                    ui.label(
+                        egui::RichText::new("✏ Édition activée")
+                            .small()
+                            .color(egui::Color32::from_rgb(100, 200, 255)),
+                    );
+                } else {
+                    ui.label(
+                        egui::RichText::new("Mettre en pause pour éditer la matrice")
+                            .small()
+                            .color(egui::Color32::from_rgb(150, 150, 150)),
+                    );
+                }
+
+                if ui
+                    .add_enabled(editable, egui::Button::new("✨ Forces intéressantes").small())
+                    .on_hover_text(
+                        "Préréglage pierre-feuille-ciseaux (3 types) ou en cycle (4 types), aléatoire validé sinon",
+                    )
+                    .clicked()
+                {
+                    // Édition manuelle : hors de la séquence reproductible du run
+                    genotype.set_interesting_forces(
+                        sim_params.allow_self_attraction,
+                        &mut rand::rng(),
+                    );
+                    edited = true;
+                    info!(
+                        "Forces intéressantes appliquées à la simulation #{}",
+                        selected_sim + 1
+                    );
+                }
+
+                if edited {
+                    ui.label(
+                        egui::RichText::new("• Matrice modifiée manuellement")
+                            .small()
+                            .color(egui::Color32::YELLOW),
+                    );
+                }
+            });
             ui.separator();
 
             // Matrice des forces particule-particule
@@ -166,66 +862,107 @@ pub fn force_matrix_window(
             );
             ui.add_space(5.0);
 
-            egui::Grid::new("force_matrix_grid")
-                .num_columns(type_count + 1)
-                .spacing([10.0, 4.0])
-                .min_col_width(70.0)
+            // Au-delà de quelques types, la matrice dépasse la fenêtre
+            egui::ScrollArea::both()
+                .id_salt("force_matrix_scroll")
+                .max_height(420.0)
                 .show(ui, |ui| {
-                    ui.label("De\\Vers");
-
-                    for j in 0..type_count {
-                        let (color, _) = particle_config.get_color_for_type(j);
-                        ui.label(
-                            egui::RichText::new(format!("Type {}", j))
-                                .color(egui::Color32::from_rgb(
-                                    (color.to_srgba().red * 255.0) as u8,
-                                    (color.to_srgba().green * 255.0) as u8,
-                                    (color.to_srgba().blue * 255.0) as u8,
-                                ))
-                                .strong(),
-                        );
-                    }
-                    ui.end_row();
+                    egui::Grid::new("force_matrix_grid")
+                        .num_columns(type_count + 1)
+                        .spacing([10.0, 4.0])
+                        .min_col_width(70.0)
+                        .show(ui, |ui| {
+                            ui.label("De\\Vers");
 
-                    for _ in 0..=type_count {
-                        ui.separator();
-                    }
-                    ui.end_row();
-
-                    for i in 0..type_count {
-                        let (color, _) = particle_config.get_color_for_type(i);
-                        ui.label(
-                            egui::RichText::new(format!("Type {}", i))
-                                .color(egui::Color32::from_rgb(
-                                    (color.to_srgba().red * 255.0) as u8,
-                                    (color.to_srgba().green * 255.0) as u8,
-                                    (color.to_srgba().blue * 255.0) as u8,
-                                ))
-                                .strong(),
-                        );
+                            for j in 0..type_count {
+                                let (color, _) = particle_config.get_color_for_type(j);
+                                ui.label(
+                                    egui::RichText::new(format!("Type {}", j))
+                                        .color(egui::Color32::from_rgb(
+                                            (color.to_srgba().red * 255.0) as u8,
+                                            (color.to_srgba().green * 255.0) as u8,
+                                            (color.to_srgba().blue * 255.0) as u8,
+                                        ))
+                                        .strong(),
+                                );
+                            }
+                            ui.end_row();
 
-                        for j in 0..type_count {
-                            let force = genotype.get_force(i, j);
-
-                            let color = if force.abs() < 0.05 {
-                                egui::Color32::from_rgb(120, 120, 120)
-                            } else if force > 0.0 {
-                                let intensity = (force.abs() * 127.5 + 127.5) as u8;
-                                egui::Color32::from_rgb(0, intensity.max(100), 0)
-                            } else {
-                                let intensity = (force.abs() * 127.5 + 127.5) as u8;
-                                egui::Color32::from_rgb(intensity.max(100), 0, 0)
-                            };
-
-                            ui.label(
-                                egui::RichText::new(format!("{:+.3}", force))
-                                    .color(color)
-                                    .monospace()
-                                    .size(11.0),
-                            );
-                        }
-                        ui.end_row();
-                    }
+                            for _ in 0..=type_count {
+                                ui.separator();
+                            }
+                            ui.end_row();
+
+                            for i in 0..type_count {
+                                let (color, _) = particle_config.get_color_for_type(i);
+                                ui.label(
+                                    egui::RichText::new(format!("Type {}", i))
+                                        .color(egui::Color32::from_rgb(
+                                            (color.to_srgba().red * 255.0) as u8,
+                                            (color.to_srgba().green * 255.0) as u8,
+                                            (color.to_srgba().blue * 255.0) as u8,
+                                        ))
+                                        .strong(),
+                                );
+
+                                for j in 0..type_count {
+                                    let force = genotype.get_force(i, j);
+                                    let locked = genotype.is_locked(i, j);
+
+                                    let color = if force.abs() < 0.05 {
+                                        egui::Color32::from_rgb(120, 120, 120)
+                                    } else if force > 0.0 {
+                                        let intensity = (force.abs() * 127.5 + 127.5) as u8;
+                                        egui::Color32::from_rgb(0, intensity.max(100), 0)
+                                    } else {
+                                        let intensity = (force.abs() * 127.5 + 127.5) as u8;
+                                        egui::Color32::from_rgb(intensity.max(100), 0, 0)
+                                    };
+
+                                    if editable {
+                                        ui.horizontal(|ui| {
+                                            let mut value = force;
+                                            if ui
+                                                .add(
+                                                    egui::DragValue::new(&mut value)
+                                                        .range(-2.0..=2.0)
+                                                        .speed(0.01)
+                                                        .fixed_decimals(3),
+                                                )
+                                                .changed()
+                                            {
+                                                genotype.set_force(i, j, value);
+                                                edited = true;
+                                            }
+
+                                            let lock_icon = if locked { "🔒" } else { "🔓" };
+                                            if ui
+                                                .small_button(lock_icon)
+                                                .on_hover_text(
+                                                    "Figer cette relation (ignorée par mutation et crossover)",
+                                                )
+                                                .clicked()
+                                            {
+                                                genotype.set_locked(i, j, !locked);
+                                            }
+                                        });
+                                    } else {
+                                        let text = if locked {
+                                            format!("🔒{:+.3}", force)
+                                        } else {
+                                            format!("{:+.3}", force)
+                                        };
+                                        let mut label =
+                                            egui::RichText::new(text).color(color).monospace().size(11.0);
+                                        if locked {
+                                            label = label.background_color(egui::Color32::from_rgb(30, 45, 70));
+                                        }
+                                        ui.label(label);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
                 });
 
             ui.add_space(10.0);
@@ -239,46 +976,66 @@ pub fn force_matrix_window(
             );
             ui.add_space(5.0);
 
-            egui::Grid::new("food_forces_grid")
-                .num_columns(type_count)
-                .spacing([20.0, 5.0])
-                .min_col_width(70.0)
+            egui::ScrollArea::horizontal()
+                .id_salt("food_forces_scroll")
                 .show(ui, |ui| {
-                    for i in 0..type_count {
-                        let (color, _) = particle_config.get_color_for_type(i);
-                        ui.label(
-                            egui::RichText::new(format!("Type {}", i))
-                                .color(egui::Color32::from_rgb(
-                                    (color.to_srgba().red * 255.0) as u8,
-                                    (color.to_srgba().green * 255.0) as u8,
-                                    (color.to_srgba().blue * 255.0) as u8,
-                                ))
-                                .strong(),
-                        );
-                    }
-                    ui.end_row();
-
-                    for i in 0..type_count {
-                        let food_force = genotype.get_food_force(i);
-
-                        let color = if food_force.abs() < 0.05 {
-                            egui::Color32::from_rgb(120, 120, 120)
-                        } else if food_force > 0.0 {
-                            let intensity = (food_force.abs() * 127.5 + 127.5) as u8;
-                            egui::Color32::from_rgb(0, intensity.max(100), 0)
-                        } else {
-                            let intensity = (food_force.abs() * 127.5 + 127.5) as u8;
-                            egui::Color32::from_rgb(intensity.max(100), 0, 0)
-                        };
-
-                        ui.label(
-                            egui::RichText::new(format!("{:+.3}", food_force))
-                                .color(color)
-                                .monospace()
-                                .size(12.0),
-                        );
-                    }
-                    ui.end_row();
+                    egui::Grid::new("food_forces_grid")
+                        .num_columns(type_count)
+                        .spacing([20.0, 5.0])
+                        .min_col_width(70.0)
+                        .show(ui, |ui| {
+                            for i in 0..type_count {
+                                let (color, _) = particle_config.get_color_for_type(i);
+                                ui.label(
+                                    egui::RichText::new(format!("Type {}", i))
+                                        .color(egui::Color32::from_rgb(
+                                            (color.to_srgba().red * 255.0) as u8,
+                                            (color.to_srgba().green * 255.0) as u8,
+                                            (color.to_srgba().blue * 255.0) as u8,
+                                        ))
+                                        .strong(),
+                                );
+                            }
+                            ui.end_row();
+
+                            for i in 0..type_count {
+                                let food_force = genotype.get_food_force(i);
+
+                                let color = if food_force.abs() < 0.05 {
+                                    egui::Color32::from_rgb(120, 120, 120)
+                                } else if food_force > 0.0 {
+                                    let intensity = (food_force.abs() * 127.5 + 127.5) as u8;
+                                    egui::Color32::from_rgb(0, intensity.max(100), 0)
+                                } else {
+                                    let intensity = (food_force.abs() * 127.5 + 127.5) as u8;
+                                    egui::Color32::from_rgb(intensity.max(100), 0, 0)
+                                };
+
+                                if editable {
+                                    let mut value = food_force;
+                                    if ui
+                                        .add(
+                                            egui::DragValue::new(&mut value)
+                                                .range(-2.0..=2.0)
+                                                .speed(0.01)
+                                                .fixed_decimals(3),
+                                        )
+                                        .changed()
+                                    {
+                                        genotype.set_food_force(i, value);
+                                        edited = true;
+                                    }
+                                } else {
+                                    ui.label(
+                                        egui::RichText::new(format!("{:+.3}", food_force))
+                                            .color(color)
+                                            .monospace()
+                                            .size(12.0),
+                                    );
+                                }
+                            }
+                            ui.end_row();
+                        });
                 });
 
             ui.add_space(10.0);
@@ -294,10 +1051,140 @@ pub fn force_matrix_window(
                     genotype.food_forces.len()
                 ));
                 ui.label(format!("Types de particules: {}", genotype.type_count));
+                ui.label(format!(
+                    "Relations figées: {}",
+                    genotype.locked_mask.iter().filter(|&&locked| locked).count()
+                ));
+                ui.separator();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Facteur de force appliqué: {:.1}",
+                        sim_params.force_scale_factor
+                    ))
+                    .strong(),
+                );
+                ui.label(format!(
+                    "Forces réelles = valeurs × {:.1}",
+                    sim_params.force_scale_factor
+                ));
+            });
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .button("📤 Exporter la matrice")
+                    .on_hover_text("CSV et JSON dans le dossier exports/")
+                    .clicked()
+                {
+                    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+                    let path = exports_dir().join(format!(
+                        "genome_sim{}_epoch{}_{}",
+                        selected_sim + 1,
+                        sim_params.current_epoch,
+                        timestamp
+                    ));
+
+                    file_status = Some(match export_genotype_matrix(&genotype, &path) {
+                        Ok((csv_path, _)) => {
+                            info!("Matrice exportée vers {}", csv_path.display());
+                            refresh_imports = true;
+                            (format!("Exporté: {}.csv/.json", path.display()), true)
+                        }
+                        Err(e) => {
+                            error!("Erreur lors de l'export de la matrice: {}", e);
+                            (format!("Erreur: {}", e), false)
+                        }
+                    });
+                }
+
                 ui.separator();
-                ui.label(egui::RichText::new("Facteur de force appliqué: 80.0").strong());
-                ui.label("Forces réelles = valeurs × 80.0");
+
+                let selected_text = import_selection
+                    .as_ref()
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "Choisir un fichier...".to_string());
+                egui::ComboBox::from_id_salt("matrix_import_file")
+                    .selected_text(selected_text)
+                    .width(200.0)
+                    .show_ui(ui, |ui| {
+                        if ui_state.import_files.is_empty() {
+                            refresh_imports = true;
+                            ui.label("Aucun fichier dans exports/");
+                        }
+                        for path in &ui_state.import_files {
+                            let name = path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default();
+                            ui.selectable_value(&mut import_selection, Some(path.clone()), name);
+                        }
+                    });
+                if ui.button("🔄").on_hover_text("Rafraîchir la liste").clicked() {
+                    refresh_imports = true;
+                }
+
+                if ui
+                    .add_enabled(
+                        import_selection.is_some(),
+                        egui::Button::new("📥 Importer"),
+                    )
+                    .on_hover_text("Remplace le génome de cette simulation")
+                    .clicked()
+                    && let Some(path) = &import_selection
+                {
+                    file_status = Some(
+                        match import_genotype_matrix(path, particle_config.type_count) {
+                            Ok(imported) => {
+                                *genotype = imported;
+                                edited = true;
+                                info!(
+                                    "Génome importé depuis {} dans la simulation #{}",
+                                    path.display(),
+                                    selected_sim + 1
+                                );
+                                (format!("Importé: {}", path.display()), true)
+                            }
+                            Err(e) => {
+                                warn!("Import de {} refusé: {}", path.display(), e);
+                                (format!("Import refusé: {}", e), false)
+                            }
+                        },
+                    );
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if let Some((message, success)) = &ui_state.file_status {
+                    let color = if *success {
+                        egui::Color32::from_rgb(100, 200, 100)
+                    } else {
+                        egui::Color32::RED
+                    };
+                    ui.label(egui::RichText::new(message).small().color(color));
+                }
             });
         }
     });
+
+    if file_status.is_some() {
+        ui_state.file_status = file_status;
+    }
+    if import_selection != ui_state.import_selection {
+        ui_state.import_selection = import_selection;
+    }
+    if refresh_imports {
+        let files = list_exported_matrices();
+        if files != ui_state.import_files {
+            ui_state.import_files = files;
+        }
+    }
+
+    // Évite de marquer la ressource comme modifiée à chaque frame (recalcul des viewports)
+    if ui_state.show_matrix_window != show_window {
+        ui_state.show_matrix_window = show_window;
+    }
+    if edited && !ui_state.edited_simulations.contains(&selected_sim) {
+        ui_state.edited_simulations.insert(selected_sim);
+    }
 }
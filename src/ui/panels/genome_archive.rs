@@ -0,0 +1,161 @@
+use crate::components::entities::simulation::Simulation;
+use crate::components::genetics::genotype::Genotype;
+use crate::components::genetics::score::Score;
+use crate::resources::config::food::FoodParameters;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::resources::config::seed::SimulationSeed;
+use crate::resources::config::simulation::SimulationParameters;
+use crate::resources::genetics::genome_archive::GenomeArchive;
+use crate::resources::world::boundary::BoundaryMode;
+use crate::resources::world::grid::GridParameters;
+use crate::states::app::AppState;
+use crate::systems::persistence::population_save::SavedPopulation;
+use crate::ui::menus::visualizer_menu::{VisualizerGenome, load_population_for_visualization};
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+#[derive(Resource, Default)]
+pub struct GenomeArchiveUI {
+    pub show_window: bool,
+    /// Époque choisie sur la frise (None = la plus récente)
+    pub selected_epoch: Option<usize>,
+}
+
+/// Frise des époques archivées : en simulation, ouvre un champion dans le visualiseur ;
+/// en visualisation, remplace à chaud le génome de la simulation affichée
+#[allow(clippy::too_many_arguments)]
+pub fn genome_archive_window(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut archive_ui: ResMut<GenomeArchiveUI>,
+    archive: Res<GenomeArchive>,
+    app_state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    sim_params: Res<SimulationParameters>,
+    grid_params: Res<GridParameters>,
+    food_params: Res<FoodParameters>,
+    particle_config: Res<ParticleTypesConfig>,
+    boundary_mode: Res<BoundaryMode>,
+    seed: Res<SimulationSeed>,
+    visualizer_genome: Option<ResMut<VisualizerGenome>>,
+    mut simulations: Query<(&mut Genotype, &mut Score), With<Simulation>>,
+) {
+    if !archive_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+    let mut chosen = None;
+    let in_visualization = *app_state.get() == AppState::Visualization;
+
+    egui::Window::new("🎞 Archive des époques")
+        .resizable(true)
+        .default_width(380.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            let Some((first_epoch, last_epoch)) = archive.epoch_range() else {
+                ui.label("Aucune époque archivée pour le moment.");
+                return;
+            };
+
+            let mut epoch = archive_ui
+                .selected_epoch
+                .unwrap_or(last_epoch)
+                .clamp(first_epoch, last_epoch);
+            ui.horizontal(|ui| {
+                ui.label("Époque:");
+                ui.add(egui::Slider::new(&mut epoch, first_epoch..=last_epoch));
+                if ui
+                    .selectable_label(archive_ui.selected_epoch.is_none(), "⏭ Dernière")
+                    .on_hover_text("Suivre la dernière époque terminée")
+                    .clicked()
+                {
+                    archive_ui.selected_epoch = None;
+                    epoch = last_epoch;
+                }
+            });
+            if epoch != archive_ui.selected_epoch.unwrap_or(last_epoch) {
+                archive_ui.selected_epoch = Some(epoch);
+            }
+
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} époque(s) archivée(s), {} génome(s) par époque",
+                    archive.epochs.len(),
+                    archive.depth
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.separator();
+
+            let Some(archived) = archive.get(epoch) else {
+                return;
+            };
+
+            egui::Grid::new("genome_archive_grid")
+                .num_columns(4)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("#").strong());
+                    ui.label(egui::RichText::new("Score").strong());
+                    ui.label(egui::RichText::new("Simulation").strong());
+                    ui.label("");
+                    ui.end_row();
+
+                    for (rank, genome) in archived.genomes.iter().enumerate() {
+                        ui.label(format!("{}", rank + 1));
+                        ui.label(format!("{:.1}", genome.score));
+                        ui.label(format!("#{}", genome.simulation_id + 1));
+                        let hover = if in_visualization {
+                            "Remplacer le génome visualisé"
+                        } else {
+                            "Ouvrir dans le visualiseur (termine le run en cours)"
+                        };
+                        if ui.small_button("👁").on_hover_text(hover).clicked() {
+                            chosen = Some((archived.epoch, rank));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some((epoch, rank)) = chosen
+        && let Some(genome) = archive
+            .get(epoch)
+            .and_then(|archived| archived.genomes.get(rank))
+    {
+        if in_visualization {
+            for (mut genotype, mut score) in simulations.iter_mut() {
+                *genotype = genome.genotype.clone();
+                *score = Score::default();
+            }
+            if let Some(mut visualizer_genome) = visualizer_genome {
+                visualizer_genome.0 = genome.genotype.clone();
+            }
+            info!("🎞 Génome #{} de l'époque {} visualisé", rank + 1, epoch);
+        } else {
+            let population = SavedPopulation::from_current_state(
+                format!("archive_epoque{}_{}", epoch, rank + 1),
+                Some(format!("Champion #{} de l'époque {}", rank + 1, epoch)),
+                vec!["archive".to_string()],
+                &genome.genotype,
+                genome.score,
+                &sim_params,
+                &grid_params,
+                &food_params,
+                &particle_config,
+                &boundary_mode,
+                Some(seed.0),
+            );
+            load_population_for_visualization(&mut commands, population);
+            next_state.set(AppState::Visualization);
+        }
+    }
+
+    if !is_open {
+        archive_ui.show_window = false;
+    }
+}
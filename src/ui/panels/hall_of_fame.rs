@@ -0,0 +1,94 @@
+use crate::resources::genetics::hall_of_fame::HallOfFame;
+use crate::systems::persistence::population_save::{SavePopulationEvent};
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+#[derive(Resource, Default)]
+pub struct HallOfFameUI {
+    pub show_window: bool,
+}
+
+pub fn hall_of_fame_window(
+    mut contexts: EguiContexts,
+    mut hall_of_fame_ui: ResMut<HallOfFameUI>,
+    hall_of_fame: Res<HallOfFame>,
+    mut save_events: EventWriter<SavePopulationEvent>,
+) {
+    if !hall_of_fame_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+    let mut to_save = None;
+
+    egui::Window::new("🏆 Hall of fame")
+        .resizable(true)
+        .default_width(360.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} meilleurs génomes du run{}",
+                    hall_of_fame.capacity,
+                    if hall_of_fame.reinject {
+                        " (réinjection active)"
+                    } else {
+                        ""
+                    }
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.separator();
+
+            if hall_of_fame.entries.is_empty() {
+                ui.label("Aucune époque terminée pour le moment.");
+                return;
+            }
+
+            egui::Grid::new("hall_of_fame_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("#").strong());
+                    ui.label(egui::RichText::new("Score").strong());
+                    ui.label(egui::RichText::new("Époque").strong());
+                    ui.label(egui::RichText::new("Simulation").strong());
+                    ui.label("");
+                    ui.end_row();
+
+                    for (rank, entry) in hall_of_fame.entries.iter().enumerate() {
+                        ui.label(format!("{}", rank + 1));
+                        ui.label(format!("{:.1}", entry.score));
+                        ui.label(format!("{}", entry.epoch));
+                        ui.label(format!("#{}", entry.simulation_id + 1));
+                        if ui.small_button("💾").on_hover_text("Sauvegarder ce génome").clicked() {
+                            to_save = Some(rank);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(rank) = to_save {
+        let entry = &hall_of_fame.entries[rank];
+        save_events.write(SavePopulationEvent {
+            simulation_id: entry.simulation_id,
+            name: format!("hof_epoque{}_sim{}", entry.epoch, entry.simulation_id + 1),
+            description: Some(format!(
+                "Hall of fame #{} (score {:.1}, époque {})",
+                rank + 1,
+                entry.score,
+                entry.epoch
+            )),
+            tags: vec!["hall of fame".to_string()],
+            genome: Some((entry.genotype.clone(), entry.score)),
+        });
+    }
+
+    if !is_open {
+        hall_of_fame_ui.show_window = false;
+    }
+}
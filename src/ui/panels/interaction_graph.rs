@@ -0,0 +1,270 @@
+use crate::components::entities::simulation::{Simulation, SimulationId};
+use crate::components::genetics::behavior::predict_emergent_behaviors;
+use crate::components::genetics::genotype::Genotype;
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::ui::panels::force_matrix::ForceMatrixUI;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use std::f32::consts::TAU;
+
+/// Comportements prédits et leur confiance, une barre par comportement
+pub fn behavior_confidence_bars<'a>(
+    ui: &mut egui::Ui,
+    behaviors: impl IntoIterator<Item = (&'a str, f32)>,
+) {
+    egui::Grid::new(ui.next_auto_id())
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            for (behavior, confidence) in behaviors {
+                ui.label(behavior);
+                ui.add(
+                    egui::ProgressBar::new(confidence)
+                        .desired_width(120.0)
+                        .text(format!("{:.0}%", confidence * 100.0)),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+/// Rayon d'un nœud de type, en points egui
+const NODE_RADIUS: f32 = 16.0;
+/// Écart entre les arêtes A→B et B→A pour qu'elles ne se superposent pas
+const EDGE_OFFSET: f32 = 5.0;
+/// Magnitude maximale d'une force du génome
+const MAX_FORCE: f32 = 2.0;
+
+#[derive(Resource)]
+pub struct InteractionGraphUI {
+    pub show_window: bool,
+    /// Les arêtes de magnitude inférieure sont masquées
+    pub min_magnitude: f32,
+    pub show_values: bool,
+    pub show_food: bool,
+}
+
+impl Default for InteractionGraphUI {
+    fn default() -> Self {
+        Self {
+            show_window: false,
+            min_magnitude: 0.1,
+            show_values: false,
+            show_food: true,
+        }
+    }
+}
+
+/// Vert pour l'attraction, rouge pour la répulsion, opacité selon la magnitude
+fn force_stroke(force: f32) -> egui::Stroke {
+    let strength = (force.abs() / MAX_FORCE).clamp(0.0, 1.0);
+    let alpha = (80.0 + 175.0 * strength) as u8;
+    let color = if force >= 0.0 {
+        egui::Color32::from_rgba_unmultiplied(80, 220, 100, alpha)
+    } else {
+        egui::Color32::from_rgba_unmultiplied(230, 70, 70, alpha)
+    };
+    egui::Stroke::new(1.0 + 4.0 * strength, color)
+}
+
+fn type_color32(particle_config: &ParticleTypesConfig, particle_type: usize) -> egui::Color32 {
+    let (color, _) = particle_config.get_color_for_type(particle_type);
+    let [red, green, blue, _] = color.to_srgba().to_u8_array();
+    egui::Color32::from_rgb(red, green, blue)
+}
+
+/// Flèche de `from` vers `to`, raccourcie pour s'arrêter au bord des nœuds
+fn draw_edge(
+    painter: &egui::Painter,
+    from: egui::Pos2,
+    to: egui::Pos2,
+    offset: f32,
+    force: f32,
+    show_value: bool,
+) {
+    let direction = (to - from).normalized();
+    let normal = egui::vec2(-direction.y, direction.x) * offset;
+    let start = from + direction * NODE_RADIUS + normal;
+    let end = to - direction * (NODE_RADIUS + 2.0) + normal;
+    let stroke = force_stroke(force);
+
+    painter.line_segment([start, end], stroke);
+    let head = 8.0 + stroke.width;
+    let back = end - direction * head;
+    let side = egui::vec2(-direction.y, direction.x) * head * 0.5;
+    painter.add(egui::Shape::convex_polygon(
+        vec![end, back + side, back - side],
+        stroke.color,
+        egui::Stroke::NONE,
+    ));
+
+    if show_value {
+        painter.text(
+            start + (end - start) * 0.35 + normal * 2.0,
+            egui::Align2::CENTER_CENTER,
+            format!("{:+.2}", force),
+            egui::FontId::monospace(10.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+    }
+}
+
+/// Boucle extérieure au nœud pour les forces d'un type sur lui-même
+fn draw_self_loop(
+    painter: &egui::Painter,
+    node: egui::Pos2,
+    outward: egui::Vec2,
+    force: f32,
+    show_value: bool,
+) {
+    let loop_radius = NODE_RADIUS * 0.7;
+    let center = node + outward * (NODE_RADIUS + loop_radius * 0.6);
+    painter.circle_stroke(center, loop_radius, force_stroke(force));
+
+    if show_value {
+        painter.text(
+            center + outward * (loop_radius + 8.0),
+            egui::Align2::CENTER_CENTER,
+            format!("{:+.2}", force),
+            egui::FontId::monospace(10.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+    }
+}
+
+/// Graphe orienté des interactions : un nœud par type sur un cercle, une arête A→B
+/// pour l'attraction (ou la répulsion) de A vers B, la nourriture au centre
+pub fn interaction_graph_window(
+    mut contexts: EguiContexts,
+    mut graph_ui: ResMut<InteractionGraphUI>,
+    ui_state: Res<ForceMatrixUI>,
+    particle_config: Res<ParticleTypesConfig>,
+    simulations: Query<(&SimulationId, &Genotype), With<Simulation>>,
+) {
+    if !graph_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+    let selected_sim = ui_state
+        .selected_simulation
+        .or_else(|| ui_state.visible_simulations().first().copied());
+
+    egui::Window::new("🕸 Graphe des interactions")
+        .resizable(true)
+        .default_width(380.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            let Some((sim_id, genotype)) = selected_sim.and_then(|selected| {
+                simulations.iter().find(|(sim_id, _)| sim_id.0 == selected)
+            }) else {
+                ui.label("Aucune simulation sélectionnée");
+                return;
+            };
+
+            ui.label(format!("Simulation #{}", sim_id.0 + 1));
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::Slider::new(&mut graph_ui.min_magnitude, 0.0..=MAX_FORCE)
+                        .text("Seuil"),
+                )
+                .on_hover_text("Masquer les forces plus faibles que ce seuil");
+                ui.checkbox(&mut graph_ui.show_values, "Valeurs");
+                ui.checkbox(&mut graph_ui.show_food, "Nourriture");
+            });
+            ui.label(
+                egui::RichText::new("Vert : attraction, rouge : répulsion, épaisseur : intensité")
+                    .small()
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+
+            let type_count = genotype.type_count;
+            let side = ui.available_width().clamp(220.0, 600.0);
+            let (response, painter) =
+                ui.allocate_painter(egui::vec2(side, side), egui::Sense::hover());
+            let rect = response.rect;
+            painter.rect_filled(rect, egui::CornerRadius::same(4), egui::Color32::from_gray(20));
+
+            let center = rect.center();
+            let circle_radius = side * 0.5 - NODE_RADIUS * 3.0;
+            let angle_of = |particle_type: usize| {
+                -TAU / 4.0 + TAU * particle_type as f32 / type_count.max(1) as f32
+            };
+            let nodes: Vec<egui::Pos2> = (0..type_count)
+                .map(|particle_type| {
+                    let angle = angle_of(particle_type);
+                    center + egui::vec2(angle.cos(), angle.sin()) * circle_radius
+                })
+                .collect();
+            let threshold = graph_ui.min_magnitude;
+            let show_values = graph_ui.show_values;
+
+            // Arêtes sous les nœuds
+            for (a, &from) in nodes.iter().enumerate() {
+                for (b, &to) in nodes.iter().enumerate() {
+                    let force = genotype.get_force(a, b);
+                    if force.abs() < threshold {
+                        continue;
+                    }
+                    if a == b {
+                        draw_self_loop(&painter, from, (from - center).normalized(), force, show_values);
+                    } else {
+                        draw_edge(&painter, from, to, EDGE_OFFSET, force, show_values);
+                    }
+                }
+
+                if graph_ui.show_food {
+                    let force = genotype.get_food_force(a);
+                    if force.abs() >= threshold {
+                        draw_edge(&painter, from, center, 0.0, force, show_values);
+                    }
+                }
+            }
+
+            if graph_ui.show_food {
+                painter.circle(
+                    center,
+                    NODE_RADIUS * 0.8,
+                    egui::Color32::from_rgb(200, 170, 60),
+                    egui::Stroke::new(1.5, egui::Color32::WHITE),
+                );
+                painter.text(
+                    center,
+                    egui::Align2::CENTER_CENTER,
+                    "🍎",
+                    egui::FontId::proportional(12.0),
+                    egui::Color32::BLACK,
+                );
+            }
+
+            for (particle_type, &node) in nodes.iter().enumerate() {
+                painter.circle(
+                    node,
+                    NODE_RADIUS,
+                    type_color32(&particle_config, particle_type),
+                    egui::Stroke::new(1.5, egui::Color32::WHITE),
+                );
+                painter.text(
+                    node,
+                    egui::Align2::CENTER_CENTER,
+                    particle_type.to_string(),
+                    egui::FontId::proportional(13.0),
+                    egui::Color32::BLACK,
+                );
+            }
+
+            ui.separator();
+            let behaviors = predict_emergent_behaviors(genotype);
+            if behaviors.is_empty() {
+                ui.label("Aucun comportement détecté");
+            } else {
+                ui.label("Comportements détectés :");
+                behavior_confidence_bars(ui, behaviors);
+            }
+        });
+
+    if !is_open {
+        graph_ui.show_window = false;
+    }
+}
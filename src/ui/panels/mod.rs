@@ -1 +1,10 @@
-pub mod force_matrix;
\ No newline at end of file
+pub mod fitness_history;
+pub mod epoch_end;
+pub mod force_matrix;
+pub mod genome_archive;
+pub mod hall_of_fame;
+pub mod interaction_graph;
+pub mod particle_inspector;
+pub mod performance;
+pub mod run_summary;
+pub mod type_colors;
@@ -0,0 +1,157 @@
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::systems::simulation::physics::ForceBreakdown;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+#[derive(Resource)]
+pub struct ParticleInspectorUI {
+    pub show_window: bool,
+    pub selected: Option<Entity>,
+    /// Voisines listées et reliées par une flèche
+    pub top_count: usize,
+    pub breakdown: Option<ForceBreakdown>,
+}
+
+impl Default for ParticleInspectorUI {
+    fn default() -> Self {
+        Self {
+            show_window: false,
+            selected: None,
+            top_count: 5,
+            breakdown: None,
+        }
+    }
+}
+
+fn format_vector(vector: Vec3) -> String {
+    format!("({:+.2}, {:+.2}, {:+.2})", vector.x, vector.y, vector.z)
+}
+
+/// Décomposition en temps réel de la force d'une particule choisie par Alt + clic
+pub fn particle_inspector_window(
+    mut contexts: EguiContexts,
+    mut inspector: ResMut<ParticleInspectorUI>,
+    particle_config: Res<ParticleTypesConfig>,
+) {
+    if !inspector.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+    let mut deselect = false;
+    let mut top_count = inspector.top_count;
+
+    egui::Window::new("🔍 Inspecteur de particule")
+        .resizable(true)
+        .default_width(420.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::Slider::new(&mut top_count, 1..=20).text("Voisines"));
+                if ui
+                    .add_enabled(inspector.selected.is_some(), egui::Button::new("Désélectionner"))
+                    .clicked()
+                {
+                    deselect = true;
+                }
+            });
+
+            let Some(breakdown) = &inspector.breakdown else {
+                ui.label("Alt + clic gauche sur une particule d'un viewport pour l'inspecter");
+                return;
+            };
+
+            let type_color = |particle_type: usize| {
+                let (color, _) = particle_config.get_color_for_type(particle_type);
+                let [red, green, blue, _] = color.to_srgba().to_u8_array();
+                egui::Color32::from_rgb(red, green, blue)
+            };
+
+            ui.horizontal(|ui| {
+                ui.label(format!("Simulation #{}", breakdown.sim_id + 1));
+                ui.label(
+                    egui::RichText::new(format!("type {}", breakdown.particle_type))
+                        .color(type_color(breakdown.particle_type)),
+                );
+                ui.label(format!("position {}", format_vector(breakdown.position)));
+            });
+            ui.label(
+                egui::RichText::new(format!(
+                    "Force totale {}  |F| = {:.2}",
+                    format_vector(breakdown.total),
+                    breakdown.total.length()
+                ))
+                .monospace()
+                .strong(),
+            );
+            ui.label(
+                egui::RichText::new(format!(
+                    "Nourriture   {}  |F| = {:.2}",
+                    format_vector(breakdown.food_force),
+                    breakdown.food_force.length()
+                ))
+                .monospace(),
+            );
+            let ignored = breakdown.neighbors.iter().filter(|neighbor| neighbor.ignored).count();
+            ui.label(format!(
+                "{} voisine(s) à portée{}",
+                breakdown.neighbors.len(),
+                if ignored > 0 {
+                    format!(", {} hors limite d'interactions", ignored)
+                } else {
+                    String::new()
+                }
+            ));
+            ui.separator();
+
+            egui::Grid::new("inspector_neighbors")
+                .num_columns(5)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong("Entité");
+                    ui.strong("Type");
+                    ui.strong("Distance");
+                    ui.strong("Force");
+                    ui.strong("|F|");
+                    ui.end_row();
+
+                    for neighbor in breakdown.neighbors.iter().take(top_count) {
+                        let weak = |text: String| {
+                            let text = egui::RichText::new(text).monospace();
+                            if neighbor.ignored { text.weak() } else { text }
+                        };
+                        ui.label(weak(format!("{}", neighbor.entity)));
+                        ui.label(
+                            egui::RichText::new(neighbor.particle_type.to_string())
+                                .color(type_color(neighbor.particle_type)),
+                        );
+                        ui.label(weak(format!("{:.1}", neighbor.distance)));
+                        ui.label(weak(format_vector(neighbor.force)));
+                        ui.label(weak(format!("{:.2}", neighbor.force.length())))
+                            .on_hover_text(if neighbor.ignored {
+                                "Ignorée : au-delà de la limite d'interactions"
+                            } else {
+                                "Comptée dans la force totale"
+                            });
+                        ui.end_row();
+                    }
+                });
+
+            ui.label(
+                egui::RichText::new("Flèches : vert attirée, rouge repoussée, blanc force totale")
+                    .small()
+                    .color(egui::Color32::from_rgb(150, 150, 150)),
+            );
+        });
+
+    inspector.top_count = top_count;
+    if deselect {
+        inspector.selected = None;
+        inspector.breakdown = None;
+    }
+    if !is_open {
+        inspector.show_window = false;
+    }
+}
@@ -0,0 +1,225 @@
+use crate::components::entities::particle::Particle;
+use crate::globals::{GPU_RECOMMENDED_PARTICLES, GPU_WORKGROUP_SIZES};
+use crate::plugins::simulation::compute::{ComputeEnabled, GpuComputeTiming, GpuDispatchSettings};
+use crate::plugins::simulation::compute_validation::ComputeValidation;
+use crate::states::app::AppState;
+use crate::resources::config::simulation::NeighborBackend;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use egui_plot::{Line, Plot, PlotPoints};
+
+#[derive(Resource, Default)]
+pub struct PerformanceUI {
+    pub show_window: bool,
+}
+
+/// FPS lissé par le plugin de diagnostics (moyenne exponentielle)
+pub fn smoothed_fps(diagnostics: &DiagnosticsStore) -> Option<f64> {
+    diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+}
+
+/// Percentile par rang le plus proche, `sorted` trié par ordre croissant
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn performance_window(
+    mut contexts: EguiContexts,
+    mut performance_ui: ResMut<PerformanceUI>,
+    diagnostics: Res<DiagnosticsStore>,
+    compute_enabled: Res<ComputeEnabled>,
+    dispatch_settings: Option<Res<GpuDispatchSettings>>,
+    compute_timing: Option<Res<GpuComputeTiming>>,
+    mut neighbor_backend: ResMut<NeighborBackend>,
+    mut validation: Option<ResMut<ComputeValidation>>,
+    app_state: Res<State<AppState>>,
+    particles: Query<(), With<Particle>>,
+) {
+    if !performance_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+
+    // Historique des temps de frame en ms, du plus ancien au plus récent
+    let frame_times: Vec<f64> = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .map(|frame_time| frame_time.values().copied().collect())
+        .unwrap_or_default();
+    let particle_count = particles.iter().count();
+
+    egui::Window::new("📊 Performances")
+        .resizable(true)
+        .default_width(320.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            match smoothed_fps(&diagnostics) {
+                Some(fps) => ui.label(format!("FPS (lissé): {:.0}", fps)),
+                None => ui.label("FPS (lissé): -"),
+            };
+
+            if frame_times.is_empty() {
+                ui.label("Pas encore de mesure de temps de frame.");
+            } else {
+                let mut sorted = frame_times.clone();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                ui.label(format!(
+                    "Temps de frame: p50 {:.1} ms | p95 {:.1} ms | p99 {:.1} ms",
+                    percentile(&sorted, 0.50),
+                    percentile(&sorted, 0.95),
+                    percentile(&sorted, 0.99)
+                ));
+
+                let points: PlotPoints = frame_times
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ms)| [i as f64, *ms])
+                    .collect();
+                Plot::new("frame_time_plot")
+                    .height(120.0)
+                    .include_y(0.0)
+                    .show_x(false)
+                    .allow_drag(false)
+                    .allow_zoom(false)
+                    .allow_scroll(false)
+                    .y_axis_label("ms")
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(points).name("Temps de frame"));
+                    });
+            }
+
+            ui.separator();
+
+            ui.label(format!("Particules: {}", particle_count));
+            ui.label(format!(
+                "Compute GPU: {}",
+                if compute_enabled.0 { "actif" } else { "inactif (CPU)" }
+            ));
+            if let Some(settings) = &dispatch_settings {
+                ui.label(format!("Workgroup GPU: {}", settings.workgroup_size))
+                    .on_hover_text(format!(
+                        "Choisi au lancement avec --workgroup-size=N ({:?}), demandé: {}",
+                        GPU_WORKGROUP_SIZES, settings.requested_workgroup_size
+                    ));
+                ui.label(format!("Voisines GPU: {}", settings.neighbor_search.label()))
+                    .on_hover_text("--gpu-neighbors=brute au lancement pour le parcours exhaustif");
+            }
+            if let Some(timing) = compute_timing.as_ref().filter(|timing| timing.enabled)
+                && let (Some(round_trip), Some(per_iteration)) =
+                    (timing.last_round_trip, timing.per_iteration())
+            {
+                ui.label(format!(
+                    "Compute: {:.2} ms pour {} itération(s), {:.2} ms/itération",
+                    round_trip.as_secs_f64() * 1000.0,
+                    timing.last_iterations,
+                    per_iteration.as_secs_f64() * 1000.0
+                ))
+                .on_hover_text("Aller-retour soumission → résultats lus, mesuré côté CPU");
+            }
+
+            ui.add_enabled_ui(!compute_enabled.0, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Voisines CPU:");
+                    let mut backend = *neighbor_backend;
+                    egui::ComboBox::from_id_salt("cpu_neighbor_backend")
+                        .selected_text(backend.label())
+                        .show_ui(ui, |ui| {
+                            for option in NeighborBackend::ALL {
+                                ui.selectable_value(&mut backend, option, option.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Grille : construction O(n), pour une densité homogène. Arbre k-d : construction O(n log n), robuste aux amas denses",
+                        );
+                    if backend != *neighbor_backend {
+                        *neighbor_backend = backend;
+                        info!("Recherche des voisines CPU : {}", backend.label());
+                    }
+                });
+            });
+
+            if let Some(validation) = validation
+                .as_deref_mut()
+                .filter(|_| *app_state.get() == AppState::Simulation)
+            {
+                ui.separator();
+                ui.label(egui::RichText::new("Validation CPU / GPU").strong());
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut validation.steps)
+                            .range(1..=500)
+                            .suffix(" pas"),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut validation.tolerance)
+                            .range(0.0001..=100.0)
+                            .speed(0.001)
+                            .prefix("tolérance: "),
+                    );
+                    let busy = validation.requested || validation.is_running();
+                    if ui
+                        .add_enabled(!busy, egui::Button::new("🧪 Comparer"))
+                        .on_hover_text(
+                            "Avance une copie des simulations sur CPU et sur GPU, sans toucher aux particules",
+                        )
+                        .clicked()
+                    {
+                        validation.requested = true;
+                    }
+                    if busy {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(report) = &validation.report {
+                    let (color, verdict) = if report.passed() {
+                        (egui::Color32::from_rgb(100, 200, 100), "✔ Dans la tolérance")
+                    } else {
+                        (egui::Color32::from_rgb(230, 90, 90), "⚠ DIVERGENCE au-delà de la tolérance")
+                    };
+                    ui.colored_label(color, verdict);
+                    ui.label(format!(
+                        "{} particules, {} pas : position max {:.4} (moy. {:.4}), vitesse max {:.4}",
+                        report.particle_count,
+                        report.steps,
+                        report.max_position_error,
+                        report.mean_position_error,
+                        report.max_velocity_error
+                    ));
+                    if let Some((entity, sim_id)) = report.worst_particle
+                        && !report.passed()
+                    {
+                        ui.label(format!(
+                            "Pire écart : particule {} de la simulation #{}",
+                            entity,
+                            sim_id + 1
+                        ));
+                    }
+                }
+            }
+
+            if !compute_enabled.0 && particle_count > GPU_RECOMMENDED_PARTICLES {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "Plus de {} particules : activer le GPU est recommandé",
+                        GPU_RECOMMENDED_PARTICLES
+                    ),
+                );
+            }
+        });
+
+    if !is_open {
+        performance_ui.show_window = false;
+    }
+}
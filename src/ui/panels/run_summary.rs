@@ -0,0 +1,207 @@
+use crate::resources::genetics::auto_adapt::AutoAdapt;
+use crate::resources::genetics::diversity_guard::DiversityGuard;
+use crate::resources::genetics::history::EvolutionHistory;
+use crate::states::app::AppState;
+use crate::systems::persistence::population_save::SavePopulationEvent;
+use crate::systems::persistence::run_report::RunSummary;
+use crate::ui::menus::visualizer_menu::load_population_for_visualization;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+fn epoch_points(values: &[f32]) -> PlotPoints<'static> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(epoch, &value)| [(epoch + 1) as f64, value as f64])
+        .collect()
+}
+
+/// Rapport de fin de run : courbe complète des scores, stagnations et meilleurs génomes
+#[allow(clippy::too_many_arguments)]
+pub fn run_report_screen(
+    mut contexts: EguiContexts,
+    mut summary: ResMut<RunSummary>,
+    history: Res<EvolutionHistory>,
+    auto_adapt: Res<AutoAdapt>,
+    diversity_guard: Res<DiversityGuard>,
+    mut save_events: EventWriter<SavePopulationEvent>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let mut save_requests = Vec::new();
+    let mut visualize = false;
+    let mut destination = None;
+
+    egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
+        ui.heading("🏁 Run terminé");
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("run_summary")
+                .num_columns(2)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Époques jouées:");
+                    ui.label(summary.epochs.to_string());
+                    ui.end_row();
+
+                    ui.label("Meilleure fitness finale:");
+                    ui.strong(format!("{:.1}", summary.final_best_score));
+                    ui.end_row();
+
+                    ui.label("Amélioration totale:");
+                    let improvement = summary.total_improvement();
+                    let color = if improvement >= 0.0 {
+                        egui::Color32::from_rgb(100, 200, 100)
+                    } else {
+                        egui::Color32::from_rgb(220, 100, 100)
+                    };
+                    ui.colored_label(
+                        color,
+                        format!(
+                            "{:+.1} (depuis {:.1} à la première époque)",
+                            improvement, summary.first_best_score
+                        ),
+                    );
+                    ui.end_row();
+
+                    ui.label("Champion:");
+                    ui.label(format!(
+                        "{:.1} — {}",
+                        summary.champion.score,
+                        summary.champion.description.as_deref().unwrap_or("")
+                    ));
+                    ui.end_row();
+
+                    if let Some(epoch) = diversity_guard.converged_at {
+                        ui.label("Arrêt:");
+                        ui.colored_label(
+                            egui::Color32::from_rgb(220, 180, 80),
+                            format!(
+                                "Population convergée à l'époque {} (diversité sous {:.3} pendant {} époques)",
+                                epoch,
+                                diversity_guard.threshold,
+                                diversity_guard.patience.max(1)
+                            ),
+                        );
+                        ui.end_row();
+                    }
+
+                    if summary.saved_genomes > 0 {
+                        ui.label("Sauvegarde:");
+                        ui.label(format!("{} génome(s) dans populations/", summary.saved_genomes));
+                        ui.end_row();
+                    }
+
+                    if let Some(path) = &summary.statistics_path {
+                        ui.label("Statistiques:");
+                        ui.label(path.display().to_string());
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            ui.label(egui::RichText::new("Scores par époque").strong());
+            Plot::new("run_report_scores_plot")
+                .height(220.0)
+                .legend(Legend::default())
+                .x_axis_label("Époque")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(epoch_points(&history.best_scores))
+                            .name("Meilleur")
+                            .color(egui::Color32::from_rgb(0, 200, 0)),
+                    );
+                    plot_ui.line(
+                        Line::new(epoch_points(&history.average_scores))
+                            .name("Moyenne")
+                            .color(egui::Color32::from_rgb(100, 200, 255)),
+                    );
+                });
+
+            ui.separator();
+            let min_length = auto_adapt.stagnation_threshold.max(1);
+            let episodes = history.stagnation_episodes(min_length);
+            ui.label(
+                egui::RichText::new(format!(
+                    "Stagnations (au moins {} époques sans nouveau record)",
+                    min_length
+                ))
+                .strong(),
+            );
+            if episodes.is_empty() {
+                ui.label("Aucune");
+            }
+            for (start, length) in &episodes {
+                ui.label(format!(
+                    "• Époques {} à {} ({} époques)",
+                    start + 1,
+                    start + length,
+                    length
+                ));
+            }
+
+            ui.separator();
+            ui.label(egui::RichText::new("Meilleurs génomes de la dernière époque").strong());
+            egui::Grid::new("run_report_top_genomes")
+                .num_columns(3)
+                .striped(true)
+                .spacing([20.0, 4.0])
+                .show(ui, |ui| {
+                    for (index, (sim_id, _, fitness)) in summary.top_genomes.iter().enumerate() {
+                        ui.label(format!("Simulation #{}", sim_id + 1));
+                        ui.label(format!("{:.1}", fitness));
+                        let saved = summary.saved_top[index];
+                        if ui
+                            .add_enabled(!saved, egui::Button::new(if saved {
+                                "✔ Sauvegardé"
+                            } else {
+                                "💾 Sauvegarder"
+                            }))
+                            .clicked()
+                        {
+                            save_requests.push(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("👁 Visualiser le champion").clicked() {
+                    visualize = true;
+                }
+                if ui.button("📂 Visualiseur").clicked() {
+                    destination = Some(AppState::Visualizer);
+                }
+                if ui.button("🏠 Menu principal").clicked() {
+                    destination = Some(AppState::MainMenu);
+                }
+            });
+        });
+    });
+
+    for index in save_requests {
+        let (sim_id, genotype, fitness) = summary.top_genomes[index].clone();
+        save_events.write(SavePopulationEvent {
+            simulation_id: sim_id,
+            name: format!("rapport_sim{}", sim_id + 1),
+            description: Some(format!(
+                "Fin de run après {} époques, fitness {:.1}",
+                summary.epochs, fitness
+            )),
+            tags: vec!["fin de run".to_string()],
+            genome: Some((genotype, fitness)),
+        });
+        summary.saved_top[index] = true;
+    }
+
+    if visualize {
+        load_population_for_visualization(&mut commands, summary.champion.clone());
+        destination = Some(AppState::Visualization);
+    }
+    if let Some(state) = destination {
+        next_state.set(state);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::resources::config::particle_types::ParticleTypesConfig;
+use crate::ui::menus::main_menu::edit_type_color;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+#[derive(Resource, Default)]
+pub struct TypeColorsUI {
+    pub show_window: bool,
+}
+
+pub fn type_colors_window(
+    mut contexts: EguiContexts,
+    mut type_colors_ui: ResMut<TypeColorsUI>,
+    mut particle_config: ResMut<ParticleTypesConfig>,
+) {
+    if !type_colors_ui.show_window {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut();
+    let mut is_open = true;
+
+    egui::Window::new("🎨 Couleurs des types")
+        .resizable(false)
+        .default_width(220.0)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            let type_count = particle_config.type_count;
+            // Lecture sans marquer la ressource modifiée : les matériaux ne sont
+            // resynchronisés que sur un vrai changement
+            let mut colors: Vec<Color> = particle_config
+                .colors
+                .iter()
+                .take(type_count)
+                .map(|(base_color, _)| *base_color)
+                .collect();
+
+            let mut changed = false;
+            egui::Grid::new("type_colors_grid")
+                .num_columns(2)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    for (particle_type, color) in colors.iter_mut().enumerate() {
+                        ui.label(format!("Type {}", particle_type));
+                        changed |= edit_type_color(ui, color);
+                        ui.end_row();
+                    }
+                });
+
+            if ui.button("↺ Teintes par défaut").clicked() {
+                colors = (0..type_count)
+                    .map(|index| ParticleTypesConfig::default_color(index, type_count))
+                    .collect();
+                changed = true;
+            }
+
+            if changed {
+                for (particle_type, color) in colors.into_iter().enumerate() {
+                    particle_config.set_color(particle_type, color);
+                }
+            }
+        });
+
+    if !is_open {
+        type_colors_ui.show_window = false;
+    }
+}